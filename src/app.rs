@@ -1,11 +1,33 @@
-use crate::config::{Config, ConfigSources, RepositorySettings};
-use crate::git::{Branch, GitManager, Worktree, WorktreeDetail};
-use crate::hooks::SetupRunner;
+use crate::config::{Config, ConfigSources, CreateBase, HookEvent, RepositorySettings};
+use crate::git::{
+    find_branch_checkout, head_commit_id, is_dirty, is_valid_branch_name, Branch, GitManager,
+    Worktree, WorktreeDetail,
+};
+use crate::hooks::{HookRunner, SetupRunner};
+use crate::multiplexer;
+use crate::shell::shell_quote;
+use crate::state::State;
 use crate::theme::Theme;
-use std::path::Path;
-use std::sync::mpsc;
+use ratatui::layout::Rect;
+use std::collections::{HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
+/// Redraw interval while the delete spinner is animating
+const SPINNER_TICK_RATE: Duration = Duration::from_millis(80);
+
+/// How long the first key of a two-key chord (e.g. `gg`) waits for the second
+pub const CHORD_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// How long a delete dialog spends measuring disk usage before showing a lower bound
+const SIZE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Number of status messages kept for the message log
+const MESSAGE_LOG_CAPACITY: usize = 100;
+
 #[derive(Error, Debug)]
 pub enum AppError {
     #[error("Git error: {0}")]
@@ -14,21 +36,222 @@ pub enum AppError {
     Hook(#[from] crate::hooks::HookError),
     #[error("Config error: {0}")]
     Config(#[from] crate::config::ConfigError),
+    #[error("State error: {0}")]
+    State(#[from] crate::state::StateError),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AppMode {
     Normal,
     Create,
+    Creating,
     Confirm,
     Deleting,
+    /// Fetching all remotes in the background
+    Fetching,
+    /// Re-running setup in an existing worktree in the background
+    SettingUp,
+    Move,
+    /// Prompt for an optional reason before locking the selected worktree
+    LockReason,
     Config,
+    Help,
+    /// Scrollable history of status messages
+    MessageLog,
+}
+
+impl AppMode {
+    /// Short name shown in the terminal title
+    pub fn label(&self) -> &'static str {
+        match self {
+            AppMode::Normal => "list",
+            AppMode::Create => "create",
+            AppMode::Creating => "creating",
+            AppMode::Confirm => "confirm",
+            AppMode::Deleting => "deleting",
+            AppMode::Fetching => "fetching",
+            AppMode::SettingUp => "setup",
+            AppMode::Move => "move",
+            AppMode::LockReason => "lock",
+            AppMode::Config => "config",
+            AppMode::Help => "help",
+            AppMode::MessageLog => "messages",
+        }
+    }
+}
+
+/// Which field the input edits on "Create new branch"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CreateField {
+    BranchName,
+    WorktreeName,
+    /// Commit, tag or ref the new worktree starts from
+    BaseRef,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ConfirmAction {
     DeleteSingle,
     Prune,
+    /// Delete every worktree marked with Space
+    DeleteMarked,
+}
+
+/// Severity of a status message
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MessageLevel {
+    #[default]
+    Success,
+    Warning,
+    Error,
+}
+
+/// A status message kept in the message log
+#[derive(Debug, Clone)]
+pub struct LoggedMessage {
+    pub text: String,
+    pub level: MessageLevel,
+    pub at: Instant,
+}
+
+/// Pane that Up / Down drive in Normal mode
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Focus {
+    #[default]
+    List,
+    Detail,
+}
+
+/// Ordering of the worktree list (main worktree always stays on top)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SortMode {
+    #[default]
+    NameAsc,
+    NameDesc,
+    DateNewest,
+    DateOldest,
+    /// Oldest added worktree first
+    Created,
+}
+
+impl SortMode {
+    /// Next sort mode in the cycle
+    pub fn next(self) -> Self {
+        match self {
+            SortMode::NameAsc => SortMode::NameDesc,
+            SortMode::NameDesc => SortMode::DateNewest,
+            SortMode::DateNewest => SortMode::DateOldest,
+            SortMode::DateOldest => SortMode::Created,
+            SortMode::Created => SortMode::NameAsc,
+        }
+    }
+
+    /// Short label for display in the header
+    pub fn label(self) -> &'static str {
+        match self {
+            SortMode::NameAsc => "name ↑",
+            SortMode::NameDesc => "name ↓",
+            SortMode::DateNewest => "newest",
+            SortMode::DateOldest => "oldest",
+            SortMode::Created => "age",
+        }
+    }
+}
+
+/// Worktree field matched by the search query
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchField {
+    Name,
+    Branch,
+    Path,
+    /// Abbreviated HEAD commit id, the only identity of a detached HEAD
+    CommitId,
+    CommitMessage,
+    CommitAuthor,
+}
+
+impl SearchField {
+    /// First field of `worktree` containing `query` (expected in lowercase)
+    pub fn find(worktree: &Worktree, query: &str) -> Option<Self> {
+        let path = worktree.path.to_string_lossy();
+        let fields = [
+            (SearchField::Name, Some(worktree.name.as_str())),
+            (SearchField::Branch, worktree.branch.as_deref()),
+            (SearchField::Path, Some(path.as_ref())),
+            (SearchField::CommitId, worktree.head_id.as_deref()),
+            (
+                SearchField::CommitMessage,
+                worktree.commit_message.as_deref(),
+            ),
+            (SearchField::CommitAuthor, worktree.commit_author.as_deref()),
+        ];
+        fields
+            .iter()
+            .find(|(_, value)| value.is_some_and(|v| v.to_lowercase().contains(query)))
+            .map(|(field, _)| *field)
+    }
+}
+
+/// What a suspended command does, used to report its outcome
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SuspendKind {
+    Editor,
+    Diff,
+    /// `worktree.on_open`, run instead of leaving gwm
+    Open,
+    Fetch,
+    Pull,
+}
+
+/// Disk usage of the worktrees a delete dialog would remove
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DirSize {
+    pub bytes: u64,
+    /// Measuring hit the time limit, so `bytes` is a lower bound
+    pub partial: bool,
+}
+
+/// A shell command to run with the TUI suspended
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuspendCommand {
+    pub command: String,
+    pub cwd: std::path::PathBuf,
+    pub kind: SuspendKind,
+}
+
+/// How a new worktree gets its branch
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CreateKind {
+    NewBranch,
+    ExistingBranch {
+        reuses_local: bool,
+    },
+    /// Detached HEAD at `CreateJob::base_ref`, no branch is created
+    Detached,
+}
+
+/// Worktree creation handed to the background thread
+#[derive(Debug)]
+struct CreateJob {
+    kind: CreateKind,
+    worktree_name: String,
+    branch_name: String,
+    base_path: String,
+    /// Revision to start from instead of HEAD
+    base_ref: Option<String>,
+    /// Appended to the result, e.g. when the base branch is behind its upstream
+    warning: Option<String>,
+}
+
+/// Result of a background create operation
+#[derive(Debug)]
+pub struct CreateResult {
+    pub level: MessageLevel,
+    pub message: String,
+    /// Whether the worktree exists afterwards (post_create hook failures included)
+    pub created: bool,
+    /// Path of the created worktree
+    pub path: Option<PathBuf>,
 }
 
 /// Result of a background delete operation
@@ -43,6 +266,10 @@ pub enum DeleteResult {
     PruneCompleted {
         worktree_count: usize,
         branch_count: usize,
+        /// Worktrees that could not be removed, as "name: reason"
+        failed: Vec<String>,
+        /// Failures of post_delete hooks, reported after the counts
+        hook_errors: Vec<String>,
     },
     Error(String),
 }
@@ -51,25 +278,107 @@ pub struct App {
     pub mode: AppMode,
     pub worktrees: Vec<Worktree>,
     pub filtered_worktrees: Vec<Worktree>,
+    /// Paths of worktrees marked for batch deletion
+    pub marked_worktrees: HashSet<PathBuf>,
     pub branches: Vec<Branch>,
     pub filtered_branches: Vec<Branch>,
+    /// Query the branch list was last filtered by in Create mode
+    pub branch_filter: String,
+    /// Branch the pending prune checked worktrees against
+    pub prune_base: String,
+    /// Worktree being relocated in Move mode
+    pub move_source: Option<Worktree>,
+    /// Field the input edits on "Create new branch"
+    pub create_field: CreateField,
+    /// Worktree name for a new branch while the branch name field is active
+    pub worktree_name_input: String,
+    /// Start point for a new branch while another create field is active
+    pub base_ref_input: String,
     pub selected_worktree: usize,
     pub selected_branch: usize,
     pub input: String,
+    /// Cursor position in `input` in characters; `None` keeps it at the end
+    pub input_cursor: Option<usize>,
     pub confirm_action: Option<ConfirmAction>,
     pub merged_worktrees: Vec<Worktree>,
+    /// Merged worktrees kept because their branch matches `worktree.prune_exclude`
+    pub prune_excluded: Vec<Worktree>,
+    /// Merged worktrees kept because they are locked
+    pub prune_locked: Vec<Worktree>,
+    /// Merged worktrees unchecked in the prune dialog, so they are kept
+    pub prune_unchecked: HashSet<PathBuf>,
+    /// Highlighted row of the prune dialog's list
+    pub prune_cursor: usize,
     pub message: Option<String>,
+    /// Severity of `message`, set together with it by `set_message`
+    pub message_level: MessageLevel,
+    /// Message last seen by `track_message` and when it first appeared
+    message_shown: Option<(String, Instant)>,
+    /// Recent status messages, newest last
+    pub message_log: VecDeque<LoggedMessage>,
     pub should_quit: bool,
+    /// Quit was requested during a background operation and awaits confirmation
+    pub quit_pending: bool,
     pub selected_worktree_path: Option<String>,
+    /// gwm prints the selected path (`-p`), so Enter always exits
+    pub print_path: bool,
+    /// Report worktree changes instead of making them (`--dry-run`)
+    pub dry_run: bool,
     pub theme: Theme,
-    pub deleting_message: Option<String>,
+    /// Message shown in the spinner dialog while creating or deleting
+    pub progress_message: Option<String>,
     pub tick: u64,
     pub config_sources: ConfigSources,
     pub config_scroll: u16,
     pub config_scroll_max: u16,
+    pub focus: Focus,
+    /// Scroll offset of the detail pane, reset when the selection changes
+    pub detail_scroll: u16,
+    pub detail_scroll_max: u16,
+    /// Worktree the detail scroll offset belongs to
+    detail_scroll_path: Option<PathBuf>,
+    /// Number of rows visible in the last rendered list
+    pub list_height: u16,
+    /// Screen area and scroll offset of the last rendered list, for mouse clicks
+    pub list_area: Rect,
+    pub list_offset: usize,
+    pub sort_mode: SortMode,
+    /// Draw each worktree on a single line (`ui.compact_list`, toggled with C)
+    pub compact_list: bool,
+    /// First key of an incomplete chord and when it was pressed
+    pub pending_key: Option<(char, Instant)>,
+    /// Selection before a chord's first key was typed into the search
+    pub chord_selection: usize,
+    pub suspend_command: Option<SuspendCommand>,
     config: Config,
     git: GitManager,
     delete_receiver: Option<mpsc::Receiver<DeleteResult>>,
+    /// Open the worktree once Create mode finishes (branch checkout)
+    pub open_after_create: bool,
+    /// Create mode is only a branch name prompt, branching off the selected worktree
+    pub quick_create: bool,
+    /// Stash taken before the running delete, reported once it finishes
+    delete_stash: Option<String>,
+    create_receiver: Option<mpsc::Receiver<CreateResult>>,
+    /// Set to stop the running create's setup commands (Esc while creating)
+    pub setup_cancel: Option<Arc<AtomicBool>>,
+    /// Result message of re-running setup in an existing worktree
+    setup_receiver: Option<mpsc::Receiver<(MessageLevel, String)>>,
+    fetch_receiver: Option<mpsc::Receiver<Result<(), String>>>,
+    /// Failure of the last file manager opened with O, once it exits
+    reveal_receiver: Option<mpsc::Receiver<Result<(), String>>>,
+    /// Worktrees whose dirty status is still being computed in the background
+    pub pending_status: HashSet<PathBuf>,
+    status_receiver: Option<mpsc::Receiver<(PathBuf, bool)>>,
+    /// Disk usage of the confirm dialog's targets, once measured
+    pub confirm_size: Option<DirSize>,
+    /// Why the confirm dialog opened although `confirm.delete` / `confirm.prune` is off
+    pub confirm_reason: Option<String>,
+    size_receiver: Option<mpsc::Receiver<DirSize>>,
+    /// Details of the last selected worktree, reused across redraws
+    detail_cache: Option<(PathBuf, WorktreeDetail)>,
+    /// Whether `branches` must be re-read before Create mode uses it
+    branches_stale: bool,
 }
 
 impl App {
@@ -78,34 +387,208 @@ impl App {
         config_sources: ConfigSources,
         git: GitManager,
     ) -> Result<Self, AppError> {
-        let worktrees = git.list_worktrees()?;
-        let branches = git.list_branches()?;
         let theme = Theme::from_config(Some(config.theme_name()), config.theme_colors());
 
-        Ok(Self {
+        let mut app = Self {
             mode: AppMode::Normal,
-            worktrees: worktrees.clone(),
-            filtered_worktrees: worktrees,
-            branches: branches.clone(),
-            filtered_branches: branches,
+            worktrees: Vec::new(),
+            filtered_worktrees: Vec::new(),
+            marked_worktrees: HashSet::new(),
+            // Branches are loaded on first entry to Create mode
+            branches: Vec::new(),
+            filtered_branches: Vec::new(),
+            branch_filter: String::new(),
+            prune_base: String::new(),
+            move_source: None,
+            create_field: CreateField::BranchName,
+            worktree_name_input: String::new(),
+            base_ref_input: String::new(),
             selected_worktree: 0,
             selected_branch: 0,
             input: String::new(),
+            input_cursor: None,
             confirm_action: None,
             merged_worktrees: Vec::new(),
+            prune_excluded: Vec::new(),
+            prune_locked: Vec::new(),
+            prune_unchecked: HashSet::new(),
+            prune_cursor: 0,
             message: None,
+            message_level: MessageLevel::Success,
+            message_shown: None,
+            message_log: VecDeque::new(),
             should_quit: false,
+            quit_pending: false,
             selected_worktree_path: None,
+            print_path: false,
+            dry_run: false,
             theme,
-            deleting_message: None,
+            progress_message: None,
             tick: 0,
             config_sources,
             config_scroll: 0,
             config_scroll_max: 0,
+            focus: Focus::default(),
+            detail_scroll: 0,
+            detail_scroll_max: 0,
+            detail_scroll_path: None,
+            list_height: 0,
+            list_area: Rect::default(),
+            list_offset: 0,
+            sort_mode: SortMode::default(),
+            compact_list: config.compact_list(),
+            pending_key: None,
+            chord_selection: 0,
+            suspend_command: None,
             config,
             git,
             delete_receiver: None,
-        })
+            open_after_create: false,
+            quick_create: false,
+            delete_stash: None,
+            create_receiver: None,
+            setup_cancel: None,
+            setup_receiver: None,
+            fetch_receiver: None,
+            reveal_receiver: None,
+            pending_status: HashSet::new(),
+            status_receiver: None,
+            confirm_size: None,
+            confirm_reason: None,
+            size_receiver: None,
+            detail_cache: None,
+            branches_stale: true,
+        };
+        app.refresh_worktrees()?;
+        if let Some(path) = State::load(app.git.git_dir()).last_selected {
+            app.restore_selection(&path);
+        }
+        if app.config.fetch_on_open() {
+            app.fetch_all();
+        }
+
+        Ok(app)
+    }
+
+    /// How long the event loop waits for input before redrawing.
+    /// `None` blocks until the next input event.
+    pub fn poll_timeout(&self) -> Option<Duration> {
+        match (self.mode, self.config.animations()) {
+            // Fast ticks keep the spinner moving while creating or deleting
+            (
+                AppMode::Deleting | AppMode::Creating | AppMode::Fetching | AppMode::SettingUp,
+                true,
+            ) => Some(SPINNER_TICK_RATE),
+            // Still wake up to notice when the background work finishes
+            (
+                AppMode::Deleting | AppMode::Creating | AppMode::Fetching | AppMode::SettingUp,
+                false,
+            ) => Some(self.config.tick_rate()),
+            (_, true) => Some(self.config.tick_rate()),
+            // Keep polling until background status results are in
+            (_, false)
+                if self.status_receiver.is_some()
+                    || self.size_receiver.is_some()
+                    || self.reveal_receiver.is_some() =>
+            {
+                Some(self.config.tick_rate())
+            }
+            // ...or until the current message expires
+            (_, false)
+                if self.message.is_some() && self.config.notification_duration().is_some() =>
+            {
+                Some(self.config.tick_rate())
+            }
+            (_, false) => None,
+        }
+    }
+
+    /// Advance animation state by one frame and expire stale chords
+    pub fn on_tick(&mut self) {
+        let animating = matches!(
+            self.mode,
+            AppMode::Deleting | AppMode::Creating | AppMode::Fetching | AppMode::SettingUp
+        ) || !self.pending_status.is_empty();
+        if animating && self.config.animations() {
+            self.tick = self.tick.wrapping_add(1);
+        }
+        if matches!(self.pending_key, Some((_, at)) if at.elapsed() > CHORD_TIMEOUT) {
+            self.pending_key = None;
+        }
+        self.track_message(Instant::now());
+    }
+
+    /// Show a status message and record it in the message log
+    pub fn set_message(&mut self, level: MessageLevel, text: impl Into<String>) {
+        let text = text.into();
+        if self.message_log.len() == MESSAGE_LOG_CAPACITY {
+            self.message_log.pop_front();
+        }
+        self.message_log.push_back(LoggedMessage {
+            text: text.clone(),
+            level,
+            at: Instant::now(),
+        });
+        self.message = Some(text);
+        self.message_level = level;
+        // Restart the display time even if the text repeats
+        self.message_shown = None;
+    }
+
+    /// Clear the status message once it has been visible for
+    /// `ui.notification_secs`
+    fn track_message(&mut self, now: Instant) {
+        let Some(message) = &self.message else {
+            self.message_shown = None;
+            return;
+        };
+        match &self.message_shown {
+            Some((shown, since)) if shown == message => {
+                let expired = self
+                    .config
+                    .notification_duration()
+                    .is_some_and(|duration| now.saturating_duration_since(*since) >= duration);
+                if expired {
+                    self.message = None;
+                    self.message_shown = None;
+                }
+            }
+            _ => self.message_shown = Some((message.clone(), now)),
+        }
+    }
+
+    /// Feed a key that can form a two-key chord. Returns true when `c`
+    /// completes a chord started by the same key within `CHORD_TIMEOUT`.
+    pub fn chord(&mut self, c: char) -> bool {
+        match self.pending_key.take() {
+            Some((pending, at)) if pending == c && at.elapsed() <= CHORD_TIMEOUT => true,
+            _ => {
+                self.pending_key = Some((c, Instant::now()));
+                false
+            }
+        }
+    }
+
+    /// Select the worktree at `path` if it is still listed and exists on disk
+    pub fn restore_selection(&mut self, path: &Path) {
+        if !path.exists() {
+            return;
+        }
+        if let Some(index) = self.filtered_worktrees.iter().position(|w| w.path == path) {
+            self.selected_worktree = index;
+        }
+    }
+
+    /// Persist the currently selected worktree for the next launch
+    pub fn save_state(&self) -> Result<(), AppError> {
+        let state = State {
+            last_selected: self
+                .filtered_worktrees
+                .get(self.selected_worktree)
+                .map(|w| w.path.clone()),
+        };
+        state.save(self.git.git_dir())?;
+        Ok(())
     }
 
     /// Get effective repository settings for the current repository
@@ -114,8 +597,11 @@ impl App {
         let repo_path = self.git.repo_root().to_string_lossy().to_string();
         let settings = self.config.get_effective_settings(&repo_path);
 
-        // Return None if no copy_files and no setup_commands
-        if settings.copy_files.is_none() && settings.setup_commands.is_none() {
+        // Return None if there is nothing to copy, link or run
+        if settings.copy_files.is_none()
+            && settings.symlink_files.is_none()
+            && settings.setup_commands.is_none()
+        {
             None
         } else {
             Some(settings)
@@ -130,9 +616,38 @@ impl App {
             .map(|w| w.path.clone())
     }
 
+    /// Repository name, taken from the main worktree directory
+    pub fn repo_name(&self) -> String {
+        match self.get_main_worktree_path() {
+            Some(path) => path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            None => self.git.repo_name(),
+        }
+    }
+
+    /// Terminal title, e.g. "gwm — project [list]", or None when `ui.set_title` is off
+    pub fn terminal_title(&self) -> Option<String> {
+        self.config
+            .set_title()
+            .then(|| format!("gwm — {} [{}]", self.repo_name(), self.mode.label()))
+    }
+
+    /// Branch checked out in the main worktree
+    pub fn main_branch(&self) -> Option<&str> {
+        self.worktrees
+            .iter()
+            .find(|w| w.is_main)
+            .and_then(|w| w.branch.as_deref())
+    }
+
     /// Create a SetupRunner with repository settings and main worktree path
     fn create_setup_runner(&self) -> SetupRunner {
-        let runner = SetupRunner::new(self.get_repository_settings());
+        let runner = SetupRunner::new(self.get_repository_settings())
+            .with_strict_copy(self.config.strict_copy_files())
+            .with_shell(self.config.setup_shell())
+            .with_timeout(self.config.setup_timeout());
         if let Some(main_path) = self.get_main_worktree_path() {
             runner.with_main_worktree(main_path)
         } else {
@@ -140,14 +655,134 @@ impl App {
         }
     }
 
+    /// Create a HookRunner with configured hooks and the repository root
+    fn create_hook_runner(&self) -> HookRunner {
+        HookRunner::new(self.config.hooks().to_vec()).with_repo_root(self.git.repo_root().clone())
+    }
+
+    /// Drop cached git data so the next use re-reads it. Called after any
+    /// operation that can change worktrees or branches.
+    pub fn invalidate_cache(&mut self) {
+        self.detail_cache = None;
+        self.branches_stale = true;
+    }
+
+    /// Re-read worktrees and discard cached details and branches
+    pub fn refresh(&mut self) -> Result<(), AppError> {
+        self.refresh_worktrees()?;
+        self.set_message(MessageLevel::Success, "Refreshed");
+        Ok(())
+    }
+
     pub fn refresh_worktrees(&mut self) -> Result<(), AppError> {
+        self.invalidate_cache();
         self.worktrees = self.git.list_worktrees()?;
-        self.filter_worktrees();
+        if self.config.show_status() {
+            self.start_status_load();
+        }
+        // Drop marks for worktrees that no longer exist
+        let worktrees = &self.worktrees;
+        self.marked_worktrees
+            .retain(|path| worktrees.iter().any(|w| &w.path == path));
+        self.sort_worktrees();
         Ok(())
     }
 
+    /// Compute dirty status for every worktree in a background thread so
+    /// large repositories don't block rendering
+    fn start_status_load(&mut self) {
+        let paths: Vec<PathBuf> = self.worktrees.iter().map(|w| w.path.clone()).collect();
+        self.pending_status = paths.iter().cloned().collect();
+
+        let (tx, rx) = mpsc::channel();
+        self.status_receiver = Some(rx);
+        std::thread::spawn(move || {
+            for path in paths {
+                let dirty = is_dirty(&path);
+                if tx.send((path, dirty)).is_err() {
+                    // A newer refresh replaced this load
+                    return;
+                }
+            }
+        });
+    }
+
+    /// Apply dirty status results that arrived from the background thread
+    pub fn check_status_updates(&mut self) {
+        let Some(receiver) = &self.status_receiver else {
+            return;
+        };
+        let mut finished = false;
+        let mut updates = Vec::new();
+        loop {
+            match receiver.try_recv() {
+                Ok(update) => updates.push(update),
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    finished = true;
+                    break;
+                }
+            }
+        }
+
+        for (path, dirty) in updates {
+            self.pending_status.remove(&path);
+            for worktree in self
+                .worktrees
+                .iter_mut()
+                .chain(self.filtered_worktrees.iter_mut())
+                .filter(|w| w.path == path)
+            {
+                worktree.is_dirty = dirty;
+            }
+        }
+        if finished {
+            self.status_receiver = None;
+            self.pending_status.clear();
+        }
+    }
+
+    /// Switch to the next sort mode and re-sort the list
+    pub fn cycle_sort(&mut self) {
+        self.sort_mode = self.sort_mode.next();
+        self.sort_worktrees();
+        self.set_message(
+            MessageLevel::Success,
+            format!("Sort: {}", self.sort_mode.label()),
+        );
+    }
+
+    /// Switch between single-line rows and the full `ui.list_format` layout
+    pub fn toggle_compact_list(&mut self) {
+        self.compact_list = !self.compact_list;
+        self.set_message(
+            MessageLevel::Success,
+            if self.compact_list {
+                "Compact list: on".to_string()
+            } else {
+                "Compact list: off".to_string()
+            },
+        );
+    }
+
+    /// Sort worktrees by the current sort mode, keeping the main worktree on top
+    fn sort_worktrees(&mut self) {
+        let sort_mode = self.sort_mode;
+        self.worktrees.sort_by(|a, b| {
+            b.is_main.cmp(&a.is_main).then_with(|| match sort_mode {
+                SortMode::NameAsc => a.name.cmp(&b.name),
+                SortMode::NameDesc => b.name.cmp(&a.name),
+                SortMode::DateNewest => b.last_commit_time.cmp(&a.last_commit_time),
+                SortMode::DateOldest => a.last_commit_time.cmp(&b.last_commit_time),
+                SortMode::Created => a.created_at.cmp(&b.created_at),
+            })
+        });
+        self.filter_worktrees();
+    }
+
     pub fn refresh_branches(&mut self) -> Result<(), AppError> {
         self.branches = self.git.list_branches()?;
+        self.branches_stale = false;
         self.filter_branches();
         Ok(())
     }
@@ -160,13 +795,7 @@ impl App {
             self.filtered_worktrees = self
                 .worktrees
                 .iter()
-                .filter(|w| {
-                    w.name.to_lowercase().contains(&query)
-                        || w.branch
-                            .as_ref()
-                            .map(|b| b.to_lowercase().contains(&query))
-                            .unwrap_or(false)
-                })
+                .filter(|w| SearchField::find(w, &query).is_some())
                 .cloned()
                 .collect();
         }
@@ -176,6 +805,7 @@ impl App {
     }
 
     pub fn filter_branches(&mut self) {
+        self.branch_filter = self.input.clone();
         if self.input.is_empty() {
             self.filtered_branches = self.branches.clone();
         } else {
@@ -187,22 +817,105 @@ impl App {
                 .cloned()
                 .collect();
         }
-        if self.selected_branch >= self.filtered_branches.len() {
-            self.selected_branch = self.filtered_branches.len().saturating_sub(1);
+        // Index 0 is "Create new branch", so the last branch is at filtered_branches.len()
+        if self.selected_branch > self.filtered_branches.len() {
+            self.selected_branch = self.filtered_branches.len();
+        }
+    }
+
+    /// Move the Create mode selection, swapping the input between the branch
+    /// filter (on "Create new branch") and the worktree name (on a branch)
+    fn select_branch(&mut self, index: usize) {
+        // Quick create has no branch list to move through
+        if self.quick_create {
+            return;
+        }
+        let was_create_new = self.selected_branch == 0;
+        self.selected_branch = index;
+        if was_create_new && index > 0 {
+            if self.create_field != CreateField::BranchName {
+                self.stash_create_field();
+                self.create_field = CreateField::BranchName;
+            }
+            // Keep the list narrowed, but start the worktree name empty
+            self.input.clear();
+        } else if !was_create_new && index == 0 && self.input.is_empty() {
+            self.input = self.branch_filter.clone();
+            self.move_cursor_end();
+        }
+    }
+
+    /// Cycle the input through the new branch name, its worktree name and its start point
+    pub fn toggle_create_field(&mut self) {
+        if self.mode != AppMode::Create || self.selected_branch != 0 {
+            return;
+        }
+        self.stash_create_field();
+        let (field, input) = match self.create_field {
+            CreateField::BranchName => (
+                CreateField::WorktreeName,
+                std::mem::take(&mut self.worktree_name_input),
+            ),
+            CreateField::WorktreeName => (
+                CreateField::BaseRef,
+                std::mem::take(&mut self.base_ref_input),
+            ),
+            CreateField::BaseRef => (CreateField::BranchName, self.branch_filter.clone()),
+        };
+        self.create_field = field;
+        self.input = input;
+        self.move_cursor_end();
+    }
+
+    /// Move the input into the storage of the active create field
+    fn stash_create_field(&mut self) {
+        match self.create_field {
+            // branch_filter already holds the branch name typed so far
+            CreateField::BranchName => {}
+            CreateField::WorktreeName => self.worktree_name_input = std::mem::take(&mut self.input),
+            CreateField::BaseRef => self.base_ref_input = std::mem::take(&mut self.input),
+        }
+    }
+
+    /// Branch name, worktree name and start point entered on "Create new branch"
+    fn create_inputs(&self) -> (&str, &str, &str) {
+        match self.create_field {
+            CreateField::BranchName => {
+                (&self.input, &self.worktree_name_input, &self.base_ref_input)
+            }
+            CreateField::WorktreeName => (&self.branch_filter, &self.input, &self.base_ref_input),
+            CreateField::BaseRef => (&self.branch_filter, &self.worktree_name_input, &self.input),
         }
     }
 
+    /// Start point for a new branch (empty = HEAD)
+    pub fn create_base_ref(&self) -> &str {
+        self.create_inputs().2.trim()
+    }
+
+    /// Form index of the branch `worktree.create_base` names for the worktree
+    /// selected in Normal mode (0 = "Create new branch")
+    fn create_base_index(&self) -> usize {
+        let base = match self.config.create_base() {
+            CreateBase::Main => self.prune_base().ok(),
+            CreateBase::Current => self
+                .filtered_worktrees
+                .get(self.selected_worktree)
+                .and_then(|w| w.branch.clone()),
+            CreateBase::First => None,
+        };
+        base.and_then(|base| self.filtered_branches.iter().position(|b| b.name == base))
+            // +1 for "Create new branch" option at index 0
+            .map_or(0, |i| i + 1)
+    }
+
     pub fn move_up(&mut self) {
         match self.mode {
-            AppMode::Normal => {
-                if self.selected_worktree > 0 {
-                    self.selected_worktree -= 1;
-                }
+            AppMode::Normal if self.selected_worktree > 0 => {
+                self.selected_worktree -= 1;
             }
-            AppMode::Create => {
-                if self.selected_branch > 0 {
-                    self.selected_branch -= 1;
-                }
+            AppMode::Create if self.selected_branch > 0 => {
+                self.select_branch(self.selected_branch - 1);
             }
             _ => {}
         }
@@ -210,77 +923,663 @@ impl App {
 
     pub fn move_down(&mut self) {
         match self.mode {
-            AppMode::Normal => {
-                if self.selected_worktree < self.filtered_worktrees.len().saturating_sub(1) {
-                    self.selected_worktree += 1;
-                }
+            AppMode::Normal
+                if self.selected_worktree < self.filtered_worktrees.len().saturating_sub(1) =>
+            {
+                self.selected_worktree += 1;
             }
             AppMode::Create => {
                 // +1 for "Create new branch" option at index 0
                 let max_index = self.filtered_branches.len();
                 if self.selected_branch < max_index {
-                    self.selected_branch += 1;
+                    self.select_branch(self.selected_branch + 1);
                 }
             }
             _ => {}
         }
     }
 
+    pub fn move_first(&mut self) {
+        match self.mode {
+            AppMode::Normal => self.selected_worktree = 0,
+            AppMode::Create => self.select_branch(0),
+            _ => {}
+        }
+    }
+
+    pub fn move_last(&mut self) {
+        match self.mode {
+            AppMode::Normal => {
+                self.selected_worktree = self.filtered_worktrees.len().saturating_sub(1);
+            }
+            // +1 for "Create new branch" option at index 0
+            AppMode::Create => self.select_branch(self.filtered_branches.len()),
+            _ => {}
+        }
+    }
+
+    /// Number of items a PageUp/PageDown moves, based on the visible list height
+    pub fn page_size(&self) -> usize {
+        (self.list_height as usize).saturating_sub(1).max(1)
+    }
+
+    pub fn page_up(&mut self) {
+        let page = self.page_size();
+        match self.mode {
+            AppMode::Normal => {
+                self.selected_worktree = self.selected_worktree.saturating_sub(page);
+            }
+            AppMode::Create => {
+                self.select_branch(self.selected_branch.saturating_sub(page));
+            }
+            _ => {}
+        }
+    }
+
+    pub fn page_down(&mut self) {
+        let page = self.page_size();
+        match self.mode {
+            AppMode::Normal => {
+                let max_index = self.filtered_worktrees.len().saturating_sub(1);
+                self.selected_worktree = (self.selected_worktree + page).min(max_index);
+            }
+            AppMode::Create => {
+                // +1 for "Create new branch" option at index 0
+                let max_index = self.filtered_branches.len();
+                self.select_branch((self.selected_branch + page).min(max_index));
+            }
+            _ => {}
+        }
+    }
+
     pub fn enter_create_mode(&mut self) -> Result<(), AppError> {
         self.input.clear();
-        self.refresh_branches()?;
+        self.worktree_name_input.clear();
+        self.base_ref_input.clear();
+        self.create_field = CreateField::BranchName;
+        if self.branches_stale {
+            self.refresh_branches()?;
+        } else {
+            self.filter_branches();
+        }
         self.mode = AppMode::Create;
-        // Select "Create new branch" by default (index 0)
+        self.selected_branch = self.create_base_index();
+        self.open_after_create = false;
+        self.quick_create = false;
+        Ok(())
+    }
+
+    /// Create mode reduced to a branch name prompt. The new branch starts at
+    /// the selected worktree's branch (its commit for a detached HEAD).
+    pub fn enter_quick_create_mode(&mut self) -> Result<(), AppError> {
+        let base = self
+            .filtered_worktrees
+            .get(self.selected_worktree)
+            .and_then(|w| w.branch.clone().or_else(|| w.head_id.clone()));
+        self.enter_create_mode()?;
         self.selected_branch = 0;
+        self.base_ref_input = base.unwrap_or_default();
+        self.quick_create = true;
+        Ok(())
+    }
+
+    /// Create mode for checking out a branch: starts on the first existing
+    /// branch and opens the worktree once it exists
+    pub fn enter_checkout_mode(&mut self) -> Result<(), AppError> {
+        self.enter_create_mode()?;
+        if self.selected_branch == 0 && !self.filtered_branches.is_empty() {
+            self.selected_branch = 1;
+        }
+        self.open_after_create = true;
         Ok(())
     }
 
+    /// Whether a background create, delete, fetch or setup is running
+    pub fn operation_in_progress(&self) -> bool {
+        matches!(
+            self.mode,
+            AppMode::Creating | AppMode::Deleting | AppMode::Fetching | AppMode::SettingUp
+        )
+    }
+
+    /// Ask to quit. Returns true when gwm can exit now; while an operation is
+    /// running, the first request only asks for confirmation.
+    pub fn request_quit(&mut self) -> bool {
+        if !self.operation_in_progress() || self.quit_pending {
+            return true;
+        }
+        self.quit_pending = true;
+        false
+    }
+
     pub fn enter_normal_mode(&mut self) {
         self.mode = AppMode::Normal;
+        self.open_after_create = false;
+        self.quick_create = false;
         self.input.clear();
         self.confirm_action = None;
+        self.confirm_size = None;
+        self.confirm_reason = None;
+        self.size_receiver = None;
+        self.move_source = None;
         self.filter_worktrees();
     }
 
-    pub fn enter_config_mode(&mut self) {
-        self.mode = AppMode::Config;
-        self.config_scroll = 0;
-    }
-
-    pub fn scroll_config_up(&mut self) {
-        self.config_scroll = self.config_scroll.saturating_sub(1);
+    /// Leave the current dialog and report what a `--dry-run` skipped
+    fn report_dry_run(&mut self, action: String) {
+        self.enter_normal_mode();
+        self.set_message(MessageLevel::Success, format!("Dry run: would {}", action));
     }
 
-    pub fn scroll_config_down(&mut self) {
-        if self.config_scroll < self.config_scroll_max {
-            self.config_scroll = self.config_scroll.saturating_add(1);
+    /// Prompt for a new location of the selected worktree, prefilled with its current path
+    pub fn enter_move_mode(&mut self) {
+        let Some(worktree) = self.filtered_worktrees.get(self.selected_worktree) else {
+            return;
+        };
+        if worktree.is_main {
+            self.set_message(MessageLevel::Error, "Cannot move main worktree");
+            return;
         }
+
+        self.move_source = Some(worktree.clone());
+        self.input = self.format_path(&worktree.path.to_string_lossy());
+        self.input_cursor = None;
+        self.mode = AppMode::Move;
     }
 
-    pub fn enter_confirm_delete(&mut self) {
-        if !self.filtered_worktrees.is_empty() {
-            let worktree = &self.filtered_worktrees[self.selected_worktree];
-            if !worktree.is_main {
-                self.mode = AppMode::Confirm;
-                self.confirm_action = Some(ConfirmAction::DeleteSingle);
-            } else {
-                self.message = Some("Cannot delete main worktree".to_string());
-            }
+    /// Move the worktree chosen in Move mode to the path in the input
+    pub fn move_worktree(&mut self) -> Result<(), AppError> {
+        let Some(source) = self.move_source.clone() else {
+            return Ok(());
+        };
+        let input = self.input.trim();
+        if input.is_empty() {
+            self.set_message(MessageLevel::Error, "Please enter a destination path");
+            return Ok(());
         }
-    }
 
-    pub fn enter_confirm_prune(&mut self) -> Result<(), AppError> {
-        self.merged_worktrees = self.git.find_merged_worktrees()?;
-        if self.merged_worktrees.is_empty() {
-            self.message = Some("No merged worktrees to prune".to_string());
-        } else {
-            self.mode = AppMode::Confirm;
-            self.confirm_action = Some(ConfirmAction::Prune);
+        // Relative destinations are resolved from the repository root, like worktree.basedir
+        let destination = self.git.repo_root().join(self.config.expand_path(input));
+        if destination == source.path {
+            self.enter_normal_mode();
+            return Ok(());
+        }
+        if self.dry_run {
+            let destination = self.format_path(&destination.to_string_lossy());
+            self.report_dry_run(format!(
+                "move worktree '{}' to {}",
+                source.name, destination
+            ));
+            return Ok(());
+        }
+        if let Err(msg) = self.ensure_parent_dir(&destination) {
+            self.set_message(MessageLevel::Error, msg);
+            return Ok(());
+        }
+        if let Err(e) = self.git.move_worktree(&source.path, &destination) {
+            // Stay in Move mode so the destination can be corrected
+            self.set_message(
+                MessageLevel::Error,
+                format!("Failed to move worktree: {}", e),
+            );
+            return Ok(());
         }
-        Ok(())
-    }
+
+        let destination = destination.canonicalize().unwrap_or(destination);
+        self.enter_normal_mode();
+        self.set_message(
+            MessageLevel::Success,
+            format!(
+                "Moved worktree '{}' to {}",
+                source.name,
+                self.format_path(&destination.to_string_lossy())
+            ),
+        );
+        self.refresh_worktrees()?;
+        self.restore_selection(&destination);
+        Ok(())
+    }
+
+    pub fn enter_help_mode(&mut self) {
+        self.mode = AppMode::Help;
+        self.config_scroll = 0;
+    }
+
+    pub fn enter_message_log_mode(&mut self) {
+        self.mode = AppMode::MessageLog;
+        self.config_scroll = 0;
+    }
+
+    pub fn enter_config_mode(&mut self) {
+        self.mode = AppMode::Config;
+        self.config_scroll = 0;
+    }
+
+    pub fn scroll_config_up(&mut self) {
+        self.config_scroll = self.config_scroll.saturating_sub(1);
+    }
+
+    pub fn scroll_config_down(&mut self) {
+        if self.config_scroll < self.config_scroll_max {
+            self.config_scroll = self.config_scroll.saturating_add(1);
+        }
+    }
+
+    pub fn scroll_config_top(&mut self) {
+        self.config_scroll = 0;
+    }
+
+    pub fn scroll_config_bottom(&mut self) {
+        self.config_scroll = self.config_scroll_max;
+    }
+
+    /// Switch Up / Down between the worktree list and the detail pane
+    pub fn toggle_focus(&mut self) {
+        self.focus = match self.focus {
+            Focus::List => Focus::Detail,
+            Focus::Detail => Focus::List,
+        };
+    }
+
+    /// Reset the detail pane to the top when another worktree was selected
+    pub fn sync_detail_scroll(&mut self) {
+        let path = self
+            .filtered_worktrees
+            .get(self.selected_worktree)
+            .map(|w| w.path.clone());
+        if path != self.detail_scroll_path {
+            self.detail_scroll = 0;
+            self.detail_scroll_path = path;
+        }
+    }
+
+    pub fn scroll_detail_up(&mut self) {
+        self.sync_detail_scroll();
+        self.detail_scroll = self.detail_scroll.saturating_sub(1);
+    }
+
+    pub fn scroll_detail_down(&mut self) {
+        self.sync_detail_scroll();
+        if self.detail_scroll < self.detail_scroll_max {
+            self.detail_scroll = self.detail_scroll.saturating_add(1);
+        }
+    }
+
+    /// Mark or unmark the selected worktree for batch deletion, then move down
+    pub fn toggle_mark(&mut self) {
+        let Some(worktree) = self.filtered_worktrees.get(self.selected_worktree) else {
+            return;
+        };
+        if worktree.is_main {
+            self.set_message(MessageLevel::Error, "Cannot mark main worktree");
+            return;
+        }
+        let path = worktree.path.clone();
+        if !self.marked_worktrees.remove(&path) {
+            self.marked_worktrees.insert(path);
+        }
+        self.move_down();
+    }
+
+    /// Marked worktrees in list order
+    pub fn marked_worktree_list(&self) -> Vec<Worktree> {
+        self.worktrees
+            .iter()
+            .filter(|w| self.marked_worktrees.contains(&w.path))
+            .cloned()
+            .collect()
+    }
+
+    /// Marked worktrees in list order, split into those a batch delete
+    /// removes, the locked ones and the ones with uncommitted changes. The
+    /// last two are kept: only the single-delete dialog can force or stash
+    pub fn marked_targets(&self) -> (Vec<Worktree>, Vec<Worktree>, Vec<Worktree>) {
+        let (mut deletable, mut locked, mut dirty) = (Vec::new(), Vec::new(), Vec::new());
+        for worktree in self.marked_worktree_list() {
+            if worktree.is_locked {
+                locked.push(worktree);
+            } else if worktree.is_dirty {
+                dirty.push(worktree);
+            } else {
+                deletable.push(worktree);
+            }
+        }
+        (deletable, locked, dirty)
+    }
+
+    /// Worktrees the open confirm dialog would delete
+    fn confirm_targets(&self) -> Vec<PathBuf> {
+        match self.confirm_action {
+            Some(ConfirmAction::DeleteSingle) => self
+                .filtered_worktrees
+                .get(self.selected_worktree)
+                .map(|w| vec![w.path.clone()])
+                .unwrap_or_default(),
+            Some(ConfirmAction::DeleteMarked) => self
+                .marked_targets()
+                .0
+                .into_iter()
+                .map(|w| w.path)
+                .collect(),
+            Some(ConfirmAction::Prune) => {
+                self.prune_selection().into_iter().map(|w| w.path).collect()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Measure the disk usage of the confirm dialog's targets in a background
+    /// thread (`confirm.show_size`)
+    fn start_size_measure(&mut self) {
+        self.confirm_size = None;
+        self.size_receiver = None;
+        if !self.config.confirm_show_size() {
+            return;
+        }
+        let paths = self.confirm_targets();
+        let (tx, rx) = mpsc::channel();
+        self.size_receiver = Some(rx);
+        std::thread::spawn(move || {
+            let deadline = Instant::now() + SIZE_TIMEOUT;
+            let mut total = DirSize::default();
+            for path in paths {
+                let size = directory_size(&path, deadline);
+                total.bytes += size.bytes;
+                total.partial |= size.partial;
+            }
+            // The dialog may be gone already
+            let _ = tx.send(total);
+        });
+    }
+
+    /// Pick up the disk usage measured for the confirm dialog
+    pub fn check_size_update(&mut self) {
+        let Some(receiver) = &self.size_receiver else {
+            return;
+        };
+        match receiver.try_recv() {
+            Ok(size) => {
+                self.confirm_size = Some(size);
+                self.size_receiver = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Disconnected) => self.size_receiver = None,
+        }
+    }
+
+    /// Whether the confirm dialog's disk usage is still being measured
+    pub fn measuring_size(&self) -> bool {
+        self.size_receiver.is_some()
+    }
+
+    /// Why removing `worktree` asks even with the confirm prompt off, if it
+    /// does: the dialog offers force delete and stash, and a pending status
+    /// may still turn out dirty
+    fn must_confirm(&self, worktree: &Worktree) -> Option<String> {
+        if worktree.is_locked {
+            Some(format!("{} is locked", worktree.name))
+        } else if worktree.is_dirty {
+            Some(format!("{} has uncommitted changes", worktree.name))
+        } else if self.pending_status.contains(&worktree.path) {
+            Some(format!(
+                "{} is still being checked for changes",
+                worktree.name
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Open the dialog for `action`, or run it right away when `confirm` is
+    /// off and none of `targets` needs asking about
+    fn open_confirm(
+        &mut self,
+        action: ConfirmAction,
+        confirm: bool,
+        setting: &str,
+        targets: &[Worktree],
+    ) -> Result<(), AppError> {
+        self.mode = AppMode::Confirm;
+        self.confirm_action = Some(action);
+        self.confirm_reason = None;
+        if !confirm {
+            let reasons: Vec<String> = targets
+                .iter()
+                .filter_map(|w| self.must_confirm(w))
+                .collect();
+            if reasons.is_empty() {
+                return self.confirm_action(false);
+            }
+            // One line per reason, since the dialog doesn't wrap
+            self.confirm_reason = Some(format!(
+                "Asking although {} is off:\n{}",
+                setting,
+                reasons
+                    .iter()
+                    .map(|r| format!("! {}", r))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ));
+        }
+        self.start_size_measure();
+        Ok(())
+    }
+
+    /// Ask before deleting the marked worktrees or the selected one, or delete
+    /// right away when `confirm.delete` is off
+    pub fn enter_confirm_delete(&mut self) -> Result<(), AppError> {
+        self.enter_delete(self.config.confirm_delete())
+    }
+
+    /// Ask before deleting even with `confirm.delete` off, for the `dd` chord
+    /// that a stray double keypress can trigger
+    pub fn enter_confirm_delete_always(&mut self) -> Result<(), AppError> {
+        self.enter_delete(true)
+    }
+
+    fn enter_delete(&mut self, confirm: bool) -> Result<(), AppError> {
+        if !self.marked_worktrees.is_empty() {
+            let marked = self.marked_worktree_list();
+            return self.open_confirm(
+                ConfirmAction::DeleteMarked,
+                confirm,
+                "confirm.delete",
+                &marked,
+            );
+        }
+        let Some(worktree) = self.filtered_worktrees.get(self.selected_worktree).cloned() else {
+            return Ok(());
+        };
+        if worktree.is_main {
+            self.set_message(MessageLevel::Error, "Cannot delete main worktree");
+            return Ok(());
+        }
+        self.open_confirm(
+            ConfirmAction::DeleteSingle,
+            confirm,
+            "confirm.delete",
+            &[worktree],
+        )
+    }
+
+    /// Branch prune checks against: `worktree.default_base`, else the
+    /// default remote's HEAD, else main / master
+    fn prune_base(&self) -> Result<String, AppError> {
+        match self.config.default_base() {
+            Some(base) => Ok(base.to_string()),
+            None => Ok(self.git.get_default_branch(self.config.default_remote())?),
+        }
+    }
+
+    pub fn enter_confirm_prune(&mut self) -> Result<(), AppError> {
+        let base = self.prune_base()?;
+        self.enter_confirm_prune_into(base)
+    }
+
+    /// Prune worktrees merged into the branch selected in Create mode
+    pub fn enter_confirm_prune_into_selected(&mut self) -> Result<(), AppError> {
+        let Some(branch) = self
+            .selected_branch
+            .checked_sub(1)
+            .and_then(|i| self.filtered_branches.get(i))
+        else {
+            self.set_message(MessageLevel::Error, "Select a branch to prune against");
+            return Ok(());
+        };
+        let base = branch.name.clone();
+        self.enter_confirm_prune_into(base)
+    }
+
+    fn enter_confirm_prune_into(&mut self, base: String) -> Result<(), AppError> {
+        let (excluded, merged): (Vec<_>, Vec<_>) = self
+            .git
+            .find_merged_worktrees(&base)?
+            .into_iter()
+            .partition(|w| {
+                w.branch
+                    .as_deref()
+                    .is_some_and(|b| self.config.prune_excluded(b))
+            });
+        // Locked worktrees are only removed one at a time with a force delete
+        let (locked, merged): (Vec<_>, Vec<_>) = merged.into_iter().partition(|w| w.is_locked);
+        self.merged_worktrees = merged;
+        self.prune_excluded = excluded;
+        self.prune_locked = locked;
+        self.prune_unchecked.clear();
+        self.prune_cursor = 0;
+        if self.merged_worktrees.is_empty() {
+            let mut kept = Vec::new();
+            if !self.prune_excluded.is_empty() {
+                kept.push(format!(
+                    "{} kept by worktree.prune_exclude",
+                    self.prune_excluded.len()
+                ));
+            }
+            if !self.prune_locked.is_empty() {
+                kept.push(format!("{} locked", self.prune_locked.len()));
+            }
+            let mut message = format!("No worktrees merged into '{}' to prune", base);
+            if !kept.is_empty() {
+                message.push_str(&format!(" ({})", kept.join(", ")));
+            }
+            self.set_message(MessageLevel::Success, message);
+        } else {
+            self.prune_base = base;
+            let merged = self.merged_worktrees.clone();
+            return self.open_confirm(
+                ConfirmAction::Prune,
+                self.config.confirm_prune(),
+                "confirm.prune",
+                &merged,
+            );
+        }
+        Ok(())
+    }
+
+    /// Merged worktrees still checked in the prune dialog, in list order
+    pub fn prune_selection(&self) -> Vec<Worktree> {
+        self.merged_worktrees
+            .iter()
+            .filter(|w| !self.prune_unchecked.contains(&w.path))
+            .cloned()
+            .collect()
+    }
+
+    /// Move the prune dialog's highlight up one row
+    pub fn prune_cursor_up(&mut self) {
+        self.prune_cursor = self.prune_cursor.saturating_sub(1);
+    }
+
+    /// Move the prune dialog's highlight down one row
+    pub fn prune_cursor_down(&mut self) {
+        if self.prune_cursor + 1 < self.merged_worktrees.len() {
+            self.prune_cursor += 1;
+        }
+    }
+
+    /// Check or uncheck the highlighted worktree in the prune dialog
+    pub fn toggle_prune_item(&mut self) {
+        if self.confirm_action != Some(ConfirmAction::Prune) {
+            return;
+        }
+        let Some(worktree) = self.merged_worktrees.get(self.prune_cursor) else {
+            return;
+        };
+        let path = worktree.path.clone();
+        if !self.prune_unchecked.remove(&path) {
+            self.prune_unchecked.insert(path);
+        }
+        self.start_size_measure();
+    }
 
     pub fn confirm_action(&mut self, delete_branch: bool) -> Result<(), AppError> {
+        self.run_confirm_action(delete_branch, false)
+    }
+
+    /// Stash the selected worktree's changes, then delete it
+    pub fn confirm_stash_delete(&mut self) -> Result<(), AppError> {
+        if self.confirm_action != Some(ConfirmAction::DeleteSingle) {
+            return Ok(());
+        }
+        let Some(worktree) = self.filtered_worktrees.get(self.selected_worktree) else {
+            return Ok(());
+        };
+        if worktree.is_main || !worktree.is_dirty {
+            return Ok(());
+        }
+        if self.dry_run {
+            let name = worktree.name.clone();
+            self.report_dry_run(format!("stash changes and delete worktree '{}'", name));
+            return Ok(());
+        }
+
+        match self.git.stash_worktree(&worktree.path) {
+            Ok(oid) => self.delete_stash = Some(oid),
+            Err(e) => {
+                self.set_message(
+                    MessageLevel::Error,
+                    format!("Failed to stash changes in '{}': {}", worktree.name, e),
+                );
+                self.enter_normal_mode();
+                return Ok(());
+            }
+        }
+        self.run_confirm_action(false, false)?;
+        // A pre-delete hook can stop the delete before it starts
+        if self.mode != AppMode::Deleting {
+            self.report_delete_stash();
+        }
+        Ok(())
+    }
+
+    /// Add the stash taken before a delete to the status message, so the
+    /// changes can be recovered
+    fn report_delete_stash(&mut self) {
+        let Some(oid) = self.delete_stash.take() else {
+            return;
+        };
+        let note = format!("changes stashed as stash@{{0}} ({})", &oid[..7]);
+        match self.message.take() {
+            Some(message) => {
+                // Log the extended message in place of the one it extends
+                if self.message_log.back().is_some_and(|m| m.text == message) {
+                    self.message_log.pop_back();
+                }
+                self.set_message(self.message_level, format!("{} ({})", message, note));
+            }
+            None => self.set_message(MessageLevel::Success, format!("Worktree {}", note)),
+        }
+    }
+
+    /// Confirm deleting the selected worktree even if it is locked
+    pub fn confirm_force_delete(&mut self) -> Result<(), AppError> {
+        if self.confirm_action != Some(ConfirmAction::DeleteSingle) {
+            return Ok(());
+        }
+        self.run_confirm_action(false, true)
+    }
+
+    fn run_confirm_action(&mut self, delete_branch: bool, force: bool) -> Result<(), AppError> {
         let repo_root = self.git.repo_root().clone();
 
         match self.confirm_action {
@@ -291,13 +1590,30 @@ impl App {
                 }
                 let worktree = self.filtered_worktrees[self.selected_worktree].clone();
                 if worktree.is_main {
-                    self.message = Some("Cannot delete main worktree".to_string());
+                    self.set_message(MessageLevel::Error, "Cannot delete main worktree");
+                    self.enter_normal_mode();
+                    return Ok(());
+                }
+                if self.dry_run {
+                    let branch = worktree
+                        .branch
+                        .as_ref()
+                        .filter(|_| delete_branch)
+                        .map(|b| format!(" and branch '{}'", b))
+                        .unwrap_or_default();
+                    self.report_dry_run(format!("delete worktree '{}'{}", worktree.name, branch));
+                    return Ok(());
+                }
+
+                let hook_runner = self.create_hook_runner();
+                if let Err(e) = hook_runner.run(HookEvent::PreDelete, &worktree) {
+                    self.set_message(MessageLevel::Error, e.to_string());
                     self.enter_normal_mode();
                     return Ok(());
                 }
 
                 let branch_name = worktree.branch.clone();
-                self.deleting_message = Some(format!("Deleting worktree '{}'...", worktree.name));
+                self.progress_message = Some(format!("Deleting worktree '{}'...", worktree.name));
 
                 let (tx, rx) = mpsc::channel();
                 self.delete_receiver = Some(rx);
@@ -306,29 +1622,54 @@ impl App {
 
                 let wt_name = worktree.name.clone();
                 std::thread::spawn(move || {
-                    let result =
-                        execute_delete_single(&repo_root, &wt_name, branch_name, delete_branch);
+                    let mut result = execute_delete_single(
+                        &repo_root,
+                        &wt_name,
+                        branch_name,
+                        delete_branch,
+                        force,
+                    );
+                    if let DeleteResult::SingleCompleted {
+                        ref mut error_message,
+                        ..
+                    } = result
+                    {
+                        if let Err(e) = hook_runner.run(HookEvent::PostDelete, &worktree) {
+                            error_message.get_or_insert_with(|| e.to_string());
+                        }
+                    }
                     let _ = tx.send(result);
                 });
             }
             Some(ConfirmAction::Prune) => {
-                let worktrees: Vec<(String, Option<String>)> = self
-                    .merged_worktrees
-                    .iter()
-                    .map(|w| (w.name.clone(), w.branch.clone()))
-                    .collect();
-                let count = worktrees.len();
-                self.deleting_message = Some(format!("Pruning {} worktree(s)...", count));
-
-                let (tx, rx) = mpsc::channel();
-                self.delete_receiver = Some(rx);
-                self.mode = AppMode::Deleting;
-                self.tick = 0;
-
-                std::thread::spawn(move || {
-                    let result = execute_prune(&repo_root, worktrees, delete_branch);
-                    let _ = tx.send(result);
-                });
+                let checked = self.prune_selection();
+                if checked.is_empty() {
+                    // Stay in the dialog so something can be checked again
+                    self.set_message(MessageLevel::Error, "No worktrees checked to prune");
+                    return Ok(());
+                }
+                self.start_batch_delete(checked, delete_branch, "Pruning");
+            }
+            Some(ConfirmAction::DeleteMarked) => {
+                let (marked, _, _) = self.marked_targets();
+                // Changes a loading status would reveal must not be deleted unseen
+                if marked.iter().any(|w| self.pending_status.contains(&w.path)) {
+                    self.set_message(
+                        MessageLevel::Warning,
+                        "Still checking marked worktrees for uncommitted changes",
+                    );
+                    return Ok(());
+                }
+                if marked.is_empty() {
+                    self.set_message(
+                        MessageLevel::Error,
+                        "All marked worktrees are locked or have uncommitted changes; \
+                         delete them one at a time",
+                    );
+                    self.enter_normal_mode();
+                    return Ok(());
+                }
+                self.start_batch_delete(marked, delete_branch, "Deleting");
             }
             None => {
                 self.enter_normal_mode();
@@ -337,6 +1678,64 @@ impl App {
         Ok(())
     }
 
+    /// Delete several worktrees in a background thread
+    fn start_batch_delete(&mut self, targets: Vec<Worktree>, delete_branch: bool, verb: &str) {
+        if self.dry_run {
+            let names: Vec<&str> = targets.iter().map(|w| w.name.as_str()).collect();
+            let verb = if verb == "Pruning" { "prune" } else { "delete" };
+            let branches = if delete_branch {
+                " and their branches"
+            } else {
+                ""
+            };
+            self.report_dry_run(format!(
+                "{} {} worktree(s){}: {}",
+                verb,
+                targets.len(),
+                branches,
+                names.join(", ")
+            ));
+            return;
+        }
+        let repo_root = self.git.repo_root().clone();
+        let hook_runner = self.create_hook_runner();
+        for worktree in &targets {
+            if let Err(e) = hook_runner.run(HookEvent::PreDelete, worktree) {
+                self.set_message(MessageLevel::Error, e.to_string());
+                self.enter_normal_mode();
+                return;
+            }
+        }
+
+        let worktrees: Vec<(String, Option<String>)> = targets
+            .iter()
+            .map(|w| (w.name.clone(), w.branch.clone()))
+            .collect();
+        self.progress_message = Some(format!("{} {} worktree(s)...", verb, worktrees.len()));
+
+        let (tx, rx) = mpsc::channel();
+        self.delete_receiver = Some(rx);
+        self.mode = AppMode::Deleting;
+        self.tick = 0;
+
+        std::thread::spawn(move || {
+            let mut result = execute_prune(&repo_root, worktrees, delete_branch);
+            if let DeleteResult::PruneCompleted {
+                ref mut hook_errors,
+                ..
+            } = result
+            {
+                // Post-delete hooks only run for worktrees that were actually removed
+                for worktree in targets.iter().filter(|w| !w.path.exists()) {
+                    if let Err(e) = hook_runner.run(HookEvent::PostDelete, worktree) {
+                        hook_errors.push(e.to_string());
+                    }
+                }
+            }
+            let _ = tx.send(result);
+        });
+    }
+
     /// Check if a background delete operation has completed
     pub fn check_delete_completion(&mut self) -> Result<(), AppError> {
         let result = match self.delete_receiver {
@@ -345,9 +1744,9 @@ impl App {
                 Err(mpsc::TryRecvError::Empty) => return Ok(()),
                 Err(mpsc::TryRecvError::Disconnected) => {
                     self.delete_receiver = None;
-                    self.deleting_message = None;
+                    self.progress_message = None;
                     self.enter_normal_mode();
-                    self.message = Some("Delete operation failed unexpectedly".to_string());
+                    self.set_message(MessageLevel::Error, "Delete operation failed unexpectedly");
                     return Ok(());
                 }
             },
@@ -363,42 +1762,72 @@ impl App {
                     error_message,
                 } => {
                     if let Some(err_msg) = error_message {
-                        self.message = Some(err_msg);
+                        self.set_message(MessageLevel::Error, err_msg);
                     } else if branch_deleted {
                         if let Some(ref branch) = branch_name {
-                            self.message = Some(format!(
-                                "Deleted worktree '{}' and branch '{}'",
-                                worktree_name, branch
-                            ));
+                            self.set_message(
+                                MessageLevel::Success,
+                                format!(
+                                    "Deleted worktree '{}' and branch '{}'",
+                                    worktree_name, branch
+                                ),
+                            );
                         } else {
-                            self.message = Some(format!("Deleted worktree: {}", worktree_name));
+                            self.set_message(
+                                MessageLevel::Success,
+                                format!("Deleted worktree: {}", worktree_name),
+                            );
                         }
                     } else {
-                        self.message = Some(format!("Deleted worktree: {}", worktree_name));
+                        self.set_message(
+                            MessageLevel::Success,
+                            format!("Deleted worktree: {}", worktree_name),
+                        );
                     }
                 }
                 DeleteResult::PruneCompleted {
                     worktree_count,
                     branch_count,
+                    failed,
+                    hook_errors,
                 } => {
-                    if branch_count > 0 {
-                        self.message = Some(format!(
+                    let marked = !self.marked_worktrees.is_empty();
+                    let mut message = match (marked, branch_count > 0) {
+                        (true, true) => format!(
+                            "Deleted {} worktree(s) and {} branch(es)",
+                            worktree_count, branch_count
+                        ),
+                        (true, false) => format!("Deleted {} worktree(s)", worktree_count),
+                        (false, true) => format!(
                             "Pruned {} worktree(s) and {} branch(es)",
                             worktree_count, branch_count
-                        ));
+                        ),
+                        (false, false) => format!("Pruned {} merged worktree(s)", worktree_count),
+                    };
+                    let mut level = MessageLevel::Success;
+                    if !failed.is_empty() {
+                        message = format!("{}; could not delete {}", message, failed.join("; "));
+                        level = MessageLevel::Error;
+                    }
+                    if !hook_errors.is_empty() {
+                        message = format!("{}, but {}", message, hook_errors.join("; "));
+                        level = MessageLevel::Error;
+                    }
+                    self.set_message(level, message);
+                    if marked {
+                        self.marked_worktrees.clear();
                     } else {
-                        self.message =
-                            Some(format!("Pruned {} merged worktree(s)", worktree_count));
+                        self.merged_worktrees.clear();
                     }
-                    self.merged_worktrees.clear();
                 }
                 DeleteResult::Error(err) => {
-                    self.message = Some(format!("Error: {}", err));
+                    self.set_message(MessageLevel::Error, format!("Error: {}", err));
                 }
             }
 
             self.delete_receiver = None;
-            self.deleting_message = None;
+            self.progress_message = None;
+            self.report_delete_stash();
             self.enter_normal_mode();
             self.refresh_worktrees()?;
         }
@@ -406,1394 +1835,6076 @@ impl App {
         Ok(())
     }
 
-    pub fn create_worktree(&mut self) -> Result<(), AppError> {
-        let base_path = self
-            .config
-            .worktree_basedir_expanded_with_repo_root(self.git.repo_root());
-        let repo_info = self.git.get_repo_info();
-
-        // Auto-create base directory if enabled
-        if self.config.auto_mkdir() {
-            let base_dir = std::path::Path::new(&base_path);
-            if !base_dir.exists() {
-                std::fs::create_dir_all(base_dir)
-                    .map_err(|e| AppError::Git(crate::git::GitError::IoError(e)))?;
-            }
+    /// Fetch all remotes in a background thread, then refresh the list
+    pub fn fetch_all(&mut self) {
+        if self.fetch_receiver.is_some() {
+            return;
         }
+        let repo_root = self.git.repo_root().clone();
+        let (tx, rx) = mpsc::channel();
+        self.fetch_receiver = Some(rx);
+        self.progress_message = Some("Fetching all remotes...".to_string());
+        self.mode = AppMode::Fetching;
+
+        std::thread::spawn(move || {
+            let result = crate::git::fetch_all(&repo_root).map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+    }
 
-        // Check if "Create new branch" is selected (index 0)
-        if self.selected_branch == 0 {
-            // Creating a new branch requires input
-            if self.input.is_empty() {
-                self.message = Some("Please enter a branch name".to_string());
-                return Ok(());
-            }
-
-            let branch_name = self.input.clone();
-            let worktree_name = match self
-                .config
-                .generate_worktree_name(&branch_name, repo_info.as_ref())
-            {
-                Ok(name) => name,
-                Err(e) => {
-                    self.message = Some(format!("{}", e));
-                    return Ok(());
-                }
-            };
-
-            // Create worktree with a new branch (atomic operation)
-            let worktree = match self.git.create_worktree_with_new_branch(
-                &worktree_name,
-                &branch_name,
-                &base_path,
-            ) {
-                Ok(wt) => wt,
-                Err(e) => {
-                    let error_msg = e.to_string();
-                    if error_msg.contains("already exists") && error_msg.contains("branch") {
-                        self.message = Some(format!("Branch '{}' already exists", branch_name));
-                    } else if error_msg.contains("directory exists") {
-                        self.message = Some(format!(
-                            "Directory '{}' already exists. Run 'git worktree prune' to clean up",
-                            worktree_name
-                        ));
-                    } else {
-                        self.message = Some(format!("Failed to create: {}", e));
-                    }
-                    return Ok(());
+    /// Check if a background fetch has completed. A failed fetch is only a
+    /// warning: the local worktrees and branches are still usable.
+    pub fn check_fetch_completion(&mut self) -> Result<(), AppError> {
+        let result = match self.fetch_receiver {
+            Some(ref receiver) => match receiver.try_recv() {
+                Ok(result) => result,
+                Err(mpsc::TryRecvError::Empty) => return Ok(()),
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    Err("fetch stopped unexpectedly".to_string())
                 }
-            };
+            },
+            None => return Ok(()),
+        };
 
-            // Run setup (copy files and commands)
-            let setup_runner = self.create_setup_runner();
-            let _ = setup_runner.run_setup(&worktree);
+        self.fetch_receiver = None;
+        self.progress_message = None;
+        match result {
+            Ok(()) => self.set_message(MessageLevel::Success, "Fetched all remotes"),
+            Err(e) => self.set_message(
+                MessageLevel::Warning,
+                format!("Warning: could not fetch remotes: {}", e),
+            ),
+        }
+        self.mode = AppMode::Normal;
+        self.refresh_worktrees()
+    }
 
-            self.message = Some(format!(
-                "Created branch '{}' and worktree '{}'",
-                branch_name, worktree_name
-            ));
-            self.enter_normal_mode();
-            self.refresh_worktrees()?;
+    /// Resolve the base directory for a new worktree
+    fn base_path_for(&self, branch_name: &str) -> String {
+        self.config
+            .worktree_basedir_for_repo(self.git.repo_root(), &self.repo_name(), branch_name)
+    }
 
+    /// Create missing parent directories of a worktree path if auto_mkdir is enabled
+    fn ensure_parent_dir(&self, worktree_path: &Path) -> Result<(), String> {
+        if !self.config.auto_mkdir() {
             return Ok(());
         }
-
-        // Existing branch selected (index 1+ maps to filtered_branches[index-1])
-        let branch_index = self.selected_branch - 1;
-        if branch_index >= self.filtered_branches.len() {
-            self.message = Some("No branch selected".to_string());
-            return Ok(());
+        match worktree_path.parent() {
+            Some(parent) if !parent.exists() => std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory '{}': {}", parent.display(), e)),
+            _ => Ok(()),
         }
+    }
 
-        let branch = &self.filtered_branches[branch_index];
-        let branch_name = if branch.is_remote {
-            // Extract branch name from remote (e.g., "origin/feature" -> "feature")
-            branch.name.split('/').skip(1).collect::<Vec<_>>().join("/")
-        } else {
-            branch.name.clone()
+    pub fn create_worktree(&mut self) -> Result<(), AppError> {
+        let repo_info = self.git.get_repo_info();
+
+        // Check if "Create new branch" is selected (index 0)
+        let (branch_name, worktree_name_input, kind) = if self.selected_branch == 0 {
+            let (branch_name, worktree_name_input, _) = self.create_inputs();
+            let (branch_name, worktree_name_input) =
+                (branch_name.to_string(), worktree_name_input.to_string());
+            let base_ref = self.create_base_ref();
+
+            // Creating a new branch requires input; a typed start point alone gives a
+            // detached HEAD
+            if branch_name.is_empty() && base_ref.is_empty() {
+                self.set_message(MessageLevel::Error, "Please enter a branch name");
+                return Ok(());
+            }
+            let kind = if branch_name.is_empty() {
+                CreateKind::Detached
+            } else {
+                CreateKind::NewBranch
+            };
+            let branch_name = if branch_name.is_empty() {
+                branch_name
+            } else {
+                let user = self.git.user_name();
+                match self
+                    .config
+                    .generate_branch_name(&branch_name, user.as_deref())
+                {
+                    Ok(name) => name,
+                    Err(e) => {
+                        self.set_message(MessageLevel::Error, format!("{}", e));
+                        return Ok(());
+                    }
+                }
+            };
+            // Catch names git would reject before starting the background job,
+            // so the dialog stays open for correction
+            if !branch_name.is_empty() && !is_valid_branch_name(&branch_name) {
+                self.set_message(
+                    MessageLevel::Error,
+                    format!("Invalid branch name '{}'", branch_name),
+                );
+                return Ok(());
+            }
+            (branch_name, worktree_name_input, kind)
+        } else {
+            // Existing branch selected (index 1+ maps to filtered_branches[index-1])
+            let branch_index = self.selected_branch - 1;
+            if branch_index >= self.filtered_branches.len() {
+                self.set_message(MessageLevel::Error, "No branch selected");
+                return Ok(());
+            }
+
+            let branch = &self.filtered_branches[branch_index];
+            let branch_name = if branch.is_remote {
+                // Extract branch name from remote (e.g., "origin/feature" -> "feature")
+                branch.name.split('/').skip(1).collect::<Vec<_>>().join("/")
+            } else {
+                branch.name.clone()
+            };
+            // A local branch with the same name takes precedence over the remote one
+            let reuses_local = branch.is_remote
+                && self
+                    .branches
+                    .iter()
+                    .any(|b| !b.is_remote && b.name == branch_name);
+            (
+                branch_name,
+                self.input.clone(),
+                CreateKind::ExistingBranch { reuses_local },
+            )
+        };
+
+        // A branch can only be checked out once; jump to the worktree that has it
+        if kind != CreateKind::Detached {
+            if let Some(path) = self.worktree_path_for_branch(&branch_name) {
+                let open = self.open_after_create;
+                self.enter_normal_mode();
+                if open {
+                    self.open_worktree_at(&path);
+                } else {
+                    let location = self.format_path(&path.to_string_lossy());
+                    if self.highlight_worktree_at(&path) {
+                        self.set_message(
+                            MessageLevel::Success,
+                            format!(
+                                "Branch '{}' already checked out at {}; selected it",
+                                branch_name, location
+                            ),
+                        );
+                    } else {
+                        self.set_message(
+                            MessageLevel::Warning,
+                            format!(
+                                "Branch '{}' already checked out at {}",
+                                branch_name, location
+                            ),
+                        );
+                    }
+                }
+                return Ok(());
+            }
+        }
+
+        let base_ref = Some(self.create_base_ref())
+            .filter(|b| self.selected_branch == 0 && !b.is_empty())
+            .map(String::from);
+        // A detached worktree is named after its start point
+        let name_source = match kind {
+            CreateKind::Detached => base_ref.clone().unwrap_or_default(),
+            _ => branch_name.clone(),
         };
+        let base_path = self.base_path_for(&name_source);
 
-        // Use input as worktree name, or branch name if input is empty
-        let worktree_name = if self.input.is_empty() {
+        // An explicit worktree name wins over the naming template
+        let worktree_name = if worktree_name_input.is_empty() {
             match self
                 .config
-                .generate_worktree_name(&branch_name, repo_info.as_ref())
+                .generate_worktree_name(&name_source, repo_info.as_ref())
             {
                 Ok(name) => name,
                 Err(e) => {
-                    self.message = Some(format!("{}", e));
+                    self.set_message(MessageLevel::Error, format!("{}", e));
                     return Ok(());
                 }
             }
         } else {
-            self.input.clone()
+            worktree_name_input
         };
 
-        let worktree = match self
-            .git
-            .create_worktree(&worktree_name, &branch_name, &base_path)
-        {
-            Ok(wt) => wt,
-            Err(e) => {
-                let error_msg = e.to_string();
-                if error_msg.contains("already checked out") {
-                    self.message = Some(format!(
-                        "Branch '{}' is already used by another worktree",
-                        branch_name
-                    ));
-                } else if error_msg.contains("directory exists") {
-                    self.message = Some(format!(
-                        "Directory '{}' already exists. Run 'git worktree prune' to clean up",
-                        worktree_name
-                    ));
-                } else {
-                    self.message = Some(format!("Failed to create: {}", e));
+        let planned_branch = (kind != CreateKind::Detached).then_some(branch_name.as_str());
+        let planned = self.planned_worktree(&base_path, &worktree_name, planned_branch);
+        if self.dry_run {
+            let source = match kind {
+                CreateKind::NewBranch => format!(
+                    "new branch '{}' from {}",
+                    branch_name,
+                    base_ref.as_deref().unwrap_or("HEAD")
+                ),
+                CreateKind::ExistingBranch { .. } => format!("branch '{}'", branch_name),
+                CreateKind::Detached => {
+                    format!("detached at {}", base_ref.as_deref().unwrap_or("HEAD"))
                 }
-                return Ok(());
-            }
-        };
-
-        // Run setup (copy files and commands)
-        let setup_runner = self.create_setup_runner();
-        let _ = setup_runner.run_setup(&worktree);
+            };
+            let path = self.format_path(&planned.path.to_string_lossy());
+            self.report_dry_run(format!(
+                "create worktree '{}' at {} on {}",
+                worktree_name, path, source
+            ));
+            return Ok(());
+        }
+        if let Err(msg) = self.ensure_parent_dir(&planned.path) {
+            self.set_message(MessageLevel::Error, msg);
+            return Ok(());
+        }
+        let hook_runner = self.create_hook_runner();
+        if let Err(e) = hook_runner.run(HookEvent::PreCreate, &planned) {
+            self.set_message(MessageLevel::Error, e.to_string());
+            return Ok(());
+        }
 
-        self.message = Some(format!("Created worktree: {}", worktree_name));
-        self.enter_normal_mode();
-        self.refresh_worktrees()?;
+        let warning = self.stale_base_warning(kind, &branch_name, base_ref.as_deref());
+        let job = CreateJob {
+            kind,
+            worktree_name,
+            branch_name,
+            base_path,
+            base_ref,
+            warning,
+        };
+        let repo_root = self.git.repo_root().clone();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let setup_runner = self.create_setup_runner().with_cancel(cancel.clone());
+        self.setup_cancel = Some(cancel);
+        self.progress_message = Some(format!("Creating worktree '{}'...", job.worktree_name));
 
+        let (tx, rx) = mpsc::channel();
+        self.create_receiver = Some(rx);
+        self.mode = AppMode::Creating;
+        self.tick = 0;
+
+        std::thread::spawn(move || {
+            let _ = tx.send(execute_create(
+                &repo_root,
+                &job,
+                &setup_runner,
+                &hook_runner,
+            ));
+        });
         Ok(())
     }
 
-    pub fn select_worktree(&mut self) {
-        if !self.filtered_worktrees.is_empty() {
-            let worktree = &self.filtered_worktrees[self.selected_worktree];
-            self.selected_worktree_path = Some(worktree.path.to_string_lossy().to_string());
-            self.should_quit = true;
+    /// Warn when the local branch a new worktree starts from is behind its
+    /// upstream (only with `worktree.warn_stale_base`)
+    fn stale_base_warning(
+        &self,
+        kind: CreateKind,
+        branch_name: &str,
+        base_ref: Option<&str>,
+    ) -> Option<String> {
+        if !self.config.warn_stale_base() {
+            return None;
         }
+        let base = match kind {
+            CreateKind::ExistingBranch { reuses_local } => {
+                let is_local = reuses_local
+                    || self
+                        .branches
+                        .iter()
+                        .any(|b| !b.is_remote && b.name == branch_name);
+                is_local.then(|| branch_name.to_string())?
+            }
+            CreateKind::NewBranch | CreateKind::Detached => match base_ref {
+                Some(base) => base.to_string(),
+                None => self.branches.iter().find(|b| b.is_head)?.name.clone(),
+            },
+        };
+        let (behind, upstream) = self.git.branch_behind_upstream(&base)?;
+        Some(format!(
+            "'{}' is {} commit(s) behind '{}'; consider updating it first",
+            base, behind, upstream
+        ))
     }
 
-    pub fn input_char(&mut self, c: char) {
-        self.input.push(c);
-        if self.mode == AppMode::Normal {
-            self.filter_worktrees();
-        }
+    /// Whether the running create or setup can still be cancelled
+    pub fn can_cancel_setup(&self) -> bool {
+        matches!(self.mode, AppMode::Creating | AppMode::SettingUp)
+            && self
+                .setup_cancel
+                .as_ref()
+                .is_some_and(|cancel| !cancel.load(Ordering::Relaxed))
     }
 
-    pub fn delete_char(&mut self) {
-        self.input.pop();
-        if self.mode == AppMode::Normal {
-            self.filter_worktrees();
+    /// Stop the running create's (or re-run's) setup. The worktree itself is
+    /// kept; a running setup command is killed and the rest are skipped.
+    pub fn cancel_setup(&mut self) {
+        if !self.can_cancel_setup() {
+            return;
+        }
+        if let Some(cancel) = &self.setup_cancel {
+            cancel.store(true, Ordering::Relaxed);
         }
+        self.progress_message = Some("Cancelling setup...".to_string());
     }
 
-    pub fn clear_message(&mut self) {
-        self.message = None;
-    }
+    /// Check if a background create operation has completed
+    pub fn check_create_completion(&mut self) -> Result<(), AppError> {
+        let result = match &self.create_receiver {
+            Some(receiver) => match receiver.try_recv() {
+                Ok(result) => result,
+                Err(mpsc::TryRecvError::Empty) => return Ok(()),
+                Err(mpsc::TryRecvError::Disconnected) => CreateResult {
+                    level: MessageLevel::Error,
+                    message: "Create operation failed unexpectedly".to_string(),
+                    created: false,
+                    path: None,
+                },
+            },
+            None => return Ok(()),
+        };
 
-    pub fn get_selected_worktree_detail(&self) -> Option<WorktreeDetail> {
-        if self.filtered_worktrees.is_empty() {
-            return None;
+        self.create_receiver = None;
+        self.setup_cancel = None;
+        self.progress_message = None;
+        if result.created {
+            let open = self.open_after_create || self.config.open_after_create();
+            self.enter_normal_mode();
+            self.set_message(result.level, result.message);
+            self.refresh_worktrees()?;
+            match result.path {
+                Some(path) if open => self.open_worktree_at(&path),
+                Some(path) => {
+                    self.highlight_worktree_at(&path);
+                }
+                None => {}
+            }
+        } else {
+            // Stay in Create mode so the input can be corrected
+            self.mode = AppMode::Create;
+            self.set_message(result.level, result.message);
         }
-        let worktree = &self.filtered_worktrees[self.selected_worktree];
-        Some(self.git.get_worktree_details(worktree))
+        Ok(())
     }
 
-    /// Format path for display (uses tilde_home config setting)
-    pub fn format_path(&self, path: &str) -> String {
-        self.config.format_path_for_display(path)
-    }
+    /// Run copy_files, symlink_files and setup_commands again in the selected
+    /// worktree, e.g. to backfill files added to copy_files after it was created
+    pub fn rerun_setup(&mut self) {
+        if self.selected_dir_missing() {
+            return;
+        }
+        let Some(worktree) = self.filtered_worktrees.get(self.selected_worktree).cloned() else {
+            return;
+        };
+        if worktree.is_main {
+            self.set_message(
+                MessageLevel::Error,
+                "Setup copies from the main worktree; select another one",
+            );
+            return;
+        }
+        if self.get_repository_settings().is_none() {
+            self.set_message(
+                MessageLevel::Error,
+                "No setup configured (copy_files, symlink_files, setup_commands)",
+            );
+            return;
+        }
+        if self.dry_run {
+            self.report_dry_run(format!("re-run setup in worktree '{}'", worktree.name));
+            return;
+        }
 
-    /// Check if icons should be displayed (uses ui.icons config setting)
-    pub fn icons_enabled(&self) -> bool {
-        self.config.icons_enabled()
+        let cancel = Arc::new(AtomicBool::new(false));
+        let setup_runner = self.create_setup_runner().with_cancel(cancel.clone());
+        self.setup_cancel = Some(cancel);
+        self.progress_message = Some(format!("Running setup in '{}'...", worktree.name));
+
+        let (tx, rx) = mpsc::channel();
+        self.setup_receiver = Some(rx);
+        self.mode = AppMode::SettingUp;
+        self.tick = 0;
+
+        std::thread::spawn(move || {
+            let message = match setup_runner.run_setup(&worktree) {
+                Ok(report) => (
+                    MessageLevel::Success,
+                    match report.summary() {
+                        Some(summary) => {
+                            format!("Re-ran setup in '{}' ({})", worktree.name, summary)
+                        }
+                        None => format!("Re-ran setup in '{}'", worktree.name),
+                    },
+                ),
+                Err(e) => (
+                    MessageLevel::Error,
+                    format!("Re-running setup in '{}' failed: {}", worktree.name, e),
+                ),
+            };
+            let _ = tx.send(message);
+        });
     }
 
-    /// Create an App instance for testing without Git operations
-    #[cfg(test)]
-    pub fn new_for_test(config: Config, worktrees: Vec<Worktree>, branches: Vec<Branch>) -> Self {
-        use std::path::PathBuf;
+    /// Check if a background setup re-run has completed
+    pub fn check_setup_completion(&mut self) -> Result<(), AppError> {
+        let (level, message) = match &self.setup_receiver {
+            Some(receiver) => match receiver.try_recv() {
+                Ok(message) => message,
+                Err(mpsc::TryRecvError::Empty) => return Ok(()),
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    (MessageLevel::Error, "Setup failed unexpectedly".to_string())
+                }
+            },
+            None => return Ok(()),
+        };
 
-        // Use the project root (where Cargo.toml is) as the repo path for testing
-        let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        let git = GitManager::from_path(&manifest_dir).unwrap();
-        let theme = Theme::from_config(Some(config.theme_name()), config.theme_colors());
+        self.setup_receiver = None;
+        self.setup_cancel = None;
+        self.progress_message = None;
+        self.enter_normal_mode();
+        self.set_message(level, message);
+        self.refresh_worktrees()
+    }
 
-        Self {
-            mode: AppMode::Normal,
-            worktrees: worktrees.clone(),
-            filtered_worktrees: worktrees,
-            branches: branches.clone(),
-            filtered_branches: branches,
-            selected_worktree: 0,
-            selected_branch: 0,
-            input: String::new(),
-            confirm_action: None,
-            merged_worktrees: Vec::new(),
-            message: None,
-            should_quit: false,
-            selected_worktree_path: None,
-            theme,
-            deleting_message: None,
-            tick: 0,
-            config_sources: ConfigSources::default(),
-            config_scroll: 0,
-            config_scroll_max: 0,
-            config,
-            git,
-            delete_receiver: None,
+    /// Describe the worktree that is about to be created (for pre_create hooks)
+    fn planned_worktree(&self, base_path: &str, name: &str, branch_name: Option<&str>) -> Worktree {
+        Worktree {
+            name: name.to_string(),
+            path: self.git.repo_root().join(base_path).join(name),
+            branch: branch_name.map(String::from),
+            ..Default::default()
         }
     }
-}
 
-/// Execute single worktree deletion in a background thread
-fn execute_delete_single(
-    repo_root: &Path,
-    worktree_name: &str,
-    branch_name: Option<String>,
-    delete_branch: bool,
-) -> DeleteResult {
-    let repo = match git2::Repository::open(repo_root) {
-        Ok(r) => r,
-        Err(e) => return DeleteResult::Error(format!("Failed to open repository: {}", e)),
-    };
-
-    // Delete the worktree (prune + remove directory)
-    match repo.find_worktree(worktree_name) {
-        Ok(wt) => {
-            let path = wt.path().to_path_buf();
-            if let Err(e) = wt.prune(Some(
-                git2::WorktreePruneOptions::new()
-                    .valid(true)
-                    .working_tree(true),
-            )) {
-                return DeleteResult::Error(format!("Failed to prune worktree: {}", e));
-            }
-            if path.exists() {
-                if let Err(e) = std::fs::remove_dir_all(&path) {
-                    return DeleteResult::Error(format!("Failed to remove directory: {}", e));
-                }
-            }
+    /// Select the worktree at `path` in the list and open it
+    fn open_worktree_at(&mut self, path: &Path) {
+        if self.highlight_worktree_at(path) {
+            self.select_worktree();
+        } else {
+            self.set_message(
+                MessageLevel::Error,
+                format!("Worktree not found: {}", path.display()),
+            );
         }
-        Err(e) => return DeleteResult::Error(format!("Worktree not found: {}", e)),
     }
 
-    // Delete the branch if requested
-    let mut branch_deleted = false;
-    let mut error_message = None;
-    if delete_branch {
-        if let Some(ref branch) = branch_name {
-            let output = std::process::Command::new("git")
-                .args(["branch", "-D", branch])
-                .current_dir(repo_root)
-                .output();
-            match output {
-                Ok(o) if o.status.success() => {
-                    branch_deleted = true;
-                }
-                Ok(o) => {
-                    let stderr = String::from_utf8_lossy(&o.stderr);
-                    error_message = Some(format!(
-                        "Deleted worktree '{}', but failed to delete branch '{}': {}",
-                        worktree_name,
-                        branch,
-                        stderr.trim()
-                    ));
-                }
-                Err(e) => {
-                    error_message = Some(format!(
-                        "Deleted worktree '{}', but failed to delete branch '{}': {}",
-                        worktree_name, branch, e
-                    ));
-                }
+    /// Move the cursor to the worktree at `path`, returning false if it is not listed
+    fn highlight_worktree_at(&mut self, path: &Path) -> bool {
+        match self.filtered_worktrees.iter().position(|w| w.path == path) {
+            Some(index) => {
+                self.selected_worktree = index;
+                true
             }
+            None => false,
         }
     }
 
-    DeleteResult::SingleCompleted {
-        worktree_name: worktree_name.to_string(),
-        branch_name,
-        branch_deleted,
-        error_message,
+    /// Path of the worktree that has `branch` checked out
+    fn worktree_path_for_branch(&self, branch: &str) -> Option<PathBuf> {
+        self.worktrees
+            .iter()
+            .find(|w| w.branch.as_deref() == Some(branch))
+            .map(|w| w.path.clone())
     }
-}
-
-/// Execute prune (multiple worktree deletion) in a background thread
-fn execute_prune(
-    repo_root: &Path,
-    worktrees: Vec<(String, Option<String>)>,
-    delete_branch: bool,
-) -> DeleteResult {
-    let repo = match git2::Repository::open(repo_root) {
-        Ok(r) => r,
-        Err(e) => return DeleteResult::Error(format!("Failed to open repository: {}", e)),
-    };
 
-    let mut deleted_worktrees = 0;
-    let mut deleted_branches = 0;
+    /// Whether the selected worktree's directory was removed outside gwm. If
+    /// so, reports it, prunes its stale entry and refreshes the list, so
+    /// callers can bail out instead of running a command in a missing directory.
+    fn selected_dir_missing(&mut self) -> bool {
+        let Some(worktree) = self.filtered_worktrees.get(self.selected_worktree) else {
+            return false;
+        };
+        if !self.git.worktree_dir_missing(&worktree.path) {
+            return false;
+        }
+        let path = worktree.path.clone();
+        if self.dry_run {
+            self.set_message(
+                MessageLevel::Warning,
+                format!(
+                    "Worktree directory no longer exists: {}. Dry run: left its entry in place",
+                    path.display()
+                ),
+            );
+            return true;
+        }
+        let pruned = self.git.prune_entry(&path);
+        if let Err(e) = self.refresh_worktrees() {
+            self.set_message(MessageLevel::Error, format!("Error: {}", e));
+            return true;
+        }
+        let (level, message) = match pruned {
+            Ok(false) => (
+                MessageLevel::Warning,
+                format!(
+                    "Worktree directory no longer exists: {}. Skipped its locked entry; unlock it to prune",
+                    path.display()
+                ),
+            ),
+            Ok(true) => (
+                MessageLevel::Warning,
+                format!(
+                    "Worktree directory no longer exists: {}. Pruned its entry",
+                    path.display()
+                ),
+            ),
+            Err(e) => (
+                MessageLevel::Error,
+                format!(
+                    "Worktree directory no longer exists: {} (prune failed: {})",
+                    path.display(),
+                    e
+                ),
+            ),
+        };
+        self.set_message(level, message);
+        true
+    }
 
-    for (wt_name, branch_name) in &worktrees {
-        match repo.find_worktree(wt_name) {
-            Ok(wt) => {
-                let path = wt.path().to_path_buf();
-                if wt
-                    .prune(Some(
-                        git2::WorktreePruneOptions::new()
-                            .valid(true)
-                            .working_tree(true),
-                    ))
-                    .is_err()
-                {
-                    continue;
-                }
-                if path.exists() && std::fs::remove_dir_all(&path).is_err() {
-                    continue;
-                }
+    pub fn select_worktree(&mut self) {
+        if self.selected_dir_missing() {
+            return;
+        }
+        if let Some(worktree) = self.filtered_worktrees.get(self.selected_worktree) {
+            let path = worktree.path.to_string_lossy();
+            let on_open = self
+                .config
+                .on_open_command(&path, worktree.branch.as_deref())
+                .filter(|_| !self.print_path);
+            if let Some(command) = on_open {
+                self.suspend_command = Some(SuspendCommand {
+                    command,
+                    cwd: worktree.path.clone(),
+                    kind: SuspendKind::Open,
+                });
+                return;
             }
-            Err(_) => continue,
         }
-        deleted_worktrees += 1;
+        if !self.filtered_worktrees.is_empty() {
+            let worktree = &self.filtered_worktrees[self.selected_worktree];
+            self.selected_worktree_path = Some(worktree.path.to_string_lossy().to_string());
+            self.should_quit = true;
+        }
+    }
 
-        if delete_branch {
-            if let Some(ref branch) = branch_name {
-                let output = std::process::Command::new("git")
-                    .args(["branch", "-D", branch])
-                    .current_dir(repo_root)
-                    .output();
-                if let Ok(o) = output {
-                    if o.status.success() {
-                        deleted_branches += 1;
-                    }
-                }
+    /// Copy the selected worktree's path to the clipboard
+    /// Falls back to showing the path when the clipboard is unavailable
+    pub fn copy_selected_path(&mut self) {
+        let Some(worktree) = self.filtered_worktrees.get(self.selected_worktree) else {
+            return;
+        };
+        let path = worktree.path.to_string_lossy().to_string();
+        self.copy_to_clipboard("", &path);
+    }
+
+    /// Copy the selected worktree's branch name, or its short commit hash
+    /// for a detached HEAD
+    pub fn copy_selected_branch(&mut self) {
+        let Some(worktree) = self.filtered_worktrees.get(self.selected_worktree) else {
+            return;
+        };
+        if let Some(branch) = worktree.branch.clone() {
+            self.copy_to_clipboard(" branch", &branch);
+            return;
+        }
+        match head_commit_id(&worktree.path) {
+            Some(id) => {
+                let short: String = id.chars().take(7).collect();
+                self.copy_to_clipboard(" commit (detached HEAD)", &short);
             }
+            None => self.set_message(MessageLevel::Error, "No branch or commit to copy"),
         }
     }
 
-    DeleteResult::PruneCompleted {
-        worktree_count: deleted_worktrees,
-        branch_count: deleted_branches,
+    /// Copy `text` to the clipboard, showing it in the message either way
+    fn copy_to_clipboard(&mut self, label: &str, text: &str) {
+        match crate::clipboard::copy(text) {
+            Ok(()) => self.set_message(MessageLevel::Success, format!("Copied{}: {}", label, text)),
+            Err(e) => self.set_message(
+                MessageLevel::Warning,
+                format!("Clipboard unavailable ({}): {}", e, text),
+            ),
+        }
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::path::PathBuf;
-    use std::process::Command;
-    use tempfile::TempDir;
 
-    /// Create a temporary git repository for testing execute_delete_* functions
-    fn setup_git_repo() -> (TempDir, std::path::PathBuf) {
+    /// Request opening the selected worktree in the configured editor
+    pub fn open_in_editor(&mut self) {
+        if self.selected_dir_missing() {
+            return;
+        }
+        let Some(worktree) = self.filtered_worktrees.get(self.selected_worktree) else {
+            return;
+        };
+        let path = worktree.path.to_string_lossy().to_string();
+
+        match self.config.editor_command(&path) {
+            Some(command) => {
+                self.suspend_command = Some(SuspendCommand {
+                    command,
+                    cwd: worktree.path.clone(),
+                    kind: SuspendKind::Editor,
+                });
+            }
+            None => {
+                self.set_message(
+                    MessageLevel::Error,
+                    "No editor configured. Set $EDITOR or worktree.editor",
+                );
+            }
+        }
+    }
+
+    /// Request showing the selected worktree's changes with the diff command
+    pub fn show_diff(&mut self) {
+        if self.selected_dir_missing() {
+            return;
+        }
+        let Some(worktree) = self.filtered_worktrees.get(self.selected_worktree) else {
+            return;
+        };
+        let path = worktree.path.to_string_lossy().to_string();
+        self.suspend_command = Some(SuspendCommand {
+            command: self.config.diff_command(&path),
+            cwd: worktree.path.clone(),
+            kind: SuspendKind::Diff,
+        });
+    }
+
+    /// Open the selected worktree in the system file manager without
+    /// suspending the TUI
+    pub fn reveal_selected(&mut self) {
+        if self.selected_dir_missing() {
+            return;
+        }
+        let Some(worktree) = self.filtered_worktrees.get(self.selected_worktree) else {
+            return;
+        };
+        let program = file_manager(std::env::consts::OS);
+        let spawned = std::process::Command::new(program)
+            .arg(&worktree.path)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn();
+
+        match spawned {
+            Ok(mut child) => {
+                // Wait in the background so the process doesn't linger as a zombie,
+                // and report a non-zero exit once it is known
+                let (tx, rx) = mpsc::channel();
+                self.reveal_receiver = Some(rx);
+                std::thread::spawn(move || {
+                    let result = match child.wait() {
+                        // explorer exits with 1 even after opening the folder
+                        Ok(status) if status.success() || program == "explorer" => Ok(()),
+                        Ok(status) => Err(format!("{} exited with {}", program, status)),
+                        Err(e) => Err(format!("Failed to wait for {}: {}", program, e)),
+                    };
+                    let _ = tx.send(result);
+                });
+                let message = format!("Opened {} in file manager", worktree.name);
+                self.set_message(MessageLevel::Success, message);
+            }
+            Err(e) => {
+                self.set_message(
+                    MessageLevel::Error,
+                    format!("Failed to run {}: {}", program, e),
+                );
+            }
+        }
+    }
+
+    /// Show an error if the file manager opened with O exited with a failure
+    pub fn check_reveal_completion(&mut self) {
+        let result = match &self.reveal_receiver {
+            Some(receiver) => match receiver.try_recv() {
+                Ok(result) => result,
+                Err(mpsc::TryRecvError::Empty) => return,
+                Err(mpsc::TryRecvError::Disconnected) => Ok(()),
+            },
+            None => return,
+        };
+        self.reveal_receiver = None;
+        if let Err(e) = result {
+            self.set_message(MessageLevel::Error, e);
+        }
+    }
+
+    /// Fetch the default remote from the selected worktree
+    pub fn fetch_selected(&mut self) {
+        if self.selected_dir_missing() {
+            return;
+        }
+        let Some(worktree) = self.filtered_worktrees.get(self.selected_worktree) else {
+            return;
+        };
+        self.suspend_command = Some(SuspendCommand {
+            command: format!("git fetch {}", shell_quote(self.config.default_remote())),
+            cwd: worktree.path.clone(),
+            kind: SuspendKind::Fetch,
+        });
+    }
+
+    /// Pull the selected worktree's branch from the default remote
+    pub fn pull_selected(&mut self) {
+        if self.selected_dir_missing() {
+            return;
+        }
+        let Some(worktree) = self.filtered_worktrees.get(self.selected_worktree) else {
+            return;
+        };
+        let Some(branch) = worktree.branch.as_deref() else {
+            self.set_message(MessageLevel::Error, "Cannot pull a detached HEAD");
+            return;
+        };
+        self.suspend_command = Some(SuspendCommand {
+            command: format!(
+                "git pull {} {}",
+                shell_quote(self.config.default_remote()),
+                shell_quote(branch)
+            ),
+            cwd: worktree.path.clone(),
+            kind: SuspendKind::Pull,
+        });
+    }
+
+    /// Report the outcome of a suspended command and refresh if it changed the repo
+    pub fn finish_suspended(&mut self, command: &SuspendCommand, result: Result<(), String>) {
+        let name = command
+            .cwd
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let remote = self.config.default_remote().to_string();
+
+        let outcome = match (command.kind, result) {
+            (SuspendKind::Editor, Ok(())) => None,
+            (SuspendKind::Editor | SuspendKind::Diff | SuspendKind::Open, Err(e)) => {
+                Some((MessageLevel::Error, format!("Error: {}", e)))
+            }
+            // Changes may have been staged or committed from the pager or on_open
+            (SuspendKind::Diff | SuspendKind::Open, Ok(())) => None,
+            (SuspendKind::Fetch, Ok(())) => Some((
+                MessageLevel::Success,
+                format!("Fetched {} in '{}'", remote, name),
+            )),
+            (SuspendKind::Fetch, Err(e)) => {
+                Some((MessageLevel::Error, format!("Fetch failed: {}", e)))
+            }
+            (SuspendKind::Pull, Ok(())) => Some((
+                MessageLevel::Success,
+                format!("Pulled {} into '{}'", remote, name),
+            )),
+            (SuspendKind::Pull, Err(_)) if self.git.has_conflicts(&command.cwd) => Some((
+                MessageLevel::Error,
+                format!(
+                    "Merge conflicts in '{}'. Resolve them in the worktree",
+                    name
+                ),
+            )),
+            (SuspendKind::Pull, Err(e)) => {
+                Some((MessageLevel::Error, format!("Pull failed: {}", e)))
+            }
+        };
+        match outcome {
+            Some((level, message)) => self.set_message(level, message),
+            None => self.message = None,
+        }
+
+        if command.kind != SuspendKind::Editor {
+            if let Err(e) = self.refresh_worktrees() {
+                self.set_message(MessageLevel::Error, format!("Error: {}", e));
+            }
+        }
+    }
+
+    /// Unlock the selected worktree, or prompt for a reason to lock it
+    pub fn toggle_lock_selected(&mut self) -> Result<(), AppError> {
+        let Some(worktree) = self.filtered_worktrees.get(self.selected_worktree) else {
+            return Ok(());
+        };
+        if worktree.is_main {
+            self.set_message(MessageLevel::Error, "Cannot lock main worktree");
+            return Ok(());
+        }
+        if !worktree.is_locked {
+            self.input.clear();
+            self.input_cursor = None;
+            self.mode = AppMode::LockReason;
+            return Ok(());
+        }
+
+        let name = worktree.name.clone();
+        if self.dry_run {
+            self.report_dry_run(format!("unlock worktree '{}'", name));
+            return Ok(());
+        }
+        self.git.unlock_worktree(&name)?;
+        self.set_message(
+            MessageLevel::Success,
+            format!("Unlocked worktree: {}", name),
+        );
+        self.refresh_worktrees()?;
+        Ok(())
+    }
+
+    /// Lock the selected worktree with the reason typed in LockReason mode
+    pub fn lock_selected(&mut self) -> Result<(), AppError> {
+        let Some(worktree) = self.filtered_worktrees.get(self.selected_worktree) else {
+            self.enter_normal_mode();
+            return Ok(());
+        };
+        let name = worktree.name.clone();
+        let reason = Some(self.input.trim().to_string()).filter(|r| !r.is_empty());
+        if self.dry_run {
+            self.report_dry_run(format!("lock worktree '{}'", name));
+            return Ok(());
+        }
+
+        self.enter_normal_mode();
+        self.git.lock_worktree(&name, reason.as_deref())?;
+        self.set_message(
+            MessageLevel::Success,
+            match reason {
+                Some(reason) => format!("Locked worktree: {} ({})", name, reason),
+                None => format!("Locked worktree: {}", name),
+            },
+        );
+        self.refresh_worktrees()?;
+        Ok(())
+    }
+
+    /// Repair worktree administrative files left stale by moved directories
+    pub fn repair_worktrees(&mut self) -> Result<(), AppError> {
+        if self.dry_run {
+            self.report_dry_run("run 'git worktree repair'".to_string());
+            return Ok(());
+        }
+        let repaired = self.git.repair_worktrees()?;
+        self.set_message(
+            MessageLevel::Success,
+            if repaired == 0 {
+                "No worktrees needed repair".to_string()
+            } else {
+                format!("Repaired {} worktree entry(ies)", repaired)
+            },
+        );
+        self.refresh_worktrees()?;
+        Ok(())
+    }
+
+    /// Clean up entries of worktrees whose directories were removed by hand
+    pub fn prune_stale_worktrees(&mut self) -> Result<(), AppError> {
+        if self.dry_run {
+            let stale = self.git.stale_entries()?;
+            if stale.is_empty() {
+                self.set_message(MessageLevel::Success, "No stale worktree entries");
+            } else {
+                self.report_dry_run(format!(
+                    "prune {} stale worktree entry(ies): {}",
+                    stale.len(),
+                    stale.join(", ")
+                ));
+            }
+            return Ok(());
+        }
+        let pruned = self.git.prune_stale()?;
+        self.set_message(
+            MessageLevel::Success,
+            if pruned == 0 {
+                "No stale worktree entries".to_string()
+            } else {
+                format!("Pruned {} stale worktree entry(ies)", pruned)
+            },
+        );
+        self.refresh_worktrees()?;
+        Ok(())
+    }
+
+    pub fn input_char(&mut self, c: char) {
+        let cursor = self.cursor();
+        self.input.insert(self.byte_index(cursor), c);
+        self.set_cursor(cursor + 1);
+        self.on_input_changed();
+    }
+
+    /// Delete the character before the cursor
+    pub fn delete_char(&mut self) {
+        let cursor = self.cursor();
+        if cursor > 0 {
+            self.input.remove(self.byte_index(cursor - 1));
+            self.set_cursor(cursor - 1);
+            self.on_input_changed();
+        }
+    }
+
+    /// Delete the word before the cursor, treating `/`, `-`, `_`, `.` and
+    /// whitespace as word boundaries so branch-style names delete by segment
+    pub fn delete_word(&mut self) {
+        let cursor = self.cursor();
+        let chars: Vec<char> = self.input.chars().take(cursor).collect();
+        let is_boundary = |c: &char| c.is_whitespace() || matches!(c, '/' | '-' | '_' | '.');
+        let trailing = chars.iter().rev().take_while(|c| is_boundary(c)).count();
+        let word = chars
+            .iter()
+            .rev()
+            .skip(trailing)
+            .take_while(|c| !is_boundary(c))
+            .count();
+        self.delete_before_cursor(trailing + word);
+    }
+
+    /// Delete everything before the cursor
+    pub fn delete_to_line_start(&mut self) {
+        self.delete_before_cursor(self.cursor());
+    }
+
+    fn delete_before_cursor(&mut self, count: usize) {
+        if count == 0 {
+            return;
+        }
+        let cursor = self.cursor();
+        let start = self.byte_index(cursor - count);
+        let end = self.byte_index(cursor);
+        self.input.replace_range(start..end, "");
+        self.set_cursor(cursor - count);
+        self.on_input_changed();
+    }
+
+    pub fn move_cursor_left(&mut self) {
+        self.set_cursor(self.cursor().saturating_sub(1));
+    }
+
+    pub fn move_cursor_right(&mut self) {
+        self.set_cursor(self.cursor() + 1);
+    }
+
+    pub fn move_cursor_start(&mut self) {
+        self.set_cursor(0);
+    }
+
+    pub fn move_cursor_end(&mut self) {
+        self.input_cursor = None;
+    }
+
+    /// Text before the cursor, used to place the terminal cursor
+    pub fn input_before_cursor(&self) -> &str {
+        &self.input[..self.byte_index(self.cursor())]
+    }
+
+    /// Cursor position in characters, clamped to the current input
+    pub fn cursor(&self) -> usize {
+        let len = self.input.chars().count();
+        self.input_cursor.map_or(len, |c| c.min(len))
+    }
+
+    fn set_cursor(&mut self, cursor: usize) {
+        let len = self.input.chars().count();
+        self.input_cursor = (cursor < len).then_some(cursor);
+    }
+
+    fn byte_index(&self, cursor: usize) -> usize {
+        self.input
+            .char_indices()
+            .nth(cursor)
+            .map_or(self.input.len(), |(i, _)| i)
+    }
+
+    fn on_input_changed(&mut self) {
+        match self.mode {
+            AppMode::Normal => self.on_search_changed(),
+            // Typing on "Create new branch" also narrows the branch list
+            AppMode::Create
+                if self.selected_branch == 0 && self.create_field == CreateField::BranchName =>
+            {
+                self.filter_branches()
+            }
+            _ => {}
+        }
+    }
+
+    /// Re-filter worktrees and jump to the first match after the query changes
+    fn on_search_changed(&mut self) {
+        self.selected_worktree = 0;
+        self.filter_worktrees();
+    }
+
+    pub fn clear_message(&mut self) {
+        self.message = None;
+    }
+
+    /// Field of the selected worktree matched by the active search, if any
+    pub fn selected_search_match(&self) -> Option<SearchField> {
+        if self.mode != AppMode::Normal || self.input.is_empty() {
+            return None;
+        }
+        let worktree = self.filtered_worktrees.get(self.selected_worktree)?;
+        SearchField::find(worktree, &self.input.to_lowercase())
+    }
+
+    /// Details of the selected worktree, read from git only when the
+    /// selection changes or the cache was invalidated
+    pub fn get_selected_worktree_detail(&mut self) -> Option<WorktreeDetail> {
+        let worktree = self.filtered_worktrees.get(self.selected_worktree)?;
+        match &self.detail_cache {
+            Some((path, detail)) if *path == worktree.path => Some(detail.clone()),
+            _ => {
+                let detail = self
+                    .git
+                    .get_worktree_details(worktree, self.config.recent_commits());
+                self.detail_cache = Some((worktree.path.clone(), detail.clone()));
+                Some(detail)
+            }
+        }
+    }
+
+    /// Format path for display (uses tilde_home config setting)
+    pub fn format_path(&self, path: &str) -> String {
+        self.config.format_path_for_display(path)
+    }
+
+    /// Command to run in the selected worktree instead of $SHELL: a multiplexer
+    /// session (integration.multiplexer) when available, else worktree.shell
+    pub fn shell_command(&self, path: &str) -> Option<String> {
+        self.config
+            .multiplexer()
+            .and_then(|m| multiplexer::open_command(m, &self.repo_name(), path))
+            .or_else(|| self.config.shell_command(path))
+    }
+
+    /// Whether `worktree` had no activity for `ui.stale_days` days
+    pub fn is_stale(&self, worktree: &Worktree) -> bool {
+        let Some(days) = self.config.stale_days() else {
+            return false;
+        };
+        is_stale(worktree, days, unix_now())
+    }
+
+    /// Check if icons should be displayed (uses ui.icons config setting)
+    pub fn icons_enabled(&self) -> bool {
+        self.config.icons_enabled()
+    }
+
+    pub fn mouse_enabled(&self) -> bool {
+        self.config.mouse()
+    }
+
+    /// Layout of a worktree row (`ui.list_format`)
+    pub fn list_format(&self) -> &str {
+        self.config.list_format()
+    }
+
+    /// Terminal lines each worktree row takes up
+    pub fn list_item_height(&self) -> u16 {
+        if self.compact_list {
+            return 1;
+        }
+        self.list_format().split('\n').count() as u16
+    }
+
+    /// Index of the listed worktree drawn at a terminal cell, if any
+    pub fn worktree_index_at(&self, column: u16, row: u16) -> Option<usize> {
+        let area = self.list_area;
+        // Border cells don't belong to any row
+        if column <= area.x
+            || column + 1 >= area.right()
+            || row <= area.y
+            || row + 1 >= area.bottom()
+        {
+            return None;
+        }
+        let index = self.list_offset + ((row - area.y - 1) / self.list_item_height()) as usize;
+        (index < self.filtered_worktrees.len()).then_some(index)
+    }
+
+    /// Whether the detail pane lists recent commits (`ui.recent_commits` > 0)
+    pub fn show_recent_commits(&self) -> bool {
+        self.config.recent_commits() > 0
+    }
+
+    /// Create an App instance for testing without Git operations
+    #[cfg(test)]
+    pub fn new_for_test(config: Config, worktrees: Vec<Worktree>, branches: Vec<Branch>) -> Self {
+        use std::path::PathBuf;
+
+        // Use the project root (where Cargo.toml is) as the repo path for testing
+        let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let git = GitManager::from_path(&manifest_dir).unwrap();
+        let theme = Theme::from_config(Some(config.theme_name()), config.theme_colors());
+
+        Self {
+            mode: AppMode::Normal,
+            worktrees: worktrees.clone(),
+            filtered_worktrees: worktrees,
+            marked_worktrees: HashSet::new(),
+            branches: branches.clone(),
+            filtered_branches: branches,
+            branch_filter: String::new(),
+            prune_base: String::new(),
+            move_source: None,
+            create_field: CreateField::BranchName,
+            worktree_name_input: String::new(),
+            base_ref_input: String::new(),
+            selected_worktree: 0,
+            selected_branch: 0,
+            input: String::new(),
+            input_cursor: None,
+            confirm_action: None,
+            merged_worktrees: Vec::new(),
+            prune_excluded: Vec::new(),
+            prune_locked: Vec::new(),
+            prune_unchecked: HashSet::new(),
+            prune_cursor: 0,
+            message: None,
+            message_level: MessageLevel::Success,
+            message_shown: None,
+            message_log: VecDeque::new(),
+            should_quit: false,
+            quit_pending: false,
+            selected_worktree_path: None,
+            print_path: false,
+            dry_run: false,
+            theme,
+            progress_message: None,
+            tick: 0,
+            config_sources: ConfigSources::default(),
+            config_scroll: 0,
+            config_scroll_max: 0,
+            focus: Focus::default(),
+            detail_scroll: 0,
+            detail_scroll_max: 0,
+            detail_scroll_path: None,
+            list_height: 0,
+            list_area: Rect::default(),
+            list_offset: 0,
+            sort_mode: SortMode::default(),
+            compact_list: config.compact_list(),
+            pending_key: None,
+            chord_selection: 0,
+            suspend_command: None,
+            config,
+            git,
+            delete_receiver: None,
+            open_after_create: false,
+            quick_create: false,
+            delete_stash: None,
+            create_receiver: None,
+            setup_cancel: None,
+            setup_receiver: None,
+            fetch_receiver: None,
+            reveal_receiver: None,
+            pending_status: HashSet::new(),
+            status_receiver: None,
+            confirm_size: None,
+            confirm_reason: None,
+            size_receiver: None,
+            detail_cache: None,
+            branches_stale: false,
+        }
+    }
+}
+
+/// Create a worktree and run setup and post_create hooks in a background thread
+fn execute_create(
+    repo_root: &Path,
+    job: &CreateJob,
+    setup_runner: &SetupRunner,
+    hook_runner: &HookRunner,
+) -> CreateResult {
+    let failed = |message: String| CreateResult {
+        level: MessageLevel::Error,
+        message,
+        created: false,
+        path: None,
+    };
+    let git = match GitManager::from_path(repo_root) {
+        Ok(git) => git,
+        Err(e) => return failed(format!("Failed to create: {}", e)),
+    };
+
+    let name = &job.worktree_name;
+    let branch = &job.branch_name;
+    let base_ref = job.base_ref.as_deref();
+    let result = match (job.kind, base_ref) {
+        // Create worktree with a new branch (atomic operation)
+        (CreateKind::NewBranch, None) => {
+            git.create_worktree_with_new_branch(name, branch, &job.base_path)
+        }
+        (CreateKind::NewBranch, Some(rev)) => {
+            git.create_worktree_from_ref(name, Some(branch), rev, &job.base_path)
+        }
+        (CreateKind::Detached, rev) => {
+            git.create_worktree_from_ref(name, None, rev.unwrap_or("HEAD"), &job.base_path)
+        }
+        (CreateKind::ExistingBranch { .. }, _) => git.create_worktree(name, branch, &job.base_path),
+    };
+    let worktree = match result {
+        Ok(wt) => wt,
+        Err(e) => {
+            let error_msg = e.to_string();
+            return failed(
+                if job.kind == CreateKind::NewBranch
+                    && error_msg.contains("already exists")
+                    && error_msg.contains("branch")
+                {
+                    format!("Branch '{}' already exists", branch)
+                } else if error_msg.contains("already checked out") {
+                    format!("Branch '{}' is already used by another worktree", branch)
+                } else if error_msg.contains("directory exists") {
+                    format!(
+                        "Directory '{}' already exists. Run 'git worktree prune' to clean up",
+                        name
+                    )
+                } else {
+                    format!("Failed to create: {}", e)
+                },
+            );
+        }
+    };
+
+    // Run setup (copy files and commands); its failure is reported before hook failures
+    let setup = setup_runner.run_setup(&worktree);
+    let hook = hook_runner.run(HookEvent::PostCreate, &worktree);
+    let summary = setup.as_ref().ok().and_then(|report| report.summary());
+    let outcome = match setup {
+        Ok(_) => hook,
+        Err(e) => Err(e),
+    };
+
+    let mut level = if outcome.is_ok() {
+        MessageLevel::Success
+    } else {
+        MessageLevel::Error
+    };
+    let message = match (outcome, job.kind) {
+        (Ok(()), CreateKind::NewBranch) => match base_ref {
+            Some(rev) => format!(
+                "Created branch '{}' from '{}' and worktree '{}'",
+                branch, rev, name
+            ),
+            None => format!("Created branch '{}' and worktree '{}'", branch, name),
+        },
+        (Ok(()), CreateKind::Detached) => format!(
+            "Created worktree '{}' at '{}' (detached HEAD)",
+            name,
+            base_ref.unwrap_or("HEAD")
+        ),
+        (Ok(()), CreateKind::ExistingBranch { reuses_local: true }) => format!(
+            "Created worktree: {} (using existing local branch '{}')",
+            name, branch
+        ),
+        (Ok(()), CreateKind::ExistingBranch { .. }) => format!("Created worktree: {}", name),
+        (Err(e), _) => format!("Created worktree '{}', but {}", name, e),
+    };
+    let message = match summary {
+        Some(summary) => format!("{} ({})", message, summary),
+        None => message,
+    };
+    let message = match &job.warning {
+        Some(warning) => {
+            if level == MessageLevel::Success {
+                level = MessageLevel::Warning;
+            }
+            format!("{}. Warning: {}", message, warning)
+        }
+        None => message,
+    };
+    CreateResult {
+        level,
+        message,
+        created: true,
+        path: Some(worktree.path),
+    }
+}
+
+/// Current time as a Unix timestamp
+pub fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Total size of the files under `path`, without following symlinks. Stops
+/// at `deadline` so huge trees can't keep the dialog waiting.
+fn directory_size(path: &Path, deadline: Instant) -> DirSize {
+    let mut size = DirSize::default();
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if Instant::now() > deadline {
+                size.partial = true;
+                return size;
+            }
+            // DirEntry::metadata does not follow symlinks
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                pending.push(entry.path());
+            } else {
+                size.bytes += metadata.len();
+            }
+        }
+    }
+    size
+}
+
+/// Whether a linked worktree was neither added nor committed to within `days`
+fn is_stale(worktree: &Worktree, days: u64, now: i64) -> bool {
+    if worktree.is_main {
+        return false;
+    }
+    let last_activity = worktree.created_at.max(worktree.last_commit_time);
+    last_activity.is_some_and(|t| now - t > days as i64 * 86_400)
+}
+
+/// Execute single worktree deletion in a background thread
+fn execute_delete_single(
+    repo_root: &Path,
+    worktree_name: &str,
+    branch_name: Option<String>,
+    delete_branch: bool,
+    force: bool,
+) -> DeleteResult {
+    let repo = match git2::Repository::open(repo_root) {
+        Ok(r) => r,
+        Err(e) => return DeleteResult::Error(format!("Failed to open repository: {}", e)),
+    };
+
+    // Delete the worktree (prune + remove directory)
+    match repo.find_worktree(worktree_name) {
+        Ok(wt) => {
+            let path = wt.path().to_path_buf();
+            let is_locked = matches!(wt.is_locked(), Ok(git2::WorktreeLockStatus::Locked(_)));
+            if is_locked && !force {
+                return DeleteResult::Error(format!(
+                    "Worktree '{}' is locked. Unlock it first or force delete",
+                    worktree_name
+                ));
+            }
+            if let Err(e) = wt.prune(Some(
+                git2::WorktreePruneOptions::new()
+                    .valid(true)
+                    .locked(force)
+                    .working_tree(true),
+            )) {
+                return DeleteResult::Error(format!("Failed to prune worktree: {}", e));
+            }
+            if path.exists() {
+                if let Err(e) = std::fs::remove_dir_all(&path) {
+                    return DeleteResult::Error(format!("Failed to remove directory: {}", e));
+                }
+            }
+        }
+        Err(e) => return DeleteResult::Error(format!("Worktree not found: {}", e)),
+    }
+
+    // Delete the branch if requested
+    let mut branch_deleted = false;
+    let mut error_message = None;
+    if delete_branch {
+        if let Some(ref branch) = branch_name {
+            match delete_local_branch(&repo, branch) {
+                Ok(()) => branch_deleted = true,
+                Err(e) => {
+                    error_message = Some(format!(
+                        "Deleted worktree '{}', but failed to delete branch '{}': {}",
+                        worktree_name, branch, e
+                    ));
+                }
+            }
+        }
+    }
+
+    DeleteResult::SingleCompleted {
+        worktree_name: worktree_name.to_string(),
+        branch_name,
+        branch_deleted,
+        error_message,
+    }
+}
+
+/// Delete a local branch unless it is still checked out in some worktree
+fn delete_local_branch(repo: &git2::Repository, branch: &str) -> Result<(), String> {
+    if let Some((holder, _)) = find_branch_checkout(repo, branch) {
+        return Err(format!("branch is checked out in worktree '{}'", holder));
+    }
+
+    repo.find_branch(branch, git2::BranchType::Local)
+        .and_then(|mut b| b.delete())
+        .map_err(|e| e.message().to_string())
+}
+
+/// Execute prune (multiple worktree deletion) in a background thread
+fn execute_prune(
+    repo_root: &Path,
+    worktrees: Vec<(String, Option<String>)>,
+    delete_branch: bool,
+) -> DeleteResult {
+    let repo = match git2::Repository::open(repo_root) {
+        Ok(r) => r,
+        Err(e) => return DeleteResult::Error(format!("Failed to open repository: {}", e)),
+    };
+
+    let mut deleted_worktrees = 0;
+    let mut deleted_branches = 0;
+    let mut failed = Vec::new();
+
+    for (wt_name, branch_name) in &worktrees {
+        if let Err(e) = remove_worktree(&repo, wt_name) {
+            failed.push(format!("{}: {}", wt_name, e));
+            continue;
+        }
+        deleted_worktrees += 1;
+
+        if delete_branch {
+            if let Some(ref branch) = branch_name {
+                if delete_local_branch(&repo, branch).is_ok() {
+                    deleted_branches += 1;
+                }
+            }
+        }
+    }
+
+    DeleteResult::PruneCompleted {
+        worktree_count: deleted_worktrees,
+        branch_count: deleted_branches,
+        failed,
+        hook_errors: Vec::new(),
+    }
+}
+
+/// Prune a linked worktree's entry and remove its directory
+fn remove_worktree(repo: &git2::Repository, name: &str) -> Result<(), String> {
+    let wt = repo
+        .find_worktree(name)
+        .map_err(|e| e.message().to_string())?;
+    let path = wt.path().to_path_buf();
+    wt.prune(Some(
+        git2::WorktreePruneOptions::new()
+            .valid(true)
+            .working_tree(true),
+    ))
+    .map_err(|e| e.message().to_string())?;
+    if path.exists() {
+        std::fs::remove_dir_all(&path).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Command that opens a directory in the file manager of the given OS
+/// (as reported by `std::env::consts::OS`)
+fn file_manager(os: &str) -> &'static str {
+    match os {
+        "macos" => "open",
+        "windows" => "explorer",
+        _ => "xdg-open",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    /// Create a temporary git repository for testing execute_delete_* functions
+    fn setup_git_repo() -> (TempDir, std::path::PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path().to_path_buf();
+
+        Command::new("git")
+            .args(["init", "-b", "main"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["config", "user.email", "test@test.com"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["config", "user.name", "Test User"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        std::fs::write(repo_path.join("README.md"), "# Test").unwrap();
+        Command::new("git")
+            .args(["add", "."])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["commit", "-m", "Initial commit"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+
+        (temp_dir, repo_path)
+    }
+
+    /// Create a worktree with a branch in the test repo
+    fn create_test_worktree_in_repo(repo_path: &std::path::Path, branch: &str, wt_name: &str) {
+        Command::new("git")
+            .args(["branch", branch])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+        let wt_path = repo_path.join(wt_name);
+        Command::new("git")
+            .args(["worktree", "add", wt_path.to_str().unwrap(), branch])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+    }
+
+    fn create_test_worktrees() -> Vec<Worktree> {
+        vec![
+            Worktree {
+                name: "main".to_string(),
+                path: PathBuf::from("/repo/main"),
+                branch: Some("main".to_string()),
+                is_main: true,
+                ..Default::default()
+            },
+            Worktree {
+                name: "feature-a".to_string(),
+                path: PathBuf::from("/repo/feature-a"),
+                branch: Some("feature/a".to_string()),
+                is_main: false,
+                ..Default::default()
+            },
+            Worktree {
+                name: "feature-b".to_string(),
+                path: PathBuf::from("/repo/feature-b"),
+                branch: Some("feature/b".to_string()),
+                is_main: false,
+                ..Default::default()
+            },
+            Worktree {
+                name: "bugfix-x".to_string(),
+                path: PathBuf::from("/repo/bugfix-x"),
+                branch: Some("bugfix/x".to_string()),
+                is_main: false,
+                ..Default::default()
+            },
+        ]
+    }
+
+    fn create_test_branches() -> Vec<Branch> {
+        vec![
+            Branch {
+                name: "main".to_string(),
+                is_remote: false,
+                is_head: true,
+            },
+            Branch {
+                name: "feature/a".to_string(),
+                is_remote: false,
+                is_head: false,
+            },
+            Branch {
+                name: "feature/b".to_string(),
+                is_remote: false,
+                is_head: false,
+            },
+            Branch {
+                name: "origin/feature/c".to_string(),
+                is_remote: true,
+                is_head: false,
+            },
+        ]
+    }
+
+    /// Upper bound for background jobs in tests
+    const JOB_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Poll a background job every 10ms until `check` returns true, failing
+    /// the test after `timeout`
+    fn wait_until(app: &mut App, mut check: impl FnMut(&mut App) -> bool, timeout: Duration) {
+        let deadline = Instant::now() + timeout;
+        while !check(app) {
+            assert!(
+                Instant::now() < deadline,
+                "background job did not finish within {:?}",
+                timeout
+            );
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    fn create_test_app() -> App {
+        App::new_for_test(
+            Config::default(),
+            create_test_worktrees(),
+            create_test_branches(),
+        )
+    }
+
+    // ========== Filter Tests ==========
+
+    #[test]
+    fn test_filter_worktrees_by_name() {
+        let mut app = create_test_app();
+
+        app.input = "feature".to_string();
+        app.filter_worktrees();
+
+        assert_eq!(app.filtered_worktrees.len(), 2);
+        assert!(app
+            .filtered_worktrees
+            .iter()
+            .all(|w| w.name.contains("feature")));
+    }
+
+    #[test]
+    fn test_filter_worktrees_by_branch() {
+        let mut app = create_test_app();
+
+        app.input = "bugfix".to_string();
+        app.filter_worktrees();
+
+        assert_eq!(app.filtered_worktrees.len(), 1);
+        assert_eq!(app.filtered_worktrees[0].name, "bugfix-x");
+    }
+
+    #[test]
+    fn test_filter_worktrees_case_insensitive() {
+        let mut app = create_test_app();
+
+        app.input = "FEATURE".to_string();
+        app.filter_worktrees();
+
+        assert_eq!(app.filtered_worktrees.len(), 2);
+    }
+
+    #[test]
+    fn test_filter_worktrees_empty_input() {
+        let mut app = create_test_app();
+        app.input = "something".to_string();
+        app.filter_worktrees();
+        assert!(app.filtered_worktrees.len() < app.worktrees.len());
+
+        app.input.clear();
+        app.filter_worktrees();
+
+        assert_eq!(app.filtered_worktrees.len(), app.worktrees.len());
+    }
+
+    #[test]
+    fn test_filter_worktrees_by_path() {
+        let mut app = App::new_for_test(
+            Config::default(),
+            vec![Worktree {
+                name: "wt".to_string(),
+                path: PathBuf::from("/home/user/worktrees/backend/wt"),
+                branch: Some("main".to_string()),
+                is_main: false,
+                ..Default::default()
+            }],
+            vec![],
+        );
+
+        app.input = "Backend".to_string();
+        app.filter_worktrees();
+
+        assert_eq!(app.filtered_worktrees.len(), 1);
+    }
+
+    #[test]
+    fn test_filter_worktrees_by_commit_message() {
+        let mut worktrees = create_test_worktrees();
+        worktrees[2].commit_message = Some("Fix auth token refresh".to_string());
+        worktrees[2].commit_author = Some("Alice".to_string());
+        let mut app = App::new_for_test(Config::default(), worktrees, vec![]);
+
+        app.input = "AUTH TOKEN".to_string();
+        app.filter_worktrees();
+
+        assert_eq!(app.filtered_worktrees.len(), 1);
+        assert_eq!(app.filtered_worktrees[0].name, "feature-b");
+        assert_eq!(
+            app.selected_search_match(),
+            Some(SearchField::CommitMessage)
+        );
+    }
+
+    #[test]
+    fn test_filter_worktrees_by_commit_author() {
+        let mut worktrees = create_test_worktrees();
+        worktrees[1].commit_author = Some("Alice".to_string());
+        let mut app = App::new_for_test(Config::default(), worktrees, vec![]);
+
+        app.input = "alice".to_string();
+        app.filter_worktrees();
+
+        assert_eq!(app.filtered_worktrees.len(), 1);
+        assert_eq!(app.selected_search_match(), Some(SearchField::CommitAuthor));
+    }
+
+    #[test]
+    fn test_filter_worktrees_by_commit_id() {
+        let mut worktrees = create_test_worktrees();
+        worktrees[2].branch = None;
+        worktrees[2].head_id = Some("4e1c0d2".to_string());
+        let mut app = App::new_for_test(Config::default(), worktrees, vec![]);
+
+        app.input = "4e1c".to_string();
+        app.filter_worktrees();
+
+        assert_eq!(app.filtered_worktrees.len(), 1);
+        assert_eq!(app.filtered_worktrees[0].name, "feature-b");
+        assert_eq!(app.selected_search_match(), Some(SearchField::CommitId));
+    }
+
+    #[test]
+    fn test_detached_worktree_is_listed_searchable_and_selectable() {
+        let (_temp_dir, repo_path) = setup_git_repo();
+        let wt_path = repo_path.join("pinned");
+        Command::new("git")
+            .args(["worktree", "add", "--detach", wt_path.to_str().unwrap()])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        // Move main on, so only the detached worktree is at the first commit
+        Command::new("git")
+            .args(["commit", "--allow-empty", "-m", "Second commit"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        let mut app = create_test_app();
+        app.git = GitManager::from_path(&repo_path).unwrap();
+        app.refresh_worktrees().unwrap();
+
+        let detached = app
+            .worktrees
+            .iter()
+            .find(|w| w.name == "pinned")
+            .expect("detached worktree is listed");
+        assert_eq!(detached.branch, None);
+        let head_id = detached.head_id.clone().unwrap();
+        assert_eq!(head_id.len(), 7);
+
+        app.input = head_id;
+        app.filter_worktrees();
+        assert_eq!(app.filtered_worktrees.len(), 1);
+
+        app.select_worktree();
+        assert_eq!(
+            app.selected_worktree_path.as_deref().map(PathBuf::from),
+            Some(wt_path.canonicalize().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_selected_search_match_prefers_name() {
+        let mut app = create_test_app();
+        assert_eq!(app.selected_search_match(), None);
+
+        app.input = "feature".to_string();
+        app.filter_worktrees();
+
+        // "feature" is in the name, branch and path; the name comes first
+        assert_eq!(app.selected_search_match(), Some(SearchField::Name));
+    }
+
+    #[test]
+    fn test_filter_branches() {
+        let mut app = create_test_app();
+
+        app.input = "feature".to_string();
+        app.filter_branches();
+
+        assert_eq!(app.filtered_branches.len(), 3);
+    }
+
+    #[test]
+    fn test_create_mode_typing_filters_branches() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+
+        for c in "feature".chars() {
+            app.input_char(c);
+        }
+
+        assert_eq!(app.filtered_branches.len(), 3);
+        assert_eq!(app.branch_filter, "feature");
+
+        app.delete_char();
+        assert_eq!(app.branch_filter, "featur");
+    }
+
+    #[test]
+    fn test_create_mode_filter_clamps_selection() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        app.selected_branch = app.filtered_branches.len();
+
+        app.input = "feature".to_string();
+        app.filter_branches();
+
+        // Index 0 is "Create new branch", so the last match is at len()
+        assert_eq!(app.selected_branch, app.filtered_branches.len());
+    }
+
+    #[test]
+    fn test_create_mode_selecting_branch_keeps_filter_and_clears_name() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        for c in "feature".chars() {
+            app.input_char(c);
+        }
+
+        app.move_down();
+
+        assert_eq!(app.selected_branch, 1);
+        assert!(app.input.is_empty());
+        assert_eq!(app.filtered_branches.len(), 3);
+
+        // Typing a worktree name does not re-filter the list
+        app.input_char('x');
+        assert_eq!(app.filtered_branches.len(), 3);
+        assert_eq!(app.branch_filter, "feature");
+    }
+
+    #[test]
+    fn test_create_mode_returning_to_create_new_restores_filter() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        for c in "feature".chars() {
+            app.input_char(c);
+        }
+
+        app.move_down();
+        app.move_down();
+        app.move_up();
+        app.move_up();
+
+        assert_eq!(app.selected_branch, 0);
+        assert_eq!(app.input, "feature");
+    }
+
+    #[test]
+    fn test_create_mode_navigation_stays_within_filtered_set() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        for c in "feature".chars() {
+            app.input_char(c);
+        }
+
+        for _ in 0..10 {
+            app.move_down();
+        }
+
+        assert_eq!(app.selected_branch, app.filtered_branches.len());
+    }
+
+    // ========== Sort Tests ==========
+
+    fn create_dated_app() -> App {
+        let wt = |name: &str, time: i64, created: i64, is_main: bool| Worktree {
+            name: name.to_string(),
+            path: PathBuf::from(format!("/repo/{}", name)),
+            branch: Some(name.to_string()),
+            is_main,
+            last_commit_time: Some(time),
+            created_at: Some(created),
+            ..Default::default()
+        };
+        App::new_for_test(
+            Config::default(),
+            vec![
+                wt("beta", 200, 50, false),
+                wt("zmain", 100, 10, true),
+                wt("alpha", 100, 90, false),
+                wt("gamma", 300, 70, false),
+            ],
+            vec![],
+        )
+    }
+
+    fn names(app: &App) -> Vec<&str> {
+        app.filtered_worktrees
+            .iter()
+            .map(|w| w.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_mode_cycle() {
+        assert_eq!(SortMode::NameAsc.next(), SortMode::NameDesc);
+        assert_eq!(SortMode::NameDesc.next(), SortMode::DateNewest);
+        assert_eq!(SortMode::DateNewest.next(), SortMode::DateOldest);
+        assert_eq!(SortMode::DateOldest.next(), SortMode::Created);
+        assert_eq!(SortMode::Created.next(), SortMode::NameAsc);
+    }
+
+    #[test]
+    fn test_sort_worktrees_keeps_main_on_top() {
+        let mut app = create_dated_app();
+
+        app.sort_worktrees();
+        assert_eq!(names(&app), vec!["zmain", "alpha", "beta", "gamma"]);
+
+        app.cycle_sort();
+        assert_eq!(names(&app), vec!["zmain", "gamma", "beta", "alpha"]);
+
+        app.cycle_sort();
+        assert_eq!(names(&app), vec!["zmain", "gamma", "beta", "alpha"]);
+
+        app.cycle_sort();
+        assert_eq!(names(&app), vec!["zmain", "alpha", "beta", "gamma"]);
+
+        app.cycle_sort();
+        assert_eq!(app.sort_mode, SortMode::Created);
+        assert_eq!(names(&app), vec!["zmain", "beta", "gamma", "alpha"]);
+    }
+
+    // ========== Stale Worktree Tests ==========
+
+    fn aged_worktree(is_main: bool, created_at: Option<i64>, commit: Option<i64>) -> Worktree {
+        Worktree {
+            name: "wt".to_string(),
+            path: PathBuf::from("/repo/wt"),
+            branch: None,
+            is_main,
+            last_commit_time: commit,
+            created_at,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_is_stale_uses_latest_activity() {
+        let day = 86_400;
+        let now = 100 * day;
+
+        assert!(is_stale(
+            &aged_worktree(false, Some(80 * day), None),
+            14,
+            now
+        ));
+        assert!(!is_stale(
+            &aged_worktree(false, Some(90 * day), None),
+            14,
+            now
+        ));
+        // A recent commit keeps an old worktree fresh
+        assert!(!is_stale(
+            &aged_worktree(false, Some(10 * day), Some(95 * day)),
+            14,
+            now
+        ));
+        assert!(!is_stale(&aged_worktree(false, None, None), 14, now));
+        assert!(!is_stale(&aged_worktree(true, Some(0), Some(0)), 14, now));
+    }
+
+    #[test]
+    fn test_app_is_stale_requires_stale_days() {
+        let worktree = aged_worktree(false, Some(0), None);
+
+        let app = create_test_app();
+        assert!(!app.is_stale(&worktree));
+
+        let config: Config = toml::from_str("[ui]\nstale_days = 7").unwrap();
+        let app = App::new_for_test(config, vec![], vec![]);
+        assert!(app.is_stale(&worktree));
+    }
+
+    #[test]
+    fn test_cycle_sort_sets_message() {
+        let mut app = create_dated_app();
+
+        app.cycle_sort();
+
+        assert_eq!(app.sort_mode, SortMode::NameDesc);
+        assert_eq!(app.message, Some("Sort: name ↓".to_string()));
+    }
+
+    #[test]
+    fn test_sort_preserves_search_filter() {
+        let mut app = create_dated_app();
+        app.input = "a".to_string();
+
+        app.cycle_sort();
+
+        assert!(app.filtered_worktrees.iter().all(|w| w.name.contains('a')));
+    }
+
+    // ========== Navigation Tests ==========
+
+    #[test]
+    fn test_move_up_boundary() {
+        let mut app = create_test_app();
+        app.selected_worktree = 0;
+
+        app.move_up();
+
+        // Should not go below 0
+        assert_eq!(app.selected_worktree, 0);
+    }
+
+    #[test]
+    fn test_move_down_boundary() {
+        let mut app = create_test_app();
+        app.selected_worktree = app.filtered_worktrees.len() - 1;
+
+        app.move_down();
+
+        // Should not exceed max index
+        assert_eq!(app.selected_worktree, app.filtered_worktrees.len() - 1);
+    }
+
+    #[test]
+    fn test_move_up_decrements() {
+        let mut app = create_test_app();
+        app.selected_worktree = 2;
+
+        app.move_up();
+
+        assert_eq!(app.selected_worktree, 1);
+    }
+
+    #[test]
+    fn test_move_down_increments() {
+        let mut app = create_test_app();
+        app.selected_worktree = 1;
+
+        app.move_down();
+
+        assert_eq!(app.selected_worktree, 2);
+    }
+
+    fn create_app_with_many_worktrees(count: usize) -> App {
+        let worktrees = (0..count)
+            .map(|i| Worktree {
+                name: format!("wt-{:02}", i),
+                path: PathBuf::from(format!("/repo/wt-{:02}", i)),
+                branch: Some(format!("wt-{:02}", i)),
+                is_main: i == 0,
+                ..Default::default()
+            })
+            .collect();
+        App::new_for_test(Config::default(), worktrees, create_test_branches())
+    }
+
+    #[test]
+    fn test_page_down_moves_by_visible_height_minus_one() {
+        let mut app = create_app_with_many_worktrees(30);
+        app.list_height = 8;
+
+        app.page_down();
+
+        assert_eq!(app.selected_worktree, 7);
+    }
+
+    #[test]
+    fn test_page_up_moves_by_visible_height_minus_one() {
+        let mut app = create_app_with_many_worktrees(30);
+        app.list_height = 8;
+        app.selected_worktree = 20;
+
+        app.page_up();
+
+        assert_eq!(app.selected_worktree, 13);
+    }
+
+    #[test]
+    fn test_page_navigation_clamps_to_bounds() {
+        let mut app = create_app_with_many_worktrees(10);
+        app.list_height = 8;
+        app.selected_worktree = 5;
+
+        app.page_down();
+        assert_eq!(app.selected_worktree, 9);
+
+        app.selected_worktree = 3;
+        app.page_up();
+        assert_eq!(app.selected_worktree, 0);
+    }
+
+    #[test]
+    fn test_worktree_index_at_maps_rows_with_offset() {
+        let mut app = create_app_with_many_worktrees(30);
+        app.list_area = Rect::new(0, 2, 40, 10);
+        app.list_offset = 5;
+
+        // First row inside the top border
+        assert_eq!(app.worktree_index_at(3, 3), Some(5));
+        assert_eq!(app.worktree_index_at(3, 10), Some(12));
+        // Borders and cells outside the list
+        assert_eq!(app.worktree_index_at(3, 2), None);
+        assert_eq!(app.worktree_index_at(3, 11), None);
+        assert_eq!(app.worktree_index_at(0, 5), None);
+        assert_eq!(app.worktree_index_at(45, 5), None);
+    }
+
+    #[test]
+    fn test_worktree_index_at_below_last_item() {
+        let mut app = create_test_app();
+        app.list_area = Rect::new(0, 0, 40, 10);
+
+        assert_eq!(app.worktree_index_at(3, 1), Some(0));
+        assert_eq!(app.worktree_index_at(3, 8), None);
+    }
+
+    #[test]
+    fn test_worktree_index_at_with_two_line_rows() {
+        let mut app = create_app_with_many_worktrees(30);
+        app.config.ui.list_format = Some("{name}\n{path}".to_string());
+        app.list_area = Rect::new(0, 2, 40, 10);
+        app.list_offset = 5;
+
+        assert_eq!(app.list_item_height(), 2);
+        assert_eq!(app.worktree_index_at(3, 3), Some(5));
+        assert_eq!(app.worktree_index_at(3, 4), Some(5));
+        assert_eq!(app.worktree_index_at(3, 5), Some(6));
+
+        app.toggle_compact_list();
+        assert_eq!(app.list_item_height(), 1);
+        assert_eq!(app.worktree_index_at(3, 5), Some(7));
+    }
+
+    #[test]
+    fn test_toggle_compact_list() {
+        let mut app = create_test_app();
+        assert!(!app.compact_list);
+
+        app.toggle_compact_list();
+        assert!(app.compact_list);
+        assert_eq!(app.message.as_deref(), Some("Compact list: on"));
+
+        app.toggle_compact_list();
+        assert!(!app.compact_list);
+        assert_eq!(app.message.as_deref(), Some("Compact list: off"));
+    }
+
+    #[test]
+    fn test_page_size_before_first_render() {
+        let app = create_test_app();
+
+        // Nothing rendered yet: still move by at least one item
+        assert_eq!(app.page_size(), 1);
+    }
+
+    #[test]
+    fn test_page_down_in_create_mode() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        app.list_height = 3;
+        app.selected_branch = 0;
+
+        app.page_down();
+
+        assert_eq!(app.selected_branch, 2);
+    }
+
+    // ========== Restore Selection Tests ==========
+
+    #[test]
+    fn test_restore_selection_selects_existing_worktree() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut worktrees = create_test_worktrees();
+        worktrees[2].path = temp_dir.path().to_path_buf();
+        let mut app = App::new_for_test(Config::default(), worktrees, create_test_branches());
+
+        app.restore_selection(temp_dir.path());
+
+        assert_eq!(app.selected_worktree, 2);
+    }
+
+    #[test]
+    fn test_restore_selection_ignores_deleted_path() {
+        let mut app = create_test_app();
+
+        // Listed but no longer on disk
+        app.restore_selection(Path::new("/repo/feature-b"));
+
+        assert_eq!(app.selected_worktree, 0);
+    }
+
+    #[test]
+    fn test_restore_selection_ignores_unknown_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut app = create_test_app();
+
+        app.restore_selection(temp_dir.path());
+
+        assert_eq!(app.selected_worktree, 0);
+    }
+
+    // ========== Tick Rate Tests ==========
+
+    #[test]
+    fn test_poll_timeout_uses_configured_tick_rate() {
+        use crate::config::UiConfig;
+
+        let config = Config {
+            ui: UiConfig {
+                tick_rate_ms: Some(500),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut app = App::new_for_test(config, create_test_worktrees(), create_test_branches());
+
+        assert_eq!(app.poll_timeout(), Some(Duration::from_millis(500)));
+
+        app.mode = AppMode::Deleting;
+        assert_eq!(app.poll_timeout(), Some(SPINNER_TICK_RATE));
+    }
+
+    #[test]
+    fn test_poll_timeout_blocks_without_animations() {
+        use crate::config::UiConfig;
+
+        let config = Config {
+            ui: UiConfig {
+                animations: Some(false),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut app = App::new_for_test(config, create_test_worktrees(), create_test_branches());
+
+        assert_eq!(app.poll_timeout(), None);
+
+        // Deleting still polls so completion is noticed
+        app.mode = AppMode::Deleting;
+        assert_eq!(app.poll_timeout(), Some(Duration::from_millis(250)));
+
+        app.on_tick();
+        assert_eq!(app.tick, 0);
+    }
+
+    #[test]
+    fn test_on_tick_advances_spinner_while_deleting() {
+        let mut app = create_test_app();
+        app.on_tick();
+        assert_eq!(app.tick, 0);
+
+        app.mode = AppMode::Deleting;
+        app.on_tick();
+        assert_eq!(app.tick, 1);
+    }
+
+    // ========== Background Status Tests ==========
+
+    #[test]
+    fn test_check_status_updates_applies_results() {
+        let mut app = create_test_app();
+        let path = app.worktrees[1].path.clone();
+        app.pending_status = app.worktrees.iter().map(|w| w.path.clone()).collect();
+        let (tx, rx) = mpsc::channel();
+        app.status_receiver = Some(rx);
+
+        tx.send((path.clone(), true)).unwrap();
+        app.check_status_updates();
+
+        assert!(!app.pending_status.contains(&path));
+        assert_eq!(app.pending_status.len(), 3);
+        assert!(app.worktrees[1].is_dirty);
+        assert!(app
+            .filtered_worktrees
+            .iter()
+            .any(|w| w.path == path && w.is_dirty));
+        assert!(app.status_receiver.is_some());
+    }
+
+    #[test]
+    fn test_check_status_updates_clears_pending_when_done() {
+        let mut app = create_test_app();
+        app.pending_status = app.worktrees.iter().map(|w| w.path.clone()).collect();
+        let (tx, rx) = mpsc::channel::<(PathBuf, bool)>();
+        app.status_receiver = Some(rx);
+
+        drop(tx);
+        app.check_status_updates();
+
+        assert!(app.pending_status.is_empty());
+        assert!(app.status_receiver.is_none());
+    }
+
+    #[test]
+    fn test_refresh_worktrees_loads_status_in_background() {
+        let mut app = create_test_app();
+
+        app.refresh_worktrees().unwrap();
+        assert!(!app.pending_status.is_empty());
+
+        wait_until(
+            &mut app,
+            |app| {
+                app.check_status_updates();
+                app.status_receiver.is_none()
+            },
+            JOB_TIMEOUT,
+        );
+        assert!(app.pending_status.is_empty());
+    }
+
+    #[test]
+    fn test_poll_timeout_wakes_for_pending_status_without_animations() {
+        use crate::config::UiConfig;
+
+        let config = Config {
+            ui: UiConfig {
+                animations: Some(false),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut app = App::new_for_test(config, create_test_worktrees(), create_test_branches());
+        assert_eq!(app.poll_timeout(), None);
+
+        let (_tx, rx) = mpsc::channel();
+        app.status_receiver = Some(rx);
+        assert!(app.poll_timeout().is_some());
+    }
+
+    // ========== Mark Tests ==========
+
+    #[test]
+    fn test_toggle_mark_marks_and_moves_down() {
+        let mut app = create_test_app();
+        app.selected_worktree = 1;
+
+        app.toggle_mark();
+
+        assert!(app
+            .marked_worktrees
+            .contains(&PathBuf::from("/repo/feature-a")));
+        assert_eq!(app.selected_worktree, 2);
+    }
+
+    #[test]
+    fn test_toggle_mark_twice_unmarks() {
+        let mut app = create_test_app();
+        app.selected_worktree = 1;
+        app.toggle_mark();
+        app.selected_worktree = 1;
+
+        app.toggle_mark();
+
+        assert!(app.marked_worktrees.is_empty());
+    }
+
+    #[test]
+    fn test_toggle_mark_rejects_main_worktree() {
+        let mut app = create_test_app();
+        app.selected_worktree = 0;
+
+        app.toggle_mark();
+
+        assert!(app.marked_worktrees.is_empty());
+        assert_eq!(app.message, Some("Cannot mark main worktree".to_string()));
+    }
+
+    #[test]
+    fn test_marked_worktree_list_follows_list_order() {
+        let mut app = create_test_app();
+        app.marked_worktrees.insert(PathBuf::from("/repo/bugfix-x"));
+        app.marked_worktrees
+            .insert(PathBuf::from("/repo/feature-a"));
+
+        let names: Vec<_> = app
+            .marked_worktree_list()
+            .into_iter()
+            .map(|w| w.name)
+            .collect();
+
+        assert_eq!(names, vec!["feature-a", "bugfix-x"]);
+    }
+
+    #[test]
+    fn test_enter_confirm_delete_with_marks_ignores_main_selection() {
+        let mut app = create_test_app();
+        app.selected_worktree = 0;
+        app.marked_worktrees
+            .insert(PathBuf::from("/repo/feature-a"));
+
+        app.enter_confirm_delete().unwrap();
+
+        assert_eq!(app.mode, AppMode::Confirm);
+        assert_eq!(app.confirm_action, Some(ConfirmAction::DeleteMarked));
+    }
+
+    // ========== Confirm Config Tests ==========
+
+    fn create_unconfirmed_app() -> App {
+        let config: Config = toml::from_str("[confirm]\ndelete = false\nprune = false").unwrap();
+        App::new_for_test(config, create_test_worktrees(), create_test_branches())
+    }
+
+    #[test]
+    fn test_enter_confirm_delete_skips_dialog_when_disabled() {
+        let mut app = create_unconfirmed_app();
+        app.selected_worktree = 1;
+
+        app.enter_confirm_delete().unwrap();
+
+        assert_eq!(app.mode, AppMode::Deleting);
+    }
+
+    #[test]
+    fn test_enter_confirm_delete_marked_skips_dialog_when_disabled() {
+        let mut app = create_unconfirmed_app();
+        app.marked_worktrees
+            .insert(PathBuf::from("/repo/feature-a"));
+
+        app.enter_confirm_delete().unwrap();
+
+        assert_eq!(app.mode, AppMode::Deleting);
+    }
+
+    #[test]
+    fn test_enter_confirm_delete_still_asks_for_locked_or_dirty() {
+        for (locked, dirty) in [(true, false), (false, true)] {
+            let mut app = create_unconfirmed_app();
+            app.selected_worktree = 1;
+            app.filtered_worktrees[1].is_locked = locked;
+            app.filtered_worktrees[1].is_dirty = dirty;
+
+            app.enter_confirm_delete().unwrap();
+
+            assert_eq!(app.mode, AppMode::Confirm);
+            assert_eq!(app.confirm_action, Some(ConfirmAction::DeleteSingle));
+        }
+    }
+
+    #[test]
+    fn test_enter_confirm_delete_marked_still_asks_for_locked_dirty_or_pending() {
+        for case in 0..3 {
+            let mut app = create_unconfirmed_app();
+            let path = PathBuf::from("/repo/feature-b");
+            app.marked_worktrees
+                .insert(PathBuf::from("/repo/feature-a"));
+            app.marked_worktrees.insert(path.clone());
+            match case {
+                0 => app.worktrees[2].is_locked = true,
+                1 => app.worktrees[2].is_dirty = true,
+                _ => {
+                    app.pending_status.insert(path);
+                }
+            }
+
+            app.enter_confirm_delete().unwrap();
+
+            assert_eq!(app.mode, AppMode::Confirm);
+            assert_eq!(app.confirm_action, Some(ConfirmAction::DeleteMarked));
+        }
+    }
+
+    #[test]
+    fn test_enter_confirm_delete_forced_dialog_says_why() {
+        let mut app = create_unconfirmed_app();
+        app.marked_worktrees
+            .insert(PathBuf::from("/repo/feature-a"));
+        app.marked_worktrees
+            .insert(PathBuf::from("/repo/feature-b"));
+        app.worktrees[1].is_dirty = true;
+
+        app.enter_confirm_delete().unwrap();
+
+        assert_eq!(
+            app.confirm_reason.as_deref(),
+            Some("Asking although confirm.delete is off:\n! feature-a has uncommitted changes")
+        );
+        app.enter_normal_mode();
+        assert!(app.confirm_reason.is_none());
+    }
+
+    #[test]
+    fn test_enter_confirm_delete_with_prompt_on_has_no_reason() {
+        let mut app = create_test_app();
+        app.selected_worktree = 1;
+        app.filtered_worktrees[1].is_dirty = true;
+
+        app.enter_confirm_delete().unwrap();
+
+        assert_eq!(app.mode, AppMode::Confirm);
+        assert!(app.confirm_reason.is_none());
+    }
+
+    #[test]
+    fn test_enter_confirm_delete_still_asks_while_status_pending() {
+        let mut app = create_unconfirmed_app();
+        app.selected_worktree = 1;
+        app.pending_status.insert(PathBuf::from("/repo/feature-a"));
+
+        app.enter_confirm_delete().unwrap();
+
+        assert_eq!(app.mode, AppMode::Confirm);
+    }
+
+    /// Repository with merged worktrees `wt` (feature) and `rel` (release/1)
+    fn create_prune_exclude_app(patterns: &[&str]) -> (TempDir, App) {
+        let (temp_dir, repo_path) = setup_git_repo();
+        create_test_worktree_in_repo(&repo_path, "feature", "wt");
+        create_test_worktree_in_repo(&repo_path, "release/1", "rel");
+        Command::new("git")
+            .args(["commit", "--allow-empty", "-m", "Later"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        let mut app = create_test_app();
+        app.config.worktree.prune_exclude = Some(patterns.iter().map(|p| p.to_string()).collect());
+        app.git = GitManager::from_path(&repo_path).unwrap();
+        (temp_dir, app)
+    }
+
+    #[test]
+    fn test_enter_confirm_prune_skips_excluded_branches() {
+        let (_temp_dir, mut app) = create_prune_exclude_app(&["release/*"]);
+
+        app.enter_confirm_prune_into("main".to_string()).unwrap();
+
+        assert_eq!(app.mode, AppMode::Confirm);
+        let names = |list: &[Worktree]| list.iter().map(|w| w.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&app.merged_worktrees), vec!["wt"]);
+        assert_eq!(names(&app.prune_excluded), vec!["rel"]);
+    }
+
+    #[test]
+    fn test_enter_confirm_prune_keeps_locked_worktrees() {
+        let (_temp_dir, mut app) = create_prune_exclude_app(&[]);
+        app.git.lock_worktree("rel", Some("release")).unwrap();
+
+        app.enter_confirm_prune_into("main".to_string()).unwrap();
+
+        let names = |list: &[Worktree]| list.iter().map(|w| w.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&app.merged_worktrees), vec!["wt"]);
+        assert_eq!(names(&app.prune_locked), vec!["rel"]);
+    }
+
+    #[test]
+    fn test_enter_confirm_prune_all_locked_reports_them() {
+        let (_temp_dir, mut app) = create_prune_exclude_app(&["feat*"]);
+        app.git.lock_worktree("rel", None).unwrap();
+
+        app.enter_confirm_prune_into("main".to_string()).unwrap();
+
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(
+            app.message.as_deref(),
+            Some("No worktrees merged into 'main' to prune (1 kept by worktree.prune_exclude, 1 locked)")
+        );
+    }
+
+    #[test]
+    fn test_enter_confirm_prune_still_asks_while_status_pending() {
+        let (_temp_dir, mut app) = create_prune_exclude_app(&[]);
+        app.config.confirm.prune = Some(false);
+        let wt = app.git.list_worktrees().unwrap();
+        app.pending_status = wt.into_iter().map(|w| w.path).collect();
+
+        app.enter_confirm_prune_into("main".to_string()).unwrap();
+
+        assert_eq!(app.mode, AppMode::Confirm);
+        assert_eq!(app.confirm_action, Some(ConfirmAction::Prune));
+    }
+
+    /// Enter the prune dialog for `create_prune_exclude_app` and return the
+    /// merged worktrees in list order
+    fn enter_prune_review(app: &mut App) -> Vec<Worktree> {
+        app.enter_confirm_prune_into("main".to_string()).unwrap();
+        assert_eq!(app.mode, AppMode::Confirm);
+        assert_eq!(app.merged_worktrees.len(), 2);
+        app.merged_worktrees.clone()
+    }
+
+    #[test]
+    fn test_toggle_prune_item_unchecks_and_rechecks() {
+        let (_temp_dir, mut app) = create_prune_exclude_app(&[]);
+        let merged = enter_prune_review(&mut app);
+        assert_eq!(app.prune_selection().len(), 2);
+
+        app.prune_cursor_down();
+        app.toggle_prune_item();
+        let names: Vec<String> = app.prune_selection().into_iter().map(|w| w.name).collect();
+        assert_eq!(names, vec![merged[0].name.clone()]);
+
+        app.toggle_prune_item();
+        assert_eq!(app.prune_selection().len(), 2);
+    }
+
+    #[test]
+    fn test_prune_cursor_stays_within_list() {
+        let (_temp_dir, mut app) = create_prune_exclude_app(&[]);
+        enter_prune_review(&mut app);
+
+        app.prune_cursor_up();
+        assert_eq!(app.prune_cursor, 0);
+        for _ in 0..5 {
+            app.prune_cursor_down();
+        }
+        assert_eq!(app.prune_cursor, 1);
+    }
+
+    #[test]
+    fn test_prune_deletes_only_checked_worktrees() {
+        let (_temp_dir, mut app) = create_prune_exclude_app(&[]);
+        let merged = enter_prune_review(&mut app);
+
+        app.toggle_prune_item();
+        app.confirm_action(false).unwrap();
+        wait_until(
+            &mut app,
+            |app| {
+                app.check_delete_completion().unwrap();
+                app.mode != AppMode::Deleting
+            },
+            JOB_TIMEOUT,
+        );
+
+        assert!(merged[0].path.exists());
+        assert!(!merged[1].path.exists());
+        assert_eq!(app.message.as_deref(), Some("Pruned 1 merged worktree(s)"));
+    }
+
+    #[test]
+    fn test_prune_with_nothing_checked_stays_in_dialog() {
+        let (_temp_dir, mut app) = create_prune_exclude_app(&[]);
+        let merged = enter_prune_review(&mut app);
+
+        app.toggle_prune_item();
+        app.prune_cursor_down();
+        app.toggle_prune_item();
+        app.confirm_action(false).unwrap();
+
+        assert_eq!(app.mode, AppMode::Confirm);
+        assert_eq!(
+            app.message.as_deref(),
+            Some("No worktrees checked to prune")
+        );
+        assert!(merged.iter().all(|w| w.path.exists()));
+    }
+
+    #[test]
+    fn test_enter_confirm_prune_checks_everything_again() {
+        let (_temp_dir, mut app) = create_prune_exclude_app(&[]);
+        enter_prune_review(&mut app);
+        app.prune_cursor_down();
+        app.toggle_prune_item();
+        app.enter_normal_mode();
+
+        enter_prune_review(&mut app);
+
+        assert_eq!(app.prune_cursor, 0);
+        assert_eq!(app.prune_selection().len(), 2);
+    }
+
+    #[test]
+    fn test_enter_confirm_prune_all_excluded_reports_them() {
+        let (_temp_dir, mut app) = create_prune_exclude_app(&["release/*", "feat*"]);
+
+        app.enter_confirm_prune_into("main".to_string()).unwrap();
+
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(
+            app.message.as_deref(),
+            Some("No worktrees merged into 'main' to prune (2 kept by worktree.prune_exclude)")
+        );
+    }
+
+    #[test]
+    fn test_enter_confirm_prune_skips_dialog_when_disabled() {
+        let (_temp_dir, repo_path) = setup_git_repo();
+        create_test_worktree_in_repo(&repo_path, "feature", "wt");
+        // main moves past feature, so feature counts as merged
+        Command::new("git")
+            .args(["commit", "--allow-empty", "-m", "Later"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        let mut app = create_unconfirmed_app();
+        app.git = GitManager::from_path(&repo_path).unwrap();
+
+        app.enter_confirm_prune_into("main".to_string()).unwrap();
+
+        assert_eq!(app.mode, AppMode::Deleting);
+        wait_until(
+            &mut app,
+            |app| {
+                app.check_delete_completion().unwrap();
+                app.mode != AppMode::Deleting
+            },
+            JOB_TIMEOUT,
+        );
+        assert!(!repo_path.join("wt").exists());
+    }
+
+    // ========== Disk Size Tests ==========
+
+    #[test]
+    fn test_directory_size_sums_nested_files() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("sub")).unwrap();
+        std::fs::write(temp_dir.path().join("a"), vec![0u8; 100]).unwrap();
+        std::fs::write(temp_dir.path().join("sub/b"), vec![0u8; 50]).unwrap();
+
+        let size = directory_size(temp_dir.path(), Instant::now() + Duration::from_secs(5));
+
+        assert_eq!(
+            size,
+            DirSize {
+                bytes: 150,
+                partial: false
+            }
+        );
+    }
+
+    #[test]
+    fn test_directory_size_past_deadline_is_partial() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("a"), vec![0u8; 100]).unwrap();
+
+        let size = directory_size(temp_dir.path(), Instant::now() - Duration::from_secs(1));
+
+        assert!(size.partial);
+        assert_eq!(size.bytes, 0);
+    }
+
+    #[test]
+    fn test_confirm_prune_measures_size_when_enabled() {
+        let (_temp_dir, mut app) = create_prune_exclude_app(&["release/*"]);
+        app.config.confirm.show_size = Some(true);
+
+        app.enter_confirm_prune_into("main".to_string()).unwrap();
+        assert!(app.measuring_size());
+        wait_until(
+            &mut app,
+            |app| {
+                app.check_size_update();
+                !app.measuring_size()
+            },
+            JOB_TIMEOUT,
+        );
+
+        let size = app.confirm_size.expect("size measured");
+        assert!(size.bytes > 0);
+        assert!(!size.partial);
+    }
+
+    #[test]
+    fn test_confirm_delete_skips_size_by_default() {
+        let mut app = create_test_app();
+        app.selected_worktree = 1;
+
+        app.enter_confirm_delete().unwrap();
+
+        assert_eq!(app.mode, AppMode::Confirm);
+        assert!(!app.measuring_size());
+        assert!(app.confirm_size.is_none());
+    }
+
+    // ========== Chord Tests ==========
+
+    #[test]
+    fn test_chord_completes_on_repeated_key() {
+        let mut app = create_test_app();
+
+        assert!(!app.chord('g'));
+        assert!(app.chord('g'));
+        assert!(app.pending_key.is_none());
+    }
+
+    #[test]
+    fn test_chord_different_key_restarts() {
+        let mut app = create_test_app();
+
+        assert!(!app.chord('g'));
+        assert!(!app.chord('d'));
+        assert_eq!(app.pending_key.map(|(c, _)| c), Some('d'));
+    }
+
+    #[test]
+    fn test_chord_times_out() {
+        let mut app = create_test_app();
+        let stale = Instant::now().checked_sub(CHORD_TIMEOUT * 2).unwrap();
+        app.pending_key = Some(('g', stale));
+
+        // A late second key starts a new chord instead of completing it
+        assert!(!app.chord('g'));
+    }
+
+    #[test]
+    fn test_on_tick_expires_pending_chord() {
+        let mut app = create_test_app();
+        let stale = Instant::now().checked_sub(CHORD_TIMEOUT * 2).unwrap();
+        app.pending_key = Some(('g', stale));
+
+        app.on_tick();
+
+        assert!(app.pending_key.is_none());
+    }
+
+    #[test]
+    fn test_move_first_and_last() {
+        let mut app = create_test_app();
+
+        app.move_last();
+        assert_eq!(app.selected_worktree, app.filtered_worktrees.len() - 1);
+
+        app.move_first();
+        assert_eq!(app.selected_worktree, 0);
+    }
+
+    // ========== Mode Transition Tests ==========
+
+    #[test]
+    fn test_enter_normal_mode_clears_input() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        app.input = "some-input".to_string();
+
+        app.enter_normal_mode();
+
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.input.is_empty());
+        assert!(app.confirm_action.is_none());
+    }
+
+    #[test]
+    fn test_enter_config_mode() {
+        let mut app = create_test_app();
+
+        app.enter_config_mode();
+
+        assert_eq!(app.mode, AppMode::Config);
+        assert_eq!(app.config_scroll, 0);
+    }
+
+    #[test]
+    fn test_enter_help_mode_resets_scroll() {
+        let mut app = create_test_app();
+        app.config_scroll = 4;
+
+        app.enter_help_mode();
+
+        assert_eq!(app.mode, AppMode::Help);
+        assert_eq!(app.config_scroll, 0);
+    }
+
+    #[test]
+    fn test_enter_config_mode_resets_scroll() {
+        let mut app = create_test_app();
+        app.config_scroll = 5;
+
+        app.enter_config_mode();
+
+        assert_eq!(app.config_scroll, 0);
+    }
+
+    #[test]
+    fn test_scroll_config_up_at_zero() {
+        let mut app = create_test_app();
+        app.config_scroll = 0;
+
+        app.scroll_config_up();
+
+        assert_eq!(app.config_scroll, 0);
+    }
+
+    #[test]
+    fn test_scroll_config_up_decrements() {
+        let mut app = create_test_app();
+        app.config_scroll = 3;
+
+        app.scroll_config_up();
+
+        assert_eq!(app.config_scroll, 2);
+    }
+
+    #[test]
+    fn test_scroll_config_down_increments() {
+        let mut app = create_test_app();
+        app.config_scroll = 0;
+        app.config_scroll_max = 10;
+
+        app.scroll_config_down();
+
+        assert_eq!(app.config_scroll, 1);
+    }
+
+    #[test]
+    fn test_scroll_config_down_clamped_at_max() {
+        let mut app = create_test_app();
+        app.config_scroll = 5;
+        app.config_scroll_max = 5;
+
+        app.scroll_config_down();
+
+        assert_eq!(app.config_scroll, 5);
+    }
+
+    #[test]
+    fn test_enter_confirm_delete_sets_mode() {
+        let mut app = create_test_app();
+        app.selected_worktree = 1; // Not main worktree
+
+        app.enter_confirm_delete().unwrap();
+
+        assert_eq!(app.mode, AppMode::Confirm);
+        assert_eq!(app.confirm_action, Some(ConfirmAction::DeleteSingle));
+    }
+
+    #[test]
+    fn test_enter_confirm_delete_prevents_main_deletion() {
+        let mut app = create_test_app();
+        app.selected_worktree = 0; // Main worktree
+
+        app.enter_confirm_delete().unwrap();
+
+        // Should not enter confirm mode
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.message.is_some());
+        assert!(app.message.as_ref().unwrap().contains("Cannot delete main"));
+    }
+
+    // ========== Input Tests ==========
+
+    #[test]
+    fn test_input_char() {
+        let mut app = create_test_app();
+
+        app.input_char('a');
+        app.input_char('b');
+        app.input_char('c');
+
+        assert_eq!(app.input, "abc");
+    }
+
+    #[test]
+    fn test_delete_char() {
+        let mut app = create_test_app();
+        app.input = "test".to_string();
+
+        app.delete_char();
+
+        assert_eq!(app.input, "tes");
+    }
+
+    #[test]
+    fn test_delete_char_empty() {
+        let mut app = create_test_app();
+        app.input = String::new();
+
+        app.delete_char();
+
+        assert!(app.input.is_empty());
+    }
+
+    #[test]
+    fn test_input_char_triggers_filter_in_normal_mode() {
+        let mut app = create_test_app();
+        assert_eq!(app.filtered_worktrees.len(), 4);
+
+        app.input_char('f');
+        app.input_char('e');
+        app.input_char('a');
+
+        // Should have filtered to just worktrees containing "fea"
+        assert_eq!(app.filtered_worktrees.len(), 2);
+    }
+
+    #[test]
+    fn test_input_char_resets_selection_to_first_match() {
+        let mut app = create_test_app();
+        app.selected_worktree = 3;
+
+        app.input_char('f');
+
+        assert_eq!(app.selected_worktree, 0);
+        assert_eq!(app.filtered_worktrees[0].name, "feature-a");
+    }
+
+    #[test]
+    fn test_delete_char_resets_selection() {
+        let mut app = create_test_app();
+        app.input_char('f');
+        app.selected_worktree = 1;
+
+        app.delete_char();
+
+        assert_eq!(app.selected_worktree, 0);
+        assert_eq!(app.filtered_worktrees.len(), 4);
+    }
+
+    #[test]
+    fn test_input_char_inserts_at_cursor() {
+        let mut app = create_test_app();
+        app.input = "featre".to_string();
+        app.move_cursor_left();
+        app.move_cursor_left();
+
+        app.input_char('u');
+
+        assert_eq!(app.input, "feature");
+        assert_eq!(app.cursor(), 5);
+    }
+
+    #[test]
+    fn test_delete_char_removes_before_cursor() {
+        let mut app = create_test_app();
+        app.input = "feaature".to_string();
+        app.move_cursor_start();
+        app.move_cursor_right();
+        app.move_cursor_right();
+        app.move_cursor_right();
+
+        app.delete_char();
+
+        assert_eq!(app.input, "feature");
+        assert_eq!(app.cursor(), 2);
+    }
+
+    #[test]
+    fn test_delete_char_at_start_does_nothing() {
+        let mut app = create_test_app();
+        app.input = "feature".to_string();
+        app.move_cursor_start();
+
+        app.delete_char();
+
+        assert_eq!(app.input, "feature");
+        assert_eq!(app.cursor(), 0);
+    }
+
+    #[test]
+    fn test_delete_word_stops_at_slash() {
+        let mut app = create_test_app();
+        app.input = "feature/login".to_string();
+
+        app.delete_word();
+
+        assert_eq!(app.input, "feature/");
+    }
+
+    #[test]
+    fn test_delete_word_stops_at_hyphen() {
+        let mut app = create_test_app();
+        app.input = "feature/fix-bug".to_string();
+
+        app.delete_word();
+        assert_eq!(app.input, "feature/fix-");
+
+        // Trailing separators go with the previous word
+        app.delete_word();
+        assert_eq!(app.input, "feature/");
+
+        app.delete_word();
+        assert_eq!(app.input, "");
+    }
+
+    #[test]
+    fn test_delete_word_respects_cursor() {
+        let mut app = create_test_app();
+        app.input = "fix-bug-now".to_string();
+        app.move_cursor_start();
+        for _ in 0..7 {
+            app.move_cursor_right();
+        }
+
+        app.delete_word();
+
+        assert_eq!(app.input, "fix--now");
+        assert_eq!(app.cursor(), 4);
+    }
+
+    #[test]
+    fn test_delete_to_line_start_respects_cursor() {
+        let mut app = create_test_app();
+        app.input = "feature/login".to_string();
+        app.move_cursor_start();
+        for _ in 0..8 {
+            app.move_cursor_right();
+        }
+
+        app.delete_to_line_start();
+
+        assert_eq!(app.input, "login");
+        assert_eq!(app.cursor(), 0);
+
+        app.delete_to_line_start();
+        assert_eq!(app.input, "login");
+    }
+
+    #[test]
+    fn test_cursor_movement_is_clamped() {
+        let mut app = create_test_app();
+        app.input = "ab".to_string();
+
+        app.move_cursor_right();
+        assert_eq!(app.cursor(), 2);
+
+        app.move_cursor_start();
+        app.move_cursor_left();
+        assert_eq!(app.cursor(), 0);
+
+        app.move_cursor_end();
+        assert_eq!(app.cursor(), 2);
+    }
+
+    #[test]
+    fn test_cursor_handles_multibyte_characters() {
+        let mut app = create_test_app();
+        app.input = "日本".to_string();
+        app.move_cursor_left();
+
+        app.input_char('x');
+
+        assert_eq!(app.input, "日x本");
+        assert_eq!(app.input_before_cursor(), "日x");
+    }
+
+    #[test]
+    fn test_cursor_clamped_after_input_cleared() {
+        let mut app = create_test_app();
+        app.input = "feature".to_string();
+        app.move_cursor_start();
+        app.move_cursor_right();
+        app.input.clear();
+
+        app.input_char('a');
+
+        assert_eq!(app.input, "a");
+        assert_eq!(app.cursor(), 1);
+    }
+
+    // ========== Selection Tests ==========
+
+    #[test]
+    fn test_select_worktree_sets_path() {
+        let mut app = create_test_app();
+        app.selected_worktree = 1;
+
+        app.select_worktree();
+
+        assert!(app.should_quit);
+        assert_eq!(
+            app.selected_worktree_path,
+            Some("/repo/feature-a".to_string())
+        );
+    }
+
+    #[test]
+    fn test_copy_selected_path_sets_message() {
+        let mut app = create_test_app();
+        app.selected_worktree = 1;
+
+        app.copy_selected_path();
+
+        // Either copied or fell back to showing the path (headless CI)
+        let message = app.message.unwrap();
+        assert!(message.ends_with("/repo/feature-a"));
+    }
+
+    #[test]
+    fn test_request_quit_when_idle() {
+        let mut app = create_test_app();
+
+        assert!(app.request_quit());
+        assert!(!app.quit_pending);
+    }
+
+    #[test]
+    fn test_request_quit_during_operation_asks_first() {
+        for mode in [AppMode::Creating, AppMode::Deleting, AppMode::Fetching] {
+            let mut app = create_test_app();
+            app.mode = mode;
+
+            assert!(!app.request_quit());
+            assert!(app.quit_pending);
+            assert!(app.request_quit());
+        }
+    }
+
+    #[test]
+    fn test_copy_selected_branch_sets_message() {
+        let mut app = create_test_app();
+        app.selected_worktree = 1;
+
+        app.copy_selected_branch();
+
+        let message = app.message.unwrap();
+        assert!(message.ends_with(": feature/a"));
+    }
+
+    #[test]
+    fn test_copy_selected_branch_detached_copies_short_hash() {
+        let (_temp_dir, repo_path) = setup_git_repo();
+        let mut worktrees = create_test_worktrees();
+        worktrees[1].branch = None;
+        worktrees[1].path = repo_path.clone();
+        let mut app = App::new_for_test(Config::default(), worktrees, vec![]);
+        app.selected_worktree = 1;
+
+        app.copy_selected_branch();
+
+        let head = head_commit_id(&repo_path).unwrap();
+        let message = app.message.unwrap();
+        assert!(message.ends_with(&format!(": {}", &head[..7])));
+        if message.starts_with("Copied") {
+            assert!(message.contains("detached HEAD"));
+        }
+    }
+
+    #[test]
+    fn test_copy_selected_branch_without_commit() {
+        let mut worktrees = create_test_worktrees();
+        worktrees[1].branch = None;
+        let mut app = App::new_for_test(Config::default(), worktrees, vec![]);
+        app.selected_worktree = 1;
+
+        app.copy_selected_branch();
+
+        assert_eq!(app.message.as_deref(), Some("No branch or commit to copy"));
+    }
+
+    #[test]
+    fn test_copy_selected_path_empty_list() {
+        let mut app = App::new_for_test(Config::default(), vec![], vec![]);
+
+        app.copy_selected_path();
+
+        assert!(app.message.is_none());
+    }
+
+    #[test]
+    fn test_fetch_selected_uses_default_remote() {
+        let mut app = create_test_app();
+        app.selected_worktree = 1;
+
+        app.fetch_selected();
+
+        assert_eq!(
+            app.suspend_command,
+            Some(SuspendCommand {
+                command: "git fetch 'origin'".to_string(),
+                cwd: PathBuf::from("/repo/feature-a"),
+                kind: SuspendKind::Fetch,
+            })
+        );
+    }
+
+    #[test]
+    fn test_pull_selected_uses_configured_remote_and_branch() {
+        let config = Config {
+            worktree: crate::config::WorktreeConfig {
+                default_remote: Some("upstream".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut app = App::new_for_test(config, create_test_worktrees(), vec![]);
+        app.selected_worktree = 1;
+
+        app.pull_selected();
+
+        let command = app.suspend_command.unwrap();
+        assert_eq!(command.command, "git pull 'upstream' 'feature/a'");
+        assert_eq!(command.kind, SuspendKind::Pull);
+    }
+
+    #[test]
+    fn test_pull_selected_detached_head() {
+        let mut worktrees = create_test_worktrees();
+        worktrees[1].branch = None;
+        let mut app = App::new_for_test(Config::default(), worktrees, vec![]);
+        app.selected_worktree = 1;
+
+        app.pull_selected();
+
+        assert!(app.suspend_command.is_none());
+        assert_eq!(app.message, Some("Cannot pull a detached HEAD".to_string()));
+    }
+
+    #[test]
+    fn test_finish_suspended_reports_fetch_and_pull() {
+        let mut app = create_test_app();
+        let command = |kind| SuspendCommand {
+            command: String::new(),
+            cwd: PathBuf::from("/repo/feature-a"),
+            kind,
+        };
+
+        app.finish_suspended(&command(SuspendKind::Fetch), Ok(()));
+        assert_eq!(
+            app.message,
+            Some("Fetched origin in 'feature-a'".to_string())
+        );
+
+        app.finish_suspended(&command(SuspendKind::Pull), Err("exit 1".to_string()));
+        assert_eq!(app.message, Some("Pull failed: exit 1".to_string()));
+
+        app.finish_suspended(&command(SuspendKind::Editor), Ok(()));
+        assert_eq!(app.message, None);
+    }
+
+    #[test]
+    fn test_finish_suspended_reports_pull_conflicts() {
+        let (_temp_dir, repo_path) = setup_git_repo();
+        std::fs::write(repo_path.join("file.txt"), "base").unwrap();
+        for args in [vec!["add", "."], vec!["commit", "-m", "base"]] {
+            Command::new("git")
+                .args(&args)
+                .current_dir(&repo_path)
+                .output()
+                .unwrap();
+        }
+        Command::new("git")
+            .args(["checkout", "-b", "other"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        std::fs::write(repo_path.join("file.txt"), "other").unwrap();
+        Command::new("git")
+            .args(["commit", "-am", "other"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["checkout", "-"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        std::fs::write(repo_path.join("file.txt"), "mine").unwrap();
+        for args in [vec!["commit", "-am", "mine"], vec!["merge", "other"]] {
+            Command::new("git")
+                .args(&args)
+                .current_dir(&repo_path)
+                .output()
+                .unwrap();
+        }
+
+        let mut app = create_test_app();
+        let command = SuspendCommand {
+            command: String::new(),
+            cwd: repo_path.clone(),
+            kind: SuspendKind::Pull,
+        };
+        app.finish_suspended(&command, Err("exit 1".to_string()));
+
+        assert!(app.message.unwrap().starts_with("Merge conflicts in"));
+    }
+
+    #[test]
+    fn test_show_diff_uses_default_command() {
+        let mut app = create_test_app();
+        app.selected_worktree = 1;
+
+        app.show_diff();
+
+        assert_eq!(
+            app.suspend_command,
+            Some(SuspendCommand {
+                command: "git diff HEAD".to_string(),
+                cwd: PathBuf::from("/repo/feature-a"),
+                kind: SuspendKind::Diff,
+            })
+        );
+    }
+
+    #[test]
+    fn test_finish_suspended_diff_refreshes() {
+        let (_temp_dir, repo_path) = setup_git_repo();
+        let mut app = create_test_app();
+        app.git = GitManager::from_path(&repo_path).unwrap();
+        let command = SuspendCommand {
+            command: "git diff HEAD".to_string(),
+            cwd: repo_path.clone(),
+            kind: SuspendKind::Diff,
+        };
+
+        app.finish_suspended(&command, Ok(()));
+
+        assert!(app.message.is_none());
+        assert_eq!(app.worktrees.len(), 1);
+    }
+
+    #[test]
+    fn test_open_in_editor_uses_configured_editor() {
+        let config = Config {
+            worktree: crate::config::WorktreeConfig {
+                editor: Some("nvim".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut app = App::new_for_test(config, create_test_worktrees(), vec![]);
+        app.selected_worktree = 1;
+
+        app.open_in_editor();
+
+        assert_eq!(
+            app.suspend_command,
+            Some(SuspendCommand {
+                command: "nvim '/repo/feature-a'".to_string(),
+                cwd: PathBuf::from("/repo/feature-a"),
+                kind: SuspendKind::Editor,
+            })
+        );
+        assert!(app.message.is_none());
+    }
+
+    #[test]
+    fn test_file_manager_per_platform() {
+        assert_eq!(file_manager("macos"), "open");
+        assert_eq!(file_manager("linux"), "xdg-open");
+        assert_eq!(file_manager("freebsd"), "xdg-open");
+        assert_eq!(file_manager("windows"), "explorer");
+    }
+
+    #[test]
+    fn test_check_reveal_completion_reports_failure() {
+        let mut app = create_test_app();
+        let (tx, rx) = mpsc::channel();
+        app.reveal_receiver = Some(rx);
+        tx.send(Err("xdg-open exited with exit status: 4".to_string()))
+            .unwrap();
+
+        app.check_reveal_completion();
+
+        assert_eq!(
+            app.message.as_deref(),
+            Some("xdg-open exited with exit status: 4")
+        );
+        assert_eq!(app.message_level, MessageLevel::Error);
+        assert!(app.reveal_receiver.is_none());
+    }
+
+    #[test]
+    fn test_check_reveal_completion_keeps_message_on_success() {
+        let mut app = create_test_app();
+        app.set_message(MessageLevel::Success, "Opened feature-a in file manager");
+        let (tx, rx) = mpsc::channel();
+        app.reveal_receiver = Some(rx);
+        tx.send(Ok(())).unwrap();
+
+        app.check_reveal_completion();
+
+        assert_eq!(
+            app.message.as_deref(),
+            Some("Opened feature-a in file manager")
+        );
+        assert!(app.reveal_receiver.is_none());
+    }
+
+    #[test]
+    fn test_reveal_selected_empty_list() {
+        let mut app = App::new_for_test(Config::default(), vec![], vec![]);
+
+        app.reveal_selected();
+
+        assert!(app.message.is_none());
+    }
+
+    #[test]
+    fn test_select_worktree_runs_on_open_command() {
+        let mut app = create_test_app();
+        app.config.worktree.on_open = Some("nvim {path} # {branch}".to_string());
+        app.selected_worktree = 1;
+
+        app.select_worktree();
+
+        assert!(!app.should_quit);
+        assert!(app.selected_worktree_path.is_none());
+        assert_eq!(
+            app.suspend_command,
+            Some(SuspendCommand {
+                command: "nvim '/repo/feature-a' # 'feature/a'".to_string(),
+                cwd: PathBuf::from("/repo/feature-a"),
+                kind: SuspendKind::Open,
+            })
+        );
+    }
+
+    #[test]
+    fn test_select_worktree_print_path_ignores_on_open() {
+        let mut app = create_test_app();
+        app.config.worktree.on_open = Some("nvim {path}".to_string());
+        app.print_path = true;
+        app.selected_worktree = 1;
+
+        app.select_worktree();
+
+        assert!(app.should_quit);
+        assert!(app.suspend_command.is_none());
+        assert_eq!(
+            app.selected_worktree_path,
+            Some("/repo/feature-a".to_string())
+        );
+    }
+
+    #[test]
+    fn test_select_worktree_empty_list() {
+        let mut app = App::new_for_test(Config::default(), vec![], vec![]);
+
+        app.select_worktree();
+
+        assert!(!app.should_quit);
+        assert!(app.selected_worktree_path.is_none());
+    }
+
+    #[test]
+    fn test_track_message_with_custom_duration() {
+        let mut app = create_test_app();
+        app.config.ui.notification_secs = Some(3);
+        let start = Instant::now();
+
+        app.message = Some("Created worktree".to_string());
+        app.track_message(start);
+        app.track_message(start + Duration::from_secs(2));
+        assert_eq!(app.message, Some("Created worktree".to_string()));
+
+        app.track_message(start + Duration::from_secs(3));
+        assert!(app.message.is_none());
+    }
+
+    #[test]
+    fn test_track_message_restarts_for_new_message() {
+        let mut app = create_test_app();
+        app.config.ui.notification_secs = Some(3);
+        let start = Instant::now();
+
+        app.message = Some("first".to_string());
+        app.track_message(start);
+        app.message = Some("second".to_string());
+        app.track_message(start + Duration::from_secs(2));
+        app.track_message(start + Duration::from_secs(4));
+        assert_eq!(app.message, Some("second".to_string()));
+
+        app.track_message(start + Duration::from_secs(5));
+        assert!(app.message.is_none());
+    }
+
+    #[test]
+    fn test_set_message_logs_each_message_with_its_level() {
+        let mut app = create_test_app();
+
+        app.set_message(MessageLevel::Success, "Created worktree: feature");
+        app.set_message(MessageLevel::Error, "Cannot lock main worktree");
+        // Set and replaced before a tick could see it
+        app.set_message(MessageLevel::Warning, "Warning: could not fetch remotes");
+        app.set_message(MessageLevel::Success, "Refreshed");
+
+        let log: Vec<_> = app
+            .message_log
+            .iter()
+            .map(|m| (m.text.as_str(), m.level))
+            .collect();
+        assert_eq!(
+            log,
+            vec![
+                ("Created worktree: feature", MessageLevel::Success),
+                ("Cannot lock main worktree", MessageLevel::Error),
+                ("Warning: could not fetch remotes", MessageLevel::Warning),
+                ("Refreshed", MessageLevel::Success),
+            ]
+        );
+        assert_eq!(app.message_level, MessageLevel::Success);
+    }
+
+    #[test]
+    fn test_set_message_caps_log() {
+        let mut app = create_test_app();
+
+        for i in 0..MESSAGE_LOG_CAPACITY + 5 {
+            app.set_message(MessageLevel::Success, format!("message {}", i));
+        }
+
+        assert_eq!(app.message_log.len(), MESSAGE_LOG_CAPACITY);
+        assert_eq!(app.message_log.front().unwrap().text, "message 5");
+    }
+
+    #[test]
+    fn test_set_message_restarts_display_time_for_repeated_text() {
+        let mut app = create_test_app();
+        app.config.ui.notification_secs = Some(3);
+        let start = Instant::now();
+
+        app.set_message(MessageLevel::Success, "Refreshed");
+        app.track_message(start);
+        app.set_message(MessageLevel::Success, "Refreshed");
+        app.track_message(start + Duration::from_secs(2));
+        app.track_message(start + Duration::from_secs(4));
+
+        assert_eq!(app.message.as_deref(), Some("Refreshed"));
+    }
+
+    #[test]
+    fn test_refusals_are_errors() {
+        let mut app = create_test_app();
+
+        app.toggle_lock_selected().unwrap();
+
+        assert_eq!(app.message.as_deref(), Some("Cannot lock main worktree"));
+        assert_eq!(app.message_level, MessageLevel::Error);
+    }
+
+    #[test]
+    fn test_track_message_disabled_by_default() {
+        let mut app = create_test_app();
+        let start = Instant::now();
+
+        app.message = Some("sticky".to_string());
+        app.track_message(start);
+        app.track_message(start + Duration::from_secs(3600));
+        assert_eq!(app.message, Some("sticky".to_string()));
+    }
+
+    #[test]
+    fn test_clear_message() {
+        let mut app = create_test_app();
+        app.message = Some("Test message".to_string());
+
+        app.clear_message();
+
+        assert!(app.message.is_none());
+    }
+
+    // ========== Filter Adjusts Selection Tests ==========
+
+    #[test]
+    fn test_filter_adjusts_selection_when_out_of_bounds() {
+        let mut app = create_test_app();
+        app.selected_worktree = 3; // Last item
+
+        app.input = "feature-a".to_string();
+        app.filter_worktrees();
+
+        // After filtering, only 1 item remains, selection should be adjusted
+        assert!(app.selected_worktree < app.filtered_worktrees.len());
+    }
+
+    // ========== Config Integration Tests ==========
+
+    #[test]
+    fn test_icons_enabled_default() {
+        let app = create_test_app();
+        // Default should be true
+        assert!(app.icons_enabled());
+    }
+
+    #[test]
+    fn test_icons_enabled_disabled() {
+        use crate::config::UiConfig;
+
+        let config = Config {
+            ui: UiConfig {
+                icons: Some(false),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let app = App::new_for_test(config, create_test_worktrees(), create_test_branches());
+
+        assert!(!app.icons_enabled());
+    }
+
+    #[test]
+    fn test_format_path_with_tilde_home() {
+        let app = create_test_app();
+        let home = dirs::home_dir().unwrap();
+        let full_path = format!("{}/projects/test", home.to_string_lossy());
+
+        let formatted = app.format_path(&full_path);
+
+        // Default tilde_home is true, so should be compressed
+        assert_eq!(formatted, "~/projects/test");
+    }
+
+    #[test]
+    fn test_format_path_without_tilde_home() {
+        use crate::config::UiConfig;
+
+        let config = Config {
+            ui: UiConfig {
+                tilde_home: Some(false),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let app = App::new_for_test(config, create_test_worktrees(), create_test_branches());
+
+        let home = dirs::home_dir().unwrap();
+        let full_path = format!("{}/projects/test", home.to_string_lossy());
+
+        let formatted = app.format_path(&full_path);
+
+        // tilde_home is false, so should NOT be compressed
+        assert_eq!(formatted, full_path);
+    }
+
+    // ========== Main Worktree Path Tests ==========
+
+    #[test]
+    fn test_get_main_worktree_path_found() {
+        let app = create_test_app();
+
+        let main_path = app.get_main_worktree_path();
+
+        assert!(main_path.is_some());
+        assert_eq!(main_path.unwrap(), PathBuf::from("/repo/main"));
+    }
+
+    #[test]
+    fn test_get_main_worktree_path_not_found() {
+        let worktrees = vec![
+            Worktree {
+                name: "feature-a".to_string(),
+                path: PathBuf::from("/repo/feature-a"),
+                branch: Some("feature/a".to_string()),
+                is_main: false,
+                ..Default::default()
+            },
+            Worktree {
+                name: "feature-b".to_string(),
+                path: PathBuf::from("/repo/feature-b"),
+                branch: Some("feature/b".to_string()),
+                is_main: false,
+                ..Default::default()
+            },
+        ];
+        let app = App::new_for_test(Config::default(), worktrees, vec![]);
+
+        let main_path = app.get_main_worktree_path();
+
+        assert!(main_path.is_none());
+    }
+
+    #[test]
+    fn test_get_main_worktree_path_empty_list() {
+        let app = App::new_for_test(Config::default(), vec![], vec![]);
+
+        let main_path = app.get_main_worktree_path();
+
+        assert!(main_path.is_none());
+    }
+
+    #[test]
+    fn test_terminal_title_shows_repo_and_mode() {
+        let mut app = create_test_app();
+
+        assert_eq!(app.terminal_title().as_deref(), Some("gwm — main [list]"));
+
+        app.mode = AppMode::Create;
+        assert_eq!(app.terminal_title().as_deref(), Some("gwm — main [create]"));
+    }
+
+    #[test]
+    fn test_terminal_title_disabled() {
+        let config: Config = toml::from_str("[ui]\nset_title = false").unwrap();
+        let app = App::new_for_test(config, create_test_worktrees(), vec![]);
+
+        assert_eq!(app.terminal_title(), None);
+    }
+
+    // ========== Create Field Tests ==========
+
+    #[test]
+    fn test_toggle_create_field_swaps_branch_and_worktree_name() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        app.selected_branch = 0;
+        for c in "feat-x".chars() {
+            app.input_char(c);
+        }
+
+        app.toggle_create_field();
+        assert_eq!(app.create_field, CreateField::WorktreeName);
+        assert!(app.input.is_empty());
+
+        app.input_char('w');
+        app.input_char('t');
+        // Typing the worktree name doesn't touch the branch name or filter
+        assert_eq!(app.branch_filter, "feat-x");
+
+        app.toggle_create_field();
+        assert_eq!(app.create_field, CreateField::BaseRef);
+        assert!(app.input.is_empty());
+        assert_eq!(app.worktree_name_input, "wt");
+
+        app.input_char('v');
+        app.toggle_create_field();
+        assert_eq!(app.create_field, CreateField::BranchName);
+        assert_eq!(app.input, "feat-x");
+        assert_eq!(app.base_ref_input, "v");
+        assert_eq!(app.cursor(), 6);
+    }
+
+    #[test]
+    fn test_create_base_ref_follows_active_field() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        app.selected_branch = 0;
+        app.base_ref_input = "v1".to_string();
+        assert_eq!(app.create_base_ref(), "v1");
+
+        app.toggle_create_field();
+        app.toggle_create_field();
+
+        assert_eq!(app.create_field, CreateField::BaseRef);
+        assert_eq!(app.input, "v1");
+        assert_eq!(app.create_base_ref(), "v1");
+    }
+
+    #[test]
+    fn test_leaving_create_new_stashes_start_point() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        app.selected_branch = 0;
+        app.toggle_create_field();
+        app.toggle_create_field();
+        app.input_char('v');
+
+        app.move_down();
+
+        assert_eq!(app.create_field, CreateField::BranchName);
+        assert_eq!(app.base_ref_input, "v");
+        assert!(app.input.is_empty());
+    }
+
+    #[test]
+    fn test_enter_create_mode_clears_start_point() {
+        let mut app = create_test_app();
+        app.base_ref_input = "v1".to_string();
+
+        app.enter_create_mode().unwrap();
+
+        assert!(app.base_ref_input.is_empty());
+    }
+
+    #[test]
+    fn test_toggle_create_field_ignored_on_existing_branch() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        app.selected_branch = 1;
+
+        app.toggle_create_field();
+
+        assert_eq!(app.create_field, CreateField::BranchName);
+    }
+
+    #[test]
+    fn test_leaving_create_new_resets_create_field() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        app.selected_branch = 0;
+        app.toggle_create_field();
+        app.input_char('w');
+
+        app.move_down();
+
+        assert_eq!(app.create_field, CreateField::BranchName);
+        assert_eq!(app.worktree_name_input, "w");
+        assert!(app.input.is_empty());
+    }
+
+    // ========== Create Worktree Logic Tests ==========
+
+    #[test]
+    fn test_create_worktree_new_branch_empty_input_shows_message() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        app.selected_branch = 0; // "Create new branch" option
+        app.input.clear();
+
+        // This would trigger "Please enter a branch name" message
+        // Since create_worktree requires actual git repo, we test the logic condition
+        let should_show_message = app.selected_branch == 0 && app.input.is_empty();
+
+        assert!(should_show_message);
+    }
+
+    #[test]
+    fn test_create_worktree_rejects_invalid_branch_name() {
+        for name in ["foo..bar", "foo/", "my branch"] {
+            let mut app = create_test_app();
+            app.mode = AppMode::Create;
+            app.selected_branch = 0;
+            app.input = name.to_string();
+
+            app.create_worktree().unwrap();
+
+            assert_eq!(app.mode, AppMode::Create);
+            assert_eq!(app.input, name);
+            assert_eq!(app.message, Some(format!("Invalid branch name '{}'", name)));
+        }
+    }
+
+    #[test]
+    fn test_create_worktree_existing_branch_selected() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        app.selected_branch = 1; // First actual branch (index 1 = filtered_branches[0])
+
+        // Verify branch index mapping
+        let branch_index = app.selected_branch - 1;
+        assert_eq!(branch_index, 0);
+        assert!(branch_index < app.filtered_branches.len());
+    }
+
+    #[test]
+    fn test_create_worktree_remote_branch_name_extraction() {
+        // Test the remote branch name extraction logic
+        let remote_branch_name = "origin/feature/test";
+
+        let extracted_name: String = remote_branch_name
+            .split('/')
+            .skip(1)
+            .collect::<Vec<_>>()
+            .join("/");
+
+        assert_eq!(extracted_name, "feature/test");
+    }
+
+    #[test]
+    fn test_create_worktree_remote_branch_nested_name() {
+        // Test nested remote branch name
+        let remote_branch_name = "origin/user/feature/auth";
+
+        let extracted_name: String = remote_branch_name
+            .split('/')
+            .skip(1)
+            .collect::<Vec<_>>()
+            .join("/");
+
+        assert_eq!(extracted_name, "user/feature/auth");
+    }
+
+    #[test]
+    fn test_create_worktree_uses_custom_name_from_input() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        app.selected_branch = 1; // Existing branch
+        app.input = "custom-worktree-name".to_string();
+
+        // When input is not empty, it should be used as worktree name
+        let worktree_name = if app.input.is_empty() {
+            "default-name".to_string()
+        } else {
+            app.input.clone()
+        };
+
+        assert_eq!(worktree_name, "custom-worktree-name");
+    }
+
+    #[test]
+    fn test_create_worktree_uses_branch_name_when_input_empty() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        app.selected_branch = 1;
+        app.input.clear();
+
+        // When input is empty, branch name should be used
+        let branch_index = app.selected_branch - 1;
+        let branch_name = &app.filtered_branches[branch_index].name;
+
+        let worktree_name = if app.input.is_empty() {
+            branch_name.clone()
+        } else {
+            app.input.clone()
+        };
+
+        assert_eq!(worktree_name, "main");
+    }
+
+    #[test]
+    fn test_ensure_parent_dir_creates_missing_parents() {
         let temp_dir = TempDir::new().unwrap();
-        let repo_path = temp_dir.path().to_path_buf();
+        let app = create_test_app();
+        let worktree_path = temp_dir.path().join("missing").join("parent").join("wt");
+
+        assert!(app.ensure_parent_dir(&worktree_path).is_ok());
+
+        assert!(temp_dir.path().join("missing").join("parent").is_dir());
+        assert!(!worktree_path.exists());
+    }
+
+    #[test]
+    fn test_ensure_parent_dir_respects_auto_mkdir_false() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Config {
+            worktree: crate::config::WorktreeConfig {
+                auto_mkdir: Some(false),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let app = App::new_for_test(config, vec![], vec![]);
+        let worktree_path = temp_dir.path().join("missing").join("wt");
+
+        assert!(app.ensure_parent_dir(&worktree_path).is_ok());
+
+        assert!(!temp_dir.path().join("missing").exists());
+    }
+
+    #[test]
+    fn test_ensure_parent_dir_reports_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let blocker = temp_dir.path().join("file");
+        std::fs::write(&blocker, "not a directory").unwrap();
+        let app = create_test_app();
+
+        let result = app.ensure_parent_dir(&blocker.join("sub").join("wt"));
+
+        let msg = result.unwrap_err();
+        assert!(msg.starts_with("Failed to create directory"));
+    }
 
+    #[test]
+    fn test_create_mode_navigation_includes_create_option() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        app.selected_branch = 0;
+
+        // In create mode, index 0 is "Create new branch"
+        // Moving down should work
+        app.move_down();
+        assert_eq!(app.selected_branch, 1);
+
+        // Can go up back to 0
+        app.move_up();
+        assert_eq!(app.selected_branch, 0);
+    }
+
+    #[test]
+    fn test_create_mode_max_navigation_boundary() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        // +1 for "Create new branch" option
+        let max_index = app.filtered_branches.len();
+
+        app.selected_branch = max_index;
+        app.move_down();
+
+        // Should not exceed max
+        assert_eq!(app.selected_branch, max_index);
+    }
+
+    #[test]
+    fn test_enter_create_mode_resets_selection() {
+        let mut app = create_test_app();
+        app.selected_branch = 5;
+        app.input = "some-filter".to_string();
+
+        let _ = app.enter_create_mode();
+
+        // Should reset to select "Create new branch" (index 0)
+        assert_eq!(app.selected_branch, 0);
+        assert!(app.input.is_empty());
+        assert_eq!(app.mode, AppMode::Create);
+    }
+
+    // ========== Create Base Tests ==========
+
+    fn create_base_test_app(create_base: &str) -> App {
+        let config = Config {
+            worktree: crate::config::WorktreeConfig {
+                create_base: Some(create_base.to_string()),
+                default_base: Some("feature/b".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        App::new_for_test(config, create_test_worktrees(), create_test_branches())
+    }
+
+    #[test]
+    fn test_create_base_first_selects_create_new_branch() {
+        let mut app = create_base_test_app("first");
+
+        app.enter_create_mode().unwrap();
+
+        assert_eq!(app.selected_branch, 0);
+        assert_eq!(app.create_base_ref(), "");
+    }
+
+    #[test]
+    fn test_create_base_main_selects_prune_base() {
+        let mut app = create_base_test_app("main");
+
+        app.enter_create_mode().unwrap();
+
+        // +1 for "Create new branch" at index 0
+        assert_eq!(app.selected_branch, 3);
+        assert_eq!(app.create_base_ref(), "");
+    }
+
+    #[test]
+    fn test_create_base_main_detects_default_branch() {
+        let (_temp_dir, repo_path) = setup_git_repo();
+        let mut app = create_base_test_app("main");
+        app.config.worktree.default_base = None;
+        app.git = GitManager::from_path(&repo_path).unwrap();
+
+        app.enter_create_mode().unwrap();
+
+        assert_eq!(app.selected_branch, 1);
+    }
+
+    #[test]
+    fn test_create_base_current_selects_worktree_branch() {
+        let mut app = create_base_test_app("current");
+        app.selected_worktree = 1;
+
+        app.enter_create_mode().unwrap();
+
+        assert_eq!(app.selected_branch, 2);
+    }
+
+    #[test]
+    fn test_create_base_without_matching_branch_selects_create_new_branch() {
+        let mut app = create_base_test_app("main");
+        app.config.worktree.default_base = Some("develop".to_string());
+
+        app.enter_create_mode().unwrap();
+
+        assert_eq!(app.selected_branch, 0);
+    }
+
+    // ========== Cache Tests ==========
+
+    #[test]
+    fn test_enter_create_mode_reuses_cached_branches() {
+        let mut app = create_test_app();
+
+        app.enter_create_mode().unwrap();
+
+        // Test branches don't exist in the real repository, so they survive
+        // only if git wasn't asked again
+        assert!(app.branches.iter().any(|b| b.name == "origin/feature/c"));
+        assert_eq!(app.filtered_branches.len(), 4);
+    }
+
+    #[test]
+    fn test_enter_create_mode_reloads_branches_after_invalidation() {
+        let mut app = create_test_app();
+        app.invalidate_cache();
+
+        app.enter_create_mode().unwrap();
+
+        assert!(!app.branches.iter().any(|b| b.name == "origin/feature/c"));
+        assert!(!app.branches_stale);
+    }
+
+    #[test]
+    fn test_selected_detail_is_cached_across_redraws() {
+        let worktrees: Vec<Worktree> = (0..200)
+            .map(|i| Worktree {
+                name: format!("wt-{}", i),
+                path: PathBuf::from(format!("/repo/wt-{}", i)),
+                branch: Some(format!("branch-{}", i)),
+                is_main: i == 0,
+                ..Default::default()
+            })
+            .collect();
+        let mut app = App::new_for_test(Config::default(), worktrees, Vec::new());
+
+        let detail = app.get_selected_worktree_detail().unwrap();
+        assert_eq!(detail.branch.as_deref(), Some("branch-0"));
+
+        // Tag the cached entry; later calls only see the tag if git wasn't re-read
+        app.detail_cache.as_mut().unwrap().1.branch = Some("cached".to_string());
+        for _ in 0..100 {
+            let detail = app.get_selected_worktree_detail().unwrap();
+            assert_eq!(detail.branch.as_deref(), Some("cached"));
+        }
+
+        // Changing the selection loads the new worktree
+        app.move_down();
+        let detail = app.get_selected_worktree_detail().unwrap();
+        assert_eq!(detail.branch.as_deref(), Some("branch-1"));
+
+        // Invalidation forces a reload of the same worktree
+        app.detail_cache.as_mut().unwrap().1.branch = Some("cached".to_string());
+        app.invalidate_cache();
+        let detail = app.get_selected_worktree_detail().unwrap();
+        assert_eq!(detail.branch.as_deref(), Some("branch-1"));
+    }
+
+    // ========== Prune Tests ==========
+
+    #[test]
+    fn test_enter_confirm_prune_into_selected_requires_branch() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        app.selected_branch = 0;
+
+        app.enter_confirm_prune_into_selected().unwrap();
+
+        assert_eq!(app.mode, AppMode::Create);
+        assert_eq!(
+            app.message.as_deref(),
+            Some("Select a branch to prune against")
+        );
+    }
+
+    #[test]
+    fn test_enter_confirm_prune_with_no_merged() {
+        let app = create_test_app();
+        // merged_worktrees is empty by default
+
+        // Note: This requires actual git operations, so we test the state
+        assert!(app.merged_worktrees.is_empty());
+    }
+
+    #[test]
+    fn test_confirm_action_delete_single() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Confirm;
+        app.confirm_action = Some(ConfirmAction::DeleteSingle);
+
+        assert_eq!(app.confirm_action, Some(ConfirmAction::DeleteSingle));
+    }
+
+    #[test]
+    fn test_confirm_action_prune() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Confirm;
+        app.confirm_action = Some(ConfirmAction::Prune);
+
+        assert_eq!(app.confirm_action, Some(ConfirmAction::Prune));
+    }
+
+    // ========== Background Delete Tests ==========
+
+    #[test]
+    fn test_confirm_action_transitions_to_deleting_mode() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Confirm;
+        app.confirm_action = Some(ConfirmAction::DeleteSingle);
+        app.selected_worktree = 1; // non-main worktree
+
+        let result = app.confirm_action(false);
+        assert!(result.is_ok());
+        assert_eq!(app.mode, AppMode::Deleting);
+        assert!(app.progress_message.is_some());
+        assert!(app.delete_receiver.is_some());
+        assert_eq!(app.tick, 0);
+    }
+
+    #[test]
+    fn test_confirm_action_main_worktree_stays_normal() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Confirm;
+        app.confirm_action = Some(ConfirmAction::DeleteSingle);
+        app.selected_worktree = 0; // main worktree
+
+        let result = app.confirm_action(false);
+        assert!(result.is_ok());
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.message.as_ref().unwrap().contains("Cannot delete main"));
+    }
+
+    #[test]
+    fn test_confirm_action_prune_transitions_to_deleting() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Confirm;
+        app.confirm_action = Some(ConfirmAction::Prune);
+        app.merged_worktrees = vec![Worktree {
+            name: "merged-wt".to_string(),
+            path: PathBuf::from("/repo/merged-wt"),
+            branch: Some("merged-branch".to_string()),
+            is_main: false,
+            ..Default::default()
+        }];
+
+        let result = app.confirm_action(false);
+        assert!(result.is_ok());
+        assert_eq!(app.mode, AppMode::Deleting);
+        assert!(app.progress_message.as_ref().unwrap().contains("Pruning"));
+    }
+
+    #[test]
+    fn test_confirm_action_delete_marked_transitions_to_deleting() {
+        let mut app = create_test_app();
+        app.marked_worktrees
+            .insert(PathBuf::from("/repo/feature-a"));
+        app.marked_worktrees.insert(PathBuf::from("/repo/bugfix-x"));
+        app.enter_confirm_delete().unwrap();
+        assert_eq!(app.confirm_action, Some(ConfirmAction::DeleteMarked));
+
+        let result = app.confirm_action(false);
+        assert!(result.is_ok());
+        assert_eq!(app.mode, AppMode::Deleting);
+        assert_eq!(
+            app.progress_message,
+            Some("Deleting 2 worktree(s)...".to_string())
+        );
+    }
+
+    #[test]
+    fn test_confirm_action_delete_marked_keeps_locked_and_dirty() {
+        let mut app = create_test_app();
+        for name in ["feature-a", "feature-b", "bugfix-x"] {
+            app.marked_worktrees
+                .insert(PathBuf::from(format!("/repo/{}", name)));
+        }
+        app.worktrees[1].is_locked = true;
+        app.worktrees[2].is_dirty = true;
+        app.enter_confirm_delete().unwrap();
+
+        let (deletable, locked, dirty) = app.marked_targets();
+        let names = |list: &[Worktree]| list.iter().map(|w| w.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&deletable), vec!["bugfix-x"]);
+        assert_eq!(names(&locked), vec!["feature-a"]);
+        assert_eq!(names(&dirty), vec!["feature-b"]);
+
+        app.confirm_action(false).unwrap();
+
+        assert_eq!(
+            app.progress_message,
+            Some("Deleting 1 worktree(s)...".to_string())
+        );
+    }
+
+    #[test]
+    fn test_confirm_action_delete_marked_only_kept_targets_deletes_nothing() {
+        let mut app = create_test_app();
+        app.marked_worktrees
+            .insert(PathBuf::from("/repo/feature-a"));
+        app.worktrees[1].is_dirty = true;
+        app.enter_confirm_delete().unwrap();
+
+        app.confirm_action(false).unwrap();
+
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.message_level, MessageLevel::Error);
+        assert!(app.delete_receiver.is_none());
+    }
+
+    #[test]
+    fn test_confirm_action_delete_marked_waits_for_pending_status() {
+        let mut app = create_test_app();
+        app.marked_worktrees
+            .insert(PathBuf::from("/repo/feature-a"));
+        app.pending_status.insert(PathBuf::from("/repo/feature-a"));
+        app.enter_confirm_delete().unwrap();
+
+        app.confirm_action(false).unwrap();
+
+        assert_eq!(app.mode, AppMode::Confirm);
+        assert_eq!(
+            app.message.as_deref(),
+            Some("Still checking marked worktrees for uncommitted changes")
+        );
+    }
+
+    #[test]
+    fn test_confirm_action_none_enters_normal() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Confirm;
+        app.confirm_action = None;
+
+        let result = app.confirm_action(false);
+        assert!(result.is_ok());
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    // ========== Fetch Tests ==========
+
+    #[test]
+    fn test_fetch_all_without_remotes() {
+        let (_temp_dir, repo_path) = setup_git_repo();
+        let mut app = create_test_app();
+        app.git = GitManager::from_path(&repo_path).unwrap();
+        app.branches_stale = false;
+
+        app.fetch_all();
+        assert_eq!(app.mode, AppMode::Fetching);
+        assert_eq!(
+            app.progress_message,
+            Some("Fetching all remotes...".to_string())
+        );
+
+        wait_until(
+            &mut app,
+            |app| {
+                app.check_fetch_completion().unwrap();
+                app.mode != AppMode::Fetching
+            },
+            JOB_TIMEOUT,
+        );
+
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.progress_message.is_none());
+        assert_eq!(app.message, Some("Fetched all remotes".to_string()));
+        assert!(app.branches_stale);
+        assert_eq!(app.worktrees.len(), 1);
+    }
+
+    #[test]
+    fn test_fetch_all_failure_is_warning() {
+        let (_temp_dir, repo_path) = setup_git_repo();
         Command::new("git")
-            .args(["init", "-b", "main"])
-            .current_dir(&repo_path)
-            .output()
-            .unwrap();
-        Command::new("git")
-            .args(["config", "user.email", "test@test.com"])
-            .current_dir(&repo_path)
-            .output()
-            .unwrap();
-        Command::new("git")
-            .args(["config", "user.name", "Test User"])
-            .current_dir(&repo_path)
-            .output()
-            .unwrap();
-        std::fs::write(repo_path.join("README.md"), "# Test").unwrap();
-        Command::new("git")
-            .args(["add", "."])
-            .current_dir(&repo_path)
-            .output()
-            .unwrap();
-        Command::new("git")
-            .args(["commit", "-m", "Initial commit"])
+            .args(["remote", "add", "origin", "/nonexistent/remote.git"])
             .current_dir(&repo_path)
             .output()
             .unwrap();
+        let mut app = create_test_app();
+        app.git = GitManager::from_path(&repo_path).unwrap();
+
+        app.fetch_all();
+        wait_until(
+            &mut app,
+            |app| {
+                app.check_fetch_completion().unwrap();
+                app.mode != AppMode::Fetching
+            },
+            JOB_TIMEOUT,
+        );
 
-        (temp_dir, repo_path)
+        assert_eq!(app.mode, AppMode::Normal);
+        let message = app.message.unwrap();
+        assert!(message.starts_with("Warning: could not fetch remotes"));
     }
 
-    /// Create a worktree with a branch in the test repo
-    fn create_test_worktree_in_repo(repo_path: &std::path::Path, branch: &str, wt_name: &str) {
-        Command::new("git")
-            .args(["branch", branch])
-            .current_dir(repo_path)
-            .output()
-            .unwrap();
-        let wt_path = repo_path.join(wt_name);
-        Command::new("git")
-            .args(["worktree", "add", wt_path.to_str().unwrap(), branch])
-            .current_dir(repo_path)
-            .output()
-            .unwrap();
+    #[test]
+    fn test_check_fetch_completion_disconnected() {
+        let mut app = create_test_app();
+        let (tx, rx) = mpsc::channel::<Result<(), String>>();
+        app.fetch_receiver = Some(rx);
+        app.mode = AppMode::Fetching;
+        drop(tx);
+
+        // The test app has no real repository, so only the state is checked
+        let _ = app.check_fetch_completion();
+
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.fetch_receiver.is_none());
+        assert!(app.message.as_ref().unwrap().contains("unexpectedly"));
     }
 
-    fn create_test_worktrees() -> Vec<Worktree> {
-        vec![
-            Worktree {
-                name: "main".to_string(),
-                path: PathBuf::from("/repo/main"),
-                branch: Some("main".to_string()),
-                is_main: true,
-            },
-            Worktree {
-                name: "feature-a".to_string(),
-                path: PathBuf::from("/repo/feature-a"),
-                branch: Some("feature/a".to_string()),
-                is_main: false,
-            },
-            Worktree {
-                name: "feature-b".to_string(),
-                path: PathBuf::from("/repo/feature-b"),
-                branch: Some("feature/b".to_string()),
-                is_main: false,
-            },
-            Worktree {
-                name: "bugfix-x".to_string(),
-                path: PathBuf::from("/repo/bugfix-x"),
-                branch: Some("bugfix/x".to_string()),
-                is_main: false,
-            },
-        ]
+    #[test]
+    fn test_check_delete_completion_no_receiver() {
+        let mut app = create_test_app();
+        // No receiver set - should be a no-op
+        let result = app.check_delete_completion();
+        assert!(result.is_ok());
+        assert_eq!(app.mode, AppMode::Normal);
     }
 
-    fn create_test_branches() -> Vec<Branch> {
-        vec![
-            Branch {
-                name: "main".to_string(),
-                is_remote: false,
-                is_head: true,
-            },
-            Branch {
-                name: "feature/a".to_string(),
-                is_remote: false,
-                is_head: false,
-            },
-            Branch {
-                name: "feature/b".to_string(),
-                is_remote: false,
-                is_head: false,
-            },
-            Branch {
-                name: "origin/feature/c".to_string(),
-                is_remote: true,
-                is_head: false,
-            },
-        ]
+    #[test]
+    fn test_check_delete_completion_pending() {
+        let mut app = create_test_app();
+        let (_tx, rx) = mpsc::channel::<DeleteResult>();
+        app.delete_receiver = Some(rx);
+        app.mode = AppMode::Deleting;
+
+        // Nothing sent yet - should remain in Deleting mode
+        let result = app.check_delete_completion();
+        assert!(result.is_ok());
+        assert_eq!(app.mode, AppMode::Deleting);
+        assert!(app.delete_receiver.is_some());
+    }
+
+    #[test]
+    fn test_check_delete_completion_disconnected() {
+        let mut app = create_test_app();
+        let (tx, rx) = mpsc::channel::<DeleteResult>();
+        app.delete_receiver = Some(rx);
+        app.mode = AppMode::Deleting;
+
+        // Drop sender to simulate thread crash
+        drop(tx);
+
+        let result = app.check_delete_completion();
+        assert!(result.is_ok());
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.delete_receiver.is_none());
+        assert!(app.message.as_ref().unwrap().contains("unexpectedly"));
+    }
+
+    #[test]
+    fn test_check_delete_completion_single_success() {
+        let mut app = create_test_app();
+        let (tx, rx) = mpsc::channel();
+        app.delete_receiver = Some(rx);
+        app.mode = AppMode::Deleting;
+
+        tx.send(DeleteResult::SingleCompleted {
+            worktree_name: "test-wt".to_string(),
+            branch_name: None,
+            branch_deleted: false,
+            error_message: None,
+        })
+        .unwrap();
+
+        let result = app.check_delete_completion();
+        assert!(result.is_ok());
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.delete_receiver.is_none());
+        assert!(app.progress_message.is_none());
+        assert!(app.message.as_ref().unwrap().contains("test-wt"));
+    }
+
+    #[test]
+    fn test_check_delete_completion_single_with_branch() {
+        let mut app = create_test_app();
+        let (tx, rx) = mpsc::channel();
+        app.delete_receiver = Some(rx);
+        app.mode = AppMode::Deleting;
+
+        tx.send(DeleteResult::SingleCompleted {
+            worktree_name: "test-wt".to_string(),
+            branch_name: Some("feature/test".to_string()),
+            branch_deleted: true,
+            error_message: None,
+        })
+        .unwrap();
+
+        let result = app.check_delete_completion();
+        assert!(result.is_ok());
+        let msg = app.message.as_ref().unwrap();
+        assert!(msg.contains("test-wt"));
+        assert!(msg.contains("feature/test"));
+    }
+
+    #[test]
+    fn test_check_delete_completion_single_branch_error() {
+        let mut app = create_test_app();
+        let (tx, rx) = mpsc::channel();
+        app.delete_receiver = Some(rx);
+        app.mode = AppMode::Deleting;
+
+        tx.send(DeleteResult::SingleCompleted {
+            worktree_name: "test-wt".to_string(),
+            branch_name: Some("feature/test".to_string()),
+            branch_deleted: false,
+            error_message: Some("failed to delete branch".to_string()),
+        })
+        .unwrap();
+
+        let result = app.check_delete_completion();
+        assert!(result.is_ok());
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .contains("failed to delete branch"));
+    }
+
+    #[test]
+    fn test_check_delete_completion_marked_clears_marks() {
+        let mut app = create_test_app();
+        let (tx, rx) = mpsc::channel();
+        app.delete_receiver = Some(rx);
+        app.mode = AppMode::Deleting;
+        app.marked_worktrees
+            .insert(PathBuf::from("/repo/feature-a"));
+
+        tx.send(DeleteResult::PruneCompleted {
+            worktree_count: 1,
+            branch_count: 0,
+            failed: Vec::new(),
+            hook_errors: Vec::new(),
+        })
+        .unwrap();
+
+        app.check_delete_completion().unwrap();
+
+        assert_eq!(app.message, Some("Deleted 1 worktree(s)".to_string()));
+        assert!(app.marked_worktrees.is_empty());
+    }
+
+    #[test]
+    fn test_check_delete_completion_prune_reports_hook_errors() {
+        let mut app = create_test_app();
+        let (tx, rx) = mpsc::channel();
+        app.delete_receiver = Some(rx);
+        app.mode = AppMode::Deleting;
+
+        tx.send(DeleteResult::PruneCompleted {
+            worktree_count: 2,
+            branch_count: 0,
+            failed: Vec::new(),
+            hook_errors: vec!["post_delete hook failed: 'false' exited with status: 1".into()],
+        })
+        .unwrap();
+
+        app.check_delete_completion().unwrap();
+
+        assert_eq!(
+            app.message.as_deref(),
+            Some(
+                "Pruned 2 merged worktree(s), but post_delete hook failed: \
+                 'false' exited with status: 1"
+            )
+        );
+    }
+
+    #[test]
+    fn test_check_delete_completion_reports_failed_worktrees() {
+        let mut app = create_test_app();
+        let (tx, rx) = mpsc::channel();
+        app.delete_receiver = Some(rx);
+        app.mode = AppMode::Deleting;
+        app.marked_worktrees
+            .insert(PathBuf::from("/repo/feature-a"));
+
+        tx.send(DeleteResult::PruneCompleted {
+            worktree_count: 2,
+            branch_count: 0,
+            failed: vec!["feature-b: worktree is locked".into()],
+            hook_errors: Vec::new(),
+        })
+        .unwrap();
+
+        app.check_delete_completion().unwrap();
+
+        assert_eq!(
+            app.message.as_deref(),
+            Some("Deleted 2 worktree(s); could not delete feature-b: worktree is locked")
+        );
+        assert_eq!(app.message_level, MessageLevel::Error);
     }
 
-    fn create_test_app() -> App {
-        App::new_for_test(
-            Config::default(),
-            create_test_worktrees(),
-            create_test_branches(),
-        )
-    }
+    #[test]
+    fn test_check_delete_completion_prune() {
+        let mut app = create_test_app();
+        let (tx, rx) = mpsc::channel();
+        app.delete_receiver = Some(rx);
+        app.mode = AppMode::Deleting;
+        app.merged_worktrees = vec![Worktree {
+            name: "wt".to_string(),
+            path: PathBuf::from("/repo/wt"),
+            branch: None,
+            is_main: false,
+            ..Default::default()
+        }];
+
+        tx.send(DeleteResult::PruneCompleted {
+            worktree_count: 3,
+            branch_count: 2,
+            failed: Vec::new(),
+            hook_errors: Vec::new(),
+        })
+        .unwrap();
 
-    // ========== Filter Tests ==========
+        let result = app.check_delete_completion();
+        assert!(result.is_ok());
+        assert_eq!(app.mode, AppMode::Normal);
+        let msg = app.message.as_ref().unwrap();
+        assert!(msg.contains("3 worktree(s)"));
+        assert!(msg.contains("2 branch(es)"));
+        assert!(app.merged_worktrees.is_empty());
+    }
 
     #[test]
-    fn test_filter_worktrees_by_name() {
+    fn test_check_delete_completion_error() {
         let mut app = create_test_app();
+        let (tx, rx) = mpsc::channel();
+        app.delete_receiver = Some(rx);
+        app.mode = AppMode::Deleting;
 
-        app.input = "feature".to_string();
-        app.filter_worktrees();
+        tx.send(DeleteResult::Error("something went wrong".to_string()))
+            .unwrap();
 
-        assert_eq!(app.filtered_worktrees.len(), 2);
+        let result = app.check_delete_completion();
+        assert!(result.is_ok());
+        assert_eq!(app.mode, AppMode::Normal);
         assert!(app
-            .filtered_worktrees
-            .iter()
-            .all(|w| w.name.contains("feature")));
+            .message
+            .as_ref()
+            .unwrap()
+            .contains("something went wrong"));
     }
 
     #[test]
-    fn test_filter_worktrees_by_branch() {
+    fn test_confirm_action_delete_branch_true() {
         let mut app = create_test_app();
+        app.mode = AppMode::Confirm;
+        app.confirm_action = Some(ConfirmAction::DeleteSingle);
+        app.selected_worktree = 1; // non-main worktree with branch "feature/a"
 
-        app.input = "bugfix".to_string();
-        app.filter_worktrees();
-
-        assert_eq!(app.filtered_worktrees.len(), 1);
-        assert_eq!(app.filtered_worktrees[0].name, "bugfix-x");
+        let result = app.confirm_action(true);
+        assert!(result.is_ok());
+        assert_eq!(app.mode, AppMode::Deleting);
+        assert!(app.delete_receiver.is_some());
     }
 
     #[test]
-    fn test_filter_worktrees_case_insensitive() {
+    fn test_confirm_action_empty_filtered_worktrees() {
         let mut app = create_test_app();
+        app.mode = AppMode::Confirm;
+        app.confirm_action = Some(ConfirmAction::DeleteSingle);
+        app.filtered_worktrees.clear();
 
-        app.input = "FEATURE".to_string();
-        app.filter_worktrees();
-
-        assert_eq!(app.filtered_worktrees.len(), 2);
+        let result = app.confirm_action(false);
+        assert!(result.is_ok());
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.delete_receiver.is_none());
     }
 
     #[test]
-    fn test_filter_worktrees_empty_input() {
+    fn test_check_delete_completion_clears_input() {
         let mut app = create_test_app();
-        app.input = "something".to_string();
-        app.filter_worktrees();
-        assert!(app.filtered_worktrees.len() < app.worktrees.len());
+        let (tx, rx) = mpsc::channel();
+        app.delete_receiver = Some(rx);
+        app.mode = AppMode::Deleting;
+        app.input = "leftover search".to_string();
+        app.confirm_action = Some(ConfirmAction::DeleteSingle);
 
-        app.input.clear();
-        app.filter_worktrees();
+        tx.send(DeleteResult::SingleCompleted {
+            worktree_name: "test-wt".to_string(),
+            branch_name: None,
+            branch_deleted: false,
+            error_message: None,
+        })
+        .unwrap();
 
-        assert_eq!(app.filtered_worktrees.len(), app.worktrees.len());
+        let result = app.check_delete_completion();
+        assert!(result.is_ok());
+        assert!(app.input.is_empty(), "input should be cleared after delete");
+        assert!(
+            app.confirm_action.is_none(),
+            "confirm_action should be cleared after delete"
+        );
     }
 
     #[test]
-    fn test_filter_branches() {
+    fn test_check_delete_completion_prune_without_branches() {
         let mut app = create_test_app();
+        let (tx, rx) = mpsc::channel();
+        app.delete_receiver = Some(rx);
+        app.mode = AppMode::Deleting;
+        app.merged_worktrees = vec![Worktree {
+            name: "wt".to_string(),
+            path: PathBuf::from("/repo/wt"),
+            branch: None,
+            is_main: false,
+            ..Default::default()
+        }];
 
-        app.input = "feature".to_string();
-        app.filter_branches();
+        tx.send(DeleteResult::PruneCompleted {
+            worktree_count: 2,
+            branch_count: 0,
+            failed: Vec::new(),
+            hook_errors: Vec::new(),
+        })
+        .unwrap();
 
-        assert_eq!(app.filtered_branches.len(), 3);
+        let result = app.check_delete_completion();
+        assert!(result.is_ok());
+        let msg = app.message.as_ref().unwrap();
+        assert!(msg.contains("2 merged worktree(s)"));
+        assert!(
+            !msg.contains("branch"),
+            "should not mention branches when count is 0"
+        );
     }
 
-    // ========== Navigation Tests ==========
+    // ========== execute_create Tests ==========
+
+    fn create_job(kind: CreateKind, name: &str, branch: &str) -> CreateJob {
+        CreateJob {
+            kind,
+            worktree_name: name.to_string(),
+            branch_name: branch.to_string(),
+            base_path: ".".to_string(),
+            base_ref: None,
+            warning: None,
+        }
+    }
+
+    /// Commit hash of HEAD~1 in the test repository, after adding a newer commit
+    fn add_commit_and_get_previous(repo_path: &Path) -> String {
+        let head = Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["commit", "--allow-empty", "-m", "Newer commit"])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+        String::from_utf8(head.stdout).unwrap().trim().to_string()
+    }
 
     #[test]
-    fn test_move_up_boundary() {
+    fn test_execute_create_appends_warning() {
+        let (_temp_dir, repo_path) = setup_git_repo();
+        let mut job = create_job(CreateKind::NewBranch, "wt", "feature");
+        job.warning = Some("'main' is 1 commit(s) behind 'origin/main'".to_string());
+
+        let result = execute_create(
+            &repo_path,
+            &job,
+            &SetupRunner::new(None),
+            &HookRunner::new(Vec::new()),
+        );
+
+        assert!(result.created, "{}", result.message);
+        assert_eq!(
+            result.message,
+            "Created branch 'feature' and worktree 'wt'. Warning: 'main' is 1 commit(s) behind 'origin/main'"
+        );
+    }
+
+    // ========== Stale Base Tests ==========
+
+    /// Repository whose `stale` branch tracks `main` and is one commit behind it
+    fn create_stale_base_app(warn: bool) -> (TempDir, App) {
+        let (temp_dir, repo_path) = setup_git_repo();
+        for args in [
+            vec!["branch", "stale"],
+            vec!["branch", "--set-upstream-to=main", "stale"],
+            vec!["commit", "--allow-empty", "-m", "Newer commit"],
+        ] {
+            Command::new("git")
+                .args(&args)
+                .current_dir(&repo_path)
+                .output()
+                .unwrap();
+        }
         let mut app = create_test_app();
-        app.selected_worktree = 0;
+        app.config.worktree.warn_stale_base = Some(warn);
+        app.git = GitManager::from_path(&repo_path).unwrap();
+        app.refresh_branches().unwrap();
+        (temp_dir, app)
+    }
 
-        app.move_up();
+    #[test]
+    fn test_stale_base_warning_for_new_branch_from_stale_base() {
+        let (_temp_dir, app) = create_stale_base_app(true);
 
-        // Should not go below 0
-        assert_eq!(app.selected_worktree, 0);
+        let warning = app.stale_base_warning(CreateKind::NewBranch, "topic", Some("stale"));
+
+        assert_eq!(
+            warning.as_deref(),
+            Some("'stale' is 1 commit(s) behind 'main'; consider updating it first")
+        );
     }
 
     #[test]
-    fn test_move_down_boundary() {
-        let mut app = create_test_app();
-        app.selected_worktree = app.filtered_worktrees.len() - 1;
+    fn test_stale_base_warning_for_existing_local_branch() {
+        let (_temp_dir, app) = create_stale_base_app(true);
 
-        app.move_down();
+        let warning = app.stale_base_warning(
+            CreateKind::ExistingBranch {
+                reuses_local: false,
+            },
+            "stale",
+            None,
+        );
 
-        // Should not exceed max index
-        assert_eq!(app.selected_worktree, app.filtered_worktrees.len() - 1);
+        assert!(warning.is_some());
     }
 
     #[test]
-    fn test_move_up_decrements() {
-        let mut app = create_test_app();
-        app.selected_worktree = 2;
+    fn test_stale_base_warning_up_to_date_or_disabled() {
+        let (_temp_dir, app) = create_stale_base_app(true);
+        // HEAD (main) has no upstream
+        assert_eq!(
+            app.stale_base_warning(CreateKind::NewBranch, "topic", None),
+            None
+        );
 
-        app.move_up();
+        let (_temp_dir, app) = create_stale_base_app(false);
+        assert_eq!(
+            app.stale_base_warning(CreateKind::NewBranch, "topic", Some("stale")),
+            None
+        );
+    }
 
-        assert_eq!(app.selected_worktree, 1);
+    #[test]
+    fn test_execute_create_detached_at_old_commit() {
+        let (_temp_dir, repo_path) = setup_git_repo();
+        let old = add_commit_and_get_previous(&repo_path);
+        let mut job = create_job(CreateKind::Detached, "pinned", "");
+        job.base_ref = Some(old.clone());
+
+        let result = execute_create(
+            &repo_path,
+            &job,
+            &SetupRunner::new(None),
+            &HookRunner::new(Vec::new()),
+        );
+
+        assert!(result.created, "{}", result.message);
+        assert_eq!(
+            result.message,
+            format!("Created worktree 'pinned' at '{}' (detached HEAD)", old)
+        );
+        let repo = git2::Repository::open(repo_path.join("pinned")).unwrap();
+        assert!(repo.head_detached().unwrap());
+        assert_eq!(repo.head().unwrap().target().unwrap().to_string(), old);
     }
 
     #[test]
-    fn test_move_down_increments() {
-        let mut app = create_test_app();
-        app.selected_worktree = 1;
+    fn test_execute_create_new_branch_from_ref() {
+        let (_temp_dir, repo_path) = setup_git_repo();
+        let old = add_commit_and_get_previous(&repo_path);
+        let mut job = create_job(CreateKind::NewBranch, "wt-old", "from-old");
+        job.base_ref = Some("HEAD~1".to_string());
 
-        app.move_down();
+        let result = execute_create(
+            &repo_path,
+            &job,
+            &SetupRunner::new(None),
+            &HookRunner::new(Vec::new()),
+        );
 
-        assert_eq!(app.selected_worktree, 2);
+        assert_eq!(
+            result.message,
+            "Created branch 'from-old' from 'HEAD~1' and worktree 'wt-old'"
+        );
+        let repo = git2::Repository::open(repo_path.join("wt-old")).unwrap();
+        assert_eq!(repo.head().unwrap().shorthand(), Some("from-old"));
+        assert_eq!(repo.head().unwrap().target().unwrap().to_string(), old);
     }
 
-    // ========== Mode Transition Tests ==========
+    #[test]
+    fn test_execute_create_invalid_ref_fails() {
+        let (_temp_dir, repo_path) = setup_git_repo();
+        let mut job = create_job(CreateKind::Detached, "bad", "");
+        job.base_ref = Some("no-such-tag".to_string());
+
+        let result = execute_create(
+            &repo_path,
+            &job,
+            &SetupRunner::new(None),
+            &HookRunner::new(Vec::new()),
+        );
+
+        assert!(!result.created);
+        assert!(result.message.contains("Cannot resolve 'no-such-tag'"));
+    }
 
     #[test]
-    fn test_enter_normal_mode_clears_input() {
-        let mut app = create_test_app();
-        app.mode = AppMode::Create;
-        app.input = "some-input".to_string();
+    fn test_execute_create_new_branch() {
+        let (_temp_dir, repo_path) = setup_git_repo();
+        let job = create_job(CreateKind::NewBranch, "wt-new", "feature-new");
 
-        app.enter_normal_mode();
+        let result = execute_create(
+            &repo_path,
+            &job,
+            &SetupRunner::new(None),
+            &HookRunner::new(Vec::new()),
+        );
 
-        assert_eq!(app.mode, AppMode::Normal);
-        assert!(app.input.is_empty());
-        assert!(app.confirm_action.is_none());
+        assert!(result.created);
+        assert_eq!(
+            result.message,
+            "Created branch 'feature-new' and worktree 'wt-new'"
+        );
+        assert!(repo_path.join("wt-new").exists());
     }
 
     #[test]
-    fn test_enter_config_mode() {
-        let mut app = create_test_app();
+    fn test_execute_create_existing_branch_name_reports_error() {
+        let (_temp_dir, repo_path) = setup_git_repo();
+        create_test_worktree_in_repo(&repo_path, "feature-dup", "wt-dup");
+        let job = create_job(CreateKind::NewBranch, "wt-other", "feature-dup");
 
-        app.enter_config_mode();
+        let result = execute_create(
+            &repo_path,
+            &job,
+            &SetupRunner::new(None),
+            &HookRunner::new(Vec::new()),
+        );
 
-        assert_eq!(app.mode, AppMode::Config);
-        assert_eq!(app.config_scroll, 0);
+        assert!(!result.created);
+        assert_eq!(result.message, "Branch 'feature-dup' already exists");
     }
 
     #[test]
-    fn test_enter_config_mode_resets_scroll() {
+    fn test_create_worktree_applies_branch_template() {
+        let (temp_dir, repo_path) = setup_git_repo();
+        let config = Config {
+            worktree: crate::config::WorktreeConfig {
+                basedir: Some(temp_dir.path().join("wts").to_string_lossy().to_string()),
+                ..Default::default()
+            },
+            naming: crate::config::NamingConfig {
+                branch_template: Some("{user}/{name}".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut app = App::new_for_test(config, vec![], vec![]);
+        app.git = GitManager::from_path(&repo_path).unwrap();
+        app.enter_create_mode().unwrap();
+        app.input = "login".to_string();
+
+        app.create_worktree().unwrap();
+        wait_until(
+            &mut app,
+            |app| {
+                app.check_create_completion().unwrap();
+                app.mode != AppMode::Creating
+            },
+            JOB_TIMEOUT,
+        );
+
+        assert_eq!(app.mode, AppMode::Normal, "{:?}", app.message);
+        assert!(app
+            .worktrees
+            .iter()
+            .any(|w| w.branch.as_deref() == Some("Test-User/login")));
+    }
+
+    #[test]
+    fn test_check_create_completion_success_returns_to_normal() {
         let mut app = create_test_app();
-        app.config_scroll = 5;
+        app.mode = AppMode::Creating;
+        app.progress_message = Some("Creating worktree 'x'...".to_string());
+        let (tx, rx) = mpsc::channel();
+        app.create_receiver = Some(rx);
 
-        app.enter_config_mode();
+        tx.send(CreateResult {
+            level: MessageLevel::Success,
+            message: "Created worktree: x".to_string(),
+            created: true,
+            path: None,
+        })
+        .unwrap();
+        app.check_create_completion().unwrap();
 
-        assert_eq!(app.config_scroll, 0);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.message.as_deref(), Some("Created worktree: x"));
+        assert!(app.progress_message.is_none());
+        assert!(app.create_receiver.is_none());
     }
 
     #[test]
-    fn test_scroll_config_up_at_zero() {
-        let mut app = create_test_app();
-        app.config_scroll = 0;
+    fn test_check_create_completion_opens_created_worktree() {
+        let (_temp_dir, repo_path, mut app) = create_move_test_app();
+        app.selected_worktree = 0;
+        app.open_after_create = true;
+        app.mode = AppMode::Creating;
+        let (tx, rx) = mpsc::channel();
+        app.create_receiver = Some(rx);
 
-        app.scroll_config_up();
+        tx.send(CreateResult {
+            level: MessageLevel::Success,
+            message: "Created worktree: wt".to_string(),
+            created: true,
+            path: Some(repo_path.join("wt")),
+        })
+        .unwrap();
+        app.check_create_completion().unwrap();
 
-        assert_eq!(app.config_scroll, 0);
+        assert!(app.should_quit);
+        assert_eq!(
+            app.selected_worktree_path,
+            Some(repo_path.join("wt").to_string_lossy().to_string())
+        );
+        assert!(!app.open_after_create);
     }
 
     #[test]
-    fn test_scroll_config_up_decrements() {
-        let mut app = create_test_app();
-        app.config_scroll = 3;
+    fn test_check_create_completion_opens_when_configured() {
+        let (_temp_dir, repo_path, mut app) = create_move_test_app();
+        app.config.worktree.open_after_create = Some(true);
+        app.config.worktree.on_open = Some("true".to_string());
+        app.selected_worktree = 0;
+        app.mode = AppMode::Creating;
+        let (tx, rx) = mpsc::channel();
+        app.create_receiver = Some(rx);
 
-        app.scroll_config_up();
+        tx.send(CreateResult {
+            level: MessageLevel::Success,
+            message: "Created worktree: wt".to_string(),
+            created: true,
+            path: Some(repo_path.join("wt")),
+        })
+        .unwrap();
+        app.check_create_completion().unwrap();
 
-        assert_eq!(app.config_scroll, 2);
+        assert_eq!(app.filtered_worktrees[app.selected_worktree].name, "wt");
+        let command = app.suspend_command.expect("on_open runs");
+        assert_eq!(command.kind, SuspendKind::Open);
+        assert_eq!(command.cwd, repo_path.join("wt"));
     }
 
     #[test]
-    fn test_scroll_config_down_increments() {
+    fn test_create_worktree_selects_new_worktree() {
+        let (temp_dir, repo_path) = setup_git_repo();
         let mut app = create_test_app();
-        app.config_scroll = 0;
-        app.config_scroll_max = 10;
-
-        app.scroll_config_down();
+        app.config.worktree.basedir =
+            Some(temp_dir.path().join("wts").to_string_lossy().to_string());
+        app.git = GitManager::from_path(&repo_path).unwrap();
+        app.refresh_worktrees().unwrap();
+        app.enter_create_mode().unwrap();
+        app.input = "fresh".to_string();
+
+        app.create_worktree().unwrap();
+        wait_until(
+            &mut app,
+            |app| {
+                app.check_create_completion().unwrap();
+                app.mode != AppMode::Creating
+            },
+            JOB_TIMEOUT,
+        );
 
-        assert_eq!(app.config_scroll, 1);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(
+            app.filtered_worktrees[app.selected_worktree]
+                .branch
+                .as_deref(),
+            Some("fresh")
+        );
+        assert!(!app.should_quit);
     }
 
     #[test]
-    fn test_scroll_config_down_clamped_at_max() {
-        let mut app = create_test_app();
-        app.config_scroll = 5;
-        app.config_scroll_max = 5;
-
-        app.scroll_config_down();
+    fn test_create_worktree_namespace_by_repo_separates_repositories() {
+        let shared = TempDir::new().unwrap();
+        let mut created = Vec::new();
+        let mut repos = Vec::new();
+        for _ in 0..2 {
+            let (temp_dir, repo_path) = setup_git_repo();
+            let mut app = create_test_app();
+            app.config.worktree.basedir = Some(shared.path().to_string_lossy().to_string());
+            app.config.worktree.namespace_by_repo = Some(true);
+            app.git = GitManager::from_path(&repo_path).unwrap();
+            app.refresh_worktrees().unwrap();
+            app.enter_create_mode().unwrap();
+            app.input = "fresh".to_string();
+
+            app.create_worktree().unwrap();
+            wait_until(
+                &mut app,
+                |app| {
+                    app.check_create_completion().unwrap();
+                    app.mode != AppMode::Creating
+                },
+                JOB_TIMEOUT,
+            );
+
+            let path = app.filtered_worktrees[app.selected_worktree].path.clone();
+            assert_eq!(
+                path,
+                shared
+                    .path()
+                    .canonicalize()
+                    .unwrap()
+                    .join(app.repo_name())
+                    .join("fresh")
+            );
+            created.push(path);
+            repos.push(temp_dir);
+        }
 
-        assert_eq!(app.config_scroll, 5);
+        assert_ne!(created[0], created[1]);
+        assert!(created.iter().all(|p| p.exists()));
     }
 
     #[test]
-    fn test_enter_confirm_delete_sets_mode() {
+    fn test_cancel_setup_stops_running_setup_command() {
+        let (temp_dir, repo_path) = setup_git_repo();
         let mut app = create_test_app();
-        app.selected_worktree = 1; // Not main worktree
-
-        app.enter_confirm_delete();
+        app.config.worktree.basedir =
+            Some(temp_dir.path().join("wts").to_string_lossy().to_string());
+        app.config.setup_commands = Some(vec!["sleep 5".into(), "touch never".into()]);
+        app.git = GitManager::from_path(&repo_path).unwrap();
+        app.refresh_worktrees().unwrap();
+        app.enter_create_mode().unwrap();
+        app.input = "slow".to_string();
+
+        app.create_worktree().unwrap();
+        assert!(app.can_cancel_setup());
+        app.cancel_setup();
+        assert!(!app.can_cancel_setup());
+        assert_eq!(app.progress_message.as_deref(), Some("Cancelling setup..."));
+
+        let started = Instant::now();
+        wait_until(
+            &mut app,
+            |app| {
+                app.check_create_completion().unwrap();
+                app.mode != AppMode::Creating
+            },
+            JOB_TIMEOUT,
+        );
 
-        assert_eq!(app.mode, AppMode::Confirm);
-        assert_eq!(app.confirm_action, Some(ConfirmAction::DeleteSingle));
+        assert!(started.elapsed() < Duration::from_secs(4));
+        let message = app.message.clone().unwrap();
+        assert!(message.ends_with("but setup was cancelled"), "{}", message);
+        let worktree = &app.filtered_worktrees[app.selected_worktree];
+        assert_eq!(worktree.branch.as_deref(), Some("slow"));
+        assert!(!worktree.path.join("never").exists());
+        assert!(app.setup_cancel.is_none());
     }
 
     #[test]
-    fn test_enter_confirm_delete_prevents_main_deletion() {
+    fn test_cancel_setup_outside_create_is_ignored() {
         let mut app = create_test_app();
-        app.selected_worktree = 0; // Main worktree
 
-        app.enter_confirm_delete();
+        app.cancel_setup();
 
-        // Should not enter confirm mode
-        assert_eq!(app.mode, AppMode::Normal);
-        assert!(app.message.is_some());
-        assert!(app.message.as_ref().unwrap().contains("Cannot delete main"));
+        assert!(!app.can_cancel_setup());
+        assert!(app.progress_message.is_none());
     }
 
-    // ========== Input Tests ==========
+    // ========== Checkout Branch Tests ==========
 
     #[test]
-    fn test_input_char() {
+    fn test_enter_checkout_mode_selects_first_branch() {
         let mut app = create_test_app();
 
-        app.input_char('a');
-        app.input_char('b');
-        app.input_char('c');
+        app.enter_checkout_mode().unwrap();
 
-        assert_eq!(app.input, "abc");
+        assert_eq!(app.mode, AppMode::Create);
+        assert_eq!(app.selected_branch, 1);
+        assert!(app.open_after_create);
+
+        app.enter_create_mode().unwrap();
+        assert_eq!(app.selected_branch, 0);
+        assert!(!app.open_after_create);
     }
 
     #[test]
-    fn test_delete_char() {
+    fn test_quick_create_bases_new_branch_on_selected_worktree() {
+        // The configured default (HEAD) is replaced by the selected branch
         let mut app = create_test_app();
-        app.input = "test".to_string();
+        app.selected_worktree = 1;
 
-        app.delete_char();
+        app.enter_quick_create_mode().unwrap();
 
-        assert_eq!(app.input, "tes");
+        assert_eq!(app.mode, AppMode::Create);
+        assert!(app.quick_create);
+        assert_eq!(app.selected_branch, 0);
+        assert_eq!(app.create_base_ref(), "feature/a");
+
+        // A typed start point still wins
+        app.toggle_create_field();
+        app.toggle_create_field();
+        app.input = "v1.0".to_string();
+        assert_eq!(app.create_base_ref(), "v1.0");
     }
 
     #[test]
-    fn test_delete_char_empty() {
-        let mut app = create_test_app();
-        app.input = String::new();
+    fn test_quick_create_detached_worktree_bases_on_commit() {
+        let mut worktrees = create_test_worktrees();
+        worktrees[1].branch = None;
+        worktrees[1].head_id = Some("4e1c0d2".to_string());
+        let mut app = App::new_for_test(Config::default(), worktrees, create_test_branches());
+        app.selected_worktree = 1;
 
-        app.delete_char();
+        app.enter_quick_create_mode().unwrap();
 
-        assert!(app.input.is_empty());
+        assert_eq!(app.create_base_ref(), "4e1c0d2");
     }
 
     #[test]
-    fn test_input_char_triggers_filter_in_normal_mode() {
+    fn test_quick_create_keeps_branch_selection_pinned() {
         let mut app = create_test_app();
-        assert_eq!(app.filtered_worktrees.len(), 4);
+        app.enter_quick_create_mode().unwrap();
 
-        app.input_char('f');
-        app.input_char('e');
-        app.input_char('a');
+        app.move_down();
+        app.move_last();
 
-        // Should have filtered to just worktrees containing "fea"
-        assert_eq!(app.filtered_worktrees.len(), 2);
-    }
+        assert_eq!(app.selected_branch, 0);
 
-    // ========== Selection Tests ==========
+        app.enter_create_mode().unwrap();
+        assert!(!app.quick_create);
+    }
 
     #[test]
-    fn test_select_worktree_sets_path() {
+    fn test_checkout_branch_with_worktree_opens_it() {
         let mut app = create_test_app();
-        app.selected_worktree = 1;
+        app.enter_checkout_mode().unwrap();
+        app.selected_branch = 1 + app
+            .filtered_branches
+            .iter()
+            .position(|b| b.name == "feature/a")
+            .unwrap();
 
-        app.select_worktree();
+        app.create_worktree().unwrap();
 
+        assert_eq!(app.mode, AppMode::Normal);
         assert!(app.should_quit);
         assert_eq!(
-            app.selected_worktree_path,
-            Some("/repo/feature-a".to_string())
+            app.selected_worktree_path.as_deref(),
+            Some("/repo/feature-a")
         );
     }
 
     #[test]
-    fn test_select_worktree_empty_list() {
-        let mut app = App::new_for_test(Config::default(), vec![], vec![]);
+    fn test_create_worktree_for_checked_out_branch_selects_it() {
+        let mut app = create_test_app();
+        app.enter_create_mode().unwrap();
+        app.selected_branch = 1 + app
+            .filtered_branches
+            .iter()
+            .position(|b| b.name == "feature/a")
+            .unwrap();
 
-        app.select_worktree();
+        app.create_worktree().unwrap();
 
+        assert_eq!(app.mode, AppMode::Normal);
         assert!(!app.should_quit);
-        assert!(app.selected_worktree_path.is_none());
+        assert!(app.create_receiver.is_none());
+        assert_eq!(
+            app.filtered_worktrees[app.selected_worktree].path,
+            PathBuf::from("/repo/feature-a")
+        );
+        assert_eq!(
+            app.message.as_deref(),
+            Some("Branch 'feature/a' already checked out at /repo/feature-a; selected it")
+        );
     }
 
     #[test]
-    fn test_clear_message() {
+    fn test_create_worktree_new_branch_named_like_checked_out_one_selects_it() {
         let mut app = create_test_app();
-        app.message = Some("Test message".to_string());
+        app.enter_create_mode().unwrap();
+        app.input = "feature/a".to_string();
 
-        app.clear_message();
+        app.create_worktree().unwrap();
 
-        assert!(app.message.is_none());
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(
+            app.filtered_worktrees[app.selected_worktree].path,
+            PathBuf::from("/repo/feature-a")
+        );
     }
 
-    // ========== Filter Adjusts Selection Tests ==========
-
     #[test]
-    fn test_filter_adjusts_selection_when_out_of_bounds() {
+    fn test_check_create_completion_failure_stays_in_create() {
         let mut app = create_test_app();
-        app.selected_worktree = 3; // Last item
-
-        app.input = "feature-a".to_string();
-        app.filter_worktrees();
-
-        // After filtering, only 1 item remains, selection should be adjusted
-        assert!(app.selected_worktree < app.filtered_worktrees.len());
-    }
+        app.mode = AppMode::Creating;
+        app.input = "wt".to_string();
+        let (tx, rx) = mpsc::channel();
+        app.create_receiver = Some(rx);
 
-    // ========== Config Integration Tests ==========
+        tx.send(CreateResult {
+            level: MessageLevel::Error,
+            message: "Branch 'x' already exists".to_string(),
+            created: false,
+            path: None,
+        })
+        .unwrap();
+        app.check_create_completion().unwrap();
 
-    #[test]
-    fn test_icons_enabled_default() {
-        let app = create_test_app();
-        // Default should be true
-        assert!(app.icons_enabled());
+        assert_eq!(app.mode, AppMode::Create);
+        assert_eq!(app.input, "wt");
+        assert_eq!(app.message.as_deref(), Some("Branch 'x' already exists"));
     }
 
     #[test]
-    fn test_icons_enabled_disabled() {
-        use crate::config::UiConfig;
+    fn test_check_create_completion_pending_keeps_creating() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Creating;
+        let (_tx, rx) = mpsc::channel();
+        app.create_receiver = Some(rx);
 
-        let config = Config {
-            ui: UiConfig {
-                icons: Some(false),
-                ..Default::default()
-            },
-            ..Default::default()
-        };
-        let app = App::new_for_test(config, create_test_worktrees(), create_test_branches());
+        app.check_create_completion().unwrap();
 
-        assert!(!app.icons_enabled());
+        assert_eq!(app.mode, AppMode::Creating);
+        assert!(app.create_receiver.is_some());
     }
 
+    // ========== execute_delete_single Tests ==========
+
     #[test]
-    fn test_format_path_with_tilde_home() {
-        let app = create_test_app();
-        let home = dirs::home_dir().unwrap();
-        let full_path = format!("{}/projects/test", home.to_string_lossy());
+    fn test_execute_delete_single_success() {
+        let (_temp_dir, repo_path) = setup_git_repo();
+        create_test_worktree_in_repo(&repo_path, "feature-del", "wt-del");
 
-        let formatted = app.format_path(&full_path);
+        let result = execute_delete_single(&repo_path, "wt-del", None, false, false);
 
-        // Default tilde_home is true, so should be compressed
-        assert_eq!(formatted, "~/projects/test");
+        match result {
+            DeleteResult::SingleCompleted {
+                worktree_name,
+                branch_deleted,
+                error_message,
+                ..
+            } => {
+                assert_eq!(worktree_name, "wt-del");
+                assert!(!branch_deleted);
+                assert!(error_message.is_none());
+                // Verify worktree directory is removed
+                assert!(!repo_path.join("wt-del").exists());
+            }
+            other => panic!("Expected SingleCompleted, got {:?}", other),
+        }
     }
 
     #[test]
-    fn test_format_path_without_tilde_home() {
-        use crate::config::UiConfig;
-
-        let config = Config {
-            ui: UiConfig {
-                tilde_home: Some(false),
-                ..Default::default()
-            },
-            ..Default::default()
-        };
-        let app = App::new_for_test(config, create_test_worktrees(), create_test_branches());
-
-        let home = dirs::home_dir().unwrap();
-        let full_path = format!("{}/projects/test", home.to_string_lossy());
+    fn test_execute_delete_single_with_branch_deletion() {
+        let (_temp_dir, repo_path) = setup_git_repo();
+        create_test_worktree_in_repo(&repo_path, "feature-br", "wt-br");
 
-        let formatted = app.format_path(&full_path);
+        let result = execute_delete_single(
+            &repo_path,
+            "wt-br",
+            Some("feature-br".to_string()),
+            true,
+            false,
+        );
 
-        // tilde_home is false, so should NOT be compressed
-        assert_eq!(formatted, full_path);
+        match result {
+            DeleteResult::SingleCompleted {
+                worktree_name,
+                branch_deleted,
+                error_message,
+                ..
+            } => {
+                assert_eq!(worktree_name, "wt-br");
+                assert!(branch_deleted);
+                assert!(error_message.is_none());
+            }
+            other => panic!("Expected SingleCompleted, got {:?}", other),
+        }
     }
 
-    // ========== Main Worktree Path Tests ==========
-
     #[test]
-    fn test_get_main_worktree_path_found() {
-        let app = create_test_app();
+    fn test_execute_delete_single_locked_requires_force() {
+        let (_tmp, repo_path) = setup_git_repo();
+        create_test_worktree_in_repo(&repo_path, "feature-lock", "wt-lock");
+        Command::new("git")
+            .args(["worktree", "lock", "wt-lock"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
 
-        let main_path = app.get_main_worktree_path();
+        let result = execute_delete_single(&repo_path, "wt-lock", None, false, false);
+        match result {
+            DeleteResult::Error(msg) => assert!(msg.contains("is locked")),
+            _ => panic!("Expected Error for locked worktree"),
+        }
+        assert!(repo_path.join("wt-lock").exists());
 
-        assert!(main_path.is_some());
-        assert_eq!(main_path.unwrap(), PathBuf::from("/repo/main"));
+        let result = execute_delete_single(&repo_path, "wt-lock", None, false, true);
+        assert!(matches!(result, DeleteResult::SingleCompleted { .. }));
+        assert!(!repo_path.join("wt-lock").exists());
     }
 
     #[test]
-    fn test_get_main_worktree_path_not_found() {
-        let worktrees = vec![
-            Worktree {
-                name: "feature-a".to_string(),
-                path: PathBuf::from("/repo/feature-a"),
-                branch: Some("feature/a".to_string()),
-                is_main: false,
-            },
-            Worktree {
-                name: "feature-b".to_string(),
-                path: PathBuf::from("/repo/feature-b"),
-                branch: Some("feature/b".to_string()),
-                is_main: false,
-            },
-        ];
-        let app = App::new_for_test(Config::default(), worktrees, vec![]);
+    fn test_confirm_stash_delete_stashes_then_deletes() {
+        let (_temp_dir, repo_path, mut app) = create_move_test_app();
+        std::fs::write(repo_path.join("wt").join("notes.txt"), "wip").unwrap();
+        app.filtered_worktrees[app.selected_worktree].is_dirty = true;
+        app.mode = AppMode::Confirm;
+        app.confirm_action = Some(ConfirmAction::DeleteSingle);
 
-        let main_path = app.get_main_worktree_path();
+        app.confirm_stash_delete().unwrap();
+        assert_eq!(app.mode, AppMode::Deleting);
+        wait_until(
+            &mut app,
+            |app| {
+                app.check_delete_completion().unwrap();
+                app.mode != AppMode::Deleting
+            },
+            JOB_TIMEOUT,
+        );
 
-        assert!(main_path.is_none());
+        assert!(!repo_path.join("wt").exists());
+        let stash = git2::Repository::open(&repo_path)
+            .unwrap()
+            .refname_to_id("refs/stash")
+            .unwrap()
+            .to_string();
+        let message = app.message.as_deref().unwrap();
+        assert!(message.starts_with("Deleted worktree: wt"));
+        assert!(message.contains(&format!("stash@{{0}} ({})", &stash[..7])));
     }
 
     #[test]
-    fn test_get_main_worktree_path_empty_list() {
-        let app = App::new_for_test(Config::default(), vec![], vec![]);
+    fn test_confirm_stash_delete_ignores_clean_worktree() {
+        let (_temp_dir, repo_path, mut app) = create_move_test_app();
+        app.mode = AppMode::Confirm;
+        app.confirm_action = Some(ConfirmAction::DeleteSingle);
 
-        let main_path = app.get_main_worktree_path();
+        app.confirm_stash_delete().unwrap();
 
-        assert!(main_path.is_none());
+        assert_eq!(app.mode, AppMode::Confirm);
+        assert!(repo_path.join("wt").exists());
     }
 
-    // ========== Create Worktree Logic Tests ==========
-
     #[test]
-    fn test_create_worktree_new_branch_empty_input_shows_message() {
+    fn test_confirm_force_delete_ignored_for_prune() {
         let mut app = create_test_app();
-        app.mode = AppMode::Create;
-        app.selected_branch = 0; // "Create new branch" option
-        app.input.clear();
+        app.mode = AppMode::Confirm;
+        app.confirm_action = Some(ConfirmAction::Prune);
 
-        // This would trigger "Please enter a branch name" message
-        // Since create_worktree requires actual git repo, we test the logic condition
-        let should_show_message = app.selected_branch == 0 && app.input.is_empty();
+        app.confirm_force_delete().unwrap();
 
-        assert!(should_show_message);
+        assert_eq!(app.mode, AppMode::Confirm);
     }
 
     #[test]
-    fn test_create_worktree_existing_branch_selected() {
+    fn test_toggle_lock_selected_main_worktree() {
         let mut app = create_test_app();
-        app.mode = AppMode::Create;
-        app.selected_branch = 1; // First actual branch (index 1 = filtered_branches[0])
+        app.selected_worktree = 0;
 
-        // Verify branch index mapping
-        let branch_index = app.selected_branch - 1;
-        assert_eq!(branch_index, 0);
-        assert!(branch_index < app.filtered_branches.len());
+        app.toggle_lock_selected().unwrap();
+
+        assert_eq!(app.message, Some("Cannot lock main worktree".to_string()));
     }
 
     #[test]
-    fn test_create_worktree_remote_branch_name_extraction() {
-        // Test the remote branch name extraction logic
-        let remote_branch_name = "origin/feature/test";
+    fn test_lock_with_reason_round_trips() {
+        let (_temp_dir, _repo_path, mut app) = create_move_test_app();
 
-        let extracted_name: String = remote_branch_name
-            .split('/')
-            .skip(1)
-            .collect::<Vec<_>>()
-            .join("/");
+        app.toggle_lock_selected().unwrap();
+        assert_eq!(app.mode, AppMode::LockReason);
+        app.input = "  on a USB drive ".to_string();
+        app.lock_selected().unwrap();
 
-        assert_eq!(extracted_name, "feature/test");
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(
+            app.message.as_deref(),
+            Some("Locked worktree: wt (on a USB drive)")
+        );
+        let wt = app
+            .filtered_worktrees
+            .iter()
+            .find(|w| w.name == "wt")
+            .unwrap();
+        assert!(wt.is_locked);
+        assert_eq!(wt.lock_reason.as_deref(), Some("on a USB drive"));
+
+        // Unlocking needs no prompt
+        app.toggle_lock_selected().unwrap();
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.message.as_deref(), Some("Unlocked worktree: wt"));
+        let wt = app
+            .filtered_worktrees
+            .iter()
+            .find(|w| w.name == "wt")
+            .unwrap();
+        assert!(!wt.is_locked);
+        assert_eq!(wt.lock_reason, None);
     }
 
     #[test]
-    fn test_create_worktree_remote_branch_nested_name() {
-        // Test nested remote branch name
-        let remote_branch_name = "origin/user/feature/auth";
+    fn test_lock_with_empty_reason() {
+        let (_temp_dir, _repo_path, mut app) = create_move_test_app();
 
-        let extracted_name: String = remote_branch_name
-            .split('/')
-            .skip(1)
-            .collect::<Vec<_>>()
-            .join("/");
+        app.toggle_lock_selected().unwrap();
+        app.lock_selected().unwrap();
 
-        assert_eq!(extracted_name, "user/feature/auth");
+        assert_eq!(app.message.as_deref(), Some("Locked worktree: wt"));
+        let wt = app
+            .filtered_worktrees
+            .iter()
+            .find(|w| w.name == "wt")
+            .unwrap();
+        assert!(wt.is_locked);
+        assert_eq!(wt.lock_reason, None);
     }
 
-    #[test]
-    fn test_create_worktree_uses_custom_name_from_input() {
-        let mut app = create_test_app();
-        app.mode = AppMode::Create;
-        app.selected_branch = 1; // Existing branch
-        app.input = "custom-worktree-name".to_string();
-
-        // When input is not empty, it should be used as worktree name
-        let worktree_name = if app.input.is_empty() {
-            "default-name".to_string()
-        } else {
-            app.input.clone()
-        };
+    // ========== Move Worktree Tests ==========
 
-        assert_eq!(worktree_name, "custom-worktree-name");
+    /// App backed by a real repository with one linked worktree named `wt`
+    fn create_move_test_app() -> (TempDir, PathBuf, App) {
+        let (temp_dir, repo_path) = setup_git_repo();
+        create_test_worktree_in_repo(&repo_path, "feature", "wt");
+        let mut app = create_test_app();
+        app.git = GitManager::from_path(&repo_path).unwrap();
+        app.refresh_worktrees().unwrap();
+        app.selected_worktree = app
+            .filtered_worktrees
+            .iter()
+            .position(|w| w.name == "wt")
+            .unwrap();
+        (temp_dir, repo_path, app)
     }
 
+    // ========== Detail Scroll Tests ==========
+
     #[test]
-    fn test_create_worktree_uses_branch_name_when_input_empty() {
+    fn test_toggle_focus() {
         let mut app = create_test_app();
-        app.mode = AppMode::Create;
-        app.selected_branch = 1;
-        app.input.clear();
-
-        // When input is empty, branch name should be used
-        let branch_index = app.selected_branch - 1;
-        let branch_name = &app.filtered_branches[branch_index].name;
+        assert_eq!(app.focus, Focus::List);
 
-        let worktree_name = if app.input.is_empty() {
-            branch_name.clone()
-        } else {
-            app.input.clone()
-        };
+        app.toggle_focus();
+        assert_eq!(app.focus, Focus::Detail);
 
-        assert_eq!(worktree_name, "main");
+        app.toggle_focus();
+        assert_eq!(app.focus, Focus::List);
     }
 
     #[test]
-    fn test_create_mode_navigation_includes_create_option() {
+    fn test_scroll_detail_stops_at_max() {
         let mut app = create_test_app();
-        app.mode = AppMode::Create;
-        app.selected_branch = 0;
+        app.detail_scroll_max = 1;
 
-        // In create mode, index 0 is "Create new branch"
-        // Moving down should work
-        app.move_down();
-        assert_eq!(app.selected_branch, 1);
+        app.scroll_detail_down();
+        app.scroll_detail_down();
+        assert_eq!(app.detail_scroll, 1);
 
-        // Can go up back to 0
-        app.move_up();
-        assert_eq!(app.selected_branch, 0);
+        app.scroll_detail_up();
+        app.scroll_detail_up();
+        assert_eq!(app.detail_scroll, 0);
     }
 
     #[test]
-    fn test_create_mode_max_navigation_boundary() {
+    fn test_detail_scroll_resets_on_selection_change() {
         let mut app = create_test_app();
-        app.mode = AppMode::Create;
-        // +1 for "Create new branch" option
-        let max_index = app.filtered_branches.len();
+        app.detail_scroll_max = 5;
+        app.scroll_detail_down();
+        app.scroll_detail_down();
 
-        app.selected_branch = max_index;
-        app.move_down();
+        app.sync_detail_scroll();
+        assert_eq!(app.detail_scroll, 2);
 
-        // Should not exceed max
-        assert_eq!(app.selected_branch, max_index);
+        app.move_down();
+        app.sync_detail_scroll();
+        assert_eq!(app.detail_scroll, 0);
     }
 
     #[test]
-    fn test_enter_create_mode_resets_selection() {
+    fn test_enter_move_mode_rejects_main_worktree() {
         let mut app = create_test_app();
-        app.selected_branch = 5;
-        app.input = "some-filter".to_string();
+        app.selected_worktree = 0;
 
-        let _ = app.enter_create_mode();
+        app.enter_move_mode();
 
-        // Should reset to select "Create new branch" (index 0)
-        assert_eq!(app.selected_branch, 0);
-        assert!(app.input.is_empty());
-        assert_eq!(app.mode, AppMode::Create);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.message.as_deref(), Some("Cannot move main worktree"));
     }
 
-    // ========== Prune Tests ==========
-
     #[test]
-    fn test_enter_confirm_prune_with_no_merged() {
+    fn test_enter_move_mode_prefills_current_path() {
         let mut app = create_test_app();
-        // merged_worktrees is empty by default
+        app.selected_worktree = 1;
 
-        // Note: This requires actual git operations, so we test the state
-        assert!(app.merged_worktrees.is_empty());
+        app.enter_move_mode();
+
+        assert_eq!(app.mode, AppMode::Move);
+        assert_eq!(app.input, "/repo/feature-a");
+        assert_eq!(app.cursor(), app.input.chars().count());
+        assert_eq!(
+            app.move_source.as_ref().map(|w| w.name.as_str()),
+            Some("feature-a")
+        );
     }
 
     #[test]
-    fn test_confirm_action_delete_single() {
+    fn test_enter_normal_mode_clears_move_source() {
         let mut app = create_test_app();
-        app.mode = AppMode::Confirm;
-        app.confirm_action = Some(ConfirmAction::DeleteSingle);
+        app.selected_worktree = 1;
+        app.enter_move_mode();
 
-        assert_eq!(app.confirm_action, Some(ConfirmAction::DeleteSingle));
+        app.enter_normal_mode();
+
+        assert!(app.move_source.is_none());
+        assert!(app.input.is_empty());
     }
 
     #[test]
-    fn test_confirm_action_prune() {
+    fn test_move_worktree_requires_destination() {
         let mut app = create_test_app();
-        app.mode = AppMode::Confirm;
-        app.confirm_action = Some(ConfirmAction::Prune);
+        app.selected_worktree = 1;
+        app.enter_move_mode();
+        app.input = "  ".to_string();
 
-        assert_eq!(app.confirm_action, Some(ConfirmAction::Prune));
-    }
+        app.move_worktree().unwrap();
 
-    // ========== Background Delete Tests ==========
+        assert_eq!(app.mode, AppMode::Move);
+        assert_eq!(
+            app.message.as_deref(),
+            Some("Please enter a destination path")
+        );
+    }
 
     #[test]
-    fn test_confirm_action_transitions_to_deleting_mode() {
-        let mut app = create_test_app();
-        app.mode = AppMode::Confirm;
-        app.confirm_action = Some(ConfirmAction::DeleteSingle);
-        app.selected_worktree = 1; // non-main worktree
+    fn test_move_worktree_moves_and_selects_it() {
+        let (_temp_dir, repo_path, mut app) = create_move_test_app();
+        app.enter_move_mode();
+        // Relative to the repository root; the parent is created by auto_mkdir
+        app.input = "nested/moved".to_string();
 
-        let result = app.confirm_action(false);
-        assert!(result.is_ok());
-        assert_eq!(app.mode, AppMode::Deleting);
-        assert!(app.deleting_message.is_some());
-        assert!(app.delete_receiver.is_some());
-        assert_eq!(app.tick, 0);
+        app.move_worktree().unwrap();
+
+        let destination = repo_path.join("nested/moved").canonicalize().unwrap();
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(!repo_path.join("wt").exists());
+        assert_eq!(
+            app.filtered_worktrees[app.selected_worktree].path,
+            destination
+        );
+        assert!(app.message.unwrap().starts_with("Moved worktree 'wt' to "));
     }
 
     #[test]
-    fn test_confirm_action_main_worktree_stays_normal() {
-        let mut app = create_test_app();
-        app.mode = AppMode::Confirm;
-        app.confirm_action = Some(ConfirmAction::DeleteSingle);
-        app.selected_worktree = 0; // main worktree
+    fn test_move_worktree_failure_stays_in_move_mode() {
+        let (_temp_dir, repo_path, mut app) = create_move_test_app();
+        std::fs::create_dir(repo_path.join("taken")).unwrap();
+        app.enter_move_mode();
+        app.input = repo_path.join("taken").to_string_lossy().to_string();
 
-        let result = app.confirm_action(false);
-        assert!(result.is_ok());
-        assert_eq!(app.mode, AppMode::Normal);
-        assert!(app.message.as_ref().unwrap().contains("Cannot delete main"));
+        app.move_worktree().unwrap();
+
+        assert_eq!(app.mode, AppMode::Move);
+        assert!(repo_path.join("wt").exists());
+        assert!(app
+            .message
+            .unwrap()
+            .starts_with("Failed to move worktree: "));
     }
 
+    // ========== Repair Tests ==========
+
     #[test]
-    fn test_confirm_action_prune_transitions_to_deleting() {
-        let mut app = create_test_app();
-        app.mode = AppMode::Confirm;
-        app.confirm_action = Some(ConfirmAction::Prune);
-        app.merged_worktrees = vec![Worktree {
-            name: "merged-wt".to_string(),
-            path: PathBuf::from("/repo/merged-wt"),
-            branch: Some("merged-branch".to_string()),
-            is_main: false,
-        }];
+    fn test_repair_worktrees_reports_count() {
+        let (_temp_dir, repo_path, mut app) = create_move_test_app();
+        std::fs::write(repo_path.join("wt/.git"), "gitdir: /nonexistent\n").unwrap();
 
-        let result = app.confirm_action(false);
-        assert!(result.is_ok());
-        assert_eq!(app.mode, AppMode::Deleting);
-        assert!(app.deleting_message.as_ref().unwrap().contains("Pruning"));
+        app.repair_worktrees().unwrap();
+
+        assert_eq!(
+            app.message.as_deref(),
+            Some("Repaired 1 worktree entry(ies)")
+        );
+        assert!(app.filtered_worktrees.iter().any(|w| w.name == "wt"));
     }
 
     #[test]
-    fn test_confirm_action_none_enters_normal() {
-        let mut app = create_test_app();
-        app.mode = AppMode::Confirm;
-        app.confirm_action = None;
+    fn test_repair_worktrees_with_nothing_to_fix() {
+        let (_temp_dir, _repo_path, mut app) = create_move_test_app();
 
-        let result = app.confirm_action(false);
-        assert!(result.is_ok());
-        assert_eq!(app.mode, AppMode::Normal);
+        app.repair_worktrees().unwrap();
+
+        assert_eq!(app.message.as_deref(), Some("No worktrees needed repair"));
     }
 
+    // ========== Prune Stale Tests ==========
+
     #[test]
-    fn test_check_delete_completion_no_receiver() {
-        let mut app = create_test_app();
-        // No receiver set - should be a no-op
-        let result = app.check_delete_completion();
-        assert!(result.is_ok());
-        assert_eq!(app.mode, AppMode::Normal);
+    fn test_prune_stale_worktrees_reports_count() {
+        let (_temp_dir, repo_path, mut app) = create_move_test_app();
+        std::fs::remove_dir_all(repo_path.join("wt")).unwrap();
+
+        app.prune_stale_worktrees().unwrap();
+
+        assert_eq!(
+            app.message.as_deref(),
+            Some("Pruned 1 stale worktree entry(ies)")
+        );
+        assert!(!app.filtered_worktrees.iter().any(|w| w.name == "wt"));
     }
 
     #[test]
-    fn test_check_delete_completion_pending() {
-        let mut app = create_test_app();
-        let (_tx, rx) = mpsc::channel::<DeleteResult>();
-        app.delete_receiver = Some(rx);
-        app.mode = AppMode::Deleting;
+    fn test_open_in_editor_with_missing_directory_prunes_it() {
+        let (_temp_dir, repo_path, mut app) = create_move_test_app();
+        app.config.worktree.editor = Some("true".to_string());
+        let wt_path = repo_path.join("wt");
+        std::fs::remove_dir_all(&wt_path).unwrap();
 
-        // Nothing sent yet - should remain in Deleting mode
-        let result = app.check_delete_completion();
-        assert!(result.is_ok());
-        assert_eq!(app.mode, AppMode::Deleting);
-        assert!(app.delete_receiver.is_some());
+        app.open_in_editor();
+
+        assert!(app.suspend_command.is_none());
+        assert_eq!(
+            app.message,
+            Some(format!(
+                "Worktree directory no longer exists: {}. Pruned its entry",
+                wt_path.display()
+            ))
+        );
+        assert!(!app.filtered_worktrees.iter().any(|w| w.name == "wt"));
     }
 
     #[test]
-    fn test_check_delete_completion_disconnected() {
-        let mut app = create_test_app();
-        let (tx, rx) = mpsc::channel::<DeleteResult>();
-        app.delete_receiver = Some(rx);
-        app.mode = AppMode::Deleting;
+    fn test_missing_directory_prunes_only_the_selected_entry() {
+        let (_temp_dir, repo_path, mut app) = create_move_test_app();
+        create_test_worktree_in_repo(&repo_path, "other", "other");
+        app.refresh_worktrees().unwrap();
+        app.selected_worktree = app
+            .filtered_worktrees
+            .iter()
+            .position(|w| w.name == "wt")
+            .unwrap();
+        std::fs::remove_dir_all(repo_path.join("wt")).unwrap();
+        std::fs::remove_dir_all(repo_path.join("other")).unwrap();
 
-        // Drop sender to simulate thread crash
-        drop(tx);
+        app.select_worktree();
 
-        let result = app.check_delete_completion();
-        assert!(result.is_ok());
-        assert_eq!(app.mode, AppMode::Normal);
-        assert!(app.delete_receiver.is_none());
-        assert!(app.message.as_ref().unwrap().contains("unexpectedly"));
+        let names: Vec<_> = app.worktrees.iter().map(|w| w.name.as_str()).collect();
+        assert!(!names.contains(&"wt"));
+        assert!(names.contains(&"other"));
     }
 
     #[test]
-    fn test_check_delete_completion_single_success() {
-        let mut app = create_test_app();
-        let (tx, rx) = mpsc::channel();
-        app.delete_receiver = Some(rx);
-        app.mode = AppMode::Deleting;
+    fn test_select_worktree_with_missing_locked_directory_keeps_entry() {
+        let (_temp_dir, repo_path, mut app) = create_move_test_app();
+        let wt_path = repo_path.join("wt");
+        app.toggle_lock_selected().unwrap();
+        app.lock_selected().unwrap();
+        std::fs::remove_dir_all(&wt_path).unwrap();
 
-        tx.send(DeleteResult::SingleCompleted {
-            worktree_name: "test-wt".to_string(),
-            branch_name: None,
-            branch_deleted: false,
-            error_message: None,
-        })
-        .unwrap();
+        app.select_worktree();
 
-        let result = app.check_delete_completion();
-        assert!(result.is_ok());
-        assert_eq!(app.mode, AppMode::Normal);
-        assert!(app.delete_receiver.is_none());
-        assert!(app.deleting_message.is_none());
-        assert!(app.message.as_ref().unwrap().contains("test-wt"));
+        assert!(!app.should_quit);
+        assert!(app.selected_worktree_path.is_none());
+        assert!(app
+            .message
+            .as_deref()
+            .unwrap()
+            .ends_with("Skipped its locked entry; unlock it to prune"));
+        assert!(app.filtered_worktrees.iter().any(|w| w.name == "wt"));
     }
 
     #[test]
-    fn test_check_delete_completion_single_with_branch() {
-        let mut app = create_test_app();
-        let (tx, rx) = mpsc::channel();
-        app.delete_receiver = Some(rx);
-        app.mode = AppMode::Deleting;
+    fn test_prune_stale_worktrees_with_nothing_to_prune() {
+        let (_temp_dir, _repo_path, mut app) = create_move_test_app();
 
-        tx.send(DeleteResult::SingleCompleted {
-            worktree_name: "test-wt".to_string(),
-            branch_name: Some("feature/test".to_string()),
-            branch_deleted: true,
-            error_message: None,
-        })
-        .unwrap();
+        app.prune_stale_worktrees().unwrap();
 
-        let result = app.check_delete_completion();
-        assert!(result.is_ok());
-        let msg = app.message.as_ref().unwrap();
-        assert!(msg.contains("test-wt"));
-        assert!(msg.contains("feature/test"));
+        assert_eq!(app.message.as_deref(), Some("No stale worktree entries"));
+        assert!(app.filtered_worktrees.iter().any(|w| w.name == "wt"));
     }
 
+    // ========== Rerun Setup Tests ==========
+
     #[test]
-    fn test_check_delete_completion_single_branch_error() {
-        let mut app = create_test_app();
-        let (tx, rx) = mpsc::channel();
-        app.delete_receiver = Some(rx);
-        app.mode = AppMode::Deleting;
+    fn test_rerun_setup_copies_files_into_existing_worktree() {
+        let (_temp_dir, repo_path, mut app) = create_move_test_app();
+        std::fs::write(repo_path.join(".env"), "KEY=1").unwrap();
+        app.config.copy_files = Some(vec![".env".to_string()]);
+        assert!(!repo_path.join("wt/.env").exists());
+
+        app.rerun_setup();
+        assert_eq!(app.mode, AppMode::SettingUp);
+        wait_until(
+            &mut app,
+            |app| {
+                app.check_setup_completion().unwrap();
+                app.mode != AppMode::SettingUp
+            },
+            JOB_TIMEOUT,
+        );
 
-        tx.send(DeleteResult::SingleCompleted {
-            worktree_name: "test-wt".to_string(),
-            branch_name: Some("feature/test".to_string()),
-            branch_deleted: false,
-            error_message: Some("failed to delete branch".to_string()),
-        })
-        .unwrap();
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(
+            std::fs::read_to_string(repo_path.join("wt/.env")).unwrap(),
+            "KEY=1"
+        );
+        assert_eq!(
+            app.message.as_deref(),
+            Some("Re-ran setup in 'wt' (copied 1 file(s))")
+        );
+    }
+
+    #[test]
+    fn test_rerun_setup_reports_failure() {
+        let (_temp_dir, _repo_path, mut app) = create_move_test_app();
+        app.config.setup_commands = Some(vec!["exit 3".into()]);
+
+        app.rerun_setup();
+        wait_until(
+            &mut app,
+            |app| {
+                app.check_setup_completion().unwrap();
+                app.mode != AppMode::SettingUp
+            },
+            JOB_TIMEOUT,
+        );
 
-        let result = app.check_delete_completion();
-        assert!(result.is_ok());
         assert!(app
             .message
-            .as_ref()
+            .as_deref()
             .unwrap()
-            .contains("failed to delete branch"));
+            .starts_with("Re-running setup in 'wt' failed: "));
     }
 
     #[test]
-    fn test_check_delete_completion_prune() {
-        let mut app = create_test_app();
-        let (tx, rx) = mpsc::channel();
-        app.delete_receiver = Some(rx);
-        app.mode = AppMode::Deleting;
-        app.merged_worktrees = vec![Worktree {
-            name: "wt".to_string(),
-            path: PathBuf::from("/repo/wt"),
-            branch: None,
-            is_main: false,
-        }];
+    fn test_rerun_setup_without_setup_configured() {
+        let (_temp_dir, _repo_path, mut app) = create_move_test_app();
 
-        tx.send(DeleteResult::PruneCompleted {
-            worktree_count: 3,
-            branch_count: 2,
-        })
-        .unwrap();
+        app.rerun_setup();
 
-        let result = app.check_delete_completion();
-        assert!(result.is_ok());
         assert_eq!(app.mode, AppMode::Normal);
-        let msg = app.message.as_ref().unwrap();
-        assert!(msg.contains("3 worktree(s)"));
-        assert!(msg.contains("2 branch(es)"));
-        assert!(app.merged_worktrees.is_empty());
+        assert_eq!(
+            app.message.as_deref(),
+            Some("No setup configured (copy_files, symlink_files, setup_commands)")
+        );
     }
 
     #[test]
-    fn test_check_delete_completion_error() {
-        let mut app = create_test_app();
-        let (tx, rx) = mpsc::channel();
-        app.delete_receiver = Some(rx);
-        app.mode = AppMode::Deleting;
-
-        tx.send(DeleteResult::Error("something went wrong".to_string()))
+    fn test_rerun_setup_rejects_main_worktree() {
+        let (_temp_dir, _repo_path, mut app) = create_move_test_app();
+        app.config.copy_files = Some(vec![".env".to_string()]);
+        app.selected_worktree = app
+            .filtered_worktrees
+            .iter()
+            .position(|w| w.is_main)
             .unwrap();
 
-        let result = app.check_delete_completion();
-        assert!(result.is_ok());
+        app.rerun_setup();
+
         assert_eq!(app.mode, AppMode::Normal);
-        assert!(app
-            .message
-            .as_ref()
-            .unwrap()
-            .contains("something went wrong"));
+        assert_eq!(
+            app.message.as_deref(),
+            Some("Setup copies from the main worktree; select another one")
+        );
     }
 
+    // ========== Dry Run Tests ==========
+
     #[test]
-    fn test_confirm_action_delete_branch_true() {
+    fn test_dry_run_create_worktree_changes_nothing() {
+        let (temp_dir, repo_path) = setup_git_repo();
         let mut app = create_test_app();
+        let basedir = temp_dir.path().join("wts");
+        app.config.worktree.basedir = Some(basedir.to_string_lossy().to_string());
+        app.git = GitManager::from_path(&repo_path).unwrap();
+        app.refresh_worktrees().unwrap();
+        app.dry_run = true;
+        app.enter_create_mode().unwrap();
+        app.input = "fresh".to_string();
+
+        app.create_worktree().unwrap();
+
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.create_receiver.is_none());
+        let message = app.message.as_deref().unwrap();
+        assert!(message.starts_with("Dry run: would create worktree 'fresh' at "));
+        assert!(message.ends_with("on new branch 'fresh' from HEAD"));
+        assert!(!basedir.exists());
+        let repo = git2::Repository::open(&repo_path).unwrap();
+        assert!(repo.find_branch("fresh", git2::BranchType::Local).is_err());
+    }
+
+    #[test]
+    fn test_dry_run_delete_keeps_worktree_and_branch() {
+        let (_temp_dir, repo_path, mut app) = create_move_test_app();
+        app.dry_run = true;
         app.mode = AppMode::Confirm;
         app.confirm_action = Some(ConfirmAction::DeleteSingle);
-        app.selected_worktree = 1; // non-main worktree with branch "feature/a"
 
-        let result = app.confirm_action(true);
-        assert!(result.is_ok());
-        assert_eq!(app.mode, AppMode::Deleting);
-        assert!(app.delete_receiver.is_some());
+        app.confirm_action(true).unwrap();
+
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(
+            app.message.as_deref(),
+            Some("Dry run: would delete worktree 'wt' and branch 'feature'")
+        );
+        assert!(repo_path.join("wt").exists());
+        let repo = git2::Repository::open(&repo_path).unwrap();
+        assert!(repo.find_branch("feature", git2::BranchType::Local).is_ok());
     }
 
     #[test]
-    fn test_confirm_action_empty_filtered_worktrees() {
-        let mut app = create_test_app();
+    fn test_dry_run_delete_marked_keeps_worktrees() {
+        let (_temp_dir, repo_path, mut app) = create_move_test_app();
+        wait_until(
+            &mut app,
+            |app| {
+                app.check_status_updates();
+                app.pending_status.is_empty()
+            },
+            JOB_TIMEOUT,
+        );
+        app.dry_run = true;
+        app.toggle_mark();
         app.mode = AppMode::Confirm;
-        app.confirm_action = Some(ConfirmAction::DeleteSingle);
-        app.filtered_worktrees.clear();
+        app.confirm_action = Some(ConfirmAction::DeleteMarked);
+
+        app.confirm_action(false).unwrap();
 
-        let result = app.confirm_action(false);
-        assert!(result.is_ok());
         assert_eq!(app.mode, AppMode::Normal);
-        assert!(app.delete_receiver.is_none());
+        assert_eq!(
+            app.message.as_deref(),
+            Some("Dry run: would delete 1 worktree(s): wt")
+        );
+        assert!(repo_path.join("wt").exists());
     }
 
     #[test]
-    fn test_check_delete_completion_clears_input() {
-        let mut app = create_test_app();
-        let (tx, rx) = mpsc::channel();
-        app.delete_receiver = Some(rx);
-        app.mode = AppMode::Deleting;
-        app.input = "leftover search".to_string();
+    fn test_dry_run_stash_delete_takes_no_stash() {
+        let (_temp_dir, repo_path, mut app) = create_move_test_app();
+        std::fs::write(repo_path.join("wt").join("notes.txt"), "wip").unwrap();
+        app.filtered_worktrees[app.selected_worktree].is_dirty = true;
+        app.dry_run = true;
+        app.mode = AppMode::Confirm;
         app.confirm_action = Some(ConfirmAction::DeleteSingle);
 
-        tx.send(DeleteResult::SingleCompleted {
-            worktree_name: "test-wt".to_string(),
-            branch_name: None,
-            branch_deleted: false,
-            error_message: None,
-        })
-        .unwrap();
+        app.confirm_stash_delete().unwrap();
 
-        let result = app.check_delete_completion();
-        assert!(result.is_ok());
-        assert!(app.input.is_empty(), "input should be cleared after delete");
-        assert!(
-            app.confirm_action.is_none(),
-            "confirm_action should be cleared after delete"
-        );
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(repo_path.join("wt").join("notes.txt").exists());
+        let repo = git2::Repository::open(&repo_path).unwrap();
+        assert!(repo.refname_to_id("refs/stash").is_err());
     }
 
     #[test]
-    fn test_check_delete_completion_prune_without_branches() {
-        let mut app = create_test_app();
-        let (tx, rx) = mpsc::channel();
-        app.delete_receiver = Some(rx);
-        app.mode = AppMode::Deleting;
-        app.merged_worktrees = vec![Worktree {
-            name: "wt".to_string(),
-            path: PathBuf::from("/repo/wt"),
-            branch: None,
-            is_main: false,
-        }];
+    fn test_dry_run_move_keeps_worktree_in_place() {
+        let (temp_dir, repo_path, mut app) = create_move_test_app();
+        app.dry_run = true;
+        app.enter_move_mode();
+        let destination = temp_dir.path().join("moved").join("wt");
+        app.input = destination.to_string_lossy().to_string();
 
-        tx.send(DeleteResult::PruneCompleted {
-            worktree_count: 2,
-            branch_count: 0,
-        })
-        .unwrap();
+        app.move_worktree().unwrap();
 
-        let result = app.check_delete_completion();
-        assert!(result.is_ok());
-        let msg = app.message.as_ref().unwrap();
-        assert!(msg.contains("2 merged worktree(s)"));
-        assert!(
-            !msg.contains("branch"),
-            "should not mention branches when count is 0"
-        );
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app
+            .message
+            .as_deref()
+            .unwrap()
+            .starts_with("Dry run: would move worktree 'wt' to "));
+        assert!(repo_path.join("wt").exists());
+        assert!(!temp_dir.path().join("moved").exists());
     }
 
-    // ========== execute_delete_single Tests ==========
+    #[test]
+    fn test_dry_run_toggle_lock_leaves_worktree_unlocked() {
+        let (_temp_dir, _repo_path, mut app) = create_move_test_app();
+        app.dry_run = true;
+
+        app.toggle_lock_selected().unwrap();
+        app.lock_selected().unwrap();
+        app.refresh_worktrees().unwrap();
+
+        assert_eq!(
+            app.message.as_deref(),
+            Some("Dry run: would lock worktree 'wt'")
+        );
+        assert!(app
+            .filtered_worktrees
+            .iter()
+            .any(|w| w.name == "wt" && !w.is_locked));
+    }
 
     #[test]
-    fn test_execute_delete_single_success() {
-        let (_temp_dir, repo_path) = setup_git_repo();
-        create_test_worktree_in_repo(&repo_path, "feature-del", "wt-del");
+    fn test_dry_run_prune_stale_lists_entries_without_pruning() {
+        let (_temp_dir, repo_path, mut app) = create_move_test_app();
+        std::fs::remove_dir_all(repo_path.join("wt")).unwrap();
+        app.dry_run = true;
 
-        let result = execute_delete_single(&repo_path, "wt-del", None, false);
+        app.prune_stale_worktrees().unwrap();
+        app.refresh_worktrees().unwrap();
 
-        match result {
-            DeleteResult::SingleCompleted {
-                worktree_name,
-                branch_deleted,
-                error_message,
-                ..
-            } => {
-                assert_eq!(worktree_name, "wt-del");
-                assert!(!branch_deleted);
-                assert!(error_message.is_none());
-                // Verify worktree directory is removed
-                assert!(!repo_path.join("wt-del").exists());
-            }
-            other => panic!("Expected SingleCompleted, got {:?}", other),
-        }
+        assert_eq!(
+            app.message.as_deref(),
+            Some("Dry run: would prune 1 stale worktree entry(ies): wt")
+        );
+        assert!(app.filtered_worktrees.iter().any(|w| w.name == "wt"));
     }
 
     #[test]
-    fn test_execute_delete_single_with_branch_deletion() {
-        let (_temp_dir, repo_path) = setup_git_repo();
-        create_test_worktree_in_repo(&repo_path, "feature-br", "wt-br");
+    fn test_dry_run_missing_directory_is_not_pruned() {
+        let (_temp_dir, repo_path, mut app) = create_move_test_app();
+        std::fs::remove_dir_all(repo_path.join("wt")).unwrap();
+        app.dry_run = true;
 
-        let result =
-            execute_delete_single(&repo_path, "wt-br", Some("feature-br".to_string()), true);
+        app.select_worktree();
 
-        match result {
-            DeleteResult::SingleCompleted {
-                worktree_name,
-                branch_deleted,
-                error_message,
-                ..
-            } => {
-                assert_eq!(worktree_name, "wt-br");
-                assert!(branch_deleted);
-                assert!(error_message.is_none());
-            }
-            other => panic!("Expected SingleCompleted, got {:?}", other),
-        }
+        assert!(app.selected_worktree_path.is_none());
+        assert!(app
+            .message
+            .as_deref()
+            .unwrap()
+            .ends_with("Dry run: left its entry in place"));
+        assert!(app.filtered_worktrees.iter().any(|w| w.name == "wt"));
     }
 
     #[test]
     fn test_execute_delete_single_worktree_not_found() {
         let (_temp_dir, repo_path) = setup_git_repo();
 
-        let result = execute_delete_single(&repo_path, "nonexistent", None, false);
+        let result = execute_delete_single(&repo_path, "nonexistent", None, false, false);
 
         match result {
             DeleteResult::Error(msg) => {
@@ -1808,7 +7919,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let bad_path = temp_dir.path().to_path_buf();
 
-        let result = execute_delete_single(&bad_path, "wt", None, false);
+        let result = execute_delete_single(&bad_path, "wt", None, false, false);
 
         match result {
             DeleteResult::Error(msg) => {
@@ -1829,6 +7940,7 @@ mod tests {
             "wt-fail",
             Some("nonexistent-branch".to_string()),
             true,
+            false,
         );
 
         match result {
@@ -1847,6 +7959,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_execute_delete_single_keeps_branch_checked_out_elsewhere() {
+        let (_temp_dir, repo_path) = setup_git_repo();
+        create_test_worktree_in_repo(&repo_path, "feature-shared", "wt-first");
+        let second = repo_path.join("wt-second");
+        Command::new("git")
+            .args([
+                "worktree",
+                "add",
+                "--force",
+                second.to_str().unwrap(),
+                "feature-shared",
+            ])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+
+        let result = execute_delete_single(
+            &repo_path,
+            "wt-first",
+            Some("feature-shared".to_string()),
+            true,
+            false,
+        );
+
+        match result {
+            DeleteResult::SingleCompleted {
+                branch_deleted,
+                error_message,
+                ..
+            } => {
+                assert!(!branch_deleted);
+                let msg = error_message.unwrap();
+                assert!(msg.contains("checked out in worktree 'wt-second'"));
+            }
+            other => panic!("Expected SingleCompleted, got {:?}", other),
+        }
+
+        let repo = git2::Repository::open(&repo_path).unwrap();
+        assert!(repo
+            .find_branch("feature-shared", git2::BranchType::Local)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_delete_local_branch_refuses_main_head() {
+        let (_temp_dir, repo_path) = setup_git_repo();
+        let repo = git2::Repository::open(&repo_path).unwrap();
+        let head = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        let result = delete_local_branch(&repo, &head);
+
+        assert!(result.unwrap_err().contains("checked out in worktree"));
+    }
+
     #[test]
     fn test_execute_delete_single_no_branch_delete_when_false() {
         let (_temp_dir, repo_path) = setup_git_repo();
@@ -1857,6 +8024,7 @@ mod tests {
             "wt-keep",
             Some("feature-keep".to_string()),
             false, // do NOT delete branch
+            false,
         );
 
         match result {
@@ -1902,6 +8070,7 @@ mod tests {
             DeleteResult::PruneCompleted {
                 worktree_count,
                 branch_count,
+                ..
             } => {
                 assert_eq!(worktree_count, 2);
                 assert_eq!(branch_count, 0);
@@ -1927,6 +8096,7 @@ mod tests {
             DeleteResult::PruneCompleted {
                 worktree_count,
                 branch_count,
+                ..
             } => {
                 assert_eq!(worktree_count, 2);
                 assert_eq!(branch_count, 2);
@@ -1951,9 +8121,13 @@ mod tests {
             DeleteResult::PruneCompleted {
                 worktree_count,
                 branch_count,
+                failed,
+                ..
             } => {
                 assert_eq!(worktree_count, 1, "only one worktree should be deleted");
                 assert_eq!(branch_count, 0);
+                assert_eq!(failed.len(), 1);
+                assert!(failed[0].starts_with("nonexistent-wt: "), "{:?}", failed);
             }
             other => panic!("Expected PruneCompleted, got {:?}", other),
         }
@@ -1974,6 +8148,7 @@ mod tests {
             DeleteResult::PruneCompleted {
                 worktree_count,
                 branch_count,
+                ..
             } => {
                 assert_eq!(worktree_count, 0);
                 assert_eq!(branch_count, 0);
@@ -2008,6 +8183,7 @@ mod tests {
             DeleteResult::PruneCompleted {
                 worktree_count,
                 branch_count,
+                ..
             } => {
                 assert_eq!(worktree_count, 0);
                 assert_eq!(branch_count, 0);
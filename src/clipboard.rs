@@ -0,0 +1,25 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ClipboardError {
+    #[cfg(feature = "clipboard")]
+    #[error("{0}")]
+    Access(#[from] arboard::Error),
+    #[cfg(not(feature = "clipboard"))]
+    #[error("clipboard support is not enabled")]
+    Disabled,
+}
+
+/// Copy text to the system clipboard
+#[cfg(feature = "clipboard")]
+pub fn copy(text: &str) -> Result<(), ClipboardError> {
+    let mut clipboard = arboard::Clipboard::new()?;
+    clipboard.set_text(text)?;
+    Ok(())
+}
+
+/// Copy text to the system clipboard (unavailable without the `clipboard` feature)
+#[cfg(not(feature = "clipboard"))]
+pub fn copy(_text: &str) -> Result<(), ClipboardError> {
+    Err(ClipboardError::Disabled)
+}
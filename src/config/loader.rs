@@ -1,5 +1,7 @@
 use crate::git::RepoInfo;
-use crate::theme::ThemeColorsConfig;
+use crate::multiplexer::Multiplexer;
+use crate::shell::shell_quote;
+use crate::theme::{parse_color, ThemeColors, ThemeColorsConfig};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -9,18 +11,81 @@ use thiserror::Error;
 pub enum ConfigError {
     #[error("Failed to read config file: {0}")]
     IoError(#[from] std::io::Error),
+    #[error("Config file not found: {}", .0.display())]
+    NotFound(PathBuf),
     #[error("Failed to parse config file: {0}")]
     ParseError(#[from] toml::de::Error),
     #[error("Unresolved template variable(s) in naming template: {0}")]
     UnresolvedTemplateVariable(String),
+    #[error("Invalid configuration:\n{}", .0.join("\n"))]
+    Invalid(Vec<String>),
 }
 
+/// Worktree row layout used when `ui.list_format` is unset
+pub const DEFAULT_LIST_FORMAT: &str = "{name} {dirty} {locked} | {branch} {main}";
+
+/// Variables `ui.list_format` understands
+pub const LIST_FORMAT_VARIABLES: &[&str] =
+    &["name", "branch", "path", "dirty", "locked", "main", "head"];
+
 #[derive(Debug, Clone, Default, Deserialize)]
 pub struct WorktreeConfig {
     /// Base directory for worktrees
     pub basedir: Option<String>,
     /// Automatically create base directory if it doesn't exist
     pub auto_mkdir: Option<bool>,
+    /// Show dirty/clean status for each worktree in the list
+    pub show_status: Option<bool>,
+    /// Editor command used to open worktrees (supports {path})
+    pub editor: Option<String>,
+    /// Command run on exit instead of spawning $SHELL (supports {path})
+    pub shell: Option<String>,
+    /// Command that shows a worktree's changes (supports {path})
+    pub diff_command: Option<String>,
+    /// Command Enter runs with gwm suspended instead of exiting (supports {path}, {branch})
+    pub on_open: Option<String>,
+    /// Remote used by fetch and pull
+    pub default_remote: Option<String>,
+    /// Branch that prune checks worktrees against (default: the remote HEAD)
+    pub default_base: Option<String>,
+    /// Fail setup when a copy_files entry matches nothing (default: only report it)
+    pub strict_copy_files: Option<bool>,
+    /// Branch selected when the create form opens: "main", "current" or "first"
+    pub create_base: Option<String>,
+    /// Warn after creating a worktree from a branch that is behind its upstream
+    pub warn_stale_base: Option<bool>,
+    /// Fetch all remotes in the background when gwm starts
+    pub fetch_on_open: Option<bool>,
+    /// Open a worktree right after creating it, as Enter would
+    pub open_after_create: Option<bool>,
+    /// Put new worktrees in a directory named after the repository under basedir
+    pub namespace_by_repo: Option<bool>,
+    /// Glob patterns of branches prune never removes, even when merged
+    pub prune_exclude: Option<Vec<String>>,
+}
+
+/// Branch selected when Create mode opens
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CreateBase {
+    /// The repository's default branch, as used by prune
+    Main,
+    /// The branch of the worktree selected when Create mode was opened
+    Current,
+    /// The first entry, "Create new branch"
+    #[default]
+    First,
+}
+
+impl CreateBase {
+    /// Parse a config value, case-insensitively
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "main" => Some(CreateBase::Main),
+            "current" => Some(CreateBase::Current),
+            "first" => Some(CreateBase::First),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -29,6 +94,8 @@ pub struct NamingConfig {
     pub template: Option<String>,
     /// Characters to sanitize in names (e.g., "/" -> "-")
     pub sanitize_chars: Option<HashMap<String, String>>,
+    /// Template for new branch names (supports {name} and {user})
+    pub branch_template: Option<String>,
 }
 
 impl NamingConfig {
@@ -36,6 +103,7 @@ impl NamingConfig {
     fn default_sanitize_chars() -> HashMap<String, String> {
         let mut map = HashMap::new();
         map.insert("/".to_string(), "-".to_string());
+        map.insert(" ".to_string(), "-".to_string());
         map
     }
 
@@ -86,6 +154,29 @@ impl NamingConfig {
         }
     }
 
+    /// Generate a new branch name from the typed name using `branch_template`.
+    /// `{user}` is sanitized so a name like "Jane Doe" gives a valid ref.
+    pub fn generate_branch_name(
+        &self,
+        name: &str,
+        user: Option<&str>,
+    ) -> Result<String, ConfigError> {
+        let Some(template) = &self.branch_template else {
+            return Ok(name.to_string());
+        };
+
+        let mut result = template.replace("{name}", name);
+        if let Some(user) = user {
+            result = result.replace("{user}", &self.sanitize(user));
+        }
+        if result.contains("{user}") {
+            return Err(ConfigError::UnresolvedTemplateVariable(
+                "{user}".to_string(),
+            ));
+        }
+        Ok(result)
+    }
+
     /// Find unreplaced template variables in a string
     fn find_unreplaced_variables(s: &str) -> Vec<&'static str> {
         let known_variables = ["{host}", "{owner}", "{repository}"];
@@ -107,6 +198,43 @@ pub struct UiConfig {
     pub theme: Option<String>,
     /// Custom color overrides
     pub colors: Option<ThemeColorsConfig>,
+    /// Idle event poll interval in milliseconds
+    pub tick_rate_ms: Option<u64>,
+    /// Animate spinners; when disabled, the UI only redraws on input
+    pub animations: Option<bool>,
+    /// Seconds a status message stays visible; unset keeps it until the next key
+    pub notification_secs: Option<u64>,
+    /// Number of recent commits shown in the detail pane
+    pub recent_commits: Option<usize>,
+    /// Dim worktrees without activity for this many days
+    pub stale_days: Option<u64>,
+    /// Show the repository and mode in the terminal title
+    pub set_title: Option<bool>,
+    /// Select and scroll the worktree list with the mouse
+    pub mouse: Option<bool>,
+    /// Layout of a worktree row, e.g. "{name} | {branch}"; a newline starts a second line
+    pub list_format: Option<String>,
+    /// Join multi-line rows onto a single line, shortening the path to fit
+    pub compact_list: Option<bool>,
+}
+
+/// Which operations ask for confirmation before running
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ConfirmConfig {
+    /// Ask before deleting worktrees (locked, dirty or still loading ones
+    /// always ask)
+    pub delete: Option<bool>,
+    /// Ask before pruning merged worktrees (dirty or still loading ones
+    /// always ask)
+    pub prune: Option<bool>,
+    /// Show the disk space a delete frees in the dialog
+    pub show_size: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct IntegrationConfig {
+    /// Terminal multiplexer to open selected worktrees in: "tmux", "zellij" or "none"
+    pub multiplexer: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -115,8 +243,118 @@ pub struct RepositorySettings {
     pub repository: String,
     /// Files to copy from main worktree to new worktree
     pub copy_files: Option<Vec<String>>,
+    /// Files to symlink from main worktree instead of copying
+    pub symlink_files: Option<Vec<String>>,
     /// Commands to run after creating worktree
-    pub setup_commands: Option<Vec<String>>,
+    pub setup_commands: Option<Vec<SetupCommand>>,
+}
+
+/// A setup command: a plain string run with `setup_shell`, or a table that
+/// picks its own shell or time limit
+/// (`{ command = "npm install", shell = "bash", timeout_secs = 600 }`)
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum SetupCommand {
+    Plain(String),
+    Table {
+        command: String,
+        shell: Option<String>,
+        timeout_secs: Option<u64>,
+    },
+}
+
+impl SetupCommand {
+    pub fn command(&self) -> &str {
+        match self {
+            SetupCommand::Plain(command) | SetupCommand::Table { command, .. } => command,
+        }
+    }
+
+    /// Shell chosen for this command, overriding `setup_shell`
+    pub fn shell(&self) -> Option<&str> {
+        match self {
+            SetupCommand::Plain(_) => None,
+            SetupCommand::Table { shell, .. } => shell.as_deref(),
+        }
+    }
+
+    /// Time limit for this command, overriding `setup_timeout_secs`
+    pub fn timeout_secs(&self) -> Option<u64> {
+        match self {
+            SetupCommand::Plain(_) => None,
+            SetupCommand::Table { timeout_secs, .. } => *timeout_secs,
+        }
+    }
+}
+
+impl From<&str> for SetupCommand {
+    fn from(command: &str) -> Self {
+        SetupCommand::Plain(command.to_string())
+    }
+}
+
+impl std::fmt::Display for SetupCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.command())?;
+        let mut options = Vec::new();
+        if let Some(shell) = self.shell() {
+            options.push(shell.to_string());
+        }
+        if let Some(secs) = self.timeout_secs() {
+            options.push(format!("{}s", secs));
+        }
+        if !options.is_empty() {
+            write!(f, " ({})", options.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Lifecycle event that triggers a hook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEvent {
+    PreCreate,
+    PostCreate,
+    PreDelete,
+    PostDelete,
+}
+
+impl HookEvent {
+    /// Name used in config files and messages
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HookEvent::PreCreate => "pre_create",
+            HookEvent::PostCreate => "post_create",
+            HookEvent::PreDelete => "pre_delete",
+            HookEvent::PostDelete => "post_delete",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Hook {
+    /// Event that triggers this hook
+    pub event: HookEvent,
+    /// Shell command to run
+    pub command: String,
+    /// Working directory (defaults to the worktree path)
+    pub cwd: Option<String>,
+    /// Seconds the hook may run before it is killed (default: 60, 0: no limit)
+    pub timeout_secs: Option<u64>,
+}
+
+/// Time limit for hooks without `timeout_secs`. `pre_*` hooks block the UI
+/// while they run, so they can't be left to run forever
+const DEFAULT_HOOK_TIMEOUT_SECS: u64 = 60;
+
+impl Hook {
+    /// Time limit for this hook; 0 means no limit
+    pub fn timeout(&self) -> Option<std::time::Duration> {
+        Some(self.timeout_secs.unwrap_or(DEFAULT_HOOK_TIMEOUT_SECS))
+            .filter(|secs| *secs > 0)
+            .map(std::time::Duration::from_secs)
+    }
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -130,15 +368,33 @@ pub struct Config {
     /// UI configuration
     #[serde(default)]
     pub ui: UiConfig,
+    /// External tool integration
+    #[serde(default)]
+    pub integration: IntegrationConfig,
+    /// Confirmation prompts
+    #[serde(default)]
+    pub confirm: ConfirmConfig,
     /// Per-repository settings
     #[serde(default)]
     pub repository_settings: Vec<RepositorySettings>,
     /// Top-level copy_files (applies to all repositories when no specific repository_settings match)
     #[serde(default)]
     pub copy_files: Option<Vec<String>>,
+    /// Top-level symlink_files (applies to all repositories when no specific repository_settings match)
+    #[serde(default)]
+    pub symlink_files: Option<Vec<String>>,
     /// Top-level setup_commands (applies to all repositories when no specific repository_settings match)
     #[serde(default)]
-    pub setup_commands: Option<Vec<String>>,
+    pub setup_commands: Option<Vec<SetupCommand>>,
+    /// Shell that runs setup_commands (default: sh)
+    #[serde(default)]
+    pub setup_shell: Option<String>,
+    /// Seconds each setup command may run before it is killed (default: no limit)
+    #[serde(default)]
+    pub setup_timeout_secs: Option<u64>,
+    /// Lifecycle hooks run around worktree create/delete
+    #[serde(default)]
+    pub hooks: Option<Vec<Hook>>,
 }
 
 /// A configuration source with its file path
@@ -200,23 +456,92 @@ impl Config {
             worktree: WorktreeConfig {
                 basedir: other.worktree.basedir.or(self.worktree.basedir),
                 auto_mkdir: other.worktree.auto_mkdir.or(self.worktree.auto_mkdir),
+                show_status: other.worktree.show_status.or(self.worktree.show_status),
+                editor: other.worktree.editor.or(self.worktree.editor),
+                shell: other.worktree.shell.or(self.worktree.shell),
+                diff_command: other.worktree.diff_command.or(self.worktree.diff_command),
+                on_open: other.worktree.on_open.or(self.worktree.on_open),
+                default_remote: other
+                    .worktree
+                    .default_remote
+                    .or(self.worktree.default_remote),
+                default_base: other.worktree.default_base.or(self.worktree.default_base),
+                strict_copy_files: other
+                    .worktree
+                    .strict_copy_files
+                    .or(self.worktree.strict_copy_files),
+                create_base: other.worktree.create_base.or(self.worktree.create_base),
+                warn_stale_base: other
+                    .worktree
+                    .warn_stale_base
+                    .or(self.worktree.warn_stale_base),
+                fetch_on_open: other.worktree.fetch_on_open.or(self.worktree.fetch_on_open),
+                open_after_create: other
+                    .worktree
+                    .open_after_create
+                    .or(self.worktree.open_after_create),
+                namespace_by_repo: other
+                    .worktree
+                    .namespace_by_repo
+                    .or(self.worktree.namespace_by_repo),
+                prune_exclude: other.worktree.prune_exclude.or(self.worktree.prune_exclude),
             },
             naming: NamingConfig {
                 template: other.naming.template.or(self.naming.template),
                 sanitize_chars: other.naming.sanitize_chars.or(self.naming.sanitize_chars),
+                branch_template: other.naming.branch_template.or(self.naming.branch_template),
             },
             ui: UiConfig {
                 icons: other.ui.icons.or(self.ui.icons),
                 tilde_home: other.ui.tilde_home.or(self.ui.tilde_home),
                 theme: other.ui.theme.or(self.ui.theme),
                 colors: other.ui.colors.or(self.ui.colors),
+                tick_rate_ms: other.ui.tick_rate_ms.or(self.ui.tick_rate_ms),
+                animations: other.ui.animations.or(self.ui.animations),
+                notification_secs: other.ui.notification_secs.or(self.ui.notification_secs),
+                recent_commits: other.ui.recent_commits.or(self.ui.recent_commits),
+                stale_days: other.ui.stale_days.or(self.ui.stale_days),
+                set_title: other.ui.set_title.or(self.ui.set_title),
+                mouse: other.ui.mouse.or(self.ui.mouse),
+                list_format: other.ui.list_format.or(self.ui.list_format),
+                compact_list: other.ui.compact_list.or(self.ui.compact_list),
+            },
+            integration: IntegrationConfig {
+                multiplexer: other
+                    .integration
+                    .multiplexer
+                    .or(self.integration.multiplexer),
+            },
+            confirm: ConfirmConfig {
+                delete: other.confirm.delete.or(self.confirm.delete),
+                prune: other.confirm.prune.or(self.confirm.prune),
+                show_size: other.confirm.show_size.or(self.confirm.show_size),
             },
             repository_settings: merged_repo_settings,
             copy_files: other.copy_files.or(self.copy_files),
+            symlink_files: other.symlink_files.or(self.symlink_files),
             setup_commands: other.setup_commands.or(self.setup_commands),
+            setup_shell: other.setup_shell.or(self.setup_shell),
+            setup_timeout_secs: other.setup_timeout_secs.or(self.setup_timeout_secs),
+            hooks: other.hooks.or(self.hooks),
         }
     }
 
+    /// Shell that runs setup commands without a shell of their own (default: "sh")
+    pub fn setup_shell(&self) -> &str {
+        self.setup_shell
+            .as_deref()
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or("sh")
+    }
+
+    /// Time limit for each setup command; unset or 0 means no limit
+    pub fn setup_timeout(&self) -> Option<std::time::Duration> {
+        self.setup_timeout_secs
+            .filter(|secs| *secs > 0)
+            .map(std::time::Duration::from_secs)
+    }
+
     /// Get the worktree base directory, defaulting to "~/worktrees"
     pub fn worktree_basedir(&self) -> String {
         self.worktree
@@ -225,22 +550,20 @@ impl Config {
             .unwrap_or_else(|| "~/worktrees".to_string())
     }
 
-    /// Expand ~ to home directory
+    /// Expand ~ to home directory and `$VAR` / `${VAR}` to their values
     pub fn expand_path(&self, path: &str) -> String {
-        if path.starts_with("~/") {
-            if let Some(home) = dirs::home_dir() {
-                return path.replacen("~", &home.to_string_lossy(), 1);
-            }
-        }
-        path.to_string()
+        expand_env_path(path)
     }
 
     /// Compress home directory to ~ (reverse of expand_path)
     pub fn compress_path(&self, path: &str) -> String {
         if let Some(home) = dirs::home_dir() {
-            let home_str = home.to_string_lossy();
-            if path.starts_with(home_str.as_ref()) {
-                return path.replacen(home_str.as_ref(), "~", 1);
+            // Compare whole components so /home/user2 isn't treated as inside /home/user
+            if let Ok(rest) = Path::new(path).strip_prefix(&home) {
+                if rest.as_os_str().is_empty() {
+                    return "~".to_string();
+                }
+                return format!("~/{}", rest.to_string_lossy());
             }
         }
         path.to_string()
@@ -262,15 +585,10 @@ impl Config {
     }
 
     /// Get expanded worktree base directory with repo root for relative paths
-    /// - Absolute paths and ~ paths are expanded normally
+    /// - ~ and environment variables are expanded first
     /// - Relative paths (starting with . or not starting with /) are resolved from repo_root
     pub fn worktree_basedir_expanded_with_repo_root(&self, repo_root: &Path) -> String {
-        let basedir = self.worktree_basedir();
-
-        // Handle ~ expansion first
-        if basedir.starts_with("~/") {
-            return self.expand_path(&basedir);
-        }
+        let basedir = self.expand_path(&self.worktree_basedir());
 
         // Absolute path - return as-is
         if basedir.starts_with('/') {
@@ -282,11 +600,168 @@ impl Config {
         resolved.to_string_lossy().to_string()
     }
 
+    /// Get the worktree base directory for a branch
+    /// Same as `worktree_basedir_expanded_with_repo_root`, with `{branch}` replaced
+    /// by the sanitized branch name so it never introduces extra path components
+    pub fn worktree_basedir_for_branch(&self, repo_root: &Path, branch_name: &str) -> String {
+        self.worktree_basedir_expanded_with_repo_root(repo_root)
+            .replace("{branch}", &self.naming.sanitize(branch_name))
+    }
+
+    /// Get the worktree base directory for a branch of the repository `repo_name`.
+    /// Same as `worktree_basedir_for_branch`, plus a `repo_name` directory with
+    /// `worktree.namespace_by_repo`, unless the naming template already has `{repository}`
+    pub fn worktree_basedir_for_repo(
+        &self,
+        repo_root: &Path,
+        repo_name: &str,
+        branch_name: &str,
+    ) -> String {
+        let basedir = self.worktree_basedir_for_branch(repo_root, branch_name);
+        let named_by_template = self
+            .naming
+            .template
+            .as_deref()
+            .is_some_and(|t| t.contains("{repository}"));
+        if !self.namespace_by_repo() || named_by_template || repo_name.is_empty() {
+            return basedir;
+        }
+        Path::new(&basedir)
+            .join(self.naming.sanitize(repo_name))
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// Check if new worktrees are grouped by repository under basedir (default: false)
+    pub fn namespace_by_repo(&self) -> bool {
+        self.worktree.namespace_by_repo.unwrap_or(false)
+    }
+
     /// Check if auto_mkdir is enabled (default: true)
     pub fn auto_mkdir(&self) -> bool {
         self.worktree.auto_mkdir.unwrap_or(true)
     }
 
+    /// Check if missing copy_files entries fail setup (default: false)
+    pub fn strict_copy_files(&self) -> bool {
+        self.worktree.strict_copy_files.unwrap_or(false)
+    }
+
+    /// Branch selected when the create form opens (default: first)
+    pub fn create_base(&self) -> CreateBase {
+        self.worktree
+            .create_base
+            .as_deref()
+            .and_then(CreateBase::parse)
+            .unwrap_or_default()
+    }
+
+    /// Check if creating from a branch behind its upstream warns (default: false)
+    pub fn warn_stale_base(&self) -> bool {
+        self.worktree.warn_stale_base.unwrap_or(false)
+    }
+
+    /// Check if all remotes are fetched at startup (default: false, to keep launch fast)
+    pub fn fetch_on_open(&self) -> bool {
+        self.worktree.fetch_on_open.unwrap_or(false)
+    }
+
+    /// Check if a newly created worktree is opened right away (default: false)
+    pub fn open_after_create(&self) -> bool {
+        self.worktree.open_after_create.unwrap_or(false)
+    }
+
+    /// Check if per-worktree dirty status should be shown (default: true)
+    pub fn show_status(&self) -> bool {
+        self.worktree.show_status.unwrap_or(true)
+    }
+
+    /// Remote used by fetch and pull (default: "origin")
+    pub fn default_remote(&self) -> &str {
+        self.worktree
+            .default_remote
+            .as_deref()
+            .filter(|r| !r.is_empty())
+            .unwrap_or("origin")
+    }
+
+    /// Branch prune checks against, if configured
+    pub fn default_base(&self) -> Option<&str> {
+        self.worktree
+            .default_base
+            .as_deref()
+            .filter(|b| !b.is_empty())
+    }
+
+    /// Build the shell command that opens `path` in an editor
+    /// Priority: worktree.editor > $VISUAL > $EDITOR. Returns None if none is set.
+    pub fn editor_command(&self, path: &str) -> Option<String> {
+        let quoted = shell_quote(path);
+        if let Some(template) = self.worktree.editor.as_deref().filter(|e| !e.is_empty()) {
+            return Some(if template.contains("{path}") {
+                substitute_path(template, path)
+            } else {
+                format!("{} {}", template, quoted)
+            });
+        }
+
+        ["VISUAL", "EDITOR"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|e| !e.is_empty())
+            .map(|editor| format!("{} {}", editor, quoted))
+    }
+
+    /// Command that replaces the default subshell when a worktree is selected,
+    /// e.g. `tmux new-window -c {path}`. None keeps the $SHELL behavior.
+    pub fn shell_command(&self, path: &str) -> Option<String> {
+        self.worktree
+            .shell
+            .as_deref()
+            .filter(|s| !s.is_empty())
+            .map(|template| substitute_path(template, path))
+    }
+
+    /// Command that shows the changes of the worktree at `path`, run from
+    /// inside it. Defaults to `git diff HEAD` (staged and unstaged changes)
+    pub fn diff_command(&self, path: &str) -> String {
+        self.worktree
+            .diff_command
+            .as_deref()
+            .filter(|c| !c.is_empty())
+            .map(|template| substitute_path(template, path))
+            .unwrap_or_else(|| "git diff HEAD".to_string())
+    }
+
+    /// Command Enter runs for the worktree at `path` instead of leaving gwm,
+    /// with `{path}` and `{branch}` (empty for a detached HEAD) substituted.
+    /// None keeps the default of exiting into a subshell.
+    pub fn on_open_command(&self, path: &str, branch: Option<&str>) -> Option<String> {
+        self.worktree
+            .on_open
+            .as_deref()
+            .filter(|c| !c.is_empty())
+            .map(|template| {
+                substitute_path(template, path)
+                    .replace("{branch}", &shell_quote(branch.unwrap_or_default()))
+            })
+    }
+
+    /// Whether `branch` matches a `worktree.prune_exclude` pattern. `*` stays
+    /// within one path segment (`release/*`); `**` crosses them.
+    pub fn prune_excluded(&self, branch: &str) -> bool {
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+        self.worktree
+            .prune_exclude
+            .iter()
+            .flatten()
+            .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+            .any(|pattern| pattern.matches_with(branch, options))
+    }
+
     /// Generate worktree directory name from branch name
     pub fn generate_worktree_name(
         &self,
@@ -296,6 +771,15 @@ impl Config {
         self.naming.generate_worktree_name(branch_name, repo_info)
     }
 
+    /// Generate a new branch name from the typed name
+    pub fn generate_branch_name(
+        &self,
+        name: &str,
+        user: Option<&str>,
+    ) -> Result<String, ConfigError> {
+        self.naming.generate_branch_name(name, user)
+    }
+
     /// Check if icons are enabled (default: true)
     pub fn icons_enabled(&self) -> bool {
         self.ui.icons.unwrap_or(true)
@@ -306,6 +790,164 @@ impl Config {
         self.ui.tilde_home.unwrap_or(true)
     }
 
+    /// Idle event poll interval (default: 250ms, minimum: 10ms)
+    pub fn tick_rate(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.ui.tick_rate_ms.unwrap_or(250).max(10))
+    }
+
+    /// Check if animations are enabled (default: true)
+    pub fn animations(&self) -> bool {
+        self.ui.animations.unwrap_or(true)
+    }
+
+    /// How long status messages stay visible, clamped to 1-300 seconds.
+    /// `None` keeps a message until the next key press.
+    pub fn notification_duration(&self) -> Option<std::time::Duration> {
+        self.ui
+            .notification_secs
+            .map(|secs| std::time::Duration::from_secs(secs.clamp(1, 300)))
+    }
+
+    /// Number of recent commits in the detail pane (default: 5, at most 50; 0 hides them)
+    pub fn recent_commits(&self) -> usize {
+        self.ui.recent_commits.unwrap_or(5).min(50)
+    }
+
+    /// Days without activity after which a worktree is dimmed (unset or 0: never)
+    pub fn stale_days(&self) -> Option<u64> {
+        self.ui.stale_days.filter(|&days| days > 0)
+    }
+
+    /// Check if gwm sets the terminal title (default: true)
+    pub fn set_title(&self) -> bool {
+        self.ui.set_title.unwrap_or(true)
+    }
+
+    /// Check if mouse capture is enabled (default: false, which keeps
+    /// the terminal's own text selection working)
+    pub fn mouse(&self) -> bool {
+        self.ui.mouse.unwrap_or(false)
+    }
+
+    /// Layout of a worktree row in the list (default: `DEFAULT_LIST_FORMAT`)
+    pub fn list_format(&self) -> &str {
+        self.ui
+            .list_format
+            .as_deref()
+            .filter(|f| !f.trim().is_empty())
+            .unwrap_or(DEFAULT_LIST_FORMAT)
+    }
+
+    /// Whether the list starts out with single-line rows (default: false)
+    pub fn compact_list(&self) -> bool {
+        self.ui.compact_list.unwrap_or(false)
+    }
+
+    /// Whether deleting worktrees asks first (default: true)
+    pub fn confirm_delete(&self) -> bool {
+        self.confirm.delete.unwrap_or(true)
+    }
+
+    /// Whether pruning merged worktrees asks first (default: true)
+    pub fn confirm_prune(&self) -> bool {
+        self.confirm.prune.unwrap_or(true)
+    }
+
+    /// Whether delete dialogs measure the space they free (default: false,
+    /// since walking large worktrees takes a while)
+    pub fn confirm_show_size(&self) -> bool {
+        self.confirm.show_size.unwrap_or(false)
+    }
+
+    /// Multiplexer to open selected worktrees in, if enabled
+    pub fn multiplexer(&self) -> Option<Multiplexer> {
+        self.integration
+            .multiplexer
+            .as_deref()
+            .and_then(Multiplexer::parse)
+    }
+
+    /// Check values that deserialize fine but would be silently ignored,
+    /// reporting every problem at once
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut errors = Vec::new();
+
+        if let Some(ref basedir) = self.worktree.basedir {
+            for var in unknown_variables(basedir, &["branch"]) {
+                errors.push(format!("worktree.basedir: unknown variable {}", var));
+            }
+        }
+        if let Some(ref template) = self.naming.template {
+            for var in unknown_variables(template, &["branch", "host", "owner", "repository"]) {
+                errors.push(format!("naming.template: unknown variable {}", var));
+            }
+        }
+        if let Some(ref template) = self.naming.branch_template {
+            for var in unknown_variables(template, &["name", "user"]) {
+                errors.push(format!("naming.branch_template: unknown variable {}", var));
+            }
+        }
+        if let Some(ref base) = self.worktree.create_base {
+            if CreateBase::parse(base).is_none() {
+                errors.push(format!(
+                    "worktree.create_base: unknown value \"{}\" (expected \"main\", \"current\" or \"first\")",
+                    base
+                ));
+            }
+        }
+        for pattern in self.worktree.prune_exclude.iter().flatten() {
+            if let Err(e) = glob::Pattern::new(pattern) {
+                errors.push(format!(
+                    "worktree.prune_exclude: invalid pattern \"{}\" ({})",
+                    pattern, e
+                ));
+            }
+        }
+        if let Some(ref format) = self.ui.list_format {
+            for var in unknown_variables(format, LIST_FORMAT_VARIABLES) {
+                errors.push(format!("ui.list_format: unknown variable {}", var));
+            }
+        }
+        if let Some(ref theme) = self.ui.theme {
+            if ThemeColors::from_preset(theme).is_none() {
+                errors.push(format!(
+                    "ui.theme: unknown theme \"{}\" (expected \"default\" or \"classic\")",
+                    theme
+                ));
+            }
+        }
+        if let Some(ref colors) = self.ui.colors {
+            for (name, value) in colors.entries() {
+                if parse_color(value).is_none() {
+                    errors.push(format!("ui.colors.{}: invalid color \"{}\"", name, value));
+                }
+            }
+        }
+        if let Some(ref name) = self.integration.multiplexer {
+            if !name.eq_ignore_ascii_case("none") && Multiplexer::parse(name).is_none() {
+                errors.push(format!(
+                    "integration.multiplexer: unknown multiplexer \"{}\" (expected \"tmux\", \"zellij\" or \"none\")",
+                    name
+                ));
+            }
+        }
+        for (i, hook) in self.hooks().iter().enumerate() {
+            if hook.command.trim().is_empty() {
+                errors.push(format!(
+                    "hooks[{}] ({}): command is empty",
+                    i,
+                    hook.event.as_str()
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(errors))
+        }
+    }
+
     /// Get the theme name (default: "default")
     pub fn theme_name(&self) -> &str {
         self.ui.theme.as_deref().unwrap_or("default")
@@ -316,6 +958,11 @@ impl Config {
         self.ui.colors.as_ref()
     }
 
+    /// Get configured lifecycle hooks
+    pub fn hooks(&self) -> &[Hook] {
+        self.hooks.as_deref().unwrap_or(&[])
+    }
+
     /// Get repository settings for a specific repository path
     pub fn get_repository_settings(&self, repo_path: &str) -> Option<&RepositorySettings> {
         self.repository_settings
@@ -336,6 +983,7 @@ impl Config {
         RepositorySettings {
             repository: repo_path.to_string(),
             copy_files: self.copy_files.clone(),
+            symlink_files: self.symlink_files.clone(),
             setup_commands: self.setup_commands.clone(),
         }
     }
@@ -425,8 +1073,38 @@ fn load_env_config() -> Config {
             auto_mkdir: std::env::var("GWM_WORKTREE_AUTO_MKDIR")
                 .ok()
                 .and_then(|v| parse_bool(&v)),
+            show_status: std::env::var("GWM_WORKTREE_SHOW_STATUS")
+                .ok()
+                .and_then(|v| parse_bool(&v)),
+            editor: std::env::var("GWM_WORKTREE_EDITOR").ok(),
+            shell: std::env::var("GWM_WORKTREE_SHELL").ok(),
+            diff_command: std::env::var("GWM_WORKTREE_DIFF_COMMAND").ok(),
+            on_open: std::env::var("GWM_WORKTREE_ON_OPEN").ok(),
+            default_remote: std::env::var("GWM_WORKTREE_DEFAULT_REMOTE").ok(),
+            default_base: std::env::var("GWM_WORKTREE_DEFAULT_BASE").ok(),
+            strict_copy_files: std::env::var("GWM_WORKTREE_STRICT_COPY_FILES")
+                .ok()
+                .and_then(|v| parse_bool(&v)),
+            create_base: std::env::var("GWM_WORKTREE_CREATE_BASE").ok(),
+            warn_stale_base: std::env::var("GWM_WORKTREE_WARN_STALE_BASE")
+                .ok()
+                .and_then(|v| parse_bool(&v)),
+            fetch_on_open: std::env::var("GWM_WORKTREE_FETCH_ON_OPEN")
+                .ok()
+                .and_then(|v| parse_bool(&v)),
+            open_after_create: std::env::var("GWM_WORKTREE_OPEN_AFTER_CREATE")
+                .ok()
+                .and_then(|v| parse_bool(&v)),
+            namespace_by_repo: std::env::var("GWM_WORKTREE_NAMESPACE_BY_REPO")
+                .ok()
+                .and_then(|v| parse_bool(&v)),
+            prune_exclude: None, // Lists can only be set via config file
+        },
+        naming: NamingConfig {
+            template: std::env::var("GWM_NAMING_TEMPLATE").ok(),
+            sanitize_chars: None, // sanitize_chars can only be set via config file
+            branch_template: std::env::var("GWM_NAMING_BRANCH_TEMPLATE").ok(),
         },
-        naming: NamingConfig::default(),
         ui: UiConfig {
             icons: std::env::var("GWM_UI_ICONS")
                 .ok()
@@ -436,11 +1114,120 @@ fn load_env_config() -> Config {
                 .and_then(|v| parse_bool(&v)),
             theme: std::env::var("GWM_UI_THEME").ok(),
             colors: None, // Colors can only be set via config file
+            tick_rate_ms: std::env::var("GWM_UI_TICK_RATE_MS")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            animations: std::env::var("GWM_UI_ANIMATIONS")
+                .ok()
+                .and_then(|v| parse_bool(&v)),
+            notification_secs: std::env::var("GWM_UI_NOTIFICATION_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            recent_commits: std::env::var("GWM_UI_RECENT_COMMITS")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            stale_days: std::env::var("GWM_UI_STALE_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            set_title: std::env::var("GWM_UI_SET_TITLE")
+                .ok()
+                .and_then(|v| parse_bool(&v)),
+            mouse: std::env::var("GWM_UI_MOUSE")
+                .ok()
+                .and_then(|v| parse_bool(&v)),
+            list_format: std::env::var("GWM_UI_LIST_FORMAT").ok(),
+            compact_list: std::env::var("GWM_UI_COMPACT_LIST")
+                .ok()
+                .and_then(|v| parse_bool(&v)),
+        },
+        integration: IntegrationConfig {
+            multiplexer: std::env::var("GWM_INTEGRATION_MULTIPLEXER").ok(),
+        },
+        confirm: ConfirmConfig {
+            delete: std::env::var("GWM_CONFIRM_DELETE")
+                .ok()
+                .and_then(|v| parse_bool(&v)),
+            prune: std::env::var("GWM_CONFIRM_PRUNE")
+                .ok()
+                .and_then(|v| parse_bool(&v)),
+            show_size: std::env::var("GWM_CONFIRM_SHOW_SIZE")
+                .ok()
+                .and_then(|v| parse_bool(&v)),
         },
         repository_settings: Vec::new(),
         copy_files: None,     // copy_files can only be set via config file
+        symlink_files: None,  // symlink_files can only be set via config file
         setup_commands: None, // setup_commands can only be set via config file
+        setup_shell: std::env::var("GWM_SETUP_SHELL").ok(),
+        setup_timeout_secs: std::env::var("GWM_SETUP_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok()),
+        hooks: None, // hooks can only be set via config file
+    }
+}
+
+/// Find `{name}` placeholders in `s` that are not in `known`
+fn unknown_variables(s: &str, known: &[&str]) -> Vec<String> {
+    let mut unknown = Vec::new();
+    let mut rest = s;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 1..start + len];
+        if !known.contains(&name) {
+            unknown.push(format!("{{{}}}", name));
+        }
+        rest = &rest[start + len + 1..];
+    }
+    unknown
+}
+
+/// Expand a leading `~` and `$VAR` / `${VAR}` references in a path.
+/// Unset variables are left untouched so the result still points somewhere recognizable.
+pub fn expand_env_path(path: &str) -> String {
+    let path = match (path.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+            format!("{}{}", home.to_string_lossy(), rest)
+        }
+        _ => path.to_string(),
+    };
+
+    let mut expanded = String::with_capacity(path.len());
+    let mut rest = path.as_str();
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let (name, len) = match after.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) => (&braced[..end], end + 2),
+                None => ("", 0),
+            },
+            None => {
+                let end = after
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(after.len());
+                (&after[..end], end)
+            }
+        };
+        match std::env::var(name) {
+            Ok(value) if !name.is_empty() => {
+                expanded.push_str(&value);
+                rest = &after[len..];
+            }
+            _ => {
+                expanded.push('$');
+                rest = after;
+            }
+        }
     }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Replace every `{path}` in a command template with the shell-quoted path
+pub fn substitute_path(template: &str, path: &str) -> String {
+    template.replace("{path}", &shell_quote(path))
 }
 
 /// Parse boolean from string (supports "true", "false", "1", "0")
@@ -454,6 +1241,10 @@ fn parse_bool(s: &str) -> Option<bool> {
 
 /// Load config from a specific file path
 fn load_config_from_path(path: &Path) -> Result<Config, ConfigError> {
+    // An explicitly requested file must exist, unlike the discovered ones
+    if !path.exists() {
+        return Err(ConfigError::NotFound(path.to_path_buf()));
+    }
     let content = std::fs::read_to_string(path)?;
     let config: Config = toml::from_str(&content)?;
     Ok(config)
@@ -461,7 +1252,7 @@ fn load_config_from_path(path: &Path) -> Result<Config, ConfigError> {
 
 /// Load and merge configs (global + local + env)
 /// Priority: env > custom/local > global
-/// If custom_path is provided, it replaces both global and local config
+/// If custom_path (or $GWM_CONFIG) is provided, it replaces both global and local config
 #[cfg(test)]
 fn load_config(custom_path: Option<&Path>) -> Result<Config, ConfigError> {
     let (config, _sources) = load_config_with_sources(custom_path)?;
@@ -474,10 +1265,14 @@ pub fn load_config_with_sources(
     custom_path: Option<&Path>,
 ) -> Result<(Config, ConfigSources), ConfigError> {
     let env = load_env_config();
+    let env_path = std::env::var_os("GWM_CONFIG")
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from);
 
-    if let Some(path) = custom_path {
+    if let Some(path) = custom_path.or(env_path.as_deref()) {
         let custom = load_config_from_path(path)?;
         let merged = custom.clone().merge(env.clone());
+        merged.validate()?;
         let sources = ConfigSources {
             global: ConfigSource {
                 path: Some(path.to_path_buf()),
@@ -498,6 +1293,7 @@ pub fn load_config_with_sources(
     let (local_path, local) = load_local_config(&current_dir)?;
 
     let merged = global.clone().merge(local.clone()).merge(env.clone());
+    merged.validate()?;
 
     let sources = ConfigSources {
         global: ConfigSource {
@@ -583,12 +1379,14 @@ mod tests {
             repository_settings: vec![
                 RepositorySettings {
                     repository: "project-a".to_string(),
-                    setup_commands: Some(vec!["npm install".to_string()]),
+                    symlink_files: None,
+                    setup_commands: Some(vec!["npm install".into()]),
                     copy_files: None,
                 },
                 RepositorySettings {
                     repository: "project-b".to_string(),
-                    setup_commands: Some(vec!["go mod download".to_string()]),
+                    symlink_files: None,
+                    setup_commands: Some(vec!["go mod download".into()]),
                     copy_files: None,
                 },
             ],
@@ -599,12 +1397,14 @@ mod tests {
             repository_settings: vec![
                 RepositorySettings {
                     repository: "project-a".to_string(),
-                    setup_commands: Some(vec!["yarn install".to_string()]),
+                    symlink_files: None,
+                    setup_commands: Some(vec!["yarn install".into()]),
                     copy_files: None,
                 },
                 RepositorySettings {
                     repository: "project-c".to_string(),
-                    setup_commands: Some(vec!["make setup".to_string()]),
+                    symlink_files: None,
+                    setup_commands: Some(vec!["make setup".into()]),
                     copy_files: None,
                 },
             ],
@@ -621,10 +1421,7 @@ mod tests {
             .iter()
             .find(|s| s.repository == "project-a")
             .unwrap();
-        assert_eq!(
-            project_a.setup_commands,
-            Some(vec!["yarn install".to_string()])
-        );
+        assert_eq!(project_a.setup_commands, Some(vec!["yarn install".into()]));
     }
 
     #[test]
@@ -641,7 +1438,8 @@ mod tests {
         let config = Config {
             repository_settings: vec![RepositorySettings {
                 repository: "my-project".to_string(),
-                setup_commands: Some(vec!["npm install".to_string()]),
+                symlink_files: None,
+                setup_commands: Some(vec!["npm install".into()]),
                 copy_files: None,
             }],
             ..Default::default()
@@ -657,7 +1455,8 @@ mod tests {
         let config = Config {
             repository_settings: vec![RepositorySettings {
                 repository: "my-project".to_string(),
-                setup_commands: Some(vec!["npm install".to_string()]),
+                symlink_files: None,
+                setup_commands: Some(vec!["npm install".into()]),
                 copy_files: None,
             }],
             ..Default::default()
@@ -674,7 +1473,8 @@ mod tests {
         let config = Config {
             repository_settings: vec![RepositorySettings {
                 repository: "~/src/my-project".to_string(),
-                setup_commands: Some(vec!["npm install".to_string()]),
+                symlink_files: None,
+                setup_commands: Some(vec!["npm install".into()]),
                 copy_files: None,
             }],
             ..Default::default()
@@ -691,7 +1491,8 @@ mod tests {
         let config = Config {
             repository_settings: vec![RepositorySettings {
                 repository: "other-project".to_string(),
-                setup_commands: Some(vec!["npm install".to_string()]),
+                symlink_files: None,
+                setup_commands: Some(vec!["npm install".into()]),
                 copy_files: None,
             }],
             ..Default::default()
@@ -739,26 +1540,79 @@ mod tests {
     }
 
     #[test]
-    fn test_worktree_basedir_expanded() {
-        let config = Config::default();
-
-        let expanded = config.worktree_basedir_expanded();
+    fn test_expand_env_path_tilde() {
+        let home = dirs::home_dir().unwrap();
 
-        // Default is ~/worktrees, should be expanded
-        assert!(!expanded.starts_with("~"));
-        assert!(expanded.ends_with("/worktrees"));
+        assert_eq!(
+            expand_env_path("~/templates/.env"),
+            format!("{}/templates/.env", home.display())
+        );
+        assert_eq!(expand_env_path("~"), home.to_string_lossy());
+        // Only a leading ~ referring to the current user is expanded
+        assert_eq!(expand_env_path("~other/x"), "~other/x");
+        assert_eq!(expand_env_path("a/~/b"), "a/~/b");
     }
 
     #[test]
-    fn test_worktree_basedir_expanded_with_repo_root_tilde() {
-        let config = Config::default(); // basedir = ~/worktrees
-        let repo_root = std::path::Path::new("/some/repo");
+    fn test_expand_env_path_variables() {
+        let home = std::env::var("HOME").unwrap();
 
-        let expanded = config.worktree_basedir_expanded_with_repo_root(repo_root);
+        assert_eq!(expand_env_path("$HOME/wt"), format!("{}/wt", home));
+        assert_eq!(expand_env_path("${HOME}-wt"), format!("{}-wt", home));
+        assert_eq!(
+            expand_env_path("$GWM_TEST_SURELY_UNSET/wt"),
+            "$GWM_TEST_SURELY_UNSET/wt"
+        );
+        assert_eq!(expand_env_path("a$/b${"), "a$/b${");
+    }
 
-        // ~ should be expanded to home, not relative to repo_root
-        assert!(!expanded.starts_with("~"));
-        assert!(expanded.ends_with("/worktrees"));
+    #[test]
+    fn test_expand_env_path_relative_unchanged() {
+        assert_eq!(expand_env_path(".env"), ".env");
+        assert_eq!(expand_env_path("config/*.yml"), "config/*.yml");
+        // {branch} placeholders are not environment variables
+        assert_eq!(expand_env_path("../wt/{branch}"), "../wt/{branch}");
+    }
+
+    #[test]
+    fn test_worktree_basedir_expanded_with_repo_root_env_var() {
+        let config = Config {
+            worktree: WorktreeConfig {
+                basedir: Some("$HOME/worktrees".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let expanded = config.worktree_basedir_expanded_with_repo_root(Path::new("/repo"));
+
+        assert_eq!(
+            expanded,
+            format!("{}/worktrees", std::env::var("HOME").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_worktree_basedir_expanded() {
+        let config = Config::default();
+
+        let expanded = config.worktree_basedir_expanded();
+
+        // Default is ~/worktrees, should be expanded
+        assert!(!expanded.starts_with("~"));
+        assert!(expanded.ends_with("/worktrees"));
+    }
+
+    #[test]
+    fn test_worktree_basedir_expanded_with_repo_root_tilde() {
+        let config = Config::default(); // basedir = ~/worktrees
+        let repo_root = std::path::Path::new("/some/repo");
+
+        let expanded = config.worktree_basedir_expanded_with_repo_root(repo_root);
+
+        // ~ should be expanded to home, not relative to repo_root
+        assert!(!expanded.starts_with("~"));
+        assert!(expanded.ends_with("/worktrees"));
         assert!(!expanded.starts_with("/some/repo"));
     }
 
@@ -814,119 +1668,776 @@ mod tests {
     }
 
     #[test]
-    fn test_compress_path_with_home() {
-        let config = Config::default();
-        let home = dirs::home_dir().unwrap();
-        let full_path = format!("{}/projects/test", home.to_string_lossy());
+    fn test_compress_path_with_home() {
+        let config = Config::default();
+        let home = dirs::home_dir().unwrap();
+        let full_path = format!("{}/projects/test", home.to_string_lossy());
+
+        let compressed = config.compress_path(&full_path);
+
+        assert!(compressed.starts_with("~"));
+        assert_eq!(compressed, "~/projects/test");
+    }
+
+    #[test]
+    fn test_compress_path_without_home() {
+        let config = Config::default();
+
+        let compressed = config.compress_path("/var/log/test");
+
+        assert_eq!(compressed, "/var/log/test");
+    }
+
+    #[test]
+    fn test_compress_path_equal_to_home() {
+        let config = Config::default();
+        let home = dirs::home_dir().unwrap();
+
+        let compressed = config.compress_path(&home.to_string_lossy());
+
+        assert_eq!(compressed, "~");
+    }
+
+    #[test]
+    fn test_compress_path_sibling_of_home() {
+        let config = Config::default();
+        let home = dirs::home_dir().unwrap();
+        // Shares the home path as a string prefix but is a different directory
+        let sibling = format!("{}2/projects", home.to_string_lossy());
+
+        let compressed = config.compress_path(&sibling);
+
+        assert_eq!(compressed, sibling);
+    }
+
+    #[test]
+    fn test_format_path_for_display_tilde_home_enabled() {
+        let config = Config::default(); // tilde_home defaults to true
+        let home = dirs::home_dir().unwrap();
+        let full_path = format!("{}/projects/test", home.to_string_lossy());
+
+        let formatted = config.format_path_for_display(&full_path);
+
+        assert_eq!(formatted, "~/projects/test");
+    }
+
+    #[test]
+    fn test_format_path_for_display_tilde_home_disabled() {
+        let config = Config {
+            ui: UiConfig {
+                tilde_home: Some(false),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let home = dirs::home_dir().unwrap();
+        let full_path = format!("{}/projects/test", home.to_string_lossy());
+
+        let formatted = config.format_path_for_display(&full_path);
+
+        // Should NOT compress when tilde_home is false
+        assert_eq!(formatted, full_path);
+    }
+
+    #[test]
+    fn test_parse_bool_true_values() {
+        assert_eq!(parse_bool("true"), Some(true));
+        assert_eq!(parse_bool("TRUE"), Some(true));
+        assert_eq!(parse_bool("1"), Some(true));
+        assert_eq!(parse_bool("yes"), Some(true));
+        assert_eq!(parse_bool("YES"), Some(true));
+    }
+
+    #[test]
+    fn test_parse_bool_false_values() {
+        assert_eq!(parse_bool("false"), Some(false));
+        assert_eq!(parse_bool("FALSE"), Some(false));
+        assert_eq!(parse_bool("0"), Some(false));
+        assert_eq!(parse_bool("no"), Some(false));
+        assert_eq!(parse_bool("NO"), Some(false));
+    }
+
+    #[test]
+    fn test_parse_bool_invalid_values() {
+        assert_eq!(parse_bool(""), None);
+        assert_eq!(parse_bool("invalid"), None);
+        assert_eq!(parse_bool("2"), None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_env_config_basedir() {
+        // Save original value
+        let original = std::env::var("GWM_WORKTREE_BASEDIR").ok();
+
+        std::env::set_var("GWM_WORKTREE_BASEDIR", "/custom/path");
+        let config = load_env_config();
+        assert_eq!(config.worktree.basedir, Some("/custom/path".to_string()));
+
+        // Restore original value
+        match original {
+            Some(v) => std::env::set_var("GWM_WORKTREE_BASEDIR", v),
+            None => std::env::remove_var("GWM_WORKTREE_BASEDIR"),
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_env_config_naming_template() {
+        let original = std::env::var("GWM_NAMING_TEMPLATE").ok();
+
+        std::env::set_var("GWM_NAMING_TEMPLATE", "{repository}-{branch}");
+        let config = load_env_config();
+        assert_eq!(
+            config.naming.template,
+            Some("{repository}-{branch}".to_string())
+        );
+
+        match original {
+            Some(v) => std::env::set_var("GWM_NAMING_TEMPLATE", v),
+            None => std::env::remove_var("GWM_NAMING_TEMPLATE"),
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_env_naming_template_overrides_file_with_source() {
+        let original = std::env::var("GWM_NAMING_TEMPLATE").ok();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        std::fs::write(&path, "[naming]\ntemplate = \"file-{branch}\"\n").unwrap();
+
+        std::env::set_var("GWM_NAMING_TEMPLATE", "env-{branch}");
+        let (config, sources) = load_config_with_sources(Some(&path)).unwrap();
+
+        assert_eq!(config.naming.template, Some("env-{branch}".to_string()));
+        assert_eq!(
+            sources.env.naming.template,
+            Some("env-{branch}".to_string())
+        );
+        assert_eq!(
+            sources.global.config.naming.template,
+            Some("file-{branch}".to_string())
+        );
+
+        match original {
+            Some(v) => std::env::set_var("GWM_NAMING_TEMPLATE", v),
+            None => std::env::remove_var("GWM_NAMING_TEMPLATE"),
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_env_config_booleans() {
+        // Save original values
+        let orig_icons = std::env::var("GWM_UI_ICONS").ok();
+        let orig_tilde = std::env::var("GWM_UI_TILDE_HOME").ok();
+
+        std::env::set_var("GWM_UI_ICONS", "false");
+        std::env::set_var("GWM_UI_TILDE_HOME", "0");
+
+        let config = load_env_config();
+        assert_eq!(config.ui.icons, Some(false));
+        assert_eq!(config.ui.tilde_home, Some(false));
+
+        // Restore original values
+        match orig_icons {
+            Some(v) => std::env::set_var("GWM_UI_ICONS", v),
+            None => std::env::remove_var("GWM_UI_ICONS"),
+        }
+        match orig_tilde {
+            Some(v) => std::env::set_var("GWM_UI_TILDE_HOME", v),
+            None => std::env::remove_var("GWM_UI_TILDE_HOME"),
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_env_config_show_status() {
+        let original = std::env::var("GWM_WORKTREE_SHOW_STATUS").ok();
+
+        std::env::set_var("GWM_WORKTREE_SHOW_STATUS", "no");
+        let config = load_env_config();
+        assert_eq!(config.worktree.show_status, Some(false));
+        assert!(!config.show_status());
+
+        match original {
+            Some(v) => std::env::set_var("GWM_WORKTREE_SHOW_STATUS", v),
+            None => std::env::remove_var("GWM_WORKTREE_SHOW_STATUS"),
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_env_config_tick_rate_and_animations() {
+        let orig_tick = std::env::var("GWM_UI_TICK_RATE_MS").ok();
+        let orig_anim = std::env::var("GWM_UI_ANIMATIONS").ok();
+
+        std::env::set_var("GWM_UI_TICK_RATE_MS", "500");
+        std::env::set_var("GWM_UI_ANIMATIONS", "false");
+        let config = load_env_config();
+        assert_eq!(config.ui.tick_rate_ms, Some(500));
+        assert_eq!(config.ui.animations, Some(false));
+
+        std::env::set_var("GWM_UI_TICK_RATE_MS", "fast");
+        let config = load_env_config();
+        assert_eq!(config.ui.tick_rate_ms, None);
+
+        match orig_tick {
+            Some(v) => std::env::set_var("GWM_UI_TICK_RATE_MS", v),
+            None => std::env::remove_var("GWM_UI_TICK_RATE_MS"),
+        }
+        match orig_anim {
+            Some(v) => std::env::set_var("GWM_UI_ANIMATIONS", v),
+            None => std::env::remove_var("GWM_UI_ANIMATIONS"),
+        }
+    }
+
+    #[test]
+    fn test_tick_rate_default_and_minimum() {
+        let config = Config::default();
+        assert_eq!(config.tick_rate(), std::time::Duration::from_millis(250));
+        assert!(config.animations());
+
+        let config = Config {
+            ui: UiConfig {
+                tick_rate_ms: Some(0),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(config.tick_rate(), std::time::Duration::from_millis(10));
+    }
+
+    #[test]
+    fn test_default_remote() {
+        let config = Config::default();
+        assert_eq!(config.default_remote(), "origin");
+
+        let config = Config {
+            worktree: WorktreeConfig {
+                default_remote: Some("upstream".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(config.default_remote(), "upstream");
+    }
+
+    #[test]
+    fn test_default_base() {
+        assert_eq!(Config::default().default_base(), None);
+
+        let config = Config {
+            worktree: WorktreeConfig {
+                default_base: Some("trunk".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(config.default_base(), Some("trunk"));
+
+        let config = Config {
+            worktree: WorktreeConfig {
+                default_base: Some(String::new()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(config.default_base(), None);
+    }
+
+    #[test]
+    fn test_validate_default_config() {
+        assert!(Config::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_accepts_known_variables() {
+        let toml_content = r##"
+            [worktree]
+            basedir = "~/worktrees/{branch}"
+
+            [naming]
+            template = "{host}/{owner}/{repository}/{branch}"
+
+            [ui]
+            theme = "classic"
+
+            [ui.colors]
+            header = "#ff0000"
+        "##;
+        let config: Config = toml::from_str(toml_content).unwrap();
+
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_parse_integration_multiplexer() {
+        let config: Config = toml::from_str(
+            r#"
+            [integration]
+            multiplexer = "tmux"
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.multiplexer(), Some(Multiplexer::Tmux));
+        assert!(config.validate().is_ok());
+        assert_eq!(Config::default().multiplexer(), None);
+    }
+
+    #[test]
+    fn test_validate_multiplexer() {
+        let none: Config = toml::from_str("[integration]\nmultiplexer = \"none\"").unwrap();
+        assert!(none.validate().is_ok());
+        assert_eq!(none.multiplexer(), None);
+
+        let unknown: Config = toml::from_str("[integration]\nmultiplexer = \"screen\"").unwrap();
+        let Err(ConfigError::Invalid(errors)) = unknown.validate() else {
+            panic!("Expected Invalid error");
+        };
+        assert_eq!(
+            errors,
+            vec!["integration.multiplexer: unknown multiplexer \"screen\" (expected \"tmux\", \"zellij\" or \"none\")"]
+        );
+    }
+
+    #[test]
+    fn test_validate_collects_all_errors() {
+        let toml_content = r#"
+            [worktree]
+            basedir = "~/worktrees/{brnach}"
+
+            [naming]
+            template = "{owner}-{repo}"
+
+            [ui]
+            theme = "solarized"
+
+            [ui.colors]
+            header = "not-a-color"
+            text = "white"
+
+            [[hooks]]
+            event = "post_create"
+            command = "  "
+        "#;
+        let config: Config = toml::from_str(toml_content).unwrap();
+
+        let Err(ConfigError::Invalid(errors)) = config.validate() else {
+            panic!("Expected Invalid error");
+        };
+        assert_eq!(
+            errors,
+            vec![
+                "worktree.basedir: unknown variable {brnach}",
+                "naming.template: unknown variable {repo}",
+                "ui.theme: unknown theme \"solarized\" (expected \"default\" or \"classic\")",
+                "ui.colors.header: invalid color \"not-a-color\"",
+                "hooks[0] (post_create): command is empty",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_config_error_lists_entries() {
+        let err = ConfigError::Invalid(vec!["a: bad".to_string(), "b: bad".to_string()]);
+
+        assert_eq!(err.to_string(), "Invalid configuration:\na: bad\nb: bad");
+    }
+
+    #[test]
+    fn test_load_config_with_sources_rejects_invalid_config() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        std::fs::write(&path, "[ui]\ntheme = \"nope\"\n").unwrap();
+
+        let result = load_config_with_sources(Some(&path));
+
+        assert!(matches!(result, Err(ConfigError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_editor_command_from_config_with_placeholder() {
+        let config = Config {
+            worktree: WorktreeConfig {
+                editor: Some("code -n {path}".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.editor_command("/tmp/wt"),
+            Some("code -n '/tmp/wt'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_editor_command_from_config_appends_path() {
+        let config = Config {
+            worktree: WorktreeConfig {
+                editor: Some("nvim".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.editor_command("/tmp/wt"),
+            Some("nvim '/tmp/wt'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_substitute_path_quotes_every_placeholder() {
+        assert_eq!(
+            substitute_path("tmux new-window -c {path} -n {path}", "/tmp/my wt"),
+            "tmux new-window -c '/tmp/my wt' -n '/tmp/my wt'"
+        );
+        assert_eq!(substitute_path("zsh -l", "/tmp/wt"), "zsh -l");
+        assert_eq!(
+            substitute_path("cd {path}", "/tmp/it's"),
+            "cd '/tmp/it'\\''s'"
+        );
+    }
+
+    #[test]
+    fn test_shell_command_from_config() {
+        let config = Config {
+            worktree: WorktreeConfig {
+                shell: Some("tmux new-window -c {path}".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.shell_command("/tmp/wt"),
+            Some("tmux new-window -c '/tmp/wt'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_diff_command_default() {
+        assert_eq!(Config::default().diff_command("/tmp/wt"), "git diff HEAD");
+    }
+
+    #[test]
+    fn test_diff_command_from_config() {
+        let config = Config {
+            worktree: WorktreeConfig {
+                diff_command: Some("git -C {path} difftool".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(config.diff_command("/tmp/wt"), "git -C '/tmp/wt' difftool");
+    }
+
+    #[test]
+    fn test_on_open_command_substitutes_path_and_branch() {
+        let config = Config {
+            worktree: WorktreeConfig {
+                on_open: Some("tmux new-window -n {branch} -c {path}".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.on_open_command("/tmp/my wt", Some("feature/it's")),
+            Some("tmux new-window -n 'feature/it'\\''s' -c '/tmp/my wt'".to_string())
+        );
+        // Detached HEAD has no branch
+        assert_eq!(
+            config.on_open_command("/tmp/wt", None),
+            Some("tmux new-window -n '' -c '/tmp/wt'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_on_open_command_defaults_to_none() {
+        assert_eq!(Config::default().on_open_command("/tmp/wt", None), None);
+
+        let config = Config {
+            worktree: WorktreeConfig {
+                on_open: Some(String::new()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(config.on_open_command("/tmp/wt", Some("main")), None);
+    }
+
+    #[test]
+    fn test_prune_excluded_matches_globs() {
+        let config: Config =
+            toml::from_str("[worktree]\nprune_exclude = [\"develop\", \"release/*\", \"keep/**\"]")
+                .unwrap();
+
+        assert!(config.prune_excluded("develop"));
+        assert!(config.prune_excluded("release/1.0"));
+        assert!(config.prune_excluded("keep/a/b"));
+        assert!(!config.prune_excluded("develop-2"));
+        // `*` does not cross a slash
+        assert!(!config.prune_excluded("release/1.0/hotfix"));
+        assert!(!config.prune_excluded("feature/release"));
+        assert!(!Config::default().prune_excluded("develop"));
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_prune_exclude_pattern() {
+        let config: Config = toml::from_str("[worktree]\nprune_exclude = [\"release/[\"]").unwrap();
+
+        let err = config.validate().unwrap_err().to_string();
+
+        assert!(err.contains("worktree.prune_exclude: invalid pattern \"release/[\""));
+    }
+
+    #[test]
+    fn test_shell_command_defaults_to_none() {
+        assert_eq!(Config::default().shell_command("/tmp/wt"), None);
+
+        let config = Config {
+            worktree: WorktreeConfig {
+                shell: Some(String::new()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(config.shell_command("/tmp/wt"), None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_editor_command_env_fallback() {
+        let orig_visual = std::env::var("VISUAL").ok();
+        let orig_editor = std::env::var("EDITOR").ok();
+
+        std::env::remove_var("VISUAL");
+        std::env::set_var("EDITOR", "vi");
+        let config = Config::default();
+        assert_eq!(
+            config.editor_command("/tmp/wt"),
+            Some("vi '/tmp/wt'".to_string())
+        );
+
+        std::env::set_var("VISUAL", "hx");
+        assert_eq!(
+            config.editor_command("/tmp/wt"),
+            Some("hx '/tmp/wt'".to_string())
+        );
+
+        std::env::remove_var("VISUAL");
+        std::env::remove_var("EDITOR");
+        assert_eq!(config.editor_command("/tmp/wt"), None);
+
+        if let Some(v) = orig_visual {
+            std::env::set_var("VISUAL", v);
+        }
+        if let Some(v) = orig_editor {
+            std::env::set_var("EDITOR", v);
+        }
+    }
+
+    #[test]
+    fn test_show_status_default() {
+        let config = Config::default();
+        assert!(config.show_status());
+    }
+
+    #[test]
+    fn test_create_base_values() {
+        assert_eq!(Config::default().create_base(), CreateBase::First);
+        for (value, expected) in [
+            ("main", CreateBase::Main),
+            ("Current", CreateBase::Current),
+            ("first", CreateBase::First),
+        ] {
+            let config: Config =
+                toml::from_str(&format!("[worktree]\ncreate_base = \"{}\"", value)).unwrap();
+            assert_eq!(config.create_base(), expected);
+            assert!(config.validate().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_validate_create_base() {
+        let config: Config = toml::from_str("[worktree]\ncreate_base = \"develop\"").unwrap();
+
+        let Err(ConfigError::Invalid(errors)) = config.validate() else {
+            panic!("expected an invalid config");
+        };
+        assert_eq!(
+            errors,
+            vec!["worktree.create_base: unknown value \"develop\" (expected \"main\", \"current\" or \"first\")"]
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_env_config_warn_stale_base() {
+        let original = std::env::var("GWM_WORKTREE_WARN_STALE_BASE").ok();
+        assert!(!Config::default().warn_stale_base());
+
+        std::env::set_var("GWM_WORKTREE_WARN_STALE_BASE", "1");
+        let config = load_env_config();
+        assert!(config.warn_stale_base());
+
+        match original {
+            Some(v) => std::env::set_var("GWM_WORKTREE_WARN_STALE_BASE", v),
+            None => std::env::remove_var("GWM_WORKTREE_WARN_STALE_BASE"),
+        }
+    }
+
+    #[test]
+    fn test_notification_duration() {
+        assert_eq!(Config::default().notification_duration(), None);
+
+        let config: Config = toml::from_str("[ui]\nnotification_secs = 8").unwrap();
+        assert_eq!(
+            config.notification_duration(),
+            Some(std::time::Duration::from_secs(8))
+        );
+
+        let config: Config = toml::from_str("[ui]\nnotification_secs = 0").unwrap();
+        assert_eq!(
+            config.notification_duration(),
+            Some(std::time::Duration::from_secs(1))
+        );
+
+        let config: Config = toml::from_str("[ui]\nnotification_secs = 86400").unwrap();
+        assert_eq!(
+            config.notification_duration(),
+            Some(std::time::Duration::from_secs(300))
+        );
+    }
+
+    #[test]
+    fn test_confirm_defaults_to_true() {
+        let config = Config::default();
+
+        assert!(config.confirm_delete());
+        assert!(config.confirm_prune());
+        assert!(!config.confirm_show_size());
+    }
+
+    #[test]
+    fn test_confirm_from_toml_and_merge() {
+        let global: Config = toml::from_str("[confirm]\ndelete = false").unwrap();
+        let local: Config = toml::from_str("[confirm]\nprune = false").unwrap();
+
+        let merged = global.merge(local);
+
+        assert!(!merged.confirm_delete());
+        assert!(!merged.confirm_prune());
+    }
+
+    #[test]
+    fn test_stale_days() {
+        assert_eq!(Config::default().stale_days(), None);
 
-        let compressed = config.compress_path(&full_path);
+        let config: Config = toml::from_str("[ui]\nstale_days = 0").unwrap();
+        assert_eq!(config.stale_days(), None);
 
-        assert!(compressed.starts_with("~"));
-        assert_eq!(compressed, "~/projects/test");
+        let config: Config = toml::from_str("[ui]\nstale_days = 14").unwrap();
+        assert_eq!(config.stale_days(), Some(14));
     }
 
     #[test]
-    fn test_compress_path_without_home() {
-        let config = Config::default();
-
-        let compressed = config.compress_path("/var/log/test");
+    fn test_set_title() {
+        assert!(Config::default().set_title());
 
-        assert_eq!(compressed, "/var/log/test");
+        let config: Config = toml::from_str("[ui]\nset_title = false").unwrap();
+        assert!(!config.set_title());
     }
 
     #[test]
-    fn test_format_path_for_display_tilde_home_enabled() {
-        let config = Config::default(); // tilde_home defaults to true
-        let home = dirs::home_dir().unwrap();
-        let full_path = format!("{}/projects/test", home.to_string_lossy());
+    fn test_list_format() {
+        assert_eq!(Config::default().list_format(), DEFAULT_LIST_FORMAT);
 
-        let formatted = config.format_path_for_display(&full_path);
+        let config: Config = toml::from_str("[ui]\nlist_format = \"{name}\\n{path}\"").unwrap();
+        assert_eq!(config.list_format(), "{name}\n{path}");
+        assert!(config.validate().is_ok());
 
-        assert_eq!(formatted, "~/projects/test");
+        let config: Config = toml::from_str("[ui]\nlist_format = \"  \"").unwrap();
+        assert_eq!(config.list_format(), DEFAULT_LIST_FORMAT);
     }
 
     #[test]
-    fn test_format_path_for_display_tilde_home_disabled() {
-        let config = Config {
-            ui: UiConfig {
-                tilde_home: Some(false),
-                ..Default::default()
-            },
-            ..Default::default()
-        };
-        let home = dirs::home_dir().unwrap();
-        let full_path = format!("{}/projects/test", home.to_string_lossy());
+    fn test_compact_list() {
+        assert!(!Config::default().compact_list());
 
-        let formatted = config.format_path_for_display(&full_path);
+        let config: Config = toml::from_str("[ui]\ncompact_list = true").unwrap();
+        assert!(config.compact_list());
+    }
 
-        // Should NOT compress when tilde_home is false
-        assert_eq!(formatted, full_path);
+    #[test]
+    fn test_validate_list_format_unknown_variable() {
+        let config: Config = toml::from_str("[ui]\nlist_format = \"{name} {size}\"").unwrap();
+
+        let Err(ConfigError::Invalid(errors)) = config.validate() else {
+            panic!("expected an invalid config");
+        };
+        assert_eq!(errors, vec!["ui.list_format: unknown variable {size}"]);
     }
 
     #[test]
-    fn test_parse_bool_true_values() {
-        assert_eq!(parse_bool("true"), Some(true));
-        assert_eq!(parse_bool("TRUE"), Some(true));
-        assert_eq!(parse_bool("1"), Some(true));
-        assert_eq!(parse_bool("yes"), Some(true));
-        assert_eq!(parse_bool("YES"), Some(true));
+    fn test_mouse() {
+        assert!(!Config::default().mouse());
+
+        let config: Config = toml::from_str("[ui]\nmouse = true").unwrap();
+        assert!(config.mouse());
     }
 
     #[test]
-    fn test_parse_bool_false_values() {
-        assert_eq!(parse_bool("false"), Some(false));
-        assert_eq!(parse_bool("FALSE"), Some(false));
-        assert_eq!(parse_bool("0"), Some(false));
-        assert_eq!(parse_bool("no"), Some(false));
-        assert_eq!(parse_bool("NO"), Some(false));
+    fn test_recent_commits() {
+        assert_eq!(Config::default().recent_commits(), 5);
+
+        let config: Config = toml::from_str("[ui]\nrecent_commits = 0").unwrap();
+        assert_eq!(config.recent_commits(), 0);
+
+        let config: Config = toml::from_str("[ui]\nrecent_commits = 1000").unwrap();
+        assert_eq!(config.recent_commits(), 50);
     }
 
     #[test]
-    fn test_parse_bool_invalid_values() {
-        assert_eq!(parse_bool(""), None);
-        assert_eq!(parse_bool("invalid"), None);
-        assert_eq!(parse_bool("2"), None);
+    fn test_fetch_on_open() {
+        assert!(!Config::default().fetch_on_open());
+
+        let config: Config = toml::from_str("[worktree]\nfetch_on_open = true").unwrap();
+        assert!(config.fetch_on_open());
     }
 
     #[test]
-    #[serial]
-    fn test_load_env_config_basedir() {
-        // Save original value
-        let original = std::env::var("GWM_WORKTREE_BASEDIR").ok();
+    fn test_open_after_create() {
+        assert!(!Config::default().open_after_create());
 
-        std::env::set_var("GWM_WORKTREE_BASEDIR", "/custom/path");
-        let config = load_env_config();
-        assert_eq!(config.worktree.basedir, Some("/custom/path".to_string()));
+        let config: Config = toml::from_str("[worktree]\nopen_after_create = true").unwrap();
+        assert!(config.open_after_create());
+    }
 
-        // Restore original value
-        match original {
-            Some(v) => std::env::set_var("GWM_WORKTREE_BASEDIR", v),
-            None => std::env::remove_var("GWM_WORKTREE_BASEDIR"),
-        }
+    #[test]
+    fn test_strict_copy_files_default() {
+        let config = Config::default();
+        assert!(!config.strict_copy_files());
     }
 
     #[test]
     #[serial]
-    fn test_load_env_config_booleans() {
-        // Save original values
-        let orig_icons = std::env::var("GWM_UI_ICONS").ok();
-        let orig_tilde = std::env::var("GWM_UI_TILDE_HOME").ok();
-
-        std::env::set_var("GWM_UI_ICONS", "false");
-        std::env::set_var("GWM_UI_TILDE_HOME", "0");
+    fn test_load_env_config_strict_copy_files() {
+        let original = std::env::var("GWM_WORKTREE_STRICT_COPY_FILES").ok();
 
+        std::env::set_var("GWM_WORKTREE_STRICT_COPY_FILES", "yes");
         let config = load_env_config();
-        assert_eq!(config.ui.icons, Some(false));
-        assert_eq!(config.ui.tilde_home, Some(false));
+        assert!(config.strict_copy_files());
 
-        // Restore original values
-        match orig_icons {
-            Some(v) => std::env::set_var("GWM_UI_ICONS", v),
-            None => std::env::remove_var("GWM_UI_ICONS"),
-        }
-        match orig_tilde {
-            Some(v) => std::env::set_var("GWM_UI_TILDE_HOME", v),
-            None => std::env::remove_var("GWM_UI_TILDE_HOME"),
+        match original {
+            Some(v) => std::env::set_var("GWM_WORKTREE_STRICT_COPY_FILES", v),
+            None => std::env::remove_var("GWM_WORKTREE_STRICT_COPY_FILES"),
         }
     }
 
@@ -1025,6 +2536,110 @@ mod tests {
         assert_eq!(result, "simple-branch");
     }
 
+    #[test]
+    fn test_naming_sanitize_spaces() {
+        let naming = NamingConfig::default();
+
+        let result = naming.sanitize("feature/my branch");
+
+        assert_eq!(result, "feature-my-branch");
+    }
+
+    #[test]
+    fn test_worktree_basedir_for_branch_absolute() {
+        let config = Config {
+            worktree: WorktreeConfig {
+                basedir: Some("/worktrees/{branch}".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let result = config.worktree_basedir_for_branch(Path::new("/repo"), "feature/x");
+
+        assert_eq!(result, "/worktrees/feature-x");
+    }
+
+    #[test]
+    fn test_worktree_basedir_for_branch_relative() {
+        let config = Config {
+            worktree: WorktreeConfig {
+                basedir: Some("../wt/{branch}".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let result = config.worktree_basedir_for_branch(Path::new("/home/user/repo"), "feature/x");
+
+        assert_eq!(result, "/home/user/repo/../wt/feature-x");
+    }
+
+    #[test]
+    fn test_worktree_basedir_for_branch_without_placeholder() {
+        let config = Config {
+            worktree: WorktreeConfig {
+                basedir: Some("/worktrees".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let result = config.worktree_basedir_for_branch(Path::new("/repo"), "feature/x");
+
+        assert_eq!(result, "/worktrees");
+    }
+
+    #[test]
+    fn test_worktree_basedir_for_repo_disabled_by_default() {
+        let config = Config {
+            worktree: WorktreeConfig {
+                basedir: Some("/worktrees".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let result = config.worktree_basedir_for_repo(Path::new("/src/api"), "api", "main");
+
+        assert_eq!(result, "/worktrees");
+    }
+
+    #[test]
+    fn test_worktree_basedir_for_repo_separates_repositories() {
+        let config: Config =
+            toml::from_str("[worktree]\nbasedir = \"/worktrees\"\nnamespace_by_repo = true")
+                .unwrap();
+
+        let api = config.worktree_basedir_for_repo(Path::new("/src/api"), "api", "main");
+        let web = config.worktree_basedir_for_repo(Path::new("/src/web"), "web", "main");
+
+        assert_eq!(api, "/worktrees/api");
+        assert_eq!(web, "/worktrees/web");
+    }
+
+    #[test]
+    fn test_worktree_basedir_for_repo_skipped_when_template_names_repository() {
+        let config: Config = toml::from_str(
+            "[worktree]\nbasedir = \"/worktrees\"\nnamespace_by_repo = true\n\n[naming]\ntemplate = \"{repository}-{branch}\"",
+        )
+        .unwrap();
+
+        let result = config.worktree_basedir_for_repo(Path::new("/src/api"), "api", "main");
+
+        assert_eq!(result, "/worktrees");
+    }
+
+    #[test]
+    fn test_generate_worktree_name_flattens_branch() {
+        let config = Config::default();
+
+        let result = config.generate_worktree_name("feature/x", None).unwrap();
+
+        assert_eq!(result, "feature-x");
+        assert!(!result.contains('/'));
+    }
+
     #[test]
     fn test_naming_sanitize_custom_chars() {
         let mut custom_chars = HashMap::new();
@@ -1034,6 +2649,7 @@ mod tests {
         let naming = NamingConfig {
             template: None,
             sanitize_chars: Some(custom_chars),
+            ..Default::default()
         };
 
         let result = naming.sanitize("feature/bug:123");
@@ -1057,6 +2673,7 @@ mod tests {
         let naming = NamingConfig {
             template: Some("wt-{branch}".to_string()),
             sanitize_chars: None,
+            ..Default::default()
         };
 
         let result = naming
@@ -1071,6 +2688,7 @@ mod tests {
         let naming = NamingConfig {
             template: Some("{branch}-dev".to_string()),
             sanitize_chars: None,
+            ..Default::default()
         };
 
         let result = naming.generate_worktree_name("main", None).unwrap();
@@ -1084,6 +2702,7 @@ mod tests {
             naming: NamingConfig {
                 template: Some("worktree-{branch}".to_string()),
                 sanitize_chars: None,
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -1100,6 +2719,7 @@ mod tests {
         let naming = NamingConfig {
             template: Some("{host}/{owner}/{repository}/{branch}".to_string()),
             sanitize_chars: None,
+            ..Default::default()
         };
         let repo_info = RepoInfo {
             host: "github.com".to_string(),
@@ -1121,6 +2741,7 @@ mod tests {
         let naming = NamingConfig {
             template: Some("{owner}-{repository}-{branch}".to_string()),
             sanitize_chars: None,
+            ..Default::default()
         };
         let repo_info = RepoInfo {
             host: "github.com".to_string(),
@@ -1140,6 +2761,7 @@ mod tests {
         let naming = NamingConfig {
             template: Some("{host}/{owner}/{repository}/{branch}".to_string()),
             sanitize_chars: None,
+            ..Default::default()
         };
 
         let result = naming.generate_worktree_name("feature/login", None);
@@ -1156,6 +2778,7 @@ mod tests {
         let naming = NamingConfig {
             template: Some("{owner}-{branch}".to_string()),
             sanitize_chars: None,
+            ..Default::default()
         };
 
         let result = naming.generate_worktree_name("main", None);
@@ -1171,6 +2794,7 @@ mod tests {
         let naming = NamingConfig {
             template: Some("wt-{branch}".to_string()),
             sanitize_chars: None,
+            ..Default::default()
         };
 
         let result = naming.generate_worktree_name("feature/login", None);
@@ -1179,6 +2803,67 @@ mod tests {
         assert_eq!(result.unwrap(), "wt-feature-login");
     }
 
+    #[test]
+    fn test_naming_generate_branch_name_without_template() {
+        let naming = NamingConfig::default();
+
+        let result = naming.generate_branch_name("login", Some("jane")).unwrap();
+
+        assert_eq!(result, "login");
+    }
+
+    #[test]
+    fn test_naming_generate_branch_name_expands_template() {
+        let naming = NamingConfig {
+            branch_template: Some("{user}/{name}".to_string()),
+            ..Default::default()
+        };
+
+        let result = naming
+            .generate_branch_name("login", Some("Jane Doe"))
+            .unwrap();
+
+        assert_eq!(result, "Jane-Doe/login");
+    }
+
+    #[test]
+    fn test_naming_generate_branch_name_uses_sanitize_chars() {
+        let mut sanitize_chars = HashMap::new();
+        sanitize_chars.insert(" ".to_string(), "_".to_string());
+        sanitize_chars.insert("/".to_string(), "_".to_string());
+        let naming = NamingConfig {
+            branch_template: Some("feature/{user}/{name}".to_string()),
+            sanitize_chars: Some(sanitize_chars),
+            ..Default::default()
+        };
+
+        let result = naming.generate_branch_name("x", Some("Jane Doe")).unwrap();
+
+        assert_eq!(result, "feature/Jane_Doe/x");
+    }
+
+    #[test]
+    fn test_naming_generate_branch_name_without_user() {
+        let naming = NamingConfig {
+            branch_template: Some("{user}/{name}".to_string()),
+            ..Default::default()
+        };
+
+        let result = naming.generate_branch_name("login", None);
+
+        assert!(result.unwrap_err().to_string().contains("{user}"));
+    }
+
+    #[test]
+    fn test_validate_unknown_branch_template_variable() {
+        let config: Config =
+            toml::from_str("[naming]\nbranch_template = \"{owner}/{name}\"").unwrap();
+
+        let err = config.validate().unwrap_err().to_string();
+
+        assert!(err.contains("naming.branch_template: unknown variable {owner}"));
+    }
+
     // ========== Global Config Path Tests ==========
 
     #[test]
@@ -1398,9 +3083,8 @@ mod tests {
         assert_eq!(config.ui.tilde_home, Some(true));
 
         // Restore env var
-        match orig_basedir {
-            Some(v) => std::env::set_var("GWM_WORKTREE_BASEDIR", v),
-            None => {}
+        if let Some(v) = orig_basedir {
+            std::env::set_var("GWM_WORKTREE_BASEDIR", v);
         }
 
         // Clean up
@@ -1411,9 +3095,46 @@ mod tests {
     fn test_load_config_from_custom_path_not_found() {
         let result = load_config(Some(Path::new("/nonexistent/path/config.toml")));
         assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert!(matches!(err, ConfigError::NotFound(_)));
+        assert_eq!(
+            err.to_string(),
+            "Config file not found: /nonexistent/path/config.toml"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_config_from_gwm_config_env() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let env_path = temp_dir.path().join("env.toml");
+        let flag_path = temp_dir.path().join("flag.toml");
+        std::fs::write(&env_path, "[worktree]\ndefault_remote = \"env\"").unwrap();
+        std::fs::write(&flag_path, "[worktree]\ndefault_remote = \"flag\"").unwrap();
+        let orig_remote = std::env::var("GWM_WORKTREE_DEFAULT_REMOTE").ok();
+        std::env::remove_var("GWM_WORKTREE_DEFAULT_REMOTE");
+        std::env::set_var("GWM_CONFIG", &env_path);
+
+        let (config, sources) = load_config_with_sources(None).unwrap();
+        assert_eq!(config.default_remote(), "env");
+        assert_eq!(sources.global.path.as_deref(), Some(env_path.as_path()));
+
+        // --config wins over $GWM_CONFIG
+        let config = load_config(Some(&flag_path)).unwrap();
+        assert_eq!(config.default_remote(), "flag");
+
+        std::env::set_var("GWM_CONFIG", temp_dir.path().join("missing.toml"));
+        let result = load_config(None);
+        assert!(matches!(result, Err(ConfigError::NotFound(_))));
+
+        std::env::remove_var("GWM_CONFIG");
+        if let Some(v) = orig_remote {
+            std::env::set_var("GWM_WORKTREE_DEFAULT_REMOTE", v);
+        }
     }
 
     #[test]
+    #[serial]
     fn test_load_config_without_custom_path() {
         // When no custom path is provided, should not error (uses default loading)
         let result = load_config(None);
@@ -1478,6 +3199,67 @@ mod tests {
 
     // ========== Top-level copy_files and setup_commands Tests ==========
 
+    #[test]
+    fn test_setup_commands_with_per_command_shell() {
+        let toml_content = r#"
+            setup_shell = "bash"
+            setup_commands = ["npm install", { command = "bundle install", shell = "zsh" }]
+        "#;
+
+        let config: Config = toml::from_str(toml_content).unwrap();
+
+        assert_eq!(config.setup_shell(), "bash");
+        let commands = config.setup_commands.unwrap();
+        assert_eq!(commands[0], "npm install".into());
+        assert_eq!(commands[1].command(), "bundle install");
+        assert_eq!(commands[1].shell(), Some("zsh"));
+        assert_eq!(commands[1].to_string(), "\"bundle install\" (zsh)");
+    }
+
+    #[test]
+    fn test_setup_shell_default() {
+        assert_eq!(Config::default().setup_shell(), "sh");
+    }
+
+    #[test]
+    fn test_hook_timeout() {
+        let config: Config = toml::from_str(
+            "[[hooks]]\nevent = \"pre_create\"\ncommand = \"a\"\n\
+             [[hooks]]\nevent = \"pre_create\"\ncommand = \"b\"\ntimeout_secs = 5\n\
+             [[hooks]]\nevent = \"pre_create\"\ncommand = \"c\"\ntimeout_secs = 0",
+        )
+        .unwrap();
+        let timeouts: Vec<_> = config.hooks().iter().map(Hook::timeout).collect();
+        assert_eq!(
+            timeouts,
+            vec![
+                Some(std::time::Duration::from_secs(60)),
+                Some(std::time::Duration::from_secs(5)),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_setup_timeout() {
+        assert_eq!(Config::default().setup_timeout(), None);
+
+        let config: Config = toml::from_str(
+            "setup_timeout_secs = 300\nsetup_commands = [{ command = \"make\", timeout_secs = 5 }]",
+        )
+        .unwrap();
+        assert_eq!(
+            config.setup_timeout(),
+            Some(std::time::Duration::from_secs(300))
+        );
+        let commands = config.setup_commands.unwrap();
+        assert_eq!(commands[0].timeout_secs(), Some(5));
+        assert_eq!(commands[0].to_string(), "\"make\" (5s)");
+
+        let config: Config = toml::from_str("setup_timeout_secs = 0").unwrap();
+        assert_eq!(config.setup_timeout(), None);
+    }
+
     #[test]
     fn test_config_with_top_level_copy_files() {
         let toml_content = r#"
@@ -1491,14 +3273,37 @@ mod tests {
             config.copy_files,
             Some(vec![".env".to_string(), ".claude".to_string()])
         );
-        assert_eq!(config.setup_commands, Some(vec!["npm install".to_string()]));
+        assert_eq!(config.setup_commands, Some(vec!["npm install".into()]));
+    }
+
+    #[test]
+    fn test_config_with_symlink_files() {
+        let toml_content = r#"
+            symlink_files = [".env.vault"]
+
+            [[repository_settings]]
+            repository = "~/src/my-project"
+            symlink_files = ["node_modules"]
+        "#;
+
+        let config: Config = toml::from_str(toml_content).unwrap();
+
+        assert_eq!(config.symlink_files, Some(vec![".env.vault".to_string()]));
+        assert_eq!(
+            config.get_effective_settings("/other/repo").symlink_files,
+            Some(vec![".env.vault".to_string()])
+        );
+        assert_eq!(
+            config.repository_settings[0].symlink_files,
+            Some(vec!["node_modules".to_string()])
+        );
     }
 
     #[test]
     fn test_config_merge_top_level_settings() {
         let global = Config {
             copy_files: Some(vec![".env".to_string()]),
-            setup_commands: Some(vec!["make setup".to_string()]),
+            setup_commands: Some(vec!["make setup".into()]),
             ..Default::default()
         };
 
@@ -1512,14 +3317,14 @@ mod tests {
         // Local copy_files overrides global
         assert_eq!(merged.copy_files, Some(vec![".env.local".to_string()]));
         // Global setup_commands is preserved since local doesn't have it
-        assert_eq!(merged.setup_commands, Some(vec!["make setup".to_string()]));
+        assert_eq!(merged.setup_commands, Some(vec!["make setup".into()]));
     }
 
     #[test]
     fn test_config_merge_local_empty_array_disables_global() {
         let global = Config {
             copy_files: Some(vec![".env".to_string(), ".claude".to_string()]),
-            setup_commands: Some(vec!["npm install".to_string()]),
+            setup_commands: Some(vec!["npm install".into()]),
             ..Default::default()
         };
 
@@ -1534,14 +3339,14 @@ mod tests {
         // Local empty array overrides global (disables copy_files)
         assert_eq!(merged.copy_files, Some(vec![]));
         // Global setup_commands is preserved since local doesn't specify it
-        assert_eq!(merged.setup_commands, Some(vec!["npm install".to_string()]));
+        assert_eq!(merged.setup_commands, Some(vec!["npm install".into()]));
     }
 
     #[test]
     fn test_get_effective_settings_with_empty_copy_files() {
         let config = Config {
             copy_files: Some(vec![]),
-            setup_commands: Some(vec!["npm install".to_string()]),
+            setup_commands: Some(vec!["npm install".into()]),
             ..Default::default()
         };
 
@@ -1549,20 +3354,18 @@ mod tests {
 
         // Empty array is valid (explicitly disabled)
         assert_eq!(settings.copy_files, Some(vec![]));
-        assert_eq!(
-            settings.setup_commands,
-            Some(vec!["npm install".to_string()])
-        );
+        assert_eq!(settings.setup_commands, Some(vec!["npm install".into()]));
     }
 
     #[test]
     fn test_get_effective_settings_with_no_match_uses_top_level() {
         let config = Config {
             copy_files: Some(vec![".env".to_string(), ".claude".to_string()]),
-            setup_commands: Some(vec!["npm install".to_string()]),
+            setup_commands: Some(vec!["npm install".into()]),
             repository_settings: vec![RepositorySettings {
                 repository: "other-project".to_string(),
                 copy_files: Some(vec!["other.txt".to_string()]),
+                symlink_files: None,
                 setup_commands: None,
             }],
             ..Default::default()
@@ -1575,21 +3378,19 @@ mod tests {
             settings.copy_files,
             Some(vec![".env".to_string(), ".claude".to_string()])
         );
-        assert_eq!(
-            settings.setup_commands,
-            Some(vec!["npm install".to_string()])
-        );
+        assert_eq!(settings.setup_commands, Some(vec!["npm install".into()]));
     }
 
     #[test]
     fn test_get_effective_settings_with_match_uses_repository_settings() {
         let config = Config {
             copy_files: Some(vec![".env".to_string()]),
-            setup_commands: Some(vec!["npm install".to_string()]),
+            setup_commands: Some(vec!["npm install".into()]),
             repository_settings: vec![RepositorySettings {
                 repository: "my-project".to_string(),
                 copy_files: Some(vec![".env.local".to_string()]),
-                setup_commands: Some(vec!["yarn install".to_string()]),
+                symlink_files: None,
+                setup_commands: Some(vec!["yarn install".into()]),
             }],
             ..Default::default()
         };
@@ -1599,10 +3400,7 @@ mod tests {
 
         // Should use repository_settings, not top-level
         assert_eq!(settings.copy_files, Some(vec![".env.local".to_string()]));
-        assert_eq!(
-            settings.setup_commands,
-            Some(vec!["yarn install".to_string()])
-        );
+        assert_eq!(settings.setup_commands, Some(vec!["yarn install".into()]));
     }
 
     #[test]
@@ -1654,9 +3452,6 @@ mod tests {
                 "secrets.json".to_string()
             ])
         );
-        assert_eq!(
-            settings2.setup_commands,
-            Some(vec!["make setup".to_string()])
-        );
+        assert_eq!(settings2.setup_commands, Some(vec!["make setup".into()]));
     }
 }
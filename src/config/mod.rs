@@ -1,11 +1,21 @@
 mod loader;
 
+pub use loader::expand_env_path;
 pub use loader::load_config_with_sources;
 pub use loader::Config;
 pub use loader::ConfigError;
 pub use loader::ConfigSources;
+pub use loader::CreateBase;
+pub use loader::Hook;
+pub use loader::HookEvent;
 pub use loader::RepositorySettings;
+pub use loader::SetupCommand;
+pub use loader::DEFAULT_LIST_FORMAT;
 
 // Re-export for tests
 #[cfg(test)]
+pub use loader::NamingConfig;
+#[cfg(test)]
 pub use loader::UiConfig;
+#[cfg(test)]
+pub use loader::WorktreeConfig;
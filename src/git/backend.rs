@@ -0,0 +1,184 @@
+use super::worktree::GitError;
+use std::fmt;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::sync::OnceLock;
+
+/// How a git operation is carried out: in-process through libgit2, or by
+/// running the `git` executable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitBackend {
+    Git2,
+    Cli,
+}
+
+impl GitBackend {
+    /// Whether this backend can be used on this machine. libgit2 is linked
+    /// in; the CLI needs `git` on PATH (checked once)
+    pub fn available(self) -> bool {
+        match self {
+            GitBackend::Git2 => true,
+            GitBackend::Cli => git_on_path(),
+        }
+    }
+}
+
+/// Worktree operations whose support differs between libgit2 and the CLI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitOperation {
+    /// `git worktree move`; libgit2 has no equivalent
+    MoveWorktree,
+    /// `git worktree repair`; libgit2 has no equivalent
+    RepairWorktrees,
+    /// `git worktree prune`; libgit2 first, the CLI when it fails
+    PruneStale,
+    /// `git worktree add`, which creates the branch and worktree in one step
+    AddWorktree,
+    /// `git branch -D`
+    DeleteBranch,
+    /// `git fetch`, which uses the user's credential helpers
+    Fetch,
+}
+
+impl GitOperation {
+    /// Backends that can run this operation, most preferred first
+    pub fn backends(self) -> &'static [GitBackend] {
+        match self {
+            GitOperation::MoveWorktree
+            | GitOperation::RepairWorktrees
+            | GitOperation::AddWorktree
+            | GitOperation::DeleteBranch
+            | GitOperation::Fetch => &[GitBackend::Cli],
+            GitOperation::PruneStale => &[GitBackend::Git2, GitBackend::Cli],
+        }
+    }
+
+    /// Pick the first backend of `backends()` that `available` accepts
+    pub fn select(self, available: impl Fn(GitBackend) -> bool) -> Result<GitBackend, GitError> {
+        self.backends()
+            .iter()
+            .copied()
+            .find(|&backend| available(backend))
+            .ok_or(GitError::Unsupported(self))
+    }
+
+    /// Pick a backend available on this machine
+    pub fn backend(self) -> Result<GitBackend, GitError> {
+        self.select(GitBackend::available)
+    }
+}
+
+impl fmt::Display for GitOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            GitOperation::MoveWorktree => "git worktree move",
+            GitOperation::RepairWorktrees => "git worktree repair",
+            GitOperation::PruneStale => "git worktree prune",
+            GitOperation::AddWorktree => "git worktree add",
+            GitOperation::DeleteBranch => "git branch -D",
+            GitOperation::Fetch => "git fetch",
+        })
+    }
+}
+
+fn git_on_path() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        Command::new("git")
+            .arg("--version")
+            .output()
+            .is_ok_and(|output| output.status.success())
+    })
+}
+
+/// Run `git <args>` in `dir`, turning a failed exit into an error that
+/// carries git's message
+pub(crate) fn run_git<I, S>(dir: &Path, args: I) -> Result<Output, GitError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<std::ffi::OsStr>,
+{
+    let output = Command::new("git").args(args).current_dir(dir).output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GitError::Git2(git2::Error::from_str(stderr.trim())));
+    }
+    Ok(output)
+}
+
+/// Count the lines of stdout and stderr that start with `prefix`, which is
+/// how `git worktree repair` and `prune --verbose` report each entry
+pub(crate) fn count_reported(output: &Output, prefix: &str) -> usize {
+    [&output.stdout, &output.stderr]
+        .iter()
+        .map(|out| {
+            String::from_utf8_lossy(out)
+                .lines()
+                .filter(|line| line.starts_with(prefix))
+                .count()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ========== Backend Selection Tests ==========
+
+    #[test]
+    fn test_select_prefers_git2_for_prune() {
+        let backend = GitOperation::PruneStale.select(|_| true).unwrap();
+        assert_eq!(backend, GitBackend::Git2);
+    }
+
+    #[test]
+    fn test_select_falls_back_to_cli() {
+        let backend = GitOperation::PruneStale
+            .select(|b| b == GitBackend::Cli)
+            .unwrap();
+        assert_eq!(backend, GitBackend::Cli);
+    }
+
+    #[test]
+    fn test_select_without_git_on_path_fails_for_move() {
+        let err = GitOperation::MoveWorktree
+            .select(|b| b == GitBackend::Git2)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            GitError::Unsupported(GitOperation::MoveWorktree)
+        ));
+        assert_eq!(
+            err.to_string(),
+            "git worktree move needs the git command, which was not found on PATH"
+        );
+    }
+
+    #[test]
+    fn test_select_without_git_on_path_fails_for_cli_only_operations() {
+        for op in [
+            GitOperation::AddWorktree,
+            GitOperation::DeleteBranch,
+            GitOperation::Fetch,
+        ] {
+            let err = op.select(|b| b == GitBackend::Git2).unwrap_err();
+            assert!(matches!(err, GitError::Unsupported(o) if o == op));
+        }
+    }
+
+    #[test]
+    fn test_git2_backend_is_always_available() {
+        assert!(GitBackend::Git2.available());
+    }
+
+    #[test]
+    fn test_count_reported_reads_both_streams() {
+        let output = Output {
+            status: std::process::ExitStatus::default(),
+            stdout: b"Removing worktrees/a: gitdir file points to non-existent location\n".to_vec(),
+            stderr: b"warning: x\nRemoving worktrees/b: not a valid directory\n".to_vec(),
+        };
+        assert_eq!(count_reported(&output, "Removing "), 2);
+    }
+}
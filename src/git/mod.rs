@@ -1,5 +1,14 @@
+mod backend;
 mod worktree;
 
+pub use backend::GitBackend;
+pub use backend::GitOperation;
+
+pub use worktree::fetch_all;
+pub use worktree::find_branch_checkout;
+pub use worktree::head_commit_id;
+pub use worktree::is_dirty;
+pub use worktree::is_valid_branch_name;
 pub use worktree::Branch;
 pub use worktree::GitError;
 pub use worktree::GitManager;
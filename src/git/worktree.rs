@@ -1,12 +1,17 @@
+use super::backend::{count_reported, run_git, GitBackend, GitOperation};
 use git2::{BranchType, Repository};
+use serde::Serialize;
+use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+/// Skip status checks for worktrees with more index entries than this
+const MAX_STATUS_INDEX_ENTRIES: usize = 50_000;
+
 #[derive(Error, Debug)]
 pub enum GitError {
     #[error("Git error: {0}")]
     Git2(#[from] git2::Error),
-    #[allow(dead_code)]
     #[error("Not a git repository")]
     NotARepository,
     #[error("Failed to get repository path")]
@@ -17,14 +22,67 @@ pub enum GitError {
     WorktreeExists(String),
     #[error("Branch not found: {0}")]
     BranchNotFound(String),
+    #[error("Branch '{0}' is already checked out in {1}")]
+    BranchCheckedOut(String, String),
+    #[error("Cannot resolve '{0}' to a commit")]
+    InvalidRevision(String),
+    #[error("{0} needs the git command, which was not found on PATH")]
+    Unsupported(GitOperation),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct Worktree {
     pub name: String,
     pub path: PathBuf,
     pub branch: Option<String>,
     pub is_main: bool,
+    /// Unix timestamp of the HEAD commit
+    pub last_commit_time: Option<i64>,
+    /// Summary line of the HEAD commit
+    pub commit_message: Option<String>,
+    /// Author name of the HEAD commit
+    pub commit_author: Option<String>,
+    /// Whether the worktree has uncommitted or untracked changes
+    pub is_dirty: bool,
+    /// Whether the worktree is locked (`git worktree lock`)
+    pub is_locked: bool,
+    /// Reason given when the worktree was locked, if any
+    pub lock_reason: Option<String>,
+    /// Unix timestamp the linked worktree was added (None for the main worktree)
+    pub created_at: Option<i64>,
+    /// Abbreviated id of the HEAD commit, which identifies a detached HEAD
+    pub head_id: Option<String>,
+}
+
+/// HEAD commit fields captured for each worktree in the list
+#[derive(Debug, Default)]
+struct HeadCommit {
+    id: Option<String>,
+    time: Option<i64>,
+    message: Option<String>,
+    author: Option<String>,
+}
+
+impl HeadCommit {
+    /// Read the HEAD commit of the worktree at `path` (all None when unavailable)
+    fn read(path: &Path) -> Self {
+        let Ok(repo) = Repository::open(path) else {
+            return Self::default();
+        };
+        let Some(commit) = repo.head().ok().and_then(|h| h.peel_to_commit().ok()) else {
+            return Self::default();
+        };
+        Self::from_commit(&commit)
+    }
+
+    fn from_commit(commit: &git2::Commit) -> Self {
+        Self {
+            id: Some(commit.id().to_string().chars().take(7).collect()),
+            time: Some(commit.time().seconds()),
+            message: commit.summary().map(String::from),
+            author: commit.author().name().map(String::from),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -60,6 +118,8 @@ impl ChangedFilesSummary {
 pub struct WorktreeDetail {
     pub branch: Option<String>,
     pub path: String,
+    /// Commits ahead/behind the upstream branch (None if no upstream)
+    pub ahead_behind: Option<(usize, usize)>,
     pub changed_files: ChangedFilesSummary,
     pub recent_commits: Vec<CommitInfo>,
 }
@@ -157,48 +217,206 @@ impl RepoInfo {
     }
 }
 
+/// Check whether the worktree at `path` has uncommitted or untracked changes.
+/// Opens its own repository, so it is safe to call from a background thread.
+/// Returns false for repositories whose index exceeds `MAX_STATUS_INDEX_ENTRIES`
+pub fn is_dirty(path: &Path) -> bool {
+    let Ok(repo) = Repository::open(path) else {
+        return false;
+    };
+    if repo
+        .index()
+        .map(|i| i.len() > MAX_STATUS_INDEX_ENTRIES)
+        .unwrap_or(false)
+    {
+        return false;
+    }
+
+    let mut opts = git2::StatusOptions::new();
+    opts.include_untracked(true).include_ignored(false);
+    repo.statuses(Some(&mut opts))
+        .map(|s| !s.is_empty())
+        .unwrap_or(false)
+}
+
+/// Unix timestamp the linked worktree `name` was added: the mtime of its admin
+/// `commondir` file, falling back to the worktree directory. Unlike `gitdir`,
+/// `commondir` is written only once, so moving or repairing the worktree
+/// keeps its age
+fn worktree_created_at(common_dir: &Path, name: &str, path: &Path) -> Option<i64> {
+    let admin = common_dir.join("worktrees").join(name).join("commondir");
+    let modified = std::fs::metadata(admin)
+        .or_else(|_| std::fs::metadata(path))
+        .and_then(|m| m.modified())
+        .ok()?;
+    let secs = modified
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_secs();
+    i64::try_from(secs).ok()
+}
+
+/// Whether `name` can be used as a local branch name (`refs/heads/<name>`)
+pub fn is_valid_branch_name(name: &str) -> bool {
+    git2::Reference::is_valid_name(&format!("refs/heads/{}", name))
+}
+
+/// Full hash of the HEAD commit of the worktree at `path`
+pub fn head_commit_id(path: &Path) -> Option<String> {
+    let repo = Repository::open(path).ok()?;
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    Some(commit.id().to_string())
+}
+
+/// Fetch every remote of the repository at `path`, pruning deleted remote
+/// branches. Never prompts for credentials, so it is safe to call from a
+/// background thread while the TUI owns the terminal.
+pub fn fetch_all(path: &Path) -> Result<(), GitError> {
+    GitOperation::Fetch.backend()?;
+    let output = std::process::Command::new("git")
+        .args(["fetch", "--all", "--prune"])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(std::process::Stdio::null())
+        .current_dir(path)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        // The last line carries the reason (e.g. "fatal: unable to access ...")
+        let reason = stderr
+            .lines()
+            .rev()
+            .find(|line| !line.trim().is_empty())
+            .unwrap_or("git fetch failed");
+        return Err(GitError::Git2(git2::Error::from_str(reason.trim())));
+    }
+    Ok(())
+}
+
+/// Find the worktree (main or linked) that has `branch` checked out,
+/// returning its name and path
+pub fn find_branch_checkout(repo: &Repository, branch: &str) -> Option<(String, PathBuf)> {
+    let head_branch = |r: &Repository| {
+        r.head()
+            .ok()
+            .filter(|h| h.is_branch())
+            .and_then(|h| h.shorthand().map(String::from))
+    };
+
+    // Always check the main worktree, even when `repo` was opened from a linked one
+    let main = Repository::open(repo.commondir()).ok()?;
+    if let Some(workdir) = main.workdir() {
+        if head_branch(&main).as_deref() == Some(branch) {
+            let name = workdir
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "main".to_string());
+            return Some((name, workdir.to_path_buf()));
+        }
+    }
+
+    let names = main.worktrees().ok()?;
+    names.iter().flatten().find_map(|name| {
+        let wt = main.find_worktree(name).ok()?;
+        let wt_repo = Repository::open_from_worktree(&wt).ok()?;
+        (head_branch(&wt_repo).as_deref() == Some(branch))
+            .then(|| (name.to_string(), wt.path().to_path_buf()))
+    })
+}
+
 pub struct GitManager {
     repo: Repository,
     repo_root: PathBuf,
+    bare: bool,
 }
 
 impl GitManager {
     pub fn new() -> Result<Self, GitError> {
         let current_dir = std::env::current_dir()?;
-        let repo = Repository::discover(&current_dir)?;
+        Self::from_path(&current_dir)
+    }
+
+    /// Create GitManager for the repository containing `path`
+    pub fn from_path(path: &Path) -> Result<Self, GitError> {
+        let repo = Repository::discover(path).map_err(|e| match e.code() {
+            git2::ErrorCode::NotFound => GitError::NotARepository,
+            _ => GitError::Git2(e),
+        })?;
+        Self::from_repo(repo)
+    }
 
+    fn from_repo(repo: Repository) -> Result<Self, GitError> {
         // Use commondir() to get main repo root even when inside a worktree
         // commondir() returns the path to .git directory (or .git/worktrees/<name> for worktrees)
         // of the main repository, equivalent to `git rev-parse --git-common-dir`
-        let repo_root = repo
-            .commondir()
-            .parent()
-            .ok_or(GitError::PathError)?
-            .to_path_buf();
+        let common_dir: PathBuf = repo.commondir().components().collect();
+
+        // A bare repository has no main worktree; its git directory is the root
+        let bare = Repository::open(&common_dir)
+            .map(|r| r.is_bare())
+            .unwrap_or(false);
+        let repo_root = if bare {
+            common_dir
+        } else {
+            common_dir
+                .parent()
+                .ok_or(GitError::PathError)?
+                .to_path_buf()
+        };
 
-        Ok(Self { repo, repo_root })
+        Ok(Self {
+            repo,
+            repo_root,
+            bare,
+        })
     }
 
-    /// Create GitManager from a specific path (for testing)
-    #[cfg(test)]
-    pub fn from_path(path: &Path) -> Result<Self, GitError> {
-        let repo = Repository::discover(path)?;
-
-        // Use commondir() to get main repo root even when inside a worktree
-        let repo_root = repo
-            .commondir()
-            .parent()
-            .ok_or(GitError::PathError)?
-            .to_path_buf();
-
-        Ok(Self { repo, repo_root })
+    /// Repository name from the root directory. For bare repositories,
+    /// "project.git" and "project/.bare" both give "project".
+    pub fn repo_name(&self) -> String {
+        let name = |p: &Path| {
+            p.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default()
+        };
+        let dir = name(&self.repo_root);
+        if !self.bare {
+            return dir;
+        }
+        match dir.strip_suffix(".git") {
+            Some(stem) if !stem.is_empty() => stem.to_string(),
+            _ if dir.starts_with('.') => self.repo_root.parent().map(name).unwrap_or(dir),
+            _ => dir,
+        }
     }
 
-    #[allow(dead_code)]
     pub fn repo_root(&self) -> &PathBuf {
         &self.repo_root
     }
 
+    /// Check whether the worktree at `path` has unresolved merge conflicts
+    pub fn has_conflicts(&self, path: &Path) -> bool {
+        Repository::open(path)
+            .and_then(|repo| repo.index())
+            .map(|index| index.has_conflicts())
+            .unwrap_or(false)
+    }
+
+    /// Path to the repository's shared git directory (`.git` of the main worktree)
+    pub fn git_dir(&self) -> &Path {
+        self.repo.commondir()
+    }
+
+    /// `user.name` from git config, falling back to `$USER`
+    pub fn user_name(&self) -> Option<String> {
+        self.repo
+            .config()
+            .and_then(|c| c.get_string("user.name"))
+            .ok()
+            .or_else(|| std::env::var("USER").ok())
+            .filter(|name| !name.is_empty())
+    }
+
     /// Get repository info from origin remote URL
     pub fn get_repo_info(&self) -> Option<RepoInfo> {
         // Try to get origin remote
@@ -213,17 +431,29 @@ impl GitManager {
 
         // Add main worktree using repo_root (derived from commondir)
         // This ensures we always get the main repo path even when called from a worktree
-        let branch = self.get_main_worktree_branch()?;
-        worktrees.push(Worktree {
-            name: self
-                .repo_root
-                .file_name()
-                .map(|s| s.to_string_lossy().to_string())
-                .unwrap_or_else(|| "main".to_string()),
-            path: self.repo_root.clone(),
-            branch,
-            is_main: true,
-        });
+        // Bare repositories have no main worktree, only linked ones
+        if !self.bare {
+            let branch = self.get_main_worktree_branch()?;
+            let head = HeadCommit::read(&self.repo_root);
+            worktrees.push(Worktree {
+                name: self
+                    .repo_root
+                    .file_name()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_else(|| "main".to_string()),
+                path: self.repo_root.clone(),
+                branch,
+                is_main: true,
+                last_commit_time: head.time,
+                commit_message: head.message,
+                commit_author: head.author,
+                is_dirty: false,
+                is_locked: false,
+                lock_reason: None,
+                created_at: None,
+                head_id: head.id,
+            });
+        }
 
         // Get linked worktrees
         let worktree_names = self.repo.worktrees()?;
@@ -231,9 +461,24 @@ impl GitManager {
             if let Ok(wt) = self.repo.find_worktree(name) {
                 let path = wt.path().to_path_buf();
                 let branch = self.get_worktree_branch(&path);
+                let head = HeadCommit::read(&path);
+                let lock_reason = match wt.is_locked() {
+                    Ok(git2::WorktreeLockStatus::Locked(reason)) => {
+                        Some(reason.filter(|r| !r.is_empty()))
+                    }
+                    _ => None,
+                };
 
                 worktrees.push(Worktree {
                     name: name.to_string(),
+                    last_commit_time: head.time,
+                    commit_message: head.message,
+                    commit_author: head.author,
+                    is_dirty: false,
+                    is_locked: lock_reason.is_some(),
+                    lock_reason: lock_reason.flatten(),
+                    created_at: worktree_created_at(self.repo.commondir(), name, &path),
+                    head_id: head.id,
                     path,
                     branch,
                     is_main: false,
@@ -344,18 +589,25 @@ impl GitManager {
         let branch = self.repo.find_branch(branch_name, BranchType::Local);
 
         let reference = if let Ok(branch) = branch {
+            // A branch can only be checked out in one worktree at a time
+            if let Some((_, path)) = find_branch_checkout(&self.repo, branch_name) {
+                return Err(GitError::BranchCheckedOut(
+                    branch_name.to_string(),
+                    path.display().to_string(),
+                ));
+            }
             // Use existing local branch
             branch.into_reference()
         } else {
             // Try to find remote branch and create local tracking branch
-            let remote_name = format!("origin/{}", branch_name);
-            if let Ok(remote_branch) = self.repo.find_branch(&remote_name, BranchType::Remote) {
-                let commit = remote_branch.get().peel_to_commit()?;
-                let new_branch = self.repo.branch(branch_name, &commit, false)?;
-                new_branch.into_reference()
-            } else {
+            let Some(remote_name) = self.find_remote_branch(branch_name) else {
                 return Err(GitError::BranchNotFound(branch_name.to_string()));
-            }
+            };
+            let remote_branch = self.repo.find_branch(&remote_name, BranchType::Remote)?;
+            let commit = remote_branch.get().peel_to_commit()?;
+            let mut new_branch = self.repo.branch(branch_name, &commit, false)?;
+            new_branch.set_upstream(Some(&remote_name))?;
+            new_branch.into_reference()
         };
 
         // Create the worktree
@@ -370,11 +622,18 @@ impl GitManager {
             path: worktree_path,
             branch: Some(branch_name.to_string()),
             is_main: false,
+            last_commit_time: None,
+            commit_message: None,
+            commit_author: None,
+            is_dirty: false,
+            is_locked: false,
+            lock_reason: None,
+            created_at: None,
+            head_id: None,
         })
     }
 
     /// Delete a worktree
-    #[allow(dead_code)]
     pub fn delete_worktree(&self, name: &str) -> Result<(), GitError> {
         let wt = self.repo.find_worktree(name)?;
         let path = wt.path().to_path_buf();
@@ -395,21 +654,9 @@ impl GitManager {
     }
 
     /// Delete a local branch (force delete, equivalent to `git branch -D`)
-    #[allow(dead_code)]
     pub fn delete_branch(&self, branch_name: &str) -> Result<(), GitError> {
-        use std::process::Command;
-
-        // Use git command for force delete (-D)
-        let output = Command::new("git")
-            .args(["branch", "-D", branch_name])
-            .current_dir(&self.repo_root)
-            .output()?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(GitError::Git2(git2::Error::from_str(&stderr)));
-        }
-
+        GitOperation::DeleteBranch.backend()?;
+        run_git(&self.repo_root, ["branch", "-D", branch_name])?;
         Ok(())
     }
 
@@ -420,8 +667,7 @@ impl GitManager {
         branch_name: &str,
         base_path: &str,
     ) -> Result<Worktree, GitError> {
-        use std::process::Command;
-
+        GitOperation::AddWorktree.backend()?;
         let worktree_path = self.repo_root.join(base_path).join(name);
 
         if worktree_path.exists() {
@@ -429,36 +675,234 @@ impl GitManager {
         }
 
         // Use git command directly for atomic branch creation + worktree add
-        let output = Command::new("git")
-            .args([
-                "worktree",
-                "add",
-                "-b",
-                branch_name,
-                worktree_path.to_str().unwrap_or(""),
-            ])
-            .current_dir(&self.repo_root)
-            .output()?;
+        run_git(
+            &self.repo_root,
+            [
+                "worktree".as_ref(),
+                "add".as_ref(),
+                "-b".as_ref(),
+                branch_name.as_ref(),
+                worktree_path.as_os_str(),
+            ],
+        )?;
+
+        Ok(Worktree {
+            name: name.to_string(),
+            path: worktree_path,
+            branch: Some(branch_name.to_string()),
+            is_main: false,
+            last_commit_time: None,
+            commit_message: None,
+            commit_author: None,
+            is_dirty: false,
+            is_locked: false,
+            lock_reason: None,
+            created_at: None,
+            head_id: None,
+        })
+    }
+
+    /// Create a worktree at any revision (commit, tag, `origin/x`, ...).
+    /// With `branch_name` a new branch is created there, otherwise HEAD is detached.
+    pub fn create_worktree_from_ref(
+        &self,
+        name: &str,
+        branch_name: Option<&str>,
+        revspec: &str,
+        base_path: &str,
+    ) -> Result<Worktree, GitError> {
+        GitOperation::AddWorktree.backend()?;
+        let worktree_path = self.repo_root.join(base_path).join(name);
+
+        if worktree_path.exists() {
+            return Err(GitError::WorktreeExists(name.to_string()));
+        }
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(GitError::Git2(git2::Error::from_str(&stderr)));
+        let commit = self
+            .repo
+            .revparse_single(revspec)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(|_| GitError::InvalidRevision(revspec.to_string()))?;
+        let commit_id = commit.id().to_string();
+        let head = HeadCommit::from_commit(&commit);
+
+        let mut args: Vec<&OsStr> = vec!["worktree".as_ref(), "add".as_ref()];
+        match branch_name {
+            Some(branch) => args.extend([OsStr::new("-b"), branch.as_ref()]),
+            None => args.push("--detach".as_ref()),
         }
+        args.extend([worktree_path.as_os_str(), commit_id.as_ref()]);
+        run_git(&self.repo_root, args)?;
 
         Ok(Worktree {
             name: name.to_string(),
             path: worktree_path,
-            branch: Some(branch_name.to_string()),
+            branch: branch_name.map(String::from),
             is_main: false,
+            last_commit_time: head.time,
+            commit_message: head.message,
+            commit_author: head.author,
+            is_dirty: false,
+            is_locked: false,
+            lock_reason: None,
+            created_at: None,
+            head_id: head.id,
+        })
+    }
+
+    /// Move a linked worktree to a new location (equivalent to `git worktree move`)
+    pub fn move_worktree(&self, old_path: &Path, new_path: &Path) -> Result<(), GitError> {
+        GitOperation::MoveWorktree.backend()?;
+        if new_path.exists() {
+            return Err(GitError::WorktreeExists(new_path.display().to_string()));
+        }
+
+        run_git(
+            &self.repo_root,
+            [
+                "worktree".as_ref(),
+                "move".as_ref(),
+                old_path.as_os_str(),
+                new_path.as_os_str(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Fix stale links between the repository and its worktrees after directories
+    /// were moved by hand (equivalent to `git worktree repair`).
+    /// Returns the number of repaired entries
+    pub fn repair_worktrees(&self) -> Result<usize, GitError> {
+        GitOperation::RepairWorktrees.backend()?;
+        let output = run_git(&self.repo_root, ["worktree", "repair"])?;
+        // git reports one "repair: ..." line per fixed entry
+        Ok(count_reported(&output, "repair:"))
+    }
+
+    /// Remove administrative entries of worktrees whose directories were deleted
+    /// by hand (equivalent to `git worktree prune`). Locked entries are kept.
+    /// Returns the number of pruned entries
+    pub fn prune_stale(&self) -> Result<usize, GitError> {
+        if GitOperation::PruneStale.backend()? == GitBackend::Cli {
+            return self.prune_stale_cli();
+        }
+        let mut pruned = 0;
+        match self.prune_stale_git2(&mut pruned) {
+            Ok(()) => Ok(pruned),
+            // Entries libgit2 can't handle (e.g. a damaged gitdir file) are left
+            // to git, which only reports the ones libgit2 didn't get to
+            Err(_) if GitBackend::Cli.available() => Ok(pruned + self.prune_stale_cli()?),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Prune stale entries through libgit2, counting each in `pruned` so a
+    /// failure part way keeps the count of those already removed
+    fn prune_stale_git2(&self, pruned: &mut usize) -> Result<(), GitError> {
+        for name in self.stale_entries()? {
+            self.repo.find_worktree(&name)?.prune(None)?;
+            *pruned += 1;
+        }
+        Ok(())
+    }
+
+    fn prune_stale_cli(&self) -> Result<usize, GitError> {
+        let output = run_git(&self.repo_root, ["worktree", "prune", "--verbose"])?;
+        Ok(count_reported(&output, "Removing "))
+    }
+
+    /// Names of the entries `prune_stale` would remove
+    pub fn stale_entries(&self) -> Result<Vec<String>, GitError> {
+        let mut stale = Vec::new();
+        for name in self.repo.worktrees()?.iter().flatten() {
+            let wt = self.repo.find_worktree(name)?;
+            if !wt.path().exists() && wt.is_prunable(None)? {
+                stale.push(name.to_string());
+            }
+        }
+        Ok(stale)
+    }
+
+    /// Whether `path` belongs to a linked worktree whose directory was deleted
+    /// outside git, leaving an entry for `prune_stale` to clean up
+    pub fn worktree_dir_missing(&self, path: &Path) -> bool {
+        if path.exists() {
+            return false;
+        }
+        let Ok(names) = self.repo.worktrees() else {
+            return false;
+        };
+        names.iter().flatten().any(|name| {
+            self.repo
+                .find_worktree(name)
+                .is_ok_and(|wt| wt.path() == path)
         })
     }
 
+    /// Prune only the entry of the worktree at `path` if its directory is
+    /// gone. Returns false when the entry is locked and was kept
+    pub fn prune_entry(&self, path: &Path) -> Result<bool, GitError> {
+        for name in self.repo.worktrees()?.iter().flatten() {
+            let wt = self.repo.find_worktree(name)?;
+            if wt.path() != path || path.exists() {
+                continue;
+            }
+            if !wt.is_prunable(None)? {
+                return Ok(false);
+            }
+            wt.prune(None)?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Lock a linked worktree, optionally recording why
+    pub fn lock_worktree(&self, name: &str, reason: Option<&str>) -> Result<(), GitError> {
+        self.repo.find_worktree(name)?.lock(reason)?;
+        Ok(())
+    }
+
+    /// Unlock a linked worktree
+    pub fn unlock_worktree(&self, name: &str) -> Result<(), GitError> {
+        self.repo.find_worktree(name)?.unlock()?;
+        Ok(())
+    }
+
+    /// Stash uncommitted and untracked changes of the worktree at `path`,
+    /// returning the stash commit id. Stashes live in the shared `refs/stash`,
+    /// so they survive deleting the worktree
+    pub fn stash_worktree(&self, path: &Path) -> Result<String, GitError> {
+        let mut repo = Repository::open(path)?;
+        let signature = repo.signature()?;
+        let message = format!("gwm: changes from {}", path.display());
+        let oid = repo.stash_save(
+            &signature,
+            &message,
+            Some(git2::StashFlags::INCLUDE_UNTRACKED),
+        )?;
+        Ok(oid.to_string())
+    }
+
+    /// Find a remote-tracking branch named `<remote>/<branch_name>`, preferring origin
+    fn find_remote_branch(&self, branch_name: &str) -> Option<String> {
+        let remotes = self.repo.remotes().ok()?;
+        let mut names: Vec<&str> = remotes.iter().flatten().collect();
+        names.sort_by_key(|name| *name != "origin");
+
+        names
+            .into_iter()
+            .map(|remote| format!("{}/{}", remote, branch_name))
+            .find(|name| self.repo.find_branch(name, BranchType::Remote).is_ok())
+    }
+
     /// Get the default branch name (usually main or master)
-    pub fn get_default_branch(&self) -> Result<String, GitError> {
-        // Try to find origin/HEAD
-        if let Ok(reference) = self.repo.find_reference("refs/remotes/origin/HEAD") {
+    pub fn get_default_branch(&self, remote: &str) -> Result<String, GitError> {
+        // Try to find <remote>/HEAD
+        let head_ref = format!("refs/remotes/{}/HEAD", remote);
+        let prefix = format!("refs/remotes/{}/", remote);
+        if let Ok(reference) = self.repo.find_reference(&head_ref) {
             if let Some(target) = reference.symbolic_target() {
-                if let Some(branch) = target.strip_prefix("refs/remotes/origin/") {
+                if let Some(branch) = target.strip_prefix(prefix.as_str()) {
                     return Ok(branch.to_string());
                 }
             }
@@ -477,31 +921,31 @@ impl GitManager {
             .ok_or_else(|| GitError::BranchNotFound("default".to_string()))
     }
 
-    /// Find merged branches (branches that are fully merged into the default branch)
-    pub fn find_merged_branches(&self) -> Result<Vec<String>, GitError> {
-        let default_branch = self.get_default_branch()?;
-        let default_commit = self
+    /// Find local branches that are fully merged into `base`
+    /// `base` can be any revision, e.g. "main" or "origin/main"
+    pub fn find_merged_branches(&self, base: &str) -> Result<Vec<String>, GitError> {
+        let base_commit = self
             .repo
-            .find_branch(&default_branch, BranchType::Local)?
-            .get()
-            .peel_to_commit()?;
+            .revparse_single(base)
+            .and_then(|obj| obj.peel_to_commit())
+            .map_err(|_| GitError::BranchNotFound(base.to_string()))?;
 
         let mut merged = Vec::new();
 
         for branch_result in self.repo.branches(Some(BranchType::Local))? {
             let (branch, _) = branch_result?;
             if let Some(name) = branch.name()? {
-                // Skip the default branch itself
-                if name == default_branch {
+                // Skip the base branch itself
+                if name == base {
                     continue;
                 }
 
                 let branch_commit = branch.get().peel_to_commit()?;
 
-                // Check if branch is an ancestor of default (i.e., merged)
+                // Check if branch is an ancestor of base (i.e., merged)
                 if self
                     .repo
-                    .graph_descendant_of(default_commit.id(), branch_commit.id())?
+                    .graph_descendant_of(base_commit.id(), branch_commit.id())?
                 {
                     merged.push(name.to_string());
                 }
@@ -511,9 +955,9 @@ impl GitManager {
         Ok(merged)
     }
 
-    /// Find worktrees with merged branches
-    pub fn find_merged_worktrees(&self) -> Result<Vec<Worktree>, GitError> {
-        let merged_branches = self.find_merged_branches()?;
+    /// Find worktrees whose branches are merged into `base`
+    pub fn find_merged_worktrees(&self, base: &str) -> Result<Vec<Worktree>, GitError> {
+        let merged_branches = self.find_merged_branches(base)?;
         let worktrees = self.list_worktrees()?;
 
         Ok(worktrees
@@ -530,18 +974,52 @@ impl GitManager {
     }
 
     /// Get detailed information for a worktree
-    pub fn get_worktree_details(&self, worktree: &Worktree) -> WorktreeDetail {
+    pub fn get_worktree_details(
+        &self,
+        worktree: &Worktree,
+        recent_commit_limit: usize,
+    ) -> WorktreeDetail {
         let changed_files = self.get_changed_files(&worktree.path);
-        let recent_commits = self.get_recent_commits(worktree);
+        let recent_commits = self.get_recent_commits(worktree, recent_commit_limit);
 
         WorktreeDetail {
             branch: worktree.branch.clone(),
             path: worktree.path.to_string_lossy().to_string(),
+            ahead_behind: self.get_ahead_behind(&worktree.path),
             changed_files,
             recent_commits,
         }
     }
 
+    /// Get commits ahead/behind the upstream of the branch checked out at `path`
+    /// Returns None for detached HEAD or branches without an upstream
+    fn get_ahead_behind(&self, path: &Path) -> Option<(usize, usize)> {
+        let repo = Repository::open(path).ok()?;
+        let head = repo.head().ok()?;
+        if !head.is_branch() {
+            return None;
+        }
+
+        let local_oid = head.target()?;
+        let branch = git2::Branch::wrap(head);
+        let upstream_oid = branch.upstream().ok()?.get().target()?;
+
+        repo.graph_ahead_behind(local_oid, upstream_oid).ok()
+    }
+
+    /// Commits the local `branch` is behind its upstream, with the upstream's name.
+    /// Returns None when the branch has no upstream or is up to date.
+    pub fn branch_behind_upstream(&self, branch: &str) -> Option<(usize, String)> {
+        let local = self.repo.find_branch(branch, BranchType::Local).ok()?;
+        let upstream = local.upstream().ok()?;
+        let upstream_name = upstream.name().ok()??.to_string();
+        let (_, behind) = self
+            .repo
+            .graph_ahead_behind(local.get().target()?, upstream.get().target()?)
+            .ok()?;
+        (behind > 0).then_some((behind, upstream_name))
+    }
+
     /// Get changed files summary in a worktree
     fn get_changed_files(&self, path: &Path) -> ChangedFilesSummary {
         // Try to open the repository at the worktree path
@@ -552,8 +1030,14 @@ impl GitManager {
 
         let mut summary = ChangedFilesSummary::default();
 
-        // Get status
-        if let Ok(statuses) = repo.statuses(None) {
+        // Get status, skipping ignored files (the default options include them,
+        // so build output would keep every worktree from showing as clean).
+        // Count each file in an untracked directory, not the directory once
+        let mut opts = git2::StatusOptions::new();
+        opts.include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .include_ignored(false);
+        if let Ok(statuses) = repo.statuses(Some(&mut opts)) {
             for entry in statuses.iter() {
                 let status = entry.status();
                 if status.is_wt_new() || status.is_index_new() {
@@ -570,7 +1054,11 @@ impl GitManager {
     }
 
     /// Get recent commits for a worktree
-    fn get_recent_commits(&self, worktree: &Worktree) -> Vec<CommitInfo> {
+    fn get_recent_commits(&self, worktree: &Worktree, limit: usize) -> Vec<CommitInfo> {
+        if limit == 0 {
+            return Vec::new();
+        }
+
         // Try to open the repository at the worktree path
         let repo = match Repository::open(&worktree.path) {
             Ok(r) => r,
@@ -600,36 +1088,30 @@ impl GitManager {
             return Vec::new();
         }
 
-        for (i, oid_result) in revwalk.enumerate() {
-            if i >= 5 {
-                break; // Limit to 5 commits
-            }
-
-            if let Ok(oid) = oid_result {
-                if let Ok(commit) = repo.find_commit(oid) {
-                    let short_id = commit
-                        .as_object()
-                        .short_id()
-                        .map(|b| b.as_str().unwrap_or("").to_string())
-                        .unwrap_or_else(|_| format!("{:.7}", oid));
-
-                    let message = commit
-                        .summary()
-                        .unwrap_or("")
-                        .chars()
-                        .take(50)
-                        .collect::<String>();
-
-                    let author = commit.author().name().unwrap_or("").to_string();
-                    let is_merge = commit.parent_count() > 1;
-
-                    commits.push(CommitInfo {
-                        short_id,
-                        message,
-                        author,
-                        is_merge,
-                    });
-                }
+        for oid in revwalk.take(limit).flatten() {
+            if let Ok(commit) = repo.find_commit(oid) {
+                let short_id = commit
+                    .as_object()
+                    .short_id()
+                    .map(|b| b.as_str().unwrap_or("").to_string())
+                    .unwrap_or_else(|_| format!("{:.7}", oid));
+
+                let message = commit
+                    .summary()
+                    .unwrap_or("")
+                    .chars()
+                    .take(50)
+                    .collect::<String>();
+
+                let author = commit.author().name().unwrap_or("").to_string();
+                let is_merge = commit.parent_count() > 1;
+
+                commits.push(CommitInfo {
+                    short_id,
+                    message,
+                    author,
+                    is_merge,
+                });
             }
         }
 
@@ -808,8 +1290,6 @@ mod tests {
                     .unwrap();
             }
 
-            // Change to repo directory and create GitManager
-            std::env::set_current_dir(repo_path).unwrap();
             let git_manager = GitManager::from_path(repo_path).unwrap();
 
             (self.temp_dir, git_manager)
@@ -821,6 +1301,15 @@ mod tests {
         TestRepoBuilder::new().build()
     }
 
+    #[test]
+    fn test_from_path_outside_repository() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let result = GitManager::from_path(temp_dir.path());
+
+        assert!(matches!(result, Err(GitError::NotARepository)));
+    }
+
     #[test]
     fn test_list_worktrees_returns_main() {
         let (_temp_dir, git) = setup_test_repo();
@@ -831,6 +1320,58 @@ mod tests {
         assert!(worktrees.iter().any(|w| w.is_main));
     }
 
+    #[test]
+    fn test_list_worktrees_captures_head_commit() {
+        let (_temp_dir, git) = setup_test_repo();
+
+        let worktrees = git.list_worktrees().unwrap();
+
+        let main = worktrees.iter().find(|w| w.is_main).unwrap();
+        assert_eq!(main.commit_message.as_deref(), Some("Initial commit"));
+        assert_eq!(main.commit_author.as_deref(), Some("Test User"));
+        assert!(main.last_commit_time.is_some());
+    }
+
+    #[test]
+    fn test_get_worktree_details_limits_recent_commits() {
+        let (_temp_dir, git) = TestRepoBuilder::new()
+            .with_commit("Second commit")
+            .with_commit("Third commit")
+            .build();
+        let main = git.list_worktrees().unwrap().remove(0);
+
+        let detail = git.get_worktree_details(&main, 2);
+        let messages: Vec<_> = detail
+            .recent_commits
+            .iter()
+            .map(|c| c.message.as_str())
+            .collect();
+        assert_eq!(messages, vec!["Third commit", "Second commit"]);
+
+        assert_eq!(git.get_worktree_details(&main, 10).recent_commits.len(), 3);
+        assert!(git.get_worktree_details(&main, 0).recent_commits.is_empty());
+    }
+
+    #[test]
+    fn test_is_valid_branch_name() {
+        assert!(is_valid_branch_name("feature/login"));
+        assert!(is_valid_branch_name("fix-123"));
+
+        assert!(!is_valid_branch_name("foo..bar"));
+        assert!(!is_valid_branch_name("foo/"));
+        assert!(!is_valid_branch_name("my branch"));
+        assert!(!is_valid_branch_name("ends.lock"));
+        assert!(!is_valid_branch_name("dot."));
+        assert!(!is_valid_branch_name("tab\tname"));
+    }
+
+    #[test]
+    fn test_user_name_from_git_config() {
+        let (_temp_dir, git) = setup_test_repo();
+
+        assert_eq!(git.user_name().as_deref(), Some("Test User"));
+    }
+
     #[test]
     fn test_list_branches() {
         let (_temp_dir, git) = setup_test_repo();
@@ -864,6 +1405,31 @@ mod tests {
         assert!(!worktree.is_main);
     }
 
+    #[test]
+    fn test_create_worktree_from_non_origin_remote_sets_upstream() {
+        let (temp_dir, git) = setup_test_repo();
+        let repo_path = temp_dir.path();
+
+        for args in [
+            vec!["remote", "add", "upstream", "/nonexistent/upstream.git"],
+            vec!["update-ref", "refs/remotes/upstream/feature", "HEAD"],
+        ] {
+            Command::new("git")
+                .args(&args)
+                .current_dir(repo_path)
+                .output()
+                .unwrap();
+        }
+
+        let result = git.create_worktree("feature-wt", "feature", ".");
+        assert!(result.is_ok());
+
+        let repo = Repository::open(repo_path).unwrap();
+        let local = repo.find_branch("feature", BranchType::Local).unwrap();
+        let upstream = local.upstream().unwrap();
+        assert_eq!(upstream.name().unwrap(), Some("upstream/feature"));
+    }
+
     #[test]
     fn test_create_worktree_with_new_branch() {
         let (_temp_dir, git) = setup_test_repo();
@@ -900,6 +1466,71 @@ mod tests {
         assert!(result.is_err());
     }
 
+    /// Repository with an older commit tagged `v1` behind HEAD
+    fn setup_tagged_repo() -> (TempDir, GitManager, String) {
+        let (temp_dir, git) = TestRepoBuilder::new().build();
+        let run = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(temp_dir.path())
+                .output()
+                .unwrap()
+        };
+        run(&["tag", "v1"]);
+        let old = String::from_utf8(run(&["rev-parse", "HEAD"]).stdout).unwrap();
+        run(&["commit", "--allow-empty", "-m", "Newer commit"]);
+        (temp_dir, git, old.trim().to_string())
+    }
+
+    fn head_of(path: &Path) -> String {
+        let repo = Repository::open(path).unwrap();
+        let id = repo.head().unwrap().peel_to_commit().unwrap().id();
+        id.to_string()
+    }
+
+    #[test]
+    fn test_create_worktree_from_ref_detached_at_old_commit() {
+        let (_temp_dir, git, old) = setup_tagged_repo();
+
+        let wt = git
+            .create_worktree_from_ref("pinned", None, &old[..7], ".")
+            .unwrap();
+
+        assert_eq!(wt.branch, None);
+        assert_eq!(head_of(&wt.path), old);
+        assert!(Repository::open(&wt.path).unwrap().head_detached().unwrap());
+        let listed = git.list_worktrees().unwrap();
+        assert!(listed.iter().any(|w| w.name == "pinned"
+            && w.branch.is_none()
+            && w.head_id.as_deref() == Some(&old[..7])));
+    }
+
+    #[test]
+    fn test_create_worktree_from_tag_with_new_branch() {
+        let (_temp_dir, git, old) = setup_tagged_repo();
+
+        let wt = git
+            .create_worktree_from_ref("hotfix", Some("hotfix/v1"), "v1", ".")
+            .unwrap();
+
+        assert_eq!(wt.branch.as_deref(), Some("hotfix/v1"));
+        assert_eq!(head_of(&wt.path), old);
+        assert!(git
+            .list_branches()
+            .unwrap()
+            .iter()
+            .any(|b| b.name == "hotfix/v1"));
+    }
+
+    #[test]
+    fn test_create_worktree_from_invalid_ref() {
+        let (_temp_dir, git, _) = setup_tagged_repo();
+
+        let result = git.create_worktree_from_ref("bad", None, "no-such-ref", ".");
+
+        assert!(matches!(result, Err(GitError::InvalidRevision(r)) if r == "no-such-ref"));
+    }
+
     #[test]
     fn test_delete_worktree() {
         let (temp_dir, git) = setup_test_repo();
@@ -924,54 +1555,578 @@ mod tests {
     }
 
     #[test]
-    fn test_delete_branch() {
+    fn test_lock_and_unlock_worktree() {
         let (temp_dir, git) = setup_test_repo();
-        let repo_path = temp_dir.path();
-
-        // Create a branch
         Command::new("git")
-            .args(["branch", "branch-to-delete"])
-            .current_dir(repo_path)
+            .args(["branch", "lock-test"])
+            .current_dir(temp_dir.path())
             .output()
             .unwrap();
+        git.create_worktree("lock-wt", "lock-test", ".").unwrap();
 
-        // Delete the branch
-        let result = git.delete_branch("branch-to-delete");
-
-        assert!(result.is_ok());
+        git.lock_worktree("lock-wt", Some("on a USB drive"))
+            .unwrap();
+        let worktrees = git.list_worktrees().unwrap();
+        let wt = worktrees.iter().find(|w| w.name == "lock-wt").unwrap();
+        assert!(wt.is_locked);
+        assert_eq!(wt.lock_reason.as_deref(), Some("on a USB drive"));
 
-        // Verify branch is gone
-        let branches = git.list_branches().unwrap();
-        assert!(!branches.iter().any(|b| b.name == "branch-to-delete"));
+        git.unlock_worktree("lock-wt").unwrap();
+        let worktrees = git.list_worktrees().unwrap();
+        let wt = worktrees.iter().find(|w| w.name == "lock-wt").unwrap();
+        assert!(!wt.is_locked);
+        assert_eq!(wt.lock_reason, None);
     }
 
     #[test]
-    fn test_delete_branch_not_found() {
-        let (_temp_dir, git) = setup_test_repo();
+    fn test_lock_worktree_without_reason() {
+        let (temp_dir, git) = setup_test_repo();
+        Command::new("git")
+            .args(["branch", "lock-test"])
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap();
+        git.create_worktree("lock-wt", "lock-test", ".").unwrap();
 
-        let result = git.delete_branch("nonexistent-branch");
+        git.lock_worktree("lock-wt", None).unwrap();
 
-        assert!(result.is_err());
+        let worktrees = git.list_worktrees().unwrap();
+        let wt = worktrees.iter().find(|w| w.name == "lock-wt").unwrap();
+        assert!(wt.is_locked);
+        assert_eq!(wt.lock_reason, None);
     }
 
     #[test]
-    fn test_changed_files_summary_is_empty() {
-        let summary = ChangedFilesSummary::default();
-        assert!(summary.is_empty());
+    fn test_list_worktrees_records_created_at() {
+        let (temp_dir, git) = setup_test_repo();
+        Command::new("git")
+            .args(["branch", "age-test"])
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap();
+        git.create_worktree("age-wt", "age-test", ".").unwrap();
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
 
-        let summary_with_added = ChangedFilesSummary {
-            added: 1,
-            deleted: 0,
-            modified: 0,
-        };
+        let worktrees = git.list_worktrees().unwrap();
+
+        let main = worktrees.iter().find(|w| w.is_main).unwrap();
+        assert_eq!(main.created_at, None);
+        let linked = worktrees.iter().find(|w| w.name == "age-wt").unwrap();
+        let created_at = linked.created_at.unwrap();
+        assert!((now - created_at).abs() < 60);
+    }
+
+    #[test]
+    fn test_created_at_survives_move() {
+        let (temp_dir, git) = setup_test_repo();
+        Command::new("git")
+            .args(["branch", "age-test"])
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap();
+        let wt = git.create_worktree("age-wt", "age-test", ".").unwrap();
+        // Backdate the worktree so a rewrite of its admin files would show
+        let added = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000_000);
+        let admin = temp_dir.path().join(".git/worktrees/age-wt");
+        for file in ["commondir", "gitdir"] {
+            std::fs::File::options()
+                .write(true)
+                .open(admin.join(file))
+                .unwrap()
+                .set_modified(added)
+                .unwrap();
+        }
+
+        git.move_worktree(&wt.path, &temp_dir.path().join("moved-wt"))
+            .unwrap();
+        git.repair_worktrees().unwrap();
+
+        let worktrees = git.list_worktrees().unwrap();
+        let linked = worktrees.iter().find(|w| w.name == "age-wt").unwrap();
+        assert_eq!(linked.created_at, Some(1_000_000_000));
+    }
+
+    #[test]
+    fn test_lock_worktree_not_found() {
+        let (_temp_dir, git) = setup_test_repo();
+
+        assert!(git.lock_worktree("missing", None).is_err());
+        assert!(git.unlock_worktree("missing").is_err());
+    }
+
+    #[test]
+    fn test_stash_worktree_saves_changes() {
+        let (temp_dir, git) = setup_test_repo();
+        Command::new("git")
+            .args(["branch", "stash-test"])
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap();
+        let wt = git.create_worktree("stash-wt", "stash-test", ".").unwrap();
+        std::fs::write(wt.path.join("README.md"), "changed").unwrap();
+        std::fs::write(wt.path.join("new.txt"), "untracked").unwrap();
+        assert!(is_dirty(&wt.path));
+
+        let oid = git.stash_worktree(&wt.path).unwrap();
+
+        assert!(!is_dirty(&wt.path));
+        let stash = git.repo.refname_to_id("refs/stash").unwrap();
+        assert_eq!(stash.to_string(), oid);
+    }
+
+    #[test]
+    fn test_stash_worktree_clean_fails() {
+        let (_temp_dir, git) = setup_test_repo();
+        let root = git.repo_root().clone();
+
+        assert!(git.stash_worktree(&root).is_err());
+    }
+
+    #[test]
+    fn test_prune_stale_removes_missing_worktrees() {
+        let (temp_dir, git) = setup_test_repo();
+        for name in ["gone", "kept", "locked"] {
+            Command::new("git")
+                .args(["branch", name])
+                .current_dir(temp_dir.path())
+                .output()
+                .unwrap();
+            git.create_worktree(&format!("{}-wt", name), name, ".")
+                .unwrap();
+        }
+        git.lock_worktree("locked-wt", None).unwrap();
+        let worktrees = git.list_worktrees().unwrap();
+        for name in ["gone-wt", "locked-wt"] {
+            let wt = worktrees.iter().find(|w| w.name == name).unwrap();
+            std::fs::remove_dir_all(&wt.path).unwrap();
+        }
+
+        assert_eq!(git.prune_stale().unwrap(), 1);
+
+        let names: Vec<String> = git
+            .list_worktrees()
+            .unwrap()
+            .into_iter()
+            .map(|w| w.name)
+            .collect();
+        assert!(!names.contains(&"gone-wt".to_string()));
+        assert!(names.contains(&"kept-wt".to_string()));
+        assert!(names.contains(&"locked-wt".to_string()));
+        assert_eq!(git.prune_stale().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_prune_stale_cli_matches_git2() {
+        let (temp_dir, git) = setup_test_repo();
+        for name in ["gone", "locked"] {
+            Command::new("git")
+                .args(["branch", name])
+                .current_dir(temp_dir.path())
+                .output()
+                .unwrap();
+            git.create_worktree(&format!("{}-wt", name), name, ".")
+                .unwrap();
+        }
+        git.lock_worktree("locked-wt", None).unwrap();
+        for wt in git.list_worktrees().unwrap().iter().filter(|w| !w.is_main) {
+            std::fs::remove_dir_all(&wt.path).unwrap();
+        }
+
+        assert_eq!(git.prune_stale_cli().unwrap(), 1);
+
+        let names: Vec<String> = git
+            .list_worktrees()
+            .unwrap()
+            .into_iter()
+            .map(|w| w.name)
+            .collect();
+        assert_eq!(names.iter().filter(|n| n.ends_with("-wt")).count(), 1);
+        assert!(names.contains(&"locked-wt".to_string()));
+    }
+
+    #[test]
+    fn test_worktree_dir_missing() {
+        let (temp_dir, git) = setup_test_repo();
+        Command::new("git")
+            .args(["branch", "gone"])
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap();
+        git.create_worktree("gone-wt", "gone", ".").unwrap();
+        let wt_path = git
+            .list_worktrees()
+            .unwrap()
+            .into_iter()
+            .find(|w| w.name == "gone-wt")
+            .unwrap()
+            .path;
+
+        assert!(!git.worktree_dir_missing(&wt_path));
+        std::fs::remove_dir_all(&wt_path).unwrap();
+        assert!(git.worktree_dir_missing(&wt_path));
+        // Paths git doesn't know about are not stale entries
+        assert!(!git.worktree_dir_missing(&temp_dir.path().join("elsewhere")));
+    }
+
+    #[test]
+    fn test_prune_entry_leaves_other_stale_entries() {
+        let (temp_dir, git) = setup_test_repo();
+        for name in ["one", "two"] {
+            Command::new("git")
+                .args(["branch", name])
+                .current_dir(temp_dir.path())
+                .output()
+                .unwrap();
+            git.create_worktree(&format!("{}-wt", name), name, ".")
+                .unwrap();
+        }
+        let paths: Vec<_> = git
+            .list_worktrees()
+            .unwrap()
+            .into_iter()
+            .filter(|w| !w.is_main)
+            .map(|w| w.path)
+            .collect();
+        for path in &paths {
+            std::fs::remove_dir_all(path).unwrap();
+        }
+
+        assert!(git.prune_entry(&paths[0]).unwrap());
+
+        assert!(!git.worktree_dir_missing(&paths[0]));
+        assert!(git.worktree_dir_missing(&paths[1]));
+    }
+
+    #[test]
+    fn test_prune_entry_keeps_locked_entry() {
+        let (temp_dir, git) = setup_test_repo();
+        Command::new("git")
+            .args(["branch", "kept"])
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap();
+        git.create_worktree("kept-wt", "kept", ".").unwrap();
+        git.lock_worktree("kept-wt", None).unwrap();
+        let wt_path = temp_dir.path().join("kept-wt");
+        std::fs::remove_dir_all(&wt_path).unwrap();
+
+        assert!(!git.prune_entry(&wt_path).unwrap());
+        assert!(git.worktree_dir_missing(&wt_path));
+    }
+
+    #[test]
+    fn test_repair_worktrees_fixes_broken_git_file() {
+        let (temp_dir, git) = setup_test_repo();
+        Command::new("git")
+            .args(["branch", "repair-test"])
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap();
+        let wt = git
+            .create_worktree("repair-wt", "repair-test", ".")
+            .unwrap();
+        std::fs::write(
+            wt.path.join(".git"),
+            "gitdir: /nonexistent/worktrees/repair-wt\n",
+        )
+        .unwrap();
+        assert!(Repository::open(&wt.path).is_err());
+
+        let repaired = git.repair_worktrees().unwrap();
+
+        assert_eq!(repaired, 1);
+        assert!(Repository::open(&wt.path).is_ok());
+    }
+
+    #[test]
+    fn test_repair_worktrees_with_nothing_to_fix() {
+        let (temp_dir, git) = setup_test_repo();
+        Command::new("git")
+            .args(["branch", "repair-test"])
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap();
+        git.create_worktree("repair-wt", "repair-test", ".")
+            .unwrap();
+
+        assert_eq!(git.repair_worktrees().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_move_worktree() {
+        let (temp_dir, git) = setup_test_repo();
+        Command::new("git")
+            .args(["branch", "move-test"])
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap();
+        let wt = git.create_worktree("move-wt", "move-test", ".").unwrap();
+        let new_path = temp_dir.path().join("moved-wt");
+
+        git.move_worktree(&wt.path, &new_path).unwrap();
+
+        assert!(!wt.path.exists());
+        assert!(new_path.exists());
+        let worktrees = git.list_worktrees().unwrap();
+        assert!(worktrees
+            .iter()
+            .any(|w| w.path.ends_with("moved-wt") && w.branch.as_deref() == Some("move-test")));
+    }
+
+    #[test]
+    fn test_move_worktree_to_existing_path_fails() {
+        let (temp_dir, git) = setup_test_repo();
+        Command::new("git")
+            .args(["branch", "move-test"])
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap();
+        let wt = git.create_worktree("move-wt", "move-test", ".").unwrap();
+        let taken = temp_dir.path().join("taken");
+        std::fs::create_dir(&taken).unwrap();
+
+        let result = git.move_worktree(&wt.path, &taken);
+
+        assert!(matches!(result, Err(GitError::WorktreeExists(_))));
+        assert!(wt.path.exists());
+    }
+
+    #[test]
+    fn test_move_locked_worktree_fails() {
+        let (temp_dir, git) = setup_test_repo();
+        Command::new("git")
+            .args(["branch", "move-test"])
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap();
+        let wt = git.create_worktree("move-wt", "move-test", ".").unwrap();
+        git.lock_worktree("move-wt", None).unwrap();
+
+        let result = git.move_worktree(&wt.path, &temp_dir.path().join("moved-wt"));
+
+        assert!(result.is_err());
+        assert!(wt.path.exists());
+    }
+
+    #[test]
+    fn test_delete_branch() {
+        let (temp_dir, git) = setup_test_repo();
+        let repo_path = temp_dir.path();
+
+        // Create a branch
+        Command::new("git")
+            .args(["branch", "branch-to-delete"])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+
+        // Delete the branch
+        let result = git.delete_branch("branch-to-delete");
+
+        assert!(result.is_ok());
+
+        // Verify branch is gone
+        let branches = git.list_branches().unwrap();
+        assert!(!branches.iter().any(|b| b.name == "branch-to-delete"));
+    }
+
+    #[test]
+    fn test_delete_branch_not_found() {
+        let (_temp_dir, git) = setup_test_repo();
+
+        let result = git.delete_branch("nonexistent-branch");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_changed_files_summary_is_empty() {
+        let summary = ChangedFilesSummary::default();
+        assert!(summary.is_empty());
+
+        let summary_with_added = ChangedFilesSummary {
+            added: 1,
+            deleted: 0,
+            modified: 0,
+        };
         assert!(!summary_with_added.is_empty());
     }
 
+    #[test]
+    fn test_get_changed_files_counts_by_kind() {
+        let (temp_dir, git) = TestRepoBuilder::new().with_commit("Add file").build();
+        let repo_path = temp_dir.path();
+        std::fs::write(repo_path.join(".gitignore"), "target/\n").unwrap();
+        std::fs::create_dir(repo_path.join("target")).unwrap();
+        std::fs::write(repo_path.join("target/out.o"), "build output").unwrap();
+        Command::new("git")
+            .args(["add", ".gitignore"])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+        std::fs::write(repo_path.join("README.md"), "# Changed").unwrap();
+        std::fs::remove_file(repo_path.join("Add_file.txt")).unwrap();
+
+        let summary = git.get_changed_files(repo_path);
+
+        assert_eq!(summary.added, 1);
+        assert_eq!(summary.deleted, 1);
+        assert_eq!(summary.modified, 1);
+    }
+
+    #[test]
+    fn test_get_changed_files_ignores_ignored_files() {
+        let (temp_dir, git) = setup_test_repo();
+        let repo_path = temp_dir.path();
+        std::fs::write(repo_path.join(".git/info/exclude"), "target/\n").unwrap();
+        std::fs::create_dir(repo_path.join("target")).unwrap();
+        std::fs::write(repo_path.join("target/out.o"), "build output").unwrap();
+
+        assert!(git.get_changed_files(repo_path).is_empty());
+    }
+
+    #[test]
+    fn test_get_changed_files_counts_files_in_untracked_dirs() {
+        let (temp_dir, git) = setup_test_repo();
+        let repo_path = temp_dir.path();
+        std::fs::create_dir_all(repo_path.join("new/nested")).unwrap();
+        std::fs::write(repo_path.join("new/a.txt"), "a").unwrap();
+        std::fs::write(repo_path.join("new/nested/b.txt"), "b").unwrap();
+
+        let summary = git.get_changed_files(repo_path);
+
+        assert_eq!(summary.added, 2);
+    }
+
+    #[test]
+    fn test_is_dirty_clean_repo() {
+        let (temp_dir, _git) = setup_test_repo();
+
+        assert!(!is_dirty(temp_dir.path()));
+    }
+
+    #[test]
+    fn test_is_dirty_with_untracked_file() {
+        let (temp_dir, _git) = setup_test_repo();
+        std::fs::write(temp_dir.path().join("new.txt"), "new").unwrap();
+
+        assert!(is_dirty(temp_dir.path()));
+    }
+
+    #[test]
+    fn test_is_dirty_with_modified_file() {
+        let (temp_dir, _git) = setup_test_repo();
+        std::fs::write(temp_dir.path().join("README.md"), "# Changed").unwrap();
+
+        assert!(is_dirty(temp_dir.path()));
+    }
+
+    #[test]
+    fn test_has_conflicts_clean_repo() {
+        let (temp_dir, git) = setup_test_repo();
+
+        assert!(!git.has_conflicts(temp_dir.path()));
+    }
+
+    #[test]
+    fn test_has_conflicts_after_conflicting_merge() {
+        let (temp_dir, git) = TestRepoBuilder::new().with_branch("feature").build();
+        let repo_path = temp_dir.path();
+
+        // Add the same file on main with different content, then merge
+        Command::new("git")
+            .args(["checkout", "main"])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+        std::fs::write(repo_path.join("feature.txt"), "main version").unwrap();
+        for args in [
+            vec!["add", "."],
+            vec!["commit", "-m", "Conflicting change"],
+            vec!["merge", "feature"],
+        ] {
+            Command::new("git")
+                .args(&args)
+                .current_dir(repo_path)
+                .output()
+                .unwrap();
+        }
+
+        assert!(git.has_conflicts(repo_path));
+    }
+
+    #[test]
+    fn test_ahead_behind_without_upstream() {
+        let (temp_dir, git) = setup_test_repo();
+
+        assert_eq!(git.get_ahead_behind(temp_dir.path()), None);
+    }
+
+    #[test]
+    fn test_ahead_behind_with_upstream() {
+        let (temp_dir, git) = TestRepoBuilder::new()
+            .with_branch("feature")
+            .with_commit("Main moves on")
+            .build();
+        let repo_path = temp_dir.path();
+
+        for args in [
+            vec!["checkout", "feature"],
+            vec!["branch", "--set-upstream-to=main"],
+        ] {
+            Command::new("git")
+                .args(&args)
+                .current_dir(repo_path)
+                .output()
+                .unwrap();
+        }
+
+        assert_eq!(git.get_ahead_behind(repo_path), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_branch_behind_upstream() {
+        let (temp_dir, git) = TestRepoBuilder::new()
+            .with_branch("feature")
+            .with_commit("Main moves on")
+            .build();
+        Command::new("git")
+            .args(["branch", "--set-upstream-to=main", "feature"])
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap();
+
+        assert_eq!(
+            git.branch_behind_upstream("feature"),
+            Some((1, "main".to_string()))
+        );
+        // No upstream, or not a local branch
+        assert_eq!(git.branch_behind_upstream("main"), None);
+        assert_eq!(git.branch_behind_upstream("missing"), None);
+    }
+
+    #[test]
+    fn test_ahead_behind_detached_head() {
+        let (temp_dir, git) = setup_test_repo();
+        let repo_path = temp_dir.path();
+
+        Command::new("git")
+            .args(["checkout", "--detach"])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+
+        assert_eq!(git.get_ahead_behind(repo_path), None);
+    }
+
     #[test]
     fn test_get_default_branch() {
         let (_temp_dir, git) = setup_test_repo();
 
-        let result = git.get_default_branch();
+        let result = git.get_default_branch("origin");
 
         assert!(result.is_ok());
         // Should be "main" or "master"
@@ -1115,13 +2270,17 @@ mod tests {
 
     // ========== find_merged_branches Tests ==========
 
+    fn default_base(git: &GitManager) -> String {
+        git.get_default_branch("origin").unwrap()
+    }
+
     #[test]
     fn test_find_merged_branches_returns_merged_branch() {
         let (_temp_dir, git) = TestRepoBuilder::new()
             .with_merged_branch("feature-merged")
             .build();
 
-        let merged = git.find_merged_branches().unwrap();
+        let merged = git.find_merged_branches(&default_base(&git)).unwrap();
 
         assert!(merged.contains(&"feature-merged".to_string()));
     }
@@ -1132,7 +2291,7 @@ mod tests {
             .with_branch("feature-unmerged")
             .build();
 
-        let merged = git.find_merged_branches().unwrap();
+        let merged = git.find_merged_branches(&default_base(&git)).unwrap();
 
         assert!(!merged.contains(&"feature-unmerged".to_string()));
     }
@@ -1143,8 +2302,8 @@ mod tests {
             .with_merged_branch("feature-merged")
             .build();
 
-        let merged = git.find_merged_branches().unwrap();
-        let default_branch = git.get_default_branch().unwrap();
+        let merged = git.find_merged_branches(&default_base(&git)).unwrap();
+        let default_branch = git.get_default_branch("origin").unwrap();
 
         // Default branch should not be in merged list
         assert!(!merged.contains(&default_branch));
@@ -1154,7 +2313,7 @@ mod tests {
     fn test_find_merged_branches_with_no_other_branches() {
         let (_temp_dir, git) = TestRepoBuilder::new().build();
 
-        let merged = git.find_merged_branches().unwrap();
+        let merged = git.find_merged_branches(&default_base(&git)).unwrap();
 
         assert!(merged.is_empty());
     }
@@ -1167,7 +2326,7 @@ mod tests {
             .with_branch("unmerged-1")
             .build();
 
-        let merged = git.find_merged_branches().unwrap();
+        let merged = git.find_merged_branches(&default_base(&git)).unwrap();
 
         assert!(merged.contains(&"merged-1".to_string()));
         assert!(merged.contains(&"merged-2".to_string()));
@@ -1175,6 +2334,66 @@ mod tests {
         assert_eq!(merged.len(), 2);
     }
 
+    /// Repository whose default branch is named `trunk`
+    fn setup_trunk_repo() -> (TempDir, GitManager) {
+        let (temp_dir, _git) = TestRepoBuilder::new()
+            .with_merged_branch("feature-merged")
+            .with_branch("feature-open")
+            .build();
+        Command::new("git")
+            .args(["branch", "-m", "main", "trunk"])
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap();
+        let git = GitManager::from_path(temp_dir.path()).unwrap();
+        (temp_dir, git)
+    }
+
+    #[test]
+    fn test_find_merged_branches_into_trunk() {
+        let (_temp_dir, git) = setup_trunk_repo();
+
+        assert_eq!(git.get_default_branch("origin").unwrap(), "trunk");
+        let merged = git.find_merged_branches("trunk").unwrap();
+
+        assert_eq!(merged, vec!["feature-merged".to_string()]);
+    }
+
+    #[test]
+    fn test_get_default_branch_uses_remote_head() {
+        let (source_dir, _git) = setup_trunk_repo();
+        let clone_dir = TempDir::new().unwrap();
+        Command::new("git")
+            .args(["clone"])
+            .arg(source_dir.path())
+            .arg(clone_dir.path())
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["remote", "rename", "origin", "upstream"])
+            .current_dir(clone_dir.path())
+            .output()
+            .unwrap();
+        // Check out another branch so HEAD doesn't give the answer away
+        Command::new("git")
+            .args(["checkout", "-b", "local-work"])
+            .current_dir(clone_dir.path())
+            .output()
+            .unwrap();
+        let git = GitManager::from_path(clone_dir.path()).unwrap();
+
+        assert_eq!(git.get_default_branch("upstream").unwrap(), "trunk");
+    }
+
+    #[test]
+    fn test_find_merged_branches_unknown_base() {
+        let (_temp_dir, git) = setup_test_repo();
+
+        let result = git.find_merged_branches("does-not-exist");
+
+        assert!(matches!(result, Err(GitError::BranchNotFound(b)) if b == "does-not-exist"));
+    }
+
     #[test]
     fn test_find_merged_worktrees_returns_worktrees_with_merged_branch() {
         let (temp_dir, git) = TestRepoBuilder::new()
@@ -1189,7 +2408,7 @@ mod tests {
             .output()
             .unwrap();
 
-        let merged_worktrees = git.find_merged_worktrees().unwrap();
+        let merged_worktrees = git.find_merged_worktrees(&default_base(&git)).unwrap();
 
         assert!(!merged_worktrees.is_empty());
         assert!(merged_worktrees.iter().any(|wt| wt.name == "wt-merged"));
@@ -1209,7 +2428,7 @@ mod tests {
             .output()
             .unwrap();
 
-        let merged_worktrees = git.find_merged_worktrees().unwrap();
+        let merged_worktrees = git.find_merged_worktrees(&default_base(&git)).unwrap();
 
         assert!(merged_worktrees.is_empty());
     }
@@ -1220,7 +2439,7 @@ mod tests {
             .with_merged_branch("merged-feature")
             .build();
 
-        let merged_worktrees = git.find_merged_worktrees().unwrap();
+        let merged_worktrees = git.find_merged_worktrees(&default_base(&git)).unwrap();
 
         // Main worktree should never be in merged worktrees
         assert!(merged_worktrees.iter().all(|wt| !wt.is_main));
@@ -1252,6 +2471,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_create_worktree_branch_checked_out_elsewhere() {
+        let (temp_dir, git) = setup_test_repo();
+        let repo_path = temp_dir.path();
+
+        Command::new("git")
+            .args(["branch", "busy-branch"])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+        git.create_worktree("first-wt", "busy-branch", ".").unwrap();
+
+        let result = git.create_worktree("second-wt", "busy-branch", ".");
+
+        match result {
+            Err(GitError::BranchCheckedOut(branch, path)) => {
+                assert_eq!(branch, "busy-branch");
+                assert!(path.ends_with("first-wt"));
+            }
+            other => panic!("Expected BranchCheckedOut error, got {:?}", other.err()),
+        }
+        assert!(!repo_path.join("second-wt").exists());
+    }
+
+    #[test]
+    fn test_create_worktree_main_branch_checked_out() {
+        let (_temp_dir, git) = setup_test_repo();
+        let head = git.repo.head().unwrap().shorthand().unwrap().to_string();
+
+        let result = git.create_worktree("main-copy", &head, ".");
+
+        assert!(matches!(result, Err(GitError::BranchCheckedOut(_, _))));
+    }
+
     #[test]
     fn test_create_worktree_already_exists() {
         let (temp_dir, git) = setup_test_repo();
@@ -1285,6 +2538,67 @@ mod tests {
         path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
     }
 
+    /// Clone a test repository as `<tmp>/project.git` with one linked worktree
+    fn setup_bare_repo() -> (TempDir, TempDir, PathBuf) {
+        let (source_dir, _git) = setup_test_repo();
+        let temp_dir = TempDir::new().unwrap();
+        let bare_path = canonicalize_path(temp_dir.path()).join("project.git");
+        Command::new("git")
+            .args(["clone", "--bare"])
+            .arg(source_dir.path())
+            .arg(&bare_path)
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["worktree", "add", "-b", "feature-bare", "../feature-bare"])
+            .current_dir(&bare_path)
+            .output()
+            .unwrap();
+        (source_dir, temp_dir, bare_path)
+    }
+
+    #[test]
+    fn test_bare_repo_lists_only_linked_worktrees() {
+        let (_source, _temp, bare_path) = setup_bare_repo();
+        let git = GitManager::from_path(&bare_path).unwrap();
+
+        let worktrees = git.list_worktrees().unwrap();
+
+        assert!(git.bare);
+        assert_eq!(canonicalize_path(&git.repo_root), bare_path);
+        assert_eq!(worktrees.len(), 1);
+        assert!(!worktrees[0].is_main);
+        assert_eq!(worktrees[0].branch.as_deref(), Some("feature-bare"));
+        assert_eq!(git.repo_name(), "project");
+    }
+
+    #[test]
+    fn test_bare_repo_from_linked_worktree() {
+        let (_source, _temp, bare_path) = setup_bare_repo();
+        let worktree_path = bare_path.parent().unwrap().join("feature-bare");
+
+        let git = GitManager::from_path(&worktree_path).unwrap();
+
+        assert!(git.bare);
+        assert_eq!(canonicalize_path(&git.repo_root), bare_path);
+        assert_eq!(git.list_worktrees().unwrap().len(), 1);
+        assert!(git.find_merged_worktrees(&default_base(&git)).is_ok());
+    }
+
+    #[test]
+    fn test_bare_repo_create_worktree_resolves_relative_basedir() {
+        let (_source, _temp, bare_path) = setup_bare_repo();
+        let git = GitManager::from_path(&bare_path).unwrap();
+
+        let worktree = git
+            .create_worktree_with_new_branch("second", "second", "../worktrees")
+            .unwrap();
+
+        let expected = bare_path.parent().unwrap().join("worktrees").join("second");
+        assert_eq!(canonicalize_path(&worktree.path), expected);
+        assert_eq!(git.list_worktrees().unwrap().len(), 2);
+    }
+
     #[test]
     fn test_git_manager_from_worktree_returns_main_repo_root() {
         let (temp_dir, _git) = setup_test_repo();
@@ -1,10 +1,17 @@
-use crate::config::RepositorySettings;
+use crate::config::{expand_env_path, Hook, HookEvent, RepositorySettings, SetupCommand};
 use crate::git::Worktree;
 use glob::glob;
+use std::io::Read;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
+/// How often a running setup command is checked for exit, timeout or cancellation
+const SETUP_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
 #[derive(Error, Debug)]
 pub enum HookError {
     #[error("Setup command failed: {0}")]
@@ -13,11 +20,68 @@ pub enum HookError {
     IoError(#[from] std::io::Error),
     #[error("File copy failed: {0}")]
     CopyFailed(String),
+    #[error("File copy failed after copying {copied} file(s): {error}")]
+    CopyIncomplete { copied: usize, error: String },
+    #[error("copy_files entry '{0}' matched no files")]
+    MissingCopySource(String),
+    #[error("Symlink failed: {0}")]
+    LinkFailed(String),
+    #[error("{0} hook failed: {1}")]
+    HookFailed(&'static str, String),
+    #[error("setup command '{0}' timed out after {1:?}")]
+    Timeout(String, Duration),
+    #[error("setup was cancelled")]
+    Cancelled,
+}
+
+/// What `SetupRunner::run_setup` did
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SetupReport {
+    /// Destination of every copied file
+    pub copied: Vec<PathBuf>,
+    /// Every symlink created for symlink_files
+    pub linked: Vec<PathBuf>,
+    /// copy_files / symlink_files entries that matched nothing in the main worktree
+    pub missing: Vec<String>,
+    /// symlink_files entries skipped because the platform has no symlinks
+    pub skipped_links: Vec<String>,
+    /// copy_files destinations that already existed and were left alone
+    pub existing: Vec<PathBuf>,
+}
+
+impl SetupReport {
+    /// One-line summary for the status message, None when nothing was copied or skipped
+    pub fn summary(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if !self.copied.is_empty() {
+            parts.push(format!("copied {} file(s)", self.copied.len()));
+        }
+        if !self.linked.is_empty() {
+            parts.push(format!("linked {} file(s)", self.linked.len()));
+        }
+        if !self.missing.is_empty() {
+            parts.push(format!("not found: {}", self.missing.join(", ")));
+        }
+        if !self.skipped_links.is_empty() {
+            parts.push(format!(
+                "symlinks unsupported, skipped: {}",
+                self.skipped_links.join(", ")
+            ));
+        }
+        if !self.existing.is_empty() {
+            parts.push(format!("skipped {} existing file(s)", self.existing.len()));
+        }
+        (!parts.is_empty()).then(|| parts.join("; "))
+    }
 }
 
 pub struct SetupRunner {
     settings: Option<RepositorySettings>,
     main_worktree_path: Option<PathBuf>,
+    strict_copy: bool,
+    shell: String,
+    timeout: Option<Duration>,
+    cancel: Option<Arc<AtomicBool>>,
 }
 
 impl SetupRunner {
@@ -25,24 +89,65 @@ impl SetupRunner {
         Self {
             settings,
             main_worktree_path: None,
+            strict_copy: false,
+            shell: "sh".to_string(),
+            timeout: None,
+            cancel: None,
         }
     }
 
+    /// Kill setup commands that run longer than `timeout` (commands may set their own)
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Stop setup, killing the running command, once `cancel` is set
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    fn cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+    }
+
+    /// Set the shell that runs setup commands without a shell of their own
+    pub fn with_shell(mut self, shell: &str) -> Self {
+        self.shell = shell.to_string();
+        self
+    }
+
     /// Set the main worktree path for file copying
     pub fn with_main_worktree(mut self, path: PathBuf) -> Self {
         self.main_worktree_path = Some(path);
         self
     }
 
-    /// Run setup tasks after creating a worktree (copy files, then run commands)
-    pub fn run_setup(&self, worktree: &Worktree) -> Result<(), HookError> {
+    /// Fail setup when a copy_files or symlink_files entry matches nothing, instead of reporting it
+    pub fn with_strict_copy(mut self, strict: bool) -> Self {
+        self.strict_copy = strict;
+        self
+    }
+
+    /// Run setup tasks after creating a worktree (copy and link files, then run commands)
+    pub fn run_setup(&self, worktree: &Worktree) -> Result<SetupReport, HookError> {
+        let mut report = SetupReport::default();
         let Some(settings) = &self.settings else {
-            return Ok(());
+            return Ok(report);
         };
+        if self.cancelled() {
+            return Err(HookError::Cancelled);
+        }
 
         // Copy files first
         if let Some(files) = &settings.copy_files {
-            self.copy_files(files, worktree)?;
+            self.copy_files(files, worktree, &mut report)?;
+        }
+        if let Some(files) = &settings.symlink_files {
+            self.link_files(files, worktree, &mut report)?;
         }
 
         // Then run setup commands
@@ -52,86 +157,137 @@ impl SetupRunner {
             }
         }
 
-        Ok(())
+        Ok(report)
     }
 
     /// Copy files from main worktree to new worktree
-    fn copy_files(&self, files: &[String], worktree: &Worktree) -> Result<(), HookError> {
+    fn copy_files(
+        &self,
+        files: &[String],
+        worktree: &Worktree,
+        report: &mut SetupReport,
+    ) -> Result<(), HookError> {
         let Some(main_path) = &self.main_worktree_path else {
             return Err(HookError::CopyFailed(
                 "Main worktree path not set".to_string(),
             ));
         };
 
-        for file_pattern in files {
-            self.copy_file_or_pattern(main_path, file_pattern, &worktree.path)?;
+        for (source, dest) in self.plan(files, main_path, &worktree.path, report)? {
+            let result = if source.is_dir() {
+                self.copy_directory(&source, &dest, report)
+            } else {
+                self.copy_single_file(&source, &dest, report)
+            };
+            // Report how far copying got, so a partial copy is not mistaken for none
+            if let Err(e) = result {
+                return Err(if report.copied.is_empty() {
+                    e
+                } else {
+                    HookError::CopyIncomplete {
+                        copied: report.copied.len(),
+                        error: e.to_string(),
+                    }
+                });
+            }
         }
 
         Ok(())
     }
 
-    /// Copy a single file, directory, or glob pattern from source to destination
-    fn copy_file_or_pattern(
+    /// Symlink files from main worktree into new worktree, so large shared
+    /// files are not duplicated
+    fn link_files(
         &self,
-        source_base: &Path,
-        pattern: &str,
-        dest_base: &Path,
+        files: &[String],
+        worktree: &Worktree,
+        report: &mut SetupReport,
     ) -> Result<(), HookError> {
-        // Check if pattern contains glob characters
-        if pattern.contains('*') || pattern.contains('?') || pattern.contains('[') {
-            return self.copy_glob_pattern(source_base, pattern, dest_base);
-        }
-
-        let source_path = source_base.join(pattern);
+        let Some(main_path) = &self.main_worktree_path else {
+            return Err(HookError::CopyFailed(
+                "Main worktree path not set".to_string(),
+            ));
+        };
 
-        if !source_path.exists() {
-            // Silently skip if source doesn't exist (file is optional)
+        let missing_before = report.missing.len();
+        let planned = self.plan(files, main_path, &worktree.path, report)?;
+        if !cfg!(unix) {
+            let missing = &report.missing[missing_before..];
+            report
+                .skipped_links
+                .extend(files.iter().filter(|file| !missing.contains(file)).cloned());
             return Ok(());
         }
 
-        if source_path.is_dir() {
-            // Copy directory recursively
-            self.copy_directory(&source_path, &dest_base.join(pattern))?;
-        } else {
-            // Copy single file
-            self.copy_single_file(&source_path, &dest_base.join(pattern))?;
+        for (source, dest) in planned {
+            // Left by an earlier setup run
+            if std::fs::read_link(&dest).is_ok_and(|target| target == source) {
+                continue;
+            }
+            symlink(&source, &dest)?;
+            report.linked.push(dest);
         }
 
         Ok(())
     }
 
-    /// Copy files matching a glob pattern
-    fn copy_glob_pattern(
+    /// Resolve copy_files / symlink_files entries to (source, destination) pairs.
+    /// Relative entries resolve against the main worktree; absolute ones (e.g. an
+    /// expanded `~/templates/.env`) land in the worktree root. Overlapping entries
+    /// such as `.env` and `.env*`, or `.claude` and `.claude/*`, yield each
+    /// destination only once.
+    fn plan(
         &self,
+        files: &[String],
         source_base: &Path,
-        pattern: &str,
         dest_base: &Path,
-    ) -> Result<(), HookError> {
-        let full_pattern = source_base.join(pattern);
-        let pattern_str = full_pattern.to_string_lossy();
+        report: &mut SetupReport,
+    ) -> Result<Vec<(PathBuf, PathBuf)>, HookError> {
+        let mut planned: Vec<(PathBuf, PathBuf)> = Vec::new();
 
-        let entries = glob(&pattern_str).map_err(|e| {
-            HookError::CopyFailed(format!("Invalid glob pattern '{}': {}", pattern, e))
-        })?;
-
-        for entry in entries.flatten() {
-            // Calculate relative path from source_base
-            if let Ok(relative) = entry.strip_prefix(source_base) {
-                let dest_path = dest_base.join(relative);
+        for file_pattern in files {
+            let sources = resolve_sources(source_base, &expand_env_path(file_pattern))?;
+            if sources.is_empty() {
+                if self.strict_copy {
+                    return Err(HookError::MissingCopySource(file_pattern.clone()));
+                }
+                report.missing.push(file_pattern.clone());
+                continue;
+            }
 
-                if entry.is_dir() {
-                    self.copy_directory(&entry, &dest_path)?;
-                } else {
-                    self.copy_single_file(&entry, &dest_path)?;
+            for source in sources {
+                let Some(dest) = destination(source_base, &source, dest_base) else {
+                    continue;
+                };
+                // Already planned, either itself or as part of a directory
+                if planned.iter().any(|(_, planned)| dest.starts_with(planned)) {
+                    continue;
                 }
+                if source.is_dir() {
+                    // The directory copy covers anything planned inside it
+                    planned.retain(|(_, planned)| !planned.starts_with(&dest));
+                }
+                planned.push((source, dest));
             }
         }
 
-        Ok(())
+        Ok(planned)
     }
 
-    /// Copy a single file
-    fn copy_single_file(&self, source: &Path, dest: &Path) -> Result<(), HookError> {
+    /// Copy a single file, leaving an existing destination alone: a re-run
+    /// must not clobber local edits, and copying onto a symlink to the source
+    /// would truncate the source
+    fn copy_single_file(
+        &self,
+        source: &Path,
+        dest: &Path,
+        report: &mut SetupReport,
+    ) -> Result<(), HookError> {
+        if dest.symlink_metadata().is_ok() {
+            report.existing.push(dest.to_path_buf());
+            return Ok(());
+        }
+
         // Create parent directories if needed
         if let Some(parent) = dest.parent() {
             if !parent.exists() {
@@ -147,12 +303,18 @@ impl SetupRunner {
                 e
             ))
         })?;
+        report.copied.push(dest.to_path_buf());
 
         Ok(())
     }
 
     /// Copy a directory recursively
-    fn copy_directory(&self, source: &Path, dest: &Path) -> Result<(), HookError> {
+    fn copy_directory(
+        &self,
+        source: &Path,
+        dest: &Path,
+        report: &mut SetupReport,
+    ) -> Result<(), HookError> {
         if !dest.exists() {
             std::fs::create_dir_all(dest)?;
         }
@@ -164,30 +326,31 @@ impl SetupRunner {
             let dest_path = dest.join(&file_name);
 
             if entry_path.is_dir() {
-                self.copy_directory(&entry_path, &dest_path)?;
+                self.copy_directory(&entry_path, &dest_path, report)?;
             } else {
-                self.copy_single_file(&entry_path, &dest_path)?;
+                self.copy_single_file(&entry_path, &dest_path, report)?;
             }
         }
 
         Ok(())
     }
 
-    /// Execute a command in the worktree directory
-    fn run_command(&self, cmd: &str, worktree: &Worktree) -> Result<(), HookError> {
-        let expanded_cmd = self.expand_variables(cmd, worktree);
+    /// Execute a command in the worktree directory. Its tail is reported when
+    /// the command fails. The command is killed when it times out or setup is
+    /// cancelled.
+    fn run_command(&self, cmd: &SetupCommand, worktree: &Worktree) -> Result<(), HookError> {
+        let expanded_cmd = self.expand_variables(cmd.command(), worktree);
+        let shell = cmd.shell().unwrap_or(&self.shell);
+        let timeout = cmd.timeout_secs().map(Duration::from_secs).or(self.timeout);
 
-        let status = Command::new("sh")
-            .arg("-c")
-            .arg(&expanded_cmd)
-            .current_dir(&worktree.path)
-            .status()?;
+        let output = run_captured(shell, &expanded_cmd, &worktree.path, timeout, || {
+            self.cancelled()
+        })?;
 
-        if !status.success() {
-            return Err(HookError::ExecutionFailed(format!(
-                "Command '{}' exited with status: {}",
-                expanded_cmd,
-                status.code().unwrap_or(-1)
+        if !output.status.success() {
+            return Err(HookError::ExecutionFailed(failure_message(
+                &format!("Command '{}'", expanded_cmd),
+                &output,
             )));
         }
 
@@ -196,10 +359,274 @@ impl SetupRunner {
 
     /// Expand variables in command
     fn expand_variables(&self, cmd: &str, worktree: &Worktree) -> String {
-        cmd.replace("$WORKTREE_NAME", &worktree.name)
-            .replace("$WORKTREE_PATH", &worktree.path.to_string_lossy())
-            .replace("$WORKTREE_BRANCH", worktree.branch.as_deref().unwrap_or(""))
+        expand_variables(cmd, worktree)
+    }
+}
+
+/// Command that runs `script` with `shell`. Words after the program name are
+/// passed along, followed by the interpreter's script flag: `/C` for cmd,
+/// `-Command` for PowerShell and `-c` for everything else
+fn shell_command(shell: &str, script: &str) -> Command {
+    let mut words = shell.split_whitespace();
+    let program = words.next().unwrap_or("sh");
+    let mut command = Command::new(program);
+    command.args(words).arg(script_flag(program)).arg(script);
+    command
+}
+
+/// Flag that makes `program` run the next argument as a script
+fn script_flag(program: &str) -> &'static str {
+    let name = Path::new(program)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    match name.as_str() {
+        "cmd" => "/C",
+        "powershell" | "pwsh" => "-Command",
+        _ => "-c",
+    }
+}
+
+/// Run `script` with `shell` in `cwd`. Output is captured, since the TUI owns
+/// the terminal, and stdin is closed so the command can't read keystrokes.
+/// The command is killed once `timeout` passes or `cancelled` returns true.
+fn run_captured(
+    shell: &str,
+    script: &str,
+    cwd: &Path,
+    timeout: Option<Duration>,
+    cancelled: impl Fn() -> bool,
+) -> Result<Output, HookError> {
+    let mut child = shell_command(shell, script)
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            HookError::ExecutionFailed(format!("Could not run '{}' with {}: {}", script, shell, e))
+        })?;
+    // Drain the pipes while waiting so a chatty command can't fill them and stall
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+
+    let started = Instant::now();
+    let interrupted = || match timeout.filter(|limit| started.elapsed() >= *limit) {
+        Some(limit) => Some(HookError::Timeout(script.to_string(), limit)),
+        None if cancelled() => Some(HookError::Cancelled),
+        None => None,
+    };
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if let Some(err) = interrupted() {
+            // Commands the shell started in the background may outlive it
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(err);
+        }
+        std::thread::sleep(SETUP_POLL_INTERVAL);
+    };
+    // A command left running in the background keeps the pipes open after
+    // the shell exits; its readers are left behind once the limit trips
+    Ok(Output {
+        status,
+        stdout: collect_pipe(&stdout, interrupted)?,
+        stderr: collect_pipe(&stderr, interrupted)?,
+    })
+}
+
+/// "`what` exited with status: N", followed by the output's last lines
+fn failure_message(what: &str, output: &Output) -> String {
+    let mut message = format!(
+        "{} exited with status: {}",
+        what,
+        output.status.code().unwrap_or(-1)
+    );
+    if let Some(tail) = output_tail(output) {
+        message.push_str(&format!(" ({})", tail));
+    }
+    message
+}
+
+/// Read a child's output pipe to the end on a separate thread
+fn read_pipe<R: Read + Send + 'static>(pipe: Option<R>) -> mpsc::Receiver<Vec<u8>> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        // Nobody listens once the command was given up on
+        let _ = tx.send(buf);
+    });
+    rx
+}
+
+/// Wait for a pipe read by `read_pipe`, giving up when `interrupted` reports
+/// the timeout or a cancel
+fn collect_pipe(
+    pipe: &mpsc::Receiver<Vec<u8>>,
+    interrupted: impl Fn() -> Option<HookError>,
+) -> Result<Vec<u8>, HookError> {
+    loop {
+        match pipe.recv_timeout(SETUP_POLL_INTERVAL) {
+            Ok(buf) => return Ok(buf),
+            Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(Vec::new()),
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                if let Some(err) = interrupted() {
+                    return Err(err);
+                }
+            }
+        }
+    }
+}
+
+/// Last few lines a failed command printed, stderr preferred, on one line
+fn output_tail(output: &Output) -> Option<String> {
+    const MAX_LINES: usize = 3;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let text = if stderr.trim().is_empty() {
+        stdout
+    } else {
+        stderr
+    };
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+    let tail = &lines[lines.len().saturating_sub(MAX_LINES)..];
+    (!tail.is_empty()).then(|| tail.join(" / "))
+}
+
+/// Paths in the main worktree named by a copy_files entry: every glob match, or
+/// the path itself when it exists (an empty directory counts)
+fn resolve_sources(source_base: &Path, pattern: &str) -> Result<Vec<PathBuf>, HookError> {
+    let source = source_base.join(pattern);
+    if !is_glob(pattern) {
+        return Ok(if source.exists() {
+            vec![source]
+        } else {
+            Vec::new()
+        });
     }
+
+    let entries = glob(&source.to_string_lossy())
+        .map_err(|e| HookError::CopyFailed(format!("Invalid glob pattern '{}': {}", pattern, e)))?;
+    Ok(entries.flatten().collect())
+}
+
+/// Where a copied entry lands: at its path relative to the main worktree, or
+/// directly in the worktree root when it lives outside the main worktree
+fn destination(source_base: &Path, source: &Path, dest_base: &Path) -> Option<PathBuf> {
+    match source.strip_prefix(source_base) {
+        Ok(relative) => Some(dest_base.join(relative)),
+        Err(_) => source.file_name().map(|name| dest_base.join(name)),
+    }
+}
+
+/// Create `dest` as a symlink to `source`, creating parent directories as needed
+fn symlink(source: &Path, dest: &Path) -> Result<(), HookError> {
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    #[cfg(unix)]
+    let result = std::os::unix::fs::symlink(source, dest);
+    #[cfg(not(unix))]
+    let result: std::io::Result<()> = Err(std::io::ErrorKind::Unsupported.into());
+
+    result.map_err(|e| {
+        HookError::LinkFailed(format!(
+            "Failed to link '{}' to '{}': {}",
+            dest.display(),
+            source.display(),
+            e
+        ))
+    })
+}
+
+/// Check if a copy_files entry contains glob characters
+fn is_glob(pattern: &str) -> bool {
+    pattern.contains('*') || pattern.contains('?') || pattern.contains('[')
+}
+
+/// Runs configured lifecycle hooks (pre/post create and delete)
+pub struct HookRunner {
+    hooks: Vec<Hook>,
+    repo_root: Option<PathBuf>,
+}
+
+impl HookRunner {
+    pub fn new(hooks: Vec<Hook>) -> Self {
+        Self {
+            hooks,
+            repo_root: None,
+        }
+    }
+
+    /// Set the repository root used when the worktree directory doesn't exist
+    pub fn with_repo_root(mut self, path: PathBuf) -> Self {
+        self.repo_root = Some(path);
+        self
+    }
+
+    /// Run all hooks registered for `event`, stopping at the first failure
+    pub fn run(&self, event: HookEvent, worktree: &Worktree) -> Result<(), HookError> {
+        for hook in self.hooks.iter().filter(|h| h.event == event) {
+            let expanded_cmd = expand_variables(&hook.command, worktree);
+            let cwd = self.resolve_cwd(hook, worktree);
+
+            let output = run_captured("sh", &expanded_cmd, &cwd, hook.timeout(), || false)
+                .map_err(|e| {
+                    let reason = match e {
+                        HookError::Timeout(cmd, limit) => {
+                            format!("'{}' timed out after {:?}", cmd, limit)
+                        }
+                        e => e.to_string(),
+                    };
+                    HookError::HookFailed(event.as_str(), reason)
+                })?;
+
+            if !output.status.success() {
+                return Err(HookError::HookFailed(
+                    event.as_str(),
+                    failure_message(&format!("'{}'", expanded_cmd), &output),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Resolve the working directory for a hook
+    /// Relative `cwd` values are resolved against the worktree path. When the
+    /// worktree directory doesn't exist (pre_create, post_delete), the repository
+    /// root is used instead.
+    fn resolve_cwd(&self, hook: &Hook, worktree: &Worktree) -> PathBuf {
+        let base = if worktree.path.exists() {
+            worktree.path.clone()
+        } else {
+            self.repo_root
+                .clone()
+                .unwrap_or_else(|| worktree.path.clone())
+        };
+
+        match &hook.cwd {
+            Some(cwd) => base.join(expand_variables(cwd, worktree)),
+            None => base,
+        }
+    }
+}
+
+/// Expand $WORKTREE_NAME, $WORKTREE_PATH and $WORKTREE_BRANCH in a string
+fn expand_variables(cmd: &str, worktree: &Worktree) -> String {
+    cmd.replace("$WORKTREE_NAME", &worktree.name)
+        .replace("$WORKTREE_PATH", &worktree.path.to_string_lossy())
+        .replace("$WORKTREE_BRANCH", worktree.branch.as_deref().unwrap_or(""))
 }
 
 #[cfg(test)]
@@ -213,6 +640,7 @@ mod tests {
             path: PathBuf::from("/repo/worktrees/feature-test"),
             branch: Some("feature/test".to_string()),
             is_main: false,
+            ..Default::default()
         }
     }
 
@@ -254,6 +682,7 @@ mod tests {
             path: PathBuf::from("/repo/worktrees/detached"),
             branch: None,
             is_main: false,
+            ..Default::default()
         };
 
         let expanded = runner.expand_variables("branch is $WORKTREE_BRANCH end", &worktree);
@@ -275,6 +704,7 @@ mod tests {
         let runner = SetupRunner::new(Some(RepositorySettings {
             repository: "test".to_string(),
             copy_files: None,
+            symlink_files: None,
             setup_commands: None,
         }));
         let worktree = create_test_worktree();
@@ -301,6 +731,7 @@ mod tests {
         let runner = SetupRunner::new(Some(RepositorySettings {
             repository: "test".to_string(),
             copy_files: Some(vec![".env".to_string()]),
+            symlink_files: None,
             setup_commands: None,
         }));
         let worktree = create_test_worktree();
@@ -334,6 +765,7 @@ mod tests {
         let runner = SetupRunner::new(Some(RepositorySettings {
             repository: "test".to_string(),
             copy_files: Some(vec![".env".to_string()]),
+            symlink_files: None,
             setup_commands: None,
         }))
         .with_main_worktree(main_dir.clone());
@@ -343,6 +775,7 @@ mod tests {
             path: worktree_dir.clone(),
             branch: Some("test".to_string()),
             is_main: false,
+            ..Default::default()
         };
 
         let result = runner.run_setup(&worktree);
@@ -376,6 +809,7 @@ mod tests {
         let runner = SetupRunner::new(Some(RepositorySettings {
             repository: "test".to_string(),
             copy_files: Some(vec!["nonexistent.env".to_string()]),
+            symlink_files: None,
             setup_commands: None,
         }))
         .with_main_worktree(main_dir.clone());
@@ -385,6 +819,7 @@ mod tests {
             path: worktree_dir.clone(),
             branch: Some("test".to_string()),
             is_main: false,
+            ..Default::default()
         };
 
         let result = runner.run_setup(&worktree);
@@ -411,7 +846,7 @@ mod tests {
 
         // Clean up and create directories
         let _ = fs::remove_dir_all(&temp_dir);
-        fs::create_dir_all(&main_dir.join("config")).unwrap();
+        fs::create_dir_all(main_dir.join("config")).unwrap();
         fs::create_dir_all(&worktree_dir).unwrap();
 
         // Create source file in nested directory
@@ -421,6 +856,7 @@ mod tests {
         let runner = SetupRunner::new(Some(RepositorySettings {
             repository: "test".to_string(),
             copy_files: Some(vec!["config/settings.json".to_string()]),
+            symlink_files: None,
             setup_commands: None,
         }))
         .with_main_worktree(main_dir.clone());
@@ -430,6 +866,7 @@ mod tests {
             path: worktree_dir.clone(),
             branch: Some("test".to_string()),
             is_main: false,
+            ..Default::default()
         };
 
         let result = runner.run_setup(&worktree);
@@ -468,6 +905,7 @@ mod tests {
         let runner = SetupRunner::new(Some(RepositorySettings {
             repository: "test".to_string(),
             copy_files: Some(vec![".env".to_string(), ".env.local".to_string()]),
+            symlink_files: None,
             setup_commands: None,
         }))
         .with_main_worktree(main_dir.clone());
@@ -477,6 +915,7 @@ mod tests {
             path: worktree_dir.clone(),
             branch: Some("test".to_string()),
             is_main: false,
+            ..Default::default()
         };
 
         let result = runner.run_setup(&worktree);
@@ -521,6 +960,7 @@ mod tests {
         let runner = SetupRunner::new(Some(RepositorySettings {
             repository: "test".to_string(),
             copy_files: Some(vec![".env*".to_string()]),
+            symlink_files: None,
             setup_commands: None,
         }))
         .with_main_worktree(main_dir.clone());
@@ -530,6 +970,7 @@ mod tests {
             path: worktree_dir.clone(),
             branch: Some("test".to_string()),
             is_main: false,
+            ..Default::default()
         };
 
         let result = runner.run_setup(&worktree);
@@ -556,7 +997,7 @@ mod tests {
         let worktree_dir = temp_dir.join("worktree");
 
         let _ = fs::remove_dir_all(&temp_dir);
-        fs::create_dir_all(&main_dir.join(".claude")).unwrap();
+        fs::create_dir_all(main_dir.join(".claude")).unwrap();
         fs::create_dir_all(&worktree_dir).unwrap();
 
         // Create files in .claude directory
@@ -574,6 +1015,7 @@ mod tests {
         let runner = SetupRunner::new(Some(RepositorySettings {
             repository: "test".to_string(),
             copy_files: Some(vec![".claude".to_string()]),
+            symlink_files: None,
             setup_commands: None,
         }))
         .with_main_worktree(main_dir.clone());
@@ -583,6 +1025,7 @@ mod tests {
             path: worktree_dir.clone(),
             branch: Some("test".to_string()),
             is_main: false,
+            ..Default::default()
         };
 
         let result = runner.run_setup(&worktree);
@@ -609,7 +1052,7 @@ mod tests {
         let worktree_dir = temp_dir.join("worktree");
 
         let _ = fs::remove_dir_all(&temp_dir);
-        fs::create_dir_all(&main_dir.join(".claude").join("prompts")).unwrap();
+        fs::create_dir_all(main_dir.join(".claude").join("prompts")).unwrap();
         fs::create_dir_all(&worktree_dir).unwrap();
 
         // Create nested structure
@@ -623,6 +1066,7 @@ mod tests {
         let runner = SetupRunner::new(Some(RepositorySettings {
             repository: "test".to_string(),
             copy_files: Some(vec![".claude".to_string()]),
+            symlink_files: None,
             setup_commands: None,
         }))
         .with_main_worktree(main_dir.clone());
@@ -632,6 +1076,7 @@ mod tests {
             path: worktree_dir.clone(),
             branch: Some("test".to_string()),
             is_main: false,
+            ..Default::default()
         };
 
         let result = runner.run_setup(&worktree);
@@ -666,6 +1111,7 @@ mod tests {
         let runner = SetupRunner::new(Some(RepositorySettings {
             repository: "test".to_string(),
             copy_files: Some(vec!["*.env".to_string()]), // No .env files exist
+            symlink_files: None,
             setup_commands: None,
         }))
         .with_main_worktree(main_dir.clone());
@@ -675,6 +1121,7 @@ mod tests {
             path: worktree_dir.clone(),
             branch: Some("test".to_string()),
             is_main: false,
+            ..Default::default()
         };
 
         // Should succeed even with no matches (silently skip)
@@ -696,8 +1143,8 @@ mod tests {
         let worktree_dir = temp_dir.join("worktree");
 
         let _ = fs::remove_dir_all(&temp_dir);
-        fs::create_dir_all(&main_dir.join("config")).unwrap();
-        fs::create_dir_all(&main_dir.join("nested").join("deep")).unwrap();
+        fs::create_dir_all(main_dir.join("config")).unwrap();
+        fs::create_dir_all(main_dir.join("nested").join("deep")).unwrap();
         fs::create_dir_all(&worktree_dir).unwrap();
 
         // Create json files at different levels
@@ -717,6 +1164,7 @@ mod tests {
         let runner = SetupRunner::new(Some(RepositorySettings {
             repository: "test".to_string(),
             copy_files: Some(vec!["**/*.json".to_string()]),
+            symlink_files: None,
             setup_commands: None,
         }))
         .with_main_worktree(main_dir.clone());
@@ -726,6 +1174,7 @@ mod tests {
             path: worktree_dir.clone(),
             branch: Some("test".to_string()),
             is_main: false,
+            ..Default::default()
         };
 
         let result = runner.run_setup(&worktree);
@@ -754,12 +1203,13 @@ mod tests {
         let worktree_dir = temp_dir.join("worktree");
 
         let _ = fs::remove_dir_all(&temp_dir);
-        fs::create_dir_all(&main_dir.join("empty_dir")).unwrap();
+        fs::create_dir_all(main_dir.join("empty_dir")).unwrap();
         fs::create_dir_all(&worktree_dir).unwrap();
 
         let runner = SetupRunner::new(Some(RepositorySettings {
             repository: "test".to_string(),
             copy_files: Some(vec!["empty_dir".to_string()]),
+            symlink_files: None,
             setup_commands: None,
         }))
         .with_main_worktree(main_dir.clone());
@@ -769,6 +1219,7 @@ mod tests {
             path: worktree_dir.clone(),
             branch: Some("test".to_string()),
             is_main: false,
+            ..Default::default()
         };
 
         let result = runner.run_setup(&worktree);
@@ -795,6 +1246,7 @@ mod tests {
         let runner = SetupRunner::new(Some(RepositorySettings {
             repository: "test".to_string(),
             copy_files: Some(vec!["nonexistent_dir".to_string()]),
+            symlink_files: None,
             setup_commands: None,
         }))
         .with_main_worktree(main_dir.clone());
@@ -804,6 +1256,7 @@ mod tests {
             path: worktree_dir.clone(),
             branch: Some("test".to_string()),
             is_main: false,
+            ..Default::default()
         };
 
         // Should succeed (silently skip nonexistent)
@@ -825,7 +1278,7 @@ mod tests {
         let worktree_dir = temp_dir.join("worktree");
 
         let _ = fs::remove_dir_all(&temp_dir);
-        fs::create_dir_all(&main_dir.join(".claude")).unwrap();
+        fs::create_dir_all(main_dir.join(".claude")).unwrap();
         fs::create_dir_all(&worktree_dir).unwrap();
 
         // Create various files
@@ -842,6 +1295,7 @@ mod tests {
                 ".claude".to_string(),   // directory
                 "README.md".to_string(), // single file
             ]),
+            symlink_files: None,
             setup_commands: None,
         }))
         .with_main_worktree(main_dir.clone());
@@ -851,6 +1305,7 @@ mod tests {
             path: worktree_dir.clone(),
             branch: Some("test".to_string()),
             is_main: false,
+            ..Default::default()
         };
 
         let result = runner.run_setup(&worktree);
@@ -864,4 +1319,757 @@ mod tests {
 
         let _ = fs::remove_dir_all(&temp_dir);
     }
+
+    // ========== Hook Runner Tests ==========
+
+    fn hook(event: HookEvent, command: &str, cwd: Option<&str>) -> Hook {
+        Hook {
+            event,
+            command: command.to_string(),
+            cwd: cwd.map(|c| c.to_string()),
+            timeout_secs: None,
+        }
+    }
+
+    #[test]
+    fn test_hook_runner_no_hooks() {
+        let runner = HookRunner::new(vec![]);
+        let worktree = create_test_worktree();
+
+        assert!(runner.run(HookEvent::PreCreate, &worktree).is_ok());
+    }
+
+    #[test]
+    fn test_hook_runner_runs_only_matching_event() {
+        use std::fs;
+
+        let temp_dir = std::env::temp_dir().join("gwm_test_hook_event");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let runner = HookRunner::new(vec![
+            hook(HookEvent::PostCreate, "touch post_create", None),
+            hook(HookEvent::PreDelete, "touch pre_delete", None),
+        ]);
+        let worktree = Worktree {
+            name: "hook-test".to_string(),
+            path: temp_dir.clone(),
+            branch: Some("hook-test".to_string()),
+            is_main: false,
+            ..Default::default()
+        };
+
+        let result = runner.run(HookEvent::PostCreate, &worktree);
+        assert!(result.is_ok());
+
+        assert!(temp_dir.join("post_create").exists());
+        assert!(!temp_dir.join("pre_delete").exists());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_hook_runner_expands_variables() {
+        use std::fs;
+
+        let temp_dir = std::env::temp_dir().join("gwm_test_hook_vars");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let runner = HookRunner::new(vec![hook(
+            HookEvent::PostCreate,
+            "echo \"$WORKTREE_NAME $WORKTREE_BRANCH\" > out.txt",
+            None,
+        )]);
+        let worktree = Worktree {
+            name: "vars".to_string(),
+            path: temp_dir.clone(),
+            branch: Some("feature/vars".to_string()),
+            is_main: false,
+            ..Default::default()
+        };
+
+        assert!(runner.run(HookEvent::PostCreate, &worktree).is_ok());
+
+        let content = fs::read_to_string(temp_dir.join("out.txt")).unwrap();
+        assert_eq!(content.trim(), "vars feature/vars");
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_hook_runner_uses_custom_cwd() {
+        use std::fs;
+
+        let temp_dir = std::env::temp_dir().join("gwm_test_hook_cwd");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(temp_dir.join("sub")).unwrap();
+
+        let runner = HookRunner::new(vec![hook(
+            HookEvent::PostCreate,
+            "touch marker",
+            Some("sub"),
+        )]);
+        let worktree = Worktree {
+            name: "cwd".to_string(),
+            path: temp_dir.clone(),
+            branch: None,
+            is_main: false,
+            ..Default::default()
+        };
+
+        assert!(runner.run(HookEvent::PostCreate, &worktree).is_ok());
+        assert!(temp_dir.join("sub").join("marker").exists());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_hook_runner_falls_back_to_repo_root() {
+        use std::fs;
+
+        let temp_dir = std::env::temp_dir().join("gwm_test_hook_root");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let runner = HookRunner::new(vec![hook(HookEvent::PreCreate, "touch pre_create", None)])
+            .with_repo_root(temp_dir.clone());
+        let worktree = Worktree {
+            name: "missing".to_string(),
+            path: temp_dir.join("does-not-exist"),
+            branch: None,
+            is_main: false,
+            ..Default::default()
+        };
+
+        assert!(runner.run(HookEvent::PreCreate, &worktree).is_ok());
+        assert!(temp_dir.join("pre_create").exists());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_hook_runner_failure() {
+        let runner = HookRunner::new(vec![hook(HookEvent::PreDelete, "exit 3", Some("/"))]);
+        let worktree = create_test_worktree();
+
+        let result = runner.run(HookEvent::PreDelete, &worktree);
+
+        match result {
+            Err(HookError::HookFailed(event, msg)) => {
+                assert_eq!(event, "pre_delete");
+                assert!(msg.contains("exit 3"));
+            }
+            _ => panic!("Expected HookFailed"),
+        }
+    }
+
+    #[test]
+    fn test_hook_runner_failure_reports_output_tail() {
+        let runner = HookRunner::new(vec![hook(
+            HookEvent::PreDelete,
+            "echo 'container busy' >&2; exit 1",
+            Some("/"),
+        )]);
+
+        let result = runner.run(HookEvent::PreDelete, &create_test_worktree());
+
+        match result {
+            Err(HookError::HookFailed(_, msg)) => {
+                assert!(
+                    msg.ends_with("exited with status: 1 (container busy)"),
+                    "{}",
+                    msg
+                );
+            }
+            other => panic!("Expected HookFailed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_hook_runner_kills_hook_after_timeout() {
+        let mut slow = hook(HookEvent::PreCreate, "sleep 5", Some("/"));
+        slow.timeout_secs = Some(1);
+        let runner = HookRunner::new(vec![slow]);
+        let started = Instant::now();
+
+        let result = runner.run(HookEvent::PreCreate, &create_test_worktree());
+
+        assert!(started.elapsed() < Duration::from_secs(4));
+        match result {
+            Err(HookError::HookFailed(event, msg)) => {
+                assert_eq!(event, "pre_create");
+                assert_eq!(msg, "'sleep 5' timed out after 1s");
+            }
+            other => panic!("Expected HookFailed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_hook_runner_closes_stdin() {
+        // `cat` would wait for keystrokes on an inherited terminal
+        let runner = HookRunner::new(vec![hook(HookEvent::PostCreate, "cat", Some("/"))]);
+
+        assert!(runner
+            .run(HookEvent::PostCreate, &create_test_worktree())
+            .is_ok());
+    }
+
+    // ========== Setup Report Tests ==========
+
+    /// Runner copying and linking files from a fresh main worktree, plus the
+    /// worktree to set up
+    fn report_fixture(
+        copy_files: &[&str],
+        symlink_files: &[&str],
+    ) -> (tempfile::TempDir, SetupRunner, Worktree) {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let main_dir = temp_dir.path().join("main");
+        let worktree_dir = temp_dir.path().join("worktree");
+        std::fs::create_dir_all(&main_dir).unwrap();
+        std::fs::create_dir_all(&worktree_dir).unwrap();
+
+        let runner = SetupRunner::new(Some(RepositorySettings {
+            repository: "test".to_string(),
+            copy_files: Some(copy_files.iter().map(|f| f.to_string()).collect()),
+            symlink_files: Some(symlink_files.iter().map(|f| f.to_string()).collect()),
+            setup_commands: None,
+        }))
+        .with_main_worktree(main_dir);
+        let worktree = Worktree {
+            name: "worktree".to_string(),
+            path: worktree_dir,
+            branch: None,
+            is_main: false,
+            ..Default::default()
+        };
+        (temp_dir, runner, worktree)
+    }
+
+    #[test]
+    fn test_run_setup_reports_copied_destinations() {
+        let (temp_dir, runner, worktree) = report_fixture(&[".env", "*.toml", "config"], &[]);
+        let main_dir = temp_dir.path().join("main");
+        std::fs::write(main_dir.join(".env"), "A=1").unwrap();
+        std::fs::write(main_dir.join("a.toml"), "").unwrap();
+        std::fs::create_dir_all(main_dir.join("config")).unwrap();
+        std::fs::write(main_dir.join("config/local.json"), "{}").unwrap();
+
+        let report = runner.run_setup(&worktree).unwrap();
+
+        assert_eq!(
+            report.copied,
+            vec![
+                worktree.path.join(".env"),
+                worktree.path.join("a.toml"),
+                worktree.path.join("config/local.json"),
+            ]
+        );
+        assert!(report.missing.is_empty());
+        assert_eq!(report.summary().as_deref(), Some("copied 3 file(s)"));
+    }
+
+    #[test]
+    fn test_run_setup_reports_missing_entries() {
+        let (temp_dir, runner, worktree) = report_fixture(&[".env", "*.local", "empty"], &[]);
+        // An existing but empty directory is not missing
+        std::fs::create_dir_all(temp_dir.path().join("main/empty")).unwrap();
+
+        let report = runner.run_setup(&worktree).unwrap();
+
+        assert!(report.copied.is_empty());
+        assert_eq!(report.missing, vec![".env", "*.local"]);
+        assert_eq!(
+            report.summary().as_deref(),
+            Some("not found: .env, *.local")
+        );
+    }
+
+    #[test]
+    fn test_run_setup_strict_copy_fails_on_missing_entry() {
+        let (_temp_dir, runner, worktree) = report_fixture(&[".env"], &[]);
+        let runner = runner.with_strict_copy(true);
+
+        let result = runner.run_setup(&worktree);
+
+        assert!(matches!(result, Err(HookError::MissingCopySource(p)) if p == ".env"));
+    }
+
+    #[test]
+    fn test_run_setup_reports_partial_copy_failure() {
+        let (temp_dir, runner, worktree) = report_fixture(&["a.txt", "dir/b.txt"], &[]);
+        let main_dir = temp_dir.path().join("main");
+        std::fs::write(main_dir.join("a.txt"), "a").unwrap();
+        std::fs::create_dir_all(main_dir.join("dir")).unwrap();
+        std::fs::write(main_dir.join("dir/b.txt"), "b").unwrap();
+        // A file where the destination directory should go makes the second copy fail
+        std::fs::write(worktree.path.join("dir"), "").unwrap();
+
+        let result = runner.run_setup(&worktree);
+
+        let Err(HookError::CopyIncomplete { copied, error }) = result else {
+            panic!("Expected CopyIncomplete, got {:?}", result);
+        };
+        assert_eq!(copied, 1);
+        assert!(!error.is_empty());
+    }
+
+    #[test]
+    fn test_copy_files_overlapping_patterns_copied_once() {
+        let (temp_dir, runner, worktree) = report_fixture(
+            &[
+                ".env",
+                ".env*",
+                ".claude",
+                ".claude/*",
+                "config/a.yml",
+                "config",
+            ],
+            &[],
+        );
+        let main_dir = temp_dir.path().join("main");
+        std::fs::write(main_dir.join(".env"), "ENV=1").unwrap();
+        std::fs::write(main_dir.join(".env.local"), "LOCAL=1").unwrap();
+        std::fs::create_dir_all(main_dir.join(".claude")).unwrap();
+        std::fs::write(main_dir.join(".claude/settings.json"), "{}").unwrap();
+        std::fs::create_dir_all(main_dir.join("config")).unwrap();
+        std::fs::write(main_dir.join("config/a.yml"), "a: 1").unwrap();
+
+        let report = runner.run_setup(&worktree).unwrap();
+
+        // Each destination is written exactly once, whichever entry names it first
+        assert_eq!(
+            report.copied,
+            vec![
+                worktree.path.join(".env"),
+                worktree.path.join(".env.local"),
+                worktree.path.join(".claude/settings.json"),
+                worktree.path.join("config/a.yml"),
+            ]
+        );
+        assert!(report.missing.is_empty());
+    }
+
+    // ========== Symlink Tests ==========
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_files_point_at_main_worktree() {
+        let (temp_dir, runner, worktree) = report_fixture(&[], &[".env.vault", "cache", "*.db"]);
+        let main_dir = temp_dir.path().join("main");
+        std::fs::write(main_dir.join(".env.vault"), "secret").unwrap();
+        std::fs::create_dir_all(main_dir.join("cache")).unwrap();
+        std::fs::write(main_dir.join("cache/blob"), "x").unwrap();
+        std::fs::write(main_dir.join("dev.db"), "").unwrap();
+
+        let report = runner.run_setup(&worktree).unwrap();
+
+        assert_eq!(
+            report.linked,
+            vec![
+                worktree.path.join(".env.vault"),
+                worktree.path.join("cache"),
+                worktree.path.join("dev.db"),
+            ]
+        );
+        assert!(report.copied.is_empty());
+        assert_eq!(
+            std::fs::read_link(worktree.path.join(".env.vault")).unwrap(),
+            main_dir.join(".env.vault")
+        );
+        // Directories are linked as a whole rather than recreated
+        assert_eq!(
+            std::fs::read_link(worktree.path.join("cache")).unwrap(),
+            main_dir.join("cache")
+        );
+        assert_eq!(
+            std::fs::read_to_string(worktree.path.join("cache/blob")).unwrap(),
+            "x"
+        );
+        assert_eq!(report.summary().as_deref(), Some("linked 3 file(s)"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_files_nested_creates_parent_dirs() {
+        let (temp_dir, runner, worktree) = report_fixture(&[], &["config/secrets.json"]);
+        let main_dir = temp_dir.path().join("main");
+        std::fs::create_dir_all(main_dir.join("config")).unwrap();
+        std::fs::write(main_dir.join("config/secrets.json"), "{}").unwrap();
+
+        runner.run_setup(&worktree).unwrap();
+
+        assert_eq!(
+            std::fs::read_link(worktree.path.join("config/secrets.json")).unwrap(),
+            main_dir.join("config/secrets.json")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_files_existing_destination_fails() {
+        let (temp_dir, runner, worktree) = report_fixture(&[], &[".env"]);
+        std::fs::write(temp_dir.path().join("main/.env"), "A=1").unwrap();
+        std::fs::write(worktree.path.join(".env"), "tracked").unwrap();
+
+        let result = runner.run_setup(&worktree);
+
+        assert!(matches!(result, Err(HookError::LinkFailed(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_files_rerun_keeps_existing_links() {
+        let (temp_dir, runner, worktree) = report_fixture(&[], &[".env.vault"]);
+        std::fs::write(temp_dir.path().join("main/.env.vault"), "secret").unwrap();
+        runner.run_setup(&worktree).unwrap();
+
+        let report = runner.run_setup(&worktree).unwrap();
+
+        assert!(report.linked.is_empty());
+        assert_eq!(
+            std::fs::read_link(worktree.path.join(".env.vault")).unwrap(),
+            temp_dir.path().join("main/.env.vault")
+        );
+    }
+
+    #[test]
+    fn test_symlink_files_missing_entry_reported() {
+        let (_temp_dir, runner, worktree) = report_fixture(&[], &["node_modules"]);
+
+        let report = runner.run_setup(&worktree).unwrap();
+
+        assert!(report.linked.is_empty());
+        assert_eq!(report.missing, vec!["node_modules"]);
+    }
+
+    #[test]
+    fn test_copy_files_skips_existing_destination() {
+        let (temp_dir, runner, worktree) = report_fixture(&[".env"], &[]);
+        std::fs::write(temp_dir.path().join("main/.env"), "A=1").unwrap();
+        std::fs::write(worktree.path.join(".env"), "A=local").unwrap();
+
+        let report = runner.run_setup(&worktree).unwrap();
+
+        assert!(report.copied.is_empty());
+        assert_eq!(report.existing, vec![worktree.path.join(".env")]);
+        assert_eq!(
+            std::fs::read_to_string(worktree.path.join(".env")).unwrap(),
+            "A=local"
+        );
+        assert_eq!(
+            report.summary().as_deref(),
+            Some("skipped 1 existing file(s)")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_files_keeps_source_behind_symlinked_destination() {
+        let (temp_dir, runner, worktree) = report_fixture(&[".env"], &[]);
+        let source = temp_dir.path().join("main/.env");
+        std::fs::write(&source, "A=1").unwrap();
+        std::os::unix::fs::symlink(&source, worktree.path.join(".env")).unwrap();
+
+        let report = runner.run_setup(&worktree).unwrap();
+
+        assert_eq!(report.existing.len(), 1);
+        assert_eq!(std::fs::read_to_string(&source).unwrap(), "A=1");
+    }
+
+    #[test]
+    fn test_setup_report_summary_skipped_links() {
+        let report = SetupReport {
+            skipped_links: vec![".env".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            report.summary().as_deref(),
+            Some("symlinks unsupported, skipped: .env")
+        );
+    }
+
+    // ========== Path Expansion Tests ==========
+
+    #[test]
+    #[serial_test::serial]
+    fn test_copy_files_expands_env_var_outside_repo() {
+        let templates = tempfile::TempDir::new().unwrap();
+        std::fs::write(templates.path().join(".env"), "SHARED=1").unwrap();
+        std::fs::write(templates.path().join("a.json"), "{}").unwrap();
+        std::env::set_var("GWM_TEST_TEMPLATES", templates.path());
+        let (_temp_dir, runner, worktree) = report_fixture(
+            &["$GWM_TEST_TEMPLATES/.env", "${GWM_TEST_TEMPLATES}/*.json"],
+            &[],
+        );
+
+        let report = runner.run_setup(&worktree);
+        std::env::remove_var("GWM_TEST_TEMPLATES");
+
+        // Files from outside the main worktree land in the worktree root
+        let report = report.unwrap();
+        assert_eq!(
+            report.copied,
+            vec![worktree.path.join(".env"), worktree.path.join("a.json")]
+        );
+        assert_eq!(
+            std::fs::read_to_string(worktree.path.join(".env")).unwrap(),
+            "SHARED=1"
+        );
+    }
+
+    #[test]
+    fn test_copy_files_expands_tilde() {
+        let (_temp_dir, runner, worktree) = report_fixture(&["~/gwm-surely-missing-template"], &[]);
+
+        let report = runner.run_setup(&worktree).unwrap();
+
+        // The entry is looked up in the home directory, not under the main worktree
+        assert!(report.copied.is_empty());
+        assert_eq!(report.missing, vec!["~/gwm-surely-missing-template"]);
+        assert!(!worktree.path.join("~").exists());
+    }
+
+    #[test]
+    fn test_copy_files_relative_path_resolves_against_main_worktree() {
+        let (temp_dir, runner, worktree) = report_fixture(&["config/local.yml"], &[]);
+        let main_dir = temp_dir.path().join("main");
+        std::fs::create_dir_all(main_dir.join("config")).unwrap();
+        std::fs::write(main_dir.join("config/local.yml"), "a: 1").unwrap();
+
+        let report = runner.run_setup(&worktree).unwrap();
+
+        assert_eq!(report.copied, vec![worktree.path.join("config/local.yml")]);
+    }
+
+    #[test]
+    fn test_setup_report_summary_empty() {
+        assert_eq!(SetupReport::default().summary(), None);
+    }
+
+    // ========== Setup Shell Tests ==========
+
+    fn setup_worktree(path: PathBuf) -> Worktree {
+        Worktree {
+            name: "setup".to_string(),
+            path,
+            branch: Some("feature/setup".to_string()),
+            is_main: false,
+            ..Default::default()
+        }
+    }
+
+    fn setup_runner(commands: Vec<SetupCommand>) -> SetupRunner {
+        SetupRunner::new(Some(RepositorySettings {
+            repository: "test".to_string(),
+            copy_files: None,
+            symlink_files: None,
+            setup_commands: Some(commands),
+        }))
+    }
+
+    #[test]
+    fn test_script_flag_by_interpreter() {
+        assert_eq!(script_flag("sh"), "-c");
+        assert_eq!(script_flag("/usr/bin/fish"), "-c");
+        assert_eq!(script_flag("cmd"), "/C");
+        assert_eq!(script_flag("cmd.exe"), "/C");
+        assert_eq!(script_flag("pwsh"), "-Command");
+        assert_eq!(script_flag("PowerShell.exe"), "-Command");
+    }
+
+    #[test]
+    fn test_shell_command_passes_extra_words_before_flag() {
+        let command = shell_command("bash -l", "npm install");
+
+        assert_eq!(command.get_program(), "bash");
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, vec!["-l", "-c", "npm install"]);
+    }
+
+    #[test]
+    fn test_run_setup_uses_configured_shell() {
+        use std::fs;
+
+        let temp_dir = std::env::temp_dir().join("gwm_test_setup_shell");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        // `sh -e` stops at the failing `false`, plain `sh` would go on
+        let runner = setup_runner(vec!["false; touch reached".into()]).with_shell("sh -e");
+        let result = runner.run_setup(&setup_worktree(temp_dir.clone()));
+
+        assert!(result.is_err());
+        assert!(!temp_dir.join("reached").exists());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_run_setup_per_command_shell_overrides_default() {
+        use std::fs;
+
+        let temp_dir = std::env::temp_dir().join("gwm_test_setup_command_shell");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let runner = setup_runner(vec![SetupCommand::Table {
+            command: "touch ran".to_string(),
+            shell: Some("sh".to_string()),
+            timeout_secs: None,
+        }])
+        .with_shell("no-such-shell-for-gwm");
+        let result = runner.run_setup(&setup_worktree(temp_dir.clone()));
+
+        assert!(result.is_ok(), "{:?}", result);
+        assert!(temp_dir.join("ran").exists());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_run_setup_missing_shell_names_it() {
+        let runner = setup_runner(vec!["true".into()]).with_shell("no-such-shell-for-gwm");
+
+        let result = runner.run_setup(&setup_worktree(std::env::temp_dir()));
+
+        match result {
+            Err(HookError::ExecutionFailed(msg)) => {
+                assert!(msg.contains("with no-such-shell-for-gwm"), "{}", msg);
+            }
+            other => panic!("Expected ExecutionFailed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_run_setup_failure_reports_output_tail() {
+        let runner = setup_runner(vec![
+            "echo progress; echo one >&2; echo two >&2; echo three >&2; echo four >&2; exit 2"
+                .into(),
+        ]);
+
+        let result = runner.run_setup(&setup_worktree(std::env::temp_dir()));
+
+        match result {
+            Err(HookError::ExecutionFailed(msg)) => {
+                assert!(
+                    msg.ends_with("exited with status: 2 (two / three / four)"),
+                    "{}",
+                    msg
+                );
+            }
+            other => panic!("Expected ExecutionFailed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_run_setup_failure_falls_back_to_stdout() {
+        let runner = setup_runner(vec!["echo missing script; exit 1".into()]);
+
+        let result = runner.run_setup(&setup_worktree(std::env::temp_dir()));
+
+        match result {
+            Err(HookError::ExecutionFailed(msg)) => {
+                assert!(msg.ends_with("(missing script)"), "{}", msg);
+            }
+            other => panic!("Expected ExecutionFailed, got {:?}", other),
+        }
+    }
+
+    // ========== Setup Timeout Tests ==========
+
+    #[test]
+    fn test_run_setup_kills_command_after_timeout() {
+        let runner =
+            setup_runner(vec!["sleep 5".into()]).with_timeout(Some(Duration::from_millis(200)));
+        let started = Instant::now();
+
+        let result = runner.run_setup(&setup_worktree(std::env::temp_dir()));
+
+        assert!(started.elapsed() < Duration::from_secs(4));
+        match result {
+            Err(HookError::Timeout(command, limit)) => {
+                assert_eq!(command, "sleep 5");
+                assert_eq!(limit, Duration::from_millis(200));
+            }
+            other => panic!("Expected Timeout, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_run_setup_timeout_covers_background_output() {
+        // The backgrounded sleep keeps stdout open after the shell exits
+        let runner =
+            setup_runner(vec!["sleep 5 &".into()]).with_timeout(Some(Duration::from_millis(200)));
+        let started = Instant::now();
+
+        let result = runner.run_setup(&setup_worktree(std::env::temp_dir()));
+
+        assert!(started.elapsed() < Duration::from_secs(4));
+        assert!(
+            matches!(result, Err(HookError::Timeout(..))),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_run_setup_command_timeout_overrides_default() {
+        let runner = setup_runner(vec![SetupCommand::Table {
+            command: "sleep 0.1".to_string(),
+            shell: None,
+            timeout_secs: Some(5),
+        }])
+        .with_timeout(Some(Duration::from_millis(10)));
+
+        let result = runner.run_setup(&setup_worktree(std::env::temp_dir()));
+
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_run_setup_cancel_kills_running_command() {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let runner =
+            setup_runner(vec!["sleep 5".into(), "touch never".into()]).with_cancel(cancel.clone());
+        let flag = cancel.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(200));
+            flag.store(true, Ordering::Relaxed);
+        });
+        let started = Instant::now();
+
+        let result = runner.run_setup(&setup_worktree(std::env::temp_dir()));
+
+        assert!(started.elapsed() < Duration::from_secs(4));
+        assert!(matches!(result, Err(HookError::Cancelled)));
+    }
+
+    #[test]
+    fn test_run_setup_cancel_stops_waiting_for_background_output() {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let runner = setup_runner(vec!["sleep 5 &".into()]).with_cancel(cancel.clone());
+        let flag = cancel.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(200));
+            flag.store(true, Ordering::Relaxed);
+        });
+        let started = Instant::now();
+
+        let result = runner.run_setup(&setup_worktree(std::env::temp_dir()));
+
+        assert!(started.elapsed() < Duration::from_secs(4));
+        assert!(matches!(result, Err(HookError::Cancelled)));
+    }
+
+    #[test]
+    fn test_run_setup_cancelled_before_start_does_nothing() {
+        let cancel = Arc::new(AtomicBool::new(true));
+        let runner = setup_runner(vec!["true".into()]).with_cancel(cancel);
+
+        let result = runner.run_setup(&setup_worktree(std::env::temp_dir()));
+
+        assert!(matches!(result, Err(HookError::Cancelled)));
+    }
 }
@@ -1,21 +1,150 @@
-use crate::app::{App, AppMode};
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crate::app::{App, AppMode, ConfirmAction, Focus, MessageLevel};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 
 pub enum InputResult {
     Continue,
     Quit,
 }
 
+/// Key bindings grouped by mode, shown in the help overlay
+/// Keep in sync with the handlers below.
+pub const KEY_BINDINGS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "Normal",
+        &[
+            ("↑ / C-p", "Move up"),
+            ("↓ / C-n", "Move down"),
+            ("PgUp / PgDn", "Move by page"),
+            ("Home / End", "Move to first / last"),
+            ("Tab", "Focus list / details (↑↓ target)"),
+            ("J / K", "Scroll details down / up"),
+            ("Enter", "Open selected worktree"),
+            ("C-o", "Create new worktree"),
+            ("B", "Checkout branch into worktree and open it"),
+            ("N", "New branch off the selected worktree"),
+            ("C-d", "Delete worktree (or all marked)"),
+            ("dd", "Delete worktree (empty search)"),
+            ("Space", "Mark worktree for deletion"),
+            ("D", "Prune merged worktrees"),
+            ("M", "Move worktree"),
+            ("R", "Repair worktree links"),
+            ("S", "Re-run setup in worktree"),
+            ("P", "Prune entries of removed worktree dirs"),
+            ("F", "Fetch all remotes, then refresh"),
+            ("L", "Show message log"),
+            ("C", "Toggle compact (single-line) rows"),
+            ("C-s", "Cycle sort order"),
+            ("C-y", "Copy worktree path"),
+            ("yy", "Copy worktree path (empty search)"),
+            ("Y", "Copy branch name (commit for detached HEAD)"),
+            ("C-e", "Open in editor"),
+            ("V", "View changes (diff)"),
+            ("O", "Open in file manager"),
+            ("C-l", "Lock (with reason) / unlock worktree"),
+            ("C-r", "Refresh from local git state (no fetch)"),
+            ("C-f", "Fetch default remote"),
+            ("C-g", "Pull from default remote"),
+            ("?", "Show help"),
+            ("Esc / C-c", "Clear search or quit"),
+            ("C-q", "Quit"),
+            ("a-z", "Filter by name, branch, path or commit"),
+            ("← / →", "Move cursor"),
+            ("C-a", "Move cursor to start"),
+            ("C-w", "Delete word before cursor"),
+            ("C-u", "Delete to start of query"),
+        ],
+    ),
+    (
+        "Create",
+        &[
+            ("↑ / C-p", "Move up"),
+            ("↓ / C-n", "Move down"),
+            ("PgUp / PgDn", "Move by page"),
+            ("Home / End", "Move to first / last"),
+            ("Enter", "Create worktree"),
+            ("Tab", "Switch branch / worktree name / start point"),
+            ("C-d", "Prune worktrees merged into selected branch"),
+            ("← / →", "Move cursor"),
+            ("C-a / C-e", "Move cursor to start / end"),
+            ("C-w", "Delete word before cursor"),
+            ("C-u", "Delete to start of input"),
+            ("Esc / C-c", "Cancel"),
+        ],
+    ),
+    (
+        "Move",
+        &[
+            ("Enter", "Move worktree to path"),
+            ("← / →", "Move cursor"),
+            ("C-a / C-e", "Move cursor to start / end"),
+            ("C-w", "Delete word before cursor"),
+            ("C-u", "Delete to start of input"),
+            ("Esc / C-c", "Cancel"),
+        ],
+    ),
+    (
+        "Confirm",
+        &[
+            ("y / Enter", "Delete worktree only"),
+            ("Y", "Delete worktree and branch"),
+            ("f", "Force delete (ignore lock)"),
+            ("s", "Stash changes, then delete (dirty worktree)"),
+            ("n / Esc", "Cancel"),
+        ],
+    ),
+    (
+        "Help",
+        &[
+            ("↑↓", "Scroll"),
+            ("gg / G", "Scroll to top / bottom"),
+            ("c", "Show configuration"),
+            ("? / Esc / q", "Close"),
+        ],
+    ),
+];
+
 pub fn handle_key_event(app: &mut App, key: KeyEvent) -> InputResult {
     // Clear any previous message
     app.clear_message();
 
+    // A quit prompt only applies while the operation it interrupted runs
+    if !app.operation_in_progress() {
+        app.quit_pending = false;
+    }
+
+    // Any other key cancels a pending chord
+    if !matches!((app.pending_key, key.code), (Some((p, _)), KeyCode::Char(c)) if p == c) {
+        app.pending_key = None;
+    }
+
     match app.mode {
         AppMode::Normal => handle_normal_mode(app, key),
         AppMode::Create => handle_create_mode(app, key),
         AppMode::Confirm => handle_confirm_mode(app, key),
-        AppMode::Deleting => handle_deleting_mode(key),
-        AppMode::Config => handle_config_mode(app, key),
+        AppMode::Move | AppMode::LockReason => handle_prompt_mode(app, key),
+        AppMode::Deleting | AppMode::Creating | AppMode::Fetching | AppMode::SettingUp => {
+            handle_progress_mode(app, key)
+        }
+        AppMode::Config | AppMode::MessageLog => handle_config_mode(app, key),
+        AppMode::Help => handle_help_mode(app, key),
+    }
+}
+
+/// Clicking a worktree selects it and the wheel moves the selection (`ui.mouse`)
+pub fn handle_mouse_event(app: &mut App, mouse: MouseEvent) {
+    if app.mode != AppMode::Normal {
+        return;
+    }
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            if let Some(index) = app.worktree_index_at(mouse.column, mouse.row) {
+                app.selected_worktree = index;
+                app.focus = Focus::List;
+            }
+        }
+        MouseEventKind::ScrollUp => app.move_up(),
+        MouseEventKind::ScrollDown => app.move_down(),
+        _ => {}
     }
 }
 
@@ -44,13 +173,41 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) -> InputResult {
             }
         }
 
+        // Switch the pane Up / Down drive
+        (KeyCode::Tab, _) => {
+            app.toggle_focus();
+            InputResult::Continue
+        }
+
         // Navigation
         (KeyCode::Up, _) | (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
-            app.move_up();
+            match app.focus {
+                Focus::List => app.move_up(),
+                Focus::Detail => app.scroll_detail_up(),
+            }
             InputResult::Continue
         }
         (KeyCode::Down, _) | (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
-            app.move_down();
+            match app.focus {
+                Focus::List => app.move_down(),
+                Focus::Detail => app.scroll_detail_down(),
+            }
+            InputResult::Continue
+        }
+        (KeyCode::PageUp, _) => {
+            app.page_up();
+            InputResult::Continue
+        }
+        (KeyCode::PageDown, _) => {
+            app.page_down();
+            InputResult::Continue
+        }
+        (KeyCode::Home, _) => {
+            app.move_first();
+            InputResult::Continue
+        }
+        (KeyCode::End, _) => {
+            app.move_last();
             InputResult::Continue
         }
 
@@ -67,28 +224,204 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) -> InputResult {
         // Create mode
         (KeyCode::Char('o'), KeyModifiers::CONTROL) => {
             if let Err(e) = app.enter_create_mode() {
-                app.message = Some(format!("Error: {}", e));
+                app.set_message(MessageLevel::Error, format!("Error: {}", e));
             }
             InputResult::Continue
         }
 
         // Delete
         (KeyCode::Char('d'), KeyModifiers::CONTROL) => {
-            app.enter_confirm_delete();
+            if let Err(e) = app.enter_confirm_delete() {
+                app.set_message(MessageLevel::Error, format!("Error: {}", e));
+            }
+            InputResult::Continue
+        }
+
+        // Re-run setup in the selected worktree (S - only when not searching)
+        (KeyCode::Char('S'), _) if app.input.is_empty() => {
+            app.rerun_setup();
+            InputResult::Continue
+        }
+
+        // Checkout a branch into a worktree and open it (B - only when not searching)
+        (KeyCode::Char('B'), _) if app.input.is_empty() => {
+            if let Err(e) = app.enter_checkout_mode() {
+                app.set_message(MessageLevel::Error, format!("Error: {}", e));
+            }
+            InputResult::Continue
+        }
+
+        // New branch off the selected worktree (N - only when not searching)
+        (KeyCode::Char('N'), _) if app.input.is_empty() => {
+            if let Err(e) = app.enter_quick_create_mode() {
+                app.set_message(MessageLevel::Error, format!("Error: {}", e));
+            }
             InputResult::Continue
         }
 
         // Prune (D - only when not searching)
         (KeyCode::Char('D'), _) if app.input.is_empty() => {
             if let Err(e) = app.enter_confirm_prune() {
-                app.message = Some(format!("Error: {}", e));
+                app.set_message(MessageLevel::Error, format!("Error: {}", e));
+            }
+            InputResult::Continue
+        }
+
+        // Move (M - only when not searching)
+        (KeyCode::Char('M'), _) if app.input.is_empty() => {
+            app.enter_move_mode();
+            InputResult::Continue
+        }
+
+        // Repair (R - only when not searching)
+        (KeyCode::Char('R'), _) if app.input.is_empty() => {
+            if let Err(e) = app.repair_worktrees() {
+                app.set_message(MessageLevel::Error, format!("Error: {}", e));
+            }
+            InputResult::Continue
+        }
+
+        // Prune entries of removed directories (P - only when not searching)
+        (KeyCode::Char('P'), _) if app.input.is_empty() => {
+            if let Err(e) = app.prune_stale_worktrees() {
+                app.set_message(MessageLevel::Error, format!("Error: {}", e));
+            }
+            InputResult::Continue
+        }
+
+        // Fetch all remotes (F - only when not searching)
+        (KeyCode::Char('F'), _) if app.input.is_empty() => {
+            app.fetch_all();
+            InputResult::Continue
+        }
+
+        // Copy branch name (Y - only when not searching)
+        (KeyCode::Char('Y'), _) if app.input.is_empty() => {
+            app.copy_selected_branch();
+            InputResult::Continue
+        }
+
+        // Reveal in file manager (O - only when not searching)
+        (KeyCode::Char('O'), _) if app.input.is_empty() => {
+            app.reveal_selected();
+            InputResult::Continue
+        }
+
+        // Scroll the detail pane (J / K - only when not searching)
+        (KeyCode::Char('J'), _) if app.input.is_empty() => {
+            app.scroll_detail_down();
+            InputResult::Continue
+        }
+        (KeyCode::Char('K'), _) if app.input.is_empty() => {
+            app.scroll_detail_up();
+            InputResult::Continue
+        }
+
+        // View the worktree's changes (V - only when not searching)
+        (KeyCode::Char('V'), _) if app.input.is_empty() => {
+            app.show_diff();
+            InputResult::Continue
+        }
+
+        // Single-line rows on/off (C - only when not searching)
+        (KeyCode::Char('C'), _) if app.input.is_empty() => {
+            app.toggle_compact_list();
+            InputResult::Continue
+        }
+
+        // Message log (L - only when not searching)
+        (KeyCode::Char('L'), _) if app.input.is_empty() => {
+            app.enter_message_log_mode();
+            InputResult::Continue
+        }
+
+        // Cycle sort mode
+        (KeyCode::Char('s'), KeyModifiers::CONTROL) => {
+            app.cycle_sort();
+            InputResult::Continue
+        }
+
+        // Copy path
+        (KeyCode::Char('y'), KeyModifiers::CONTROL) => {
+            app.copy_selected_path();
+            InputResult::Continue
+        }
+
+        // Open in editor
+        (KeyCode::Char('e'), KeyModifiers::CONTROL) => {
+            app.open_in_editor();
+            InputResult::Continue
+        }
+
+        // Fetch / pull
+        (KeyCode::Char('f'), KeyModifiers::CONTROL) => {
+            app.fetch_selected();
+            InputResult::Continue
+        }
+        (KeyCode::Char('g'), KeyModifiers::CONTROL) => {
+            app.pull_selected();
+            InputResult::Continue
+        }
+
+        // Re-read worktrees and branches
+        (KeyCode::Char('r'), KeyModifiers::CONTROL) => {
+            if let Err(e) = app.refresh() {
+                app.set_message(MessageLevel::Error, format!("Error: {}", e));
             }
             InputResult::Continue
         }
 
-        // Config
+        // Toggle lock
+        (KeyCode::Char('l'), KeyModifiers::CONTROL) => {
+            if let Err(e) = app.toggle_lock_selected() {
+                app.set_message(MessageLevel::Error, format!("Error: {}", e));
+            }
+            InputResult::Continue
+        }
+
+        // Help
         (KeyCode::Char('?'), _) => {
-            app.enter_config_mode();
+            app.enter_help_mode();
+            InputResult::Continue
+        }
+
+        // Mark for batch deletion
+        (KeyCode::Char(' '), _) => {
+            app.toggle_mark();
+            InputResult::Continue
+        }
+
+        // Cursor movement in the search query
+        (KeyCode::Left, _) => {
+            app.move_cursor_left();
+            InputResult::Continue
+        }
+        (KeyCode::Right, _) => {
+            app.move_cursor_right();
+            InputResult::Continue
+        }
+        (KeyCode::Char('a'), KeyModifiers::CONTROL) => {
+            app.move_cursor_start();
+            InputResult::Continue
+        }
+        (KeyCode::Char('w'), KeyModifiers::CONTROL) => {
+            app.delete_word();
+            InputResult::Continue
+        }
+        (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
+            app.delete_to_line_start();
+            InputResult::Continue
+        }
+
+        // vi-style chords: dd deletes, yy copies the path
+        (KeyCode::Char(c @ ('d' | 'y')), KeyModifiers::NONE) if vi_chord(app, c) => {
+            if c == 'd' {
+                if let Err(e) = app.enter_confirm_delete_always() {
+                    app.set_message(MessageLevel::Error, format!("Error: {}", e));
+                }
+            } else {
+                app.copy_selected_path();
+            }
             InputResult::Continue
         }
 
@@ -106,6 +439,87 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) -> InputResult {
     }
 }
 
+/// Feed `d` / `y` to the vi-style chords, which only start on an empty
+/// search. The first key is still typed into the search, so a lone `d`
+/// filters as before; it is taken back when the second key completes the
+/// chord.
+fn vi_chord(app: &mut App, c: char) -> bool {
+    if app.input.is_empty() {
+        app.chord(c);
+        app.chord_selection = app.selected_worktree;
+        return false;
+    }
+    if app.input.len() == 1 && app.input.starts_with(c) && app.pending_key.is_some() && app.chord(c)
+    {
+        // Undo the search, including the jump to its first match
+        app.delete_char();
+        app.selected_worktree = app
+            .chord_selection
+            .min(app.filtered_worktrees.len().saturating_sub(1));
+        return true;
+    }
+    false
+}
+
+/// Single-line prompts: the Move destination and the LockReason reason
+fn handle_prompt_mode(app: &mut App, key: KeyEvent) -> InputResult {
+    match (key.code, key.modifiers) {
+        // Cancel
+        (KeyCode::Esc, _) | (KeyCode::Char('c'), KeyModifiers::CONTROL) => {
+            app.enter_normal_mode();
+            InputResult::Continue
+        }
+
+        (KeyCode::Enter, _) => {
+            let result = if app.mode == AppMode::LockReason {
+                app.lock_selected()
+            } else {
+                app.move_worktree()
+            };
+            if let Err(e) = result {
+                app.set_message(MessageLevel::Error, format!("Error: {}", e));
+            }
+            InputResult::Continue
+        }
+
+        // Cursor movement and editing
+        (KeyCode::Left, _) => {
+            app.move_cursor_left();
+            InputResult::Continue
+        }
+        (KeyCode::Right, _) => {
+            app.move_cursor_right();
+            InputResult::Continue
+        }
+        (KeyCode::Home, _) | (KeyCode::Char('a'), KeyModifiers::CONTROL) => {
+            app.move_cursor_start();
+            InputResult::Continue
+        }
+        (KeyCode::End, _) | (KeyCode::Char('e'), KeyModifiers::CONTROL) => {
+            app.move_cursor_end();
+            InputResult::Continue
+        }
+        (KeyCode::Char('w'), KeyModifiers::CONTROL) => {
+            app.delete_word();
+            InputResult::Continue
+        }
+        (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
+            app.delete_to_line_start();
+            InputResult::Continue
+        }
+        (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+            app.input_char(c);
+            InputResult::Continue
+        }
+        (KeyCode::Backspace, _) => {
+            app.delete_char();
+            InputResult::Continue
+        }
+
+        _ => InputResult::Continue,
+    }
+}
+
 fn handle_create_mode(app: &mut App, key: KeyEvent) -> InputResult {
     match (key.code, key.modifiers) {
         // Cancel
@@ -123,12 +537,73 @@ fn handle_create_mode(app: &mut App, key: KeyEvent) -> InputResult {
             app.move_down();
             InputResult::Continue
         }
+        (KeyCode::PageUp, _) => {
+            app.page_up();
+            InputResult::Continue
+        }
+        (KeyCode::PageDown, _) => {
+            app.page_down();
+            InputResult::Continue
+        }
+        (KeyCode::Home, _) => {
+            app.move_first();
+            InputResult::Continue
+        }
+        (KeyCode::End, _) => {
+            app.move_last();
+            InputResult::Continue
+        }
+
+        // Prune worktrees merged into the selected branch
+        (KeyCode::Char('d'), KeyModifiers::CONTROL) => {
+            if let Err(e) = app.enter_confirm_prune_into_selected() {
+                app.set_message(MessageLevel::Error, format!("Error: {}", e));
+            }
+            InputResult::Continue
+        }
+
+        // Switch between branch name and worktree name
+        (KeyCode::Tab, _) => {
+            app.toggle_create_field();
+            InputResult::Continue
+        }
 
         // Create worktree
         (KeyCode::Enter, _) => {
             if let Err(e) = app.create_worktree() {
-                app.message = Some(format!("Error: {}", e));
+                app.set_message(MessageLevel::Error, format!("Error: {}", e));
             }
+            // Checking out a branch that has a worktree opens it right away
+            if app.should_quit {
+                InputResult::Quit
+            } else {
+                InputResult::Continue
+            }
+        }
+
+        // Cursor movement
+        (KeyCode::Left, _) => {
+            app.move_cursor_left();
+            InputResult::Continue
+        }
+        (KeyCode::Right, _) => {
+            app.move_cursor_right();
+            InputResult::Continue
+        }
+        (KeyCode::Char('a'), KeyModifiers::CONTROL) => {
+            app.move_cursor_start();
+            InputResult::Continue
+        }
+        (KeyCode::Char('w'), KeyModifiers::CONTROL) => {
+            app.delete_word();
+            InputResult::Continue
+        }
+        (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
+            app.delete_to_line_start();
+            InputResult::Continue
+        }
+        (KeyCode::Char('e'), KeyModifiers::CONTROL) => {
+            app.move_cursor_end();
             InputResult::Continue
         }
 
@@ -151,7 +626,7 @@ fn handle_confirm_mode(app: &mut App, key: KeyEvent) -> InputResult {
         // Confirm (worktree only)
         KeyCode::Enter | KeyCode::Char('y') => {
             if let Err(e) = app.confirm_action(false) {
-                app.message = Some(format!("Error: {}", e));
+                app.set_message(MessageLevel::Error, format!("Error: {}", e));
             }
             InputResult::Continue
         }
@@ -159,11 +634,41 @@ fn handle_confirm_mode(app: &mut App, key: KeyEvent) -> InputResult {
         // Confirm (worktree and branch)
         KeyCode::Char('Y') => {
             if let Err(e) = app.confirm_action(true) {
-                app.message = Some(format!("Error: {}", e));
+                app.set_message(MessageLevel::Error, format!("Error: {}", e));
+            }
+            InputResult::Continue
+        }
+
+        // Force delete (ignore lock)
+        KeyCode::Char('f') => {
+            if let Err(e) = app.confirm_force_delete() {
+                app.set_message(MessageLevel::Error, format!("Error: {}", e));
             }
             InputResult::Continue
         }
 
+        // Stash changes, then delete
+        KeyCode::Char('s') => {
+            if let Err(e) = app.confirm_stash_delete() {
+                app.set_message(MessageLevel::Error, format!("Error: {}", e));
+            }
+            InputResult::Continue
+        }
+
+        // Review the prune list: uncheck worktrees to keep
+        KeyCode::Char(' ') => {
+            app.toggle_prune_item();
+            InputResult::Continue
+        }
+        KeyCode::Up | KeyCode::Char('k') if app.confirm_action == Some(ConfirmAction::Prune) => {
+            app.prune_cursor_up();
+            InputResult::Continue
+        }
+        KeyCode::Down | KeyCode::Char('j') if app.confirm_action == Some(ConfirmAction::Prune) => {
+            app.prune_cursor_down();
+            InputResult::Continue
+        }
+
         // Cancel
         KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
             app.enter_normal_mode();
@@ -174,9 +679,28 @@ fn handle_confirm_mode(app: &mut App, key: KeyEvent) -> InputResult {
     }
 }
 
-fn handle_deleting_mode(_key: KeyEvent) -> InputResult {
-    // Ignore all key input during deletion
-    InputResult::Continue
+fn handle_progress_mode(app: &mut App, key: KeyEvent) -> InputResult {
+    // Other keys are ignored while creating, deleting or fetching. Quitting
+    // asks first, since it can interrupt git half way through.
+    match (key.code, key.modifiers) {
+        (KeyCode::Char('q' | 'c'), KeyModifiers::CONTROL) => {
+            if app.request_quit() {
+                InputResult::Quit
+            } else {
+                InputResult::Continue
+            }
+        }
+        (KeyCode::Char('y' | 'Y'), _) if app.quit_pending => InputResult::Quit,
+        // Esc answers "no" to a pending quit before it cancels setup
+        (KeyCode::Esc, _) if !app.quit_pending => {
+            app.cancel_setup();
+            InputResult::Continue
+        }
+        _ => {
+            app.quit_pending = false;
+            InputResult::Continue
+        }
+    }
 }
 
 fn handle_config_mode(app: &mut App, key: KeyEvent) -> InputResult {
@@ -193,6 +717,56 @@ fn handle_config_mode(app: &mut App, key: KeyEvent) -> InputResult {
             app.scroll_config_down();
             InputResult::Continue
         }
+        (KeyCode::Char('g'), KeyModifiers::NONE) => {
+            if app.chord('g') {
+                app.scroll_config_top();
+            }
+            InputResult::Continue
+        }
+        (KeyCode::Char('G'), _) | (KeyCode::End, _) => {
+            app.scroll_config_bottom();
+            InputResult::Continue
+        }
+        (KeyCode::Home, _) => {
+            app.scroll_config_top();
+            InputResult::Continue
+        }
+        _ => InputResult::Continue,
+    }
+}
+
+fn handle_help_mode(app: &mut App, key: KeyEvent) -> InputResult {
+    match (key.code, key.modifiers) {
+        (KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?'), _) => {
+            app.enter_normal_mode();
+            InputResult::Continue
+        }
+        (KeyCode::Char('c'), KeyModifiers::NONE) => {
+            app.enter_config_mode();
+            InputResult::Continue
+        }
+        (KeyCode::Up, _) | (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
+            app.scroll_config_up();
+            InputResult::Continue
+        }
+        (KeyCode::Down, _) | (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
+            app.scroll_config_down();
+            InputResult::Continue
+        }
+        (KeyCode::Char('g'), KeyModifiers::NONE) => {
+            if app.chord('g') {
+                app.scroll_config_top();
+            }
+            InputResult::Continue
+        }
+        (KeyCode::Char('G'), _) | (KeyCode::End, _) => {
+            app.scroll_config_bottom();
+            InputResult::Continue
+        }
+        (KeyCode::Home, _) => {
+            app.scroll_config_top();
+            InputResult::Continue
+        }
         _ => InputResult::Continue,
     }
 }
@@ -200,32 +774,40 @@ fn handle_config_mode(app: &mut App, key: KeyEvent) -> InputResult {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app::SortMode;
     use crate::config::Config;
     use crate::git::{Branch, Worktree};
     use std::path::PathBuf;
 
     /// Create a test App without Git dependencies
     fn create_test_app() -> App {
+        create_test_app_with(Config::default())
+    }
+
+    fn create_test_app_with(config: Config) -> App {
         App::new_for_test(
-            Config::default(),
+            config,
             vec![
                 Worktree {
                     name: "main".to_string(),
                     path: PathBuf::from("/repo/main"),
                     branch: Some("main".to_string()),
                     is_main: true,
+                    ..Default::default()
                 },
                 Worktree {
                     name: "feature-a".to_string(),
                     path: PathBuf::from("/repo/feature-a"),
                     branch: Some("feature/a".to_string()),
                     is_main: false,
+                    ..Default::default()
                 },
                 Worktree {
                     name: "feature-b".to_string(),
                     path: PathBuf::from("/repo/feature-b"),
                     branch: Some("feature/b".to_string()),
                     is_main: false,
+                    ..Default::default()
                 },
             ],
             vec![
@@ -284,6 +866,97 @@ mod tests {
         assert_eq!(app.selected_worktree, 1);
     }
 
+    #[test]
+    fn test_normal_mode_page_down_and_up() {
+        let mut app = create_test_app();
+        app.list_height = 3;
+
+        handle_key_event(&mut app, key(KeyCode::PageDown));
+        assert_eq!(app.selected_worktree, 2);
+
+        handle_key_event(&mut app, key(KeyCode::PageUp));
+        assert_eq!(app.selected_worktree, 0);
+    }
+
+    #[test]
+    fn test_normal_mode_space_toggles_mark() {
+        let mut app = create_test_app();
+        app.selected_worktree = 1;
+
+        handle_key_event(&mut app, key(KeyCode::Char(' ')));
+
+        assert_eq!(app.marked_worktrees.len(), 1);
+        assert!(app.input.is_empty());
+    }
+
+    #[test]
+    fn test_normal_mode_fetch_and_pull() {
+        let mut app = create_test_app();
+        app.selected_worktree = 1;
+
+        handle_key_event(&mut app, key_ctrl('f'));
+        assert_eq!(
+            app.suspend_command.take().map(|c| c.kind),
+            Some(crate::app::SuspendKind::Fetch)
+        );
+
+        handle_key_event(&mut app, key_ctrl('g'));
+        assert_eq!(
+            app.suspend_command.take().map(|c| c.kind),
+            Some(crate::app::SuspendKind::Pull)
+        );
+    }
+
+    #[test]
+    fn test_help_mode_gg_scrolls_to_top() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Help;
+        app.config_scroll_max = 10;
+        app.config_scroll = 5;
+
+        handle_key_event(&mut app, key(KeyCode::Char('g')));
+        assert_eq!(app.config_scroll, 5);
+
+        handle_key_event(&mut app, key(KeyCode::Char('g')));
+        assert_eq!(app.config_scroll, 0);
+    }
+
+    #[test]
+    fn test_help_mode_g_interrupted_by_other_key() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Help;
+        app.config_scroll_max = 10;
+        app.config_scroll = 5;
+
+        handle_key_event(&mut app, key(KeyCode::Char('g')));
+        handle_key_event(&mut app, key(KeyCode::Down));
+        handle_key_event(&mut app, key(KeyCode::Char('g')));
+
+        assert_eq!(app.config_scroll, 6);
+    }
+
+    #[test]
+    fn test_config_mode_upper_g_scrolls_to_bottom() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Config;
+        app.config_scroll_max = 10;
+
+        handle_key_event(&mut app, key_shift('G'));
+
+        assert_eq!(app.config_scroll, 10);
+    }
+
+    #[test]
+    fn test_normal_mode_home_end() {
+        let mut app = create_test_app();
+
+        handle_key_event(&mut app, key(KeyCode::End));
+        assert_eq!(app.selected_worktree, app.filtered_worktrees.len() - 1);
+
+        handle_key_event(&mut app, key(KeyCode::Home));
+        assert_eq!(app.selected_worktree, 0);
+    }
+
     #[test]
     fn test_normal_mode_quit_ctrl_q() {
         let mut app = create_test_app();
@@ -314,15 +987,232 @@ mod tests {
     }
 
     #[test]
-    fn test_normal_mode_enter_config() {
+    fn test_normal_mode_enter_help() {
         let mut app = create_test_app();
 
         let result = handle_key_event(&mut app, key(KeyCode::Char('?')));
 
+        assert!(matches!(result, InputResult::Continue));
+        assert_eq!(app.mode, AppMode::Help);
+    }
+
+    #[test]
+    fn test_normal_mode_enter_config_via_help() {
+        let mut app = create_test_app();
+
+        handle_key_event(&mut app, key(KeyCode::Char('?')));
+        let result = handle_key_event(&mut app, key(KeyCode::Char('c')));
+
         assert!(matches!(result, InputResult::Continue));
         assert_eq!(app.mode, AppMode::Config);
     }
 
+    #[test]
+    fn test_normal_mode_upper_l_opens_message_log() {
+        let mut app = create_test_app();
+
+        handle_key_event(&mut app, key_shift('L'));
+        assert_eq!(app.mode, AppMode::MessageLog);
+
+        handle_key_event(&mut app, key(KeyCode::Char('q')));
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn test_normal_mode_upper_c_toggles_compact_list() {
+        let mut app = create_test_app();
+
+        handle_key_event(&mut app, key_shift('C'));
+        assert!(app.compact_list);
+
+        app.input = "fe".to_string();
+        handle_key_event(&mut app, key_shift('C'));
+        assert!(app.compact_list);
+        assert_eq!(app.input, "feC");
+    }
+
+    #[test]
+    fn test_normal_mode_upper_l_types_while_searching() {
+        let mut app = create_test_app();
+        app.input = "fe".to_string();
+
+        handle_key_event(&mut app, key_shift('L'));
+
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.input, "feL");
+    }
+
+    #[test]
+    fn test_normal_mode_tab_moves_focus_to_details() {
+        let mut app = create_test_app();
+        app.detail_scroll_max = 3;
+
+        handle_key_event(&mut app, key(KeyCode::Tab));
+        assert_eq!(app.focus, Focus::Detail);
+
+        handle_key_event(&mut app, key(KeyCode::Down));
+        handle_key_event(&mut app, key_ctrl('n'));
+        assert_eq!(app.detail_scroll, 2);
+        assert_eq!(app.selected_worktree, 0);
+
+        handle_key_event(&mut app, key(KeyCode::Up));
+        assert_eq!(app.detail_scroll, 1);
+
+        handle_key_event(&mut app, key(KeyCode::Tab));
+        assert_eq!(app.focus, Focus::List);
+        handle_key_event(&mut app, key(KeyCode::Down));
+        assert_eq!(app.selected_worktree, 1);
+    }
+
+    #[test]
+    fn test_normal_mode_upper_j_k_scroll_details() {
+        let mut app = create_test_app();
+        app.detail_scroll_max = 3;
+
+        handle_key_event(&mut app, key_shift('J'));
+        handle_key_event(&mut app, key_shift('J'));
+        handle_key_event(&mut app, key_shift('K'));
+
+        assert_eq!(app.detail_scroll, 1);
+        assert!(app.input.is_empty());
+    }
+
+    #[test]
+    fn test_normal_mode_upper_b_checkout_opens_existing_worktree() {
+        let mut app = create_test_app();
+
+        handle_key_event(&mut app, key_shift('B'));
+        assert_eq!(app.mode, AppMode::Create);
+        assert!(app.open_after_create);
+        app.selected_branch = 1 + app
+            .filtered_branches
+            .iter()
+            .position(|b| b.name == "feature/a")
+            .unwrap();
+
+        let result = handle_key_event(&mut app, key(KeyCode::Enter));
+
+        assert!(matches!(result, InputResult::Quit));
+        assert_eq!(
+            app.selected_worktree_path.as_deref(),
+            Some("/repo/feature-a")
+        );
+    }
+
+    #[test]
+    fn test_normal_mode_upper_v_shows_diff() {
+        let mut app = create_test_app();
+
+        handle_key_event(&mut app, key_shift('V'));
+
+        assert_eq!(
+            app.suspend_command.map(|c| c.kind),
+            Some(crate::app::SuspendKind::Diff)
+        );
+    }
+
+    #[test]
+    fn test_normal_mode_ctrl_o_enters_create_mode() {
+        let mut app = create_test_app();
+
+        let result = handle_key_event(&mut app, key_ctrl('o'));
+
+        assert!(matches!(result, InputResult::Continue));
+        assert_eq!(app.mode, AppMode::Create);
+        assert!(!app.open_after_create);
+    }
+
+    #[test]
+    fn test_normal_mode_ctrl_d_asks_to_delete() {
+        let mut app = create_test_app();
+        app.selected_worktree = 1;
+
+        let result = handle_key_event(&mut app, key_ctrl('d'));
+
+        assert!(matches!(result, InputResult::Continue));
+        assert_eq!(app.mode, AppMode::Confirm);
+        assert_eq!(
+            app.confirm_action,
+            Some(crate::app::ConfirmAction::DeleteSingle)
+        );
+    }
+
+    #[test]
+    fn test_normal_mode_upper_n_quick_creates_from_selection() {
+        let mut app = create_test_app();
+        app.selected_worktree = 2;
+
+        handle_key_event(&mut app, key_shift('N'));
+
+        assert_eq!(app.mode, AppMode::Create);
+        assert!(app.quick_create);
+        assert_eq!(app.create_base_ref(), "feature/b");
+    }
+
+    #[test]
+    fn test_normal_mode_cycle_sort() {
+        let mut app = create_test_app();
+
+        let result = handle_key_event(&mut app, key_ctrl('s'));
+
+        assert!(matches!(result, InputResult::Continue));
+        assert_eq!(app.sort_mode, SortMode::NameDesc);
+        assert!(app.input.is_empty());
+    }
+
+    #[test]
+    fn test_normal_mode_upper_m_enters_move_mode() {
+        let mut app = create_test_app();
+        app.selected_worktree = 1;
+
+        handle_key_event(&mut app, key_shift('M'));
+
+        assert_eq!(app.mode, AppMode::Move);
+        assert!(!app.input.is_empty());
+    }
+
+    #[test]
+    fn test_ctrl_l_prompts_for_lock_reason() {
+        let mut app = create_test_app();
+        app.selected_worktree = 1;
+
+        handle_key_event(&mut app, key_ctrl('l'));
+        assert_eq!(app.mode, AppMode::LockReason);
+        handle_key_event(&mut app, key(KeyCode::Char('x')));
+        assert_eq!(app.input, "x");
+
+        handle_key_event(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.input.is_empty());
+    }
+
+    #[test]
+    fn test_normal_mode_upper_m_types_while_searching() {
+        let mut app = create_test_app();
+        app.input = "fe".to_string();
+
+        handle_key_event(&mut app, key_shift('M'));
+
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.input, "feM");
+    }
+
+    #[test]
+    fn test_move_mode_edits_and_cancels() {
+        let mut app = create_test_app();
+        app.selected_worktree = 1;
+        app.enter_move_mode();
+
+        handle_key_event(&mut app, key_ctrl('w'));
+        handle_key_event(&mut app, key_ctrl('w'));
+        handle_key_event(&mut app, key(KeyCode::Char('x')));
+        assert_eq!(app.input, "/repo/x");
+
+        handle_key_event(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.input.is_empty());
+    }
+
     #[test]
     fn test_normal_mode_input_char() {
         let mut app = create_test_app();
@@ -420,6 +1310,92 @@ mod tests {
         assert_eq!(app.input, "tes");
     }
 
+    #[test]
+    fn test_create_mode_cursor_keys_edit_mid_input() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        app.input = "fix-bug".to_string();
+
+        handle_key_event(&mut app, key_ctrl('a'));
+        handle_key_event(&mut app, key(KeyCode::Right));
+        handle_key_event(&mut app, key(KeyCode::Right));
+        handle_key_event(&mut app, key(KeyCode::Right));
+        handle_key_event(&mut app, key(KeyCode::Char('e')));
+        assert_eq!(app.input, "fixe-bug");
+
+        handle_key_event(&mut app, key_ctrl('e'));
+        handle_key_event(&mut app, key(KeyCode::Left));
+        handle_key_event(&mut app, key(KeyCode::Backspace));
+        assert_eq!(app.input, "fixe-bg");
+    }
+
+    #[test]
+    fn test_normal_mode_cursor_keys_edit_search() {
+        let mut app = create_test_app();
+        app.input = "feat".to_string();
+
+        handle_key_event(&mut app, key(KeyCode::Left));
+        handle_key_event(&mut app, key(KeyCode::Backspace));
+        assert_eq!(app.input, "fet");
+
+        handle_key_event(&mut app, key_ctrl('a'));
+        handle_key_event(&mut app, key(KeyCode::Char('x')));
+        assert_eq!(app.input, "xfet");
+    }
+
+    #[test]
+    fn test_normal_mode_ctrl_r_refreshes() {
+        let mut app = create_test_app();
+
+        handle_key_event(&mut app, key_ctrl('r'));
+
+        assert_eq!(app.message.as_deref(), Some("Refreshed"));
+    }
+
+    #[test]
+    fn test_create_mode_delete_word_and_line() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        app.input = "feature/fix-bug".to_string();
+
+        handle_key_event(&mut app, key_ctrl('w'));
+        assert_eq!(app.input, "feature/fix-");
+
+        handle_key_event(&mut app, key_ctrl('u'));
+        assert!(app.input.is_empty());
+    }
+
+    #[test]
+    fn test_create_mode_tab_switches_field() {
+        use crate::app::CreateField;
+
+        let mut app = create_test_app();
+        app.mode = AppMode::Create;
+        app.selected_branch = 0;
+
+        handle_key_event(&mut app, key(KeyCode::Tab));
+        assert_eq!(app.create_field, CreateField::WorktreeName);
+
+        handle_key_event(&mut app, key(KeyCode::Tab));
+        assert_eq!(app.create_field, CreateField::BaseRef);
+
+        handle_key_event(&mut app, key(KeyCode::Tab));
+        assert_eq!(app.create_field, CreateField::BranchName);
+    }
+
+    #[test]
+    fn test_normal_mode_delete_word_filters() {
+        let mut app = create_test_app();
+        app.input = "feature-zzz".to_string();
+        app.filter_worktrees();
+        assert!(app.filtered_worktrees.is_empty());
+
+        handle_key_event(&mut app, key_ctrl('w'));
+
+        assert_eq!(app.input, "feature-");
+        assert!(!app.filtered_worktrees.is_empty());
+    }
+
     // ========== Confirm Mode Tests ==========
 
     #[test]
@@ -496,6 +1472,50 @@ mod tests {
         assert_eq!(app.mode, AppMode::Deleting);
     }
 
+    #[test]
+    fn test_confirm_mode_force_delete_f() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Confirm;
+        app.confirm_action = Some(crate::app::ConfirmAction::DeleteSingle);
+        app.selected_worktree = 1; // non-main worktree
+
+        let result = handle_key_event(&mut app, key(KeyCode::Char('f')));
+
+        assert!(matches!(result, InputResult::Continue));
+        assert_eq!(app.mode, AppMode::Deleting);
+    }
+
+    #[test]
+    fn test_confirm_mode_s_stashes_dirty_worktree() {
+        let mut app = create_test_app();
+        app.filtered_worktrees[1].is_dirty = true;
+        app.mode = AppMode::Confirm;
+        app.confirm_action = Some(crate::app::ConfirmAction::DeleteSingle);
+        app.selected_worktree = 1;
+
+        let result = handle_key_event(&mut app, key(KeyCode::Char('s')));
+
+        // The fake worktree has no repository to stash, but the key reached the stash
+        assert!(matches!(result, InputResult::Continue));
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app
+            .message
+            .as_deref()
+            .is_some_and(|m| m.starts_with("Failed to stash changes in 'feature-a'")));
+    }
+
+    #[test]
+    fn test_confirm_mode_s_ignored_for_clean_worktree() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Confirm;
+        app.confirm_action = Some(crate::app::ConfirmAction::DeleteSingle);
+        app.selected_worktree = 1;
+
+        handle_key_event(&mut app, key(KeyCode::Char('s')));
+
+        assert_eq!(app.mode, AppMode::Confirm);
+    }
+
     #[test]
     fn test_confirm_mode_ignores_other_keys() {
         let mut app = create_test_app();
@@ -508,6 +1528,36 @@ mod tests {
         assert_eq!(app.mode, AppMode::Confirm);
     }
 
+    #[test]
+    fn test_confirm_mode_space_toggles_prune_item() {
+        let mut app = create_test_app();
+        app.merged_worktrees = app.worktrees[1..].to_vec();
+        app.mode = AppMode::Confirm;
+        app.confirm_action = Some(ConfirmAction::Prune);
+
+        handle_key_event(&mut app, key(KeyCode::Char('j')));
+        handle_key_event(&mut app, key(KeyCode::Char(' ')));
+
+        assert_eq!(app.mode, AppMode::Confirm);
+        assert_eq!(app.prune_cursor, 1);
+        assert!(app.prune_unchecked.contains(&app.merged_worktrees[1].path));
+        assert_eq!(app.prune_selection().len(), app.merged_worktrees.len() - 1);
+    }
+
+    #[test]
+    fn test_confirm_mode_space_ignored_for_single_delete() {
+        let mut app = create_test_app();
+        app.merged_worktrees = app.worktrees[1..].to_vec();
+        app.mode = AppMode::Confirm;
+        app.confirm_action = Some(ConfirmAction::DeleteSingle);
+
+        handle_key_event(&mut app, key(KeyCode::Char(' ')));
+        handle_key_event(&mut app, key(KeyCode::Char('j')));
+
+        assert!(app.prune_unchecked.is_empty());
+        assert_eq!(app.prune_cursor, 0);
+    }
+
     // ========== Config Mode Tests ==========
 
     #[test]
@@ -666,23 +1716,288 @@ mod tests {
 
     // ========== Deleting Mode Tests ==========
 
+    #[test]
+    fn test_creating_mode_ignores_all_keys() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Creating;
+
+        for key_event in [key(KeyCode::Esc), key(KeyCode::Enter), key_ctrl('c')] {
+            let result = handle_key_event(&mut app, key_event);
+            assert!(matches!(result, InputResult::Continue));
+            assert_eq!(app.mode, AppMode::Creating);
+        }
+    }
+
+    #[test]
+    fn test_creating_mode_esc_cancels_setup() {
+        let mut app = create_test_app();
+        let cancel = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        app.mode = AppMode::Creating;
+        app.setup_cancel = Some(cancel.clone());
+
+        handle_key_event(&mut app, key(KeyCode::Esc));
+
+        assert!(cancel.load(std::sync::atomic::Ordering::Relaxed));
+        assert_eq!(app.mode, AppMode::Creating);
+        assert_eq!(app.progress_message.as_deref(), Some("Cancelling setup..."));
+    }
+
     #[test]
     fn test_deleting_mode_ignores_all_keys() {
         let mut app = create_test_app();
         app.mode = AppMode::Deleting;
 
-        // All keys should be ignored during deletion
+        // Keys other than quit are ignored during deletion
         for key_event in [
             key(KeyCode::Char('q')),
             key(KeyCode::Esc),
             key(KeyCode::Enter),
             key(KeyCode::Char('y')),
-            key_ctrl('c'),
-            key_ctrl('q'),
         ] {
             let result = handle_key_event(&mut app, key_event);
             assert!(matches!(result, InputResult::Continue));
             assert_eq!(app.mode, AppMode::Deleting);
         }
     }
+
+    #[test]
+    fn test_progress_mode_quit_asks_for_confirmation() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Fetching;
+
+        let result = handle_key_event(&mut app, key_ctrl('c'));
+        assert!(matches!(result, InputResult::Continue));
+        assert!(app.quit_pending);
+
+        let result = handle_key_event(&mut app, key(KeyCode::Char('y')));
+        assert!(matches!(result, InputResult::Quit));
+    }
+
+    #[test]
+    fn test_progress_mode_quit_twice_forces_quit() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Deleting;
+
+        handle_key_event(&mut app, key_ctrl('q'));
+        let result = handle_key_event(&mut app, key_ctrl('q'));
+
+        assert!(matches!(result, InputResult::Quit));
+    }
+
+    #[test]
+    fn test_progress_mode_other_key_cancels_quit() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Creating;
+
+        handle_key_event(&mut app, key_ctrl('c'));
+        let result = handle_key_event(&mut app, key(KeyCode::Esc));
+
+        assert!(matches!(result, InputResult::Continue));
+        assert!(!app.quit_pending);
+        assert_eq!(app.mode, AppMode::Creating);
+    }
+
+    // ========== vi Chord Tests ==========
+
+    #[test]
+    fn test_dd_opens_delete_confirmation() {
+        let mut app = create_test_app();
+        app.selected_worktree = 1;
+
+        handle_key_event(&mut app, key(KeyCode::Char('d')));
+        // The first key filters like any other character
+        assert_eq!(app.input, "d");
+
+        handle_key_event(&mut app, key(KeyCode::Char('d')));
+
+        assert_eq!(app.mode, AppMode::Confirm);
+        assert!(app.input.is_empty());
+        assert_eq!(app.filtered_worktrees.len(), 3);
+        assert_eq!(app.selected_worktree, 1);
+    }
+
+    #[test]
+    fn test_dd_asks_even_with_confirm_delete_off() {
+        let config: Config = toml::from_str("[confirm]\ndelete = false").unwrap();
+        let mut app = create_test_app_with(config);
+        app.selected_worktree = 1;
+
+        handle_key_event(&mut app, key(KeyCode::Char('d')));
+        handle_key_event(&mut app, key(KeyCode::Char('d')));
+
+        assert_eq!(app.mode, AppMode::Confirm);
+        assert_eq!(app.confirm_action, Some(ConfirmAction::DeleteSingle));
+    }
+
+    #[test]
+    fn test_single_d_keeps_filtering() {
+        let mut app = create_test_app();
+
+        handle_key_event(&mut app, key(KeyCode::Char('d')));
+        handle_key_event(&mut app, key(KeyCode::Char('e')));
+
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.input, "de");
+        assert!(app.pending_key.is_none());
+    }
+
+    #[test]
+    fn test_dd_only_starts_on_empty_search() {
+        let mut app = create_test_app();
+        app.input = "fe".to_string();
+        app.filter_worktrees();
+
+        handle_key_event(&mut app, key(KeyCode::Char('d')));
+        handle_key_event(&mut app, key(KeyCode::Char('d')));
+
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.input, "fedd");
+    }
+
+    #[test]
+    fn test_dd_after_timeout_types_both_keys() {
+        let mut app = create_test_app();
+
+        handle_key_event(&mut app, key(KeyCode::Char('d')));
+        let stale = std::time::Instant::now()
+            .checked_sub(crate::app::CHORD_TIMEOUT * 2)
+            .unwrap();
+        app.pending_key = Some(('d', stale));
+        handle_key_event(&mut app, key(KeyCode::Char('d')));
+
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.input, "dd");
+    }
+
+    #[test]
+    fn test_yy_clears_the_typed_key() {
+        let mut app = create_test_app();
+
+        handle_key_event(&mut app, key(KeyCode::Char('y')));
+        handle_key_event(&mut app, key(KeyCode::Char('y')));
+
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.input.is_empty());
+        assert!(app.pending_key.is_none());
+    }
+
+    #[test]
+    fn test_dy_does_not_complete_a_chord() {
+        let mut app = create_test_app();
+
+        handle_key_event(&mut app, key(KeyCode::Char('d')));
+        handle_key_event(&mut app, key(KeyCode::Char('y')));
+
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.input, "dy");
+    }
+
+    // ========== Mouse Tests ==========
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_mouse_click_selects_worktree() {
+        let mut app = create_test_app();
+        app.list_area = ratatui::layout::Rect::new(0, 2, 40, 10);
+        app.focus = Focus::Detail;
+
+        handle_mouse_event(
+            &mut app,
+            mouse(MouseEventKind::Down(MouseButton::Left), 5, 5),
+        );
+
+        assert_eq!(app.selected_worktree, 2);
+        assert_eq!(app.focus, Focus::List);
+    }
+
+    #[test]
+    fn test_mouse_click_outside_list_keeps_selection() {
+        let mut app = create_test_app();
+        app.list_area = ratatui::layout::Rect::new(0, 2, 40, 10);
+        app.selected_worktree = 1;
+
+        handle_mouse_event(
+            &mut app,
+            mouse(MouseEventKind::Down(MouseButton::Left), 60, 5),
+        );
+
+        assert_eq!(app.selected_worktree, 1);
+    }
+
+    #[test]
+    fn test_mouse_wheel_moves_selection() {
+        let mut app = create_test_app();
+
+        handle_mouse_event(&mut app, mouse(MouseEventKind::ScrollDown, 0, 0));
+        handle_mouse_event(&mut app, mouse(MouseEventKind::ScrollDown, 0, 0));
+        assert_eq!(app.selected_worktree, 2);
+
+        handle_mouse_event(&mut app, mouse(MouseEventKind::ScrollUp, 0, 0));
+        assert_eq!(app.selected_worktree, 1);
+    }
+
+    #[test]
+    fn test_mouse_ignored_outside_normal_mode() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Help;
+
+        handle_mouse_event(&mut app, mouse(MouseEventKind::ScrollDown, 0, 0));
+
+        assert_eq!(app.selected_worktree, 0);
+    }
+
+    // ========== Help Mode Tests ==========
+
+    #[test]
+    fn test_help_mode_toggle_closes() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Help;
+
+        handle_key_event(&mut app, key(KeyCode::Char('?')));
+
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn test_help_mode_exit_esc_and_q() {
+        let mut app = create_test_app();
+
+        app.mode = AppMode::Help;
+        handle_key_event(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.mode, AppMode::Normal);
+
+        app.mode = AppMode::Help;
+        handle_key_event(&mut app, key(KeyCode::Char('q')));
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn test_help_mode_scroll() {
+        let mut app = create_test_app();
+        app.mode = AppMode::Help;
+        app.config_scroll_max = 3;
+
+        handle_key_event(&mut app, key(KeyCode::Down));
+        handle_key_event(&mut app, key(KeyCode::Down));
+        handle_key_event(&mut app, key(KeyCode::Up));
+
+        assert_eq!(app.config_scroll, 1);
+    }
+
+    #[test]
+    fn test_key_bindings_cover_modes() {
+        let modes: Vec<&str> = KEY_BINDINGS.iter().map(|(mode, _)| *mode).collect();
+        assert_eq!(modes, vec!["Normal", "Create", "Move", "Confirm", "Help"]);
+        assert!(KEY_BINDINGS
+            .iter()
+            .all(|(_, bindings)| !bindings.is_empty()));
+    }
 }
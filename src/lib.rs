@@ -0,0 +1,23 @@
+//! Git worktree management used by the `gwm` TUI, usable without the terminal UI.
+//!
+//! [`git::GitManager`] opens the repository containing a path and lists,
+//! creates and removes its worktrees:
+//!
+//! ```no_run
+//! use gwm::git::GitManager;
+//! use std::path::Path;
+//!
+//! let git = GitManager::from_path(Path::new("/path/to/repo"))?;
+//! for worktree in git.list_worktrees()? {
+//!     println!("{} {:?}", worktree.path.display(), worktree.branch);
+//! }
+//!
+//! git.create_worktree_with_new_branch("feature-x", "feature/x", ".worktrees")?;
+//! let merged = git.find_merged_worktrees("main")?;
+//! for worktree in merged {
+//!     git.delete_worktree(&worktree.name)?;
+//! }
+//! # Ok::<(), gwm::git::GitError>(())
+//! ```
+
+pub mod git;
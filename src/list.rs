@@ -0,0 +1,154 @@
+//! Non-interactive worktree listing for scripts (`gwm --list`)
+
+use crate::git::{self, GitError, GitManager, Worktree};
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// Output format of `--list`
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum ListFormat {
+    /// One worktree per line: path, branch and short commit, tab separated
+    #[default]
+    Text,
+    /// JSON array of worktree objects
+    Json,
+}
+
+/// A worktree as printed by `--list`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ListEntry {
+    pub name: String,
+    pub path: String,
+    /// Checked out branch, or null for a detached HEAD
+    pub branch: Option<String>,
+    /// Full hash of the HEAD commit
+    pub commit: Option<String>,
+    pub is_main: bool,
+    pub is_dirty: bool,
+    pub is_locked: bool,
+}
+
+impl ListEntry {
+    fn from_worktree(worktree: Worktree) -> Self {
+        Self {
+            commit: git::head_commit_id(&worktree.path),
+            is_dirty: git::is_dirty(&worktree.path),
+            name: worktree.name,
+            path: worktree.path.to_string_lossy().to_string(),
+            branch: worktree.branch,
+            is_main: worktree.is_main,
+            is_locked: worktree.is_locked,
+        }
+    }
+}
+
+/// Read every worktree of the repository, including commit and dirty status
+pub fn list_entries(git: &GitManager) -> Result<Vec<ListEntry>, GitError> {
+    Ok(git
+        .list_worktrees()?
+        .into_iter()
+        .map(ListEntry::from_worktree)
+        .collect())
+}
+
+/// Render entries in the requested format
+pub fn render(entries: &[ListEntry], format: ListFormat) -> String {
+    match format {
+        ListFormat::Text => entries
+            .iter()
+            .map(|e| {
+                format!(
+                    "{}\t{}\t{}",
+                    e.path,
+                    e.branch.as_deref().unwrap_or("(detached)"),
+                    e.commit.as_deref().map_or("", |c| &c[..c.len().min(7)])
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        // Serializing plain strings and bools cannot fail
+        ListFormat::Json => serde_json::to_string_pretty(entries).unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn entry(path: &str, branch: Option<&str>) -> ListEntry {
+        ListEntry {
+            name: path.rsplit('/').next().unwrap().to_string(),
+            path: path.to_string(),
+            branch: branch.map(String::from),
+            commit: Some("0123456789abcdef0123456789abcdef01234567".to_string()),
+            is_main: false,
+            is_dirty: false,
+            is_locked: false,
+        }
+    }
+
+    #[test]
+    fn test_render_text() {
+        let entries = vec![
+            entry("/repo/main", Some("main")),
+            entry("/repo/detached", None),
+        ];
+
+        assert_eq!(
+            render(&entries, ListFormat::Text),
+            "/repo/main\tmain\t0123456\n/repo/detached\t(detached)\t0123456"
+        );
+    }
+
+    #[test]
+    fn test_render_json() {
+        let entries = vec![entry("/repo/wt", Some("feature/a"))];
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(&entries, ListFormat::Json)).unwrap();
+
+        assert_eq!(json[0]["path"], "/repo/wt");
+        assert_eq!(json[0]["branch"], "feature/a");
+        assert_eq!(
+            json[0]["commit"],
+            "0123456789abcdef0123456789abcdef01234567"
+        );
+        assert_eq!(json[0]["is_main"], false);
+        assert_eq!(json[0]["is_dirty"], false);
+    }
+
+    #[test]
+    fn test_render_json_empty() {
+        assert_eq!(render(&[], ListFormat::Json), "[]");
+    }
+
+    #[test]
+    fn test_list_entries_reads_commit_and_status() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        for args in [
+            vec!["init", "-b", "main"],
+            vec!["config", "user.email", "test@test.com"],
+            vec!["config", "user.name", "Test User"],
+            vec!["commit", "--allow-empty", "-m", "Initial commit"],
+        ] {
+            Command::new("git")
+                .args(&args)
+                .current_dir(path)
+                .output()
+                .unwrap();
+        }
+        std::fs::write(path.join("untracked.txt"), "x").unwrap();
+        let git = GitManager::from_path(path).unwrap();
+
+        let entries = list_entries(&git).unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].is_main);
+        assert!(entries[0].is_dirty);
+        assert_eq!(entries[0].branch.as_deref(), Some("main"));
+        assert_eq!(entries[0].commit.as_ref().map(String::len), Some(40));
+    }
+}
@@ -1,60 +1,186 @@
 mod app;
+mod clipboard;
 mod config;
-mod git;
 mod hooks;
 mod input;
+mod list;
+mod multiplexer;
+mod shell;
+mod state;
 mod theme;
 mod ui;
 
-use app::App;
+use app::{App, MessageLevel};
 use clap::Parser;
 use color_eyre::eyre::Result;
 use crossterm::{
-    event::{self, Event, KeyEventKind},
-    terminal::{disable_raw_mode, enable_raw_mode},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind},
+    terminal::{disable_raw_mode, enable_raw_mode, SetTitle},
 };
-use input::{handle_key_event, InputResult};
+use gwm::git;
+use input::{handle_key_event, handle_mouse_event, InputResult};
 use ratatui::{backend::CrosstermBackend, Terminal, Viewport};
 use std::io::stdout;
 use std::path::PathBuf;
 use std::process::Command;
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Git Worktree Manager - A TUI application for managing git worktrees
 #[derive(Parser)]
 #[command(name = "gwm")]
 #[command(version, about, long_about = None)]
 struct Cli {
-    /// Path to config file
+    /// Path to config file, used instead of the global and local ones
+    /// (default: $GWM_CONFIG)
     #[arg(short, long, value_name = "FILE")]
     config: Option<PathBuf>,
 
     /// Print selected worktree path to stdout (for shell integration)
     #[arg(short = 'p', long = "print-path")]
     print_path: bool,
+
+    /// Print all worktrees and exit without starting the TUI
+    #[arg(short, long)]
+    list: bool,
+
+    /// Output format for --list
+    #[arg(long, value_enum, default_value_t = list::ListFormat::Text, requires = "list")]
+    format: list::ListFormat,
+
+    /// Report what create, delete, prune, move, lock and repair would do
+    /// without changing anything
+    #[arg(long)]
+    dry_run: bool,
 }
 
 const INLINE_HEIGHT: u16 = 20;
 
+/// Set while a suspended command runs in the foreground, so Ctrl+C reaches
+/// that command instead of quitting gwm
+static SUSPENDED: AtomicBool = AtomicBool::new(false);
+
+/// Set while gwm owns the terminal title, so it is restored on exit
+static TITLE_SET: AtomicBool = AtomicBool::new(false);
+
+/// Set while mouse events are captured, so the capture is released on exit
+static MOUSE_CAPTURED: AtomicBool = AtomicBool::new(false);
+
+/// Save the terminal title on the xterm title stack
+const PUSH_TITLE: &str = "\x1b[22;0t";
+/// Restore the title saved by `PUSH_TITLE`
+const POP_TITLE: &str = "\x1b[23;0t";
+
+/// Keeps the terminal in raw mode and restores it when dropped, so early
+/// returns and panics don't leave the shell unusable
+struct TerminalGuard {
+    active: bool,
+}
+
+impl TerminalGuard {
+    /// Enter raw mode, saving the terminal title first when gwm will change it
+    fn enter(set_title: bool, mouse: bool) -> std::io::Result<Self> {
+        if set_title {
+            crossterm::execute!(stdout(), crossterm::style::Print(PUSH_TITLE))?;
+            TITLE_SET.store(true, Ordering::SeqCst);
+        }
+        enable_raw_mode()?;
+        if mouse {
+            crossterm::execute!(stdout(), EnableMouseCapture)?;
+            MOUSE_CAPTURED.store(true, Ordering::SeqCst);
+        }
+        Ok(Self { active: true })
+    }
+
+    /// Leave raw mode, release the mouse and restore the title. Safe to
+    /// call more than once.
+    fn restore(&mut self) -> std::io::Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+        self.active = false;
+        release_mouse();
+        restore_title();
+        disable_raw_mode()
+    }
+}
+
+/// Stop capturing mouse events so the terminal's text selection works again
+fn release_mouse() {
+    if MOUSE_CAPTURED.swap(false, Ordering::SeqCst) {
+        let _ = crossterm::execute!(stdout(), DisableMouseCapture);
+    }
+}
+
+/// Put back the title saved when gwm started
+fn restore_title() {
+    if TITLE_SET.swap(false, Ordering::SeqCst) {
+        let _ = crossterm::execute!(stdout(), crossterm::style::Print(POP_TITLE));
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = self.restore();
+    }
+}
+
+/// Restore the terminal and exit when gwm receives SIGINT, SIGTERM or SIGHUP
+/// from outside (in raw mode, Ctrl+C arrives as a key event instead)
+#[cfg(unix)]
+fn install_signal_handler() -> std::io::Result<()> {
+    use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM};
+
+    let mut signals = signal_hook::iterator::Signals::new([SIGINT, SIGTERM, SIGHUP])?;
+    std::thread::spawn(move || {
+        for signal in signals.forever() {
+            if signal == SIGINT && SUSPENDED.load(Ordering::SeqCst) {
+                continue;
+            }
+            release_mouse();
+            restore_title();
+            let _ = disable_raw_mode();
+            let _ = crossterm::execute!(stdout(), crossterm::cursor::Show);
+            std::process::exit(128 + signal);
+        }
+    });
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn install_signal_handler() -> std::io::Result<()> {
+    Ok(())
+}
+
 fn main() -> Result<()> {
     color_eyre::install()?;
 
     let cli = Cli::parse();
 
-    // Load configuration
-    let (config, config_sources) = config::load_config_with_sources(cli.config.as_deref())
-        .unwrap_or_else(|_| (config::Config::default(), config::ConfigSources::default()));
+    // Scripting mode: no configuration or terminal needed
+    if cli.list {
+        let git = open_repository();
+        match list::list_entries(&git) {
+            Ok(entries) => println!("{}", list::render(&entries, cli.format)),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
 
-    // Initialize git manager
-    let git = match git::GitManager::new() {
-        Ok(git) => git,
+    // Load configuration
+    let (config, config_sources) = match config::load_config_with_sources(cli.config.as_deref()) {
+        Ok(loaded) => loaded,
         Err(e) => {
             eprintln!("Error: {}", e);
-            eprintln!("Please run this command from within a git repository.");
             std::process::exit(1);
         }
     };
 
+    // Initialize git manager
+    let git = open_repository();
+
     // Create application
     let mut app = match App::new(config, config_sources, git) {
         Ok(app) => app,
@@ -64,8 +190,12 @@ fn main() -> Result<()> {
         }
     };
 
+    app.print_path = cli.print_path;
+    app.dry_run = cli.dry_run;
+
     // Setup terminal with inline viewport
-    enable_raw_mode()?;
+    install_signal_handler()?;
+    let mut guard = TerminalGuard::enter(app.terminal_title().is_some(), app.mouse_enabled())?;
     let backend = CrosstermBackend::new(stdout());
     let options = ratatui::TerminalOptions {
         viewport: Viewport::Inline(INLINE_HEIGHT),
@@ -75,8 +205,11 @@ fn main() -> Result<()> {
     // Run the application
     let result = run_app(&mut terminal, &mut app);
 
+    // Remember the selection for the next launch (best effort)
+    let _ = app.save_state();
+
     // Restore terminal
-    disable_raw_mode()?;
+    guard.restore()?;
 
     // Move cursor below the inline viewport and clear it
     terminal.clear()?;
@@ -89,8 +222,9 @@ fn main() -> Result<()> {
                     // Print the path so it can be captured by a shell function
                     println!("{}", path);
                 } else {
-                    // Launch a subshell in the selected worktree directory
-                    launch_subshell(path);
+                    // Launch a subshell (or worktree.shell) in the selected worktree directory
+                    SUSPENDED.store(true, Ordering::SeqCst);
+                    launch_subshell(path, app.shell_command(path));
                 }
             }
         }
@@ -103,46 +237,130 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Open the repository containing the current directory, exiting on failure
+fn open_repository() -> git::GitManager {
+    match git::GitManager::new() {
+        Ok(git) => git,
+        Err(git::GitError::NotARepository) => {
+            eprintln!("gwm must be run inside a git repository");
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
 fn run_app(
     terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
     app: &mut App,
 ) -> Result<()> {
+    let mut title = String::new();
     loop {
         terminal.draw(|frame| ui::draw(frame, app))?;
 
-        // Check for background delete completion
+        // Keep the terminal title in sync with the current mode
+        if let Some(current) = app.terminal_title() {
+            if current != title {
+                crossterm::execute!(stdout(), SetTitle(&current))?;
+                title = current;
+            }
+        }
+
+        // Check for background create/delete/fetch/setup completion
+        if let Err(e) = app.check_create_completion() {
+            app.set_message(MessageLevel::Error, format!("Error: {}", e));
+        }
         if let Err(e) = app.check_delete_completion() {
-            app.message = Some(format!("Error: {}", e));
+            app.set_message(MessageLevel::Error, format!("Error: {}", e));
+        }
+        if let Err(e) = app.check_fetch_completion() {
+            app.set_message(MessageLevel::Error, format!("Error: {}", e));
+        }
+        if let Err(e) = app.check_setup_completion() {
+            app.set_message(MessageLevel::Error, format!("Error: {}", e));
+        }
+        // A finished checkout opens its worktree
+        if app.should_quit {
+            break;
         }
 
-        // Use shorter poll timeout during deletion for responsive spinner animation
-        let poll_timeout = if app.mode == app::AppMode::Deleting {
-            Duration::from_millis(80)
-        } else {
-            Duration::from_millis(250)
+        // Pick up worktree status computed in the background
+        app.check_status_updates();
+        app.check_size_update();
+        app.check_reveal_completion();
+
+        // Without a timeout (animations disabled), block until the next event
+        let has_event = match app.poll_timeout() {
+            Some(timeout) => event::poll(timeout)?,
+            None => true,
         };
 
-        if event::poll(poll_timeout)? {
-            if let Event::Key(key) = event::read()? {
+        if has_event {
+            match event::read()? {
                 // Only handle key press events (not release)
-                if key.kind == KeyEventKind::Press {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
                     match handle_key_event(app, key) {
                         InputResult::Quit => break,
                         InputResult::Continue => {}
                     }
                 }
+                Event::Mouse(mouse) => handle_mouse_event(app, mouse),
+                _ => {}
             }
         }
 
-        // Increment tick for spinner animation during deletion
-        if app.mode == app::AppMode::Deleting {
-            app.tick = app.tick.wrapping_add(1);
+        // Run external commands (e.g. editor) with the TUI suspended
+        if let Some(command) = app.suspend_command.take() {
+            let result = run_suspended(terminal, &command).map_err(|e| e.to_string());
+            app.finish_suspended(&command, result);
         }
+
+        // Advance spinner animation
+        app.on_tick();
     }
 
     Ok(())
 }
 
+/// Suspend the TUI, run a command in the foreground, then restore the TUI
+fn run_suspended(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    command: &app::SuspendCommand,
+) -> Result<()> {
+    // Let the command (and copy-paste) see the mouse while gwm is suspended
+    let mouse = MOUSE_CAPTURED.load(Ordering::SeqCst);
+    release_mouse();
+    disable_raw_mode()?;
+    terminal.clear()?;
+
+    SUSPENDED.store(true, Ordering::SeqCst);
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(&command.command)
+        .current_dir(&command.cwd)
+        .status();
+    SUSPENDED.store(false, Ordering::SeqCst);
+
+    enable_raw_mode()?;
+    if mouse {
+        crossterm::execute!(stdout(), EnableMouseCapture)?;
+        MOUSE_CAPTURED.store(true, Ordering::SeqCst);
+    }
+    terminal.clear()?;
+
+    let status = status?;
+    if !status.success() {
+        return Err(color_eyre::eyre::eyre!(
+            "'{}' exited with status: {}",
+            command.command,
+            status.code().unwrap_or(-1)
+        ));
+    }
+    Ok(())
+}
+
 /// Error type for shell execution
 #[derive(Debug)]
 enum ShellError {
@@ -154,10 +372,13 @@ enum ShellError {
     ExecutionFailed(std::io::Error),
 }
 
-/// Launch a subshell in the specified directory
-fn launch_subshell(path: &str) {
-    let shell = get_shell();
-    if let Err(e) = run_shell(&shell, path) {
+/// Launch a subshell in the specified directory, or run `custom_command` there instead
+fn launch_subshell(path: &str, custom_command: Option<String>) {
+    let result = match custom_command {
+        Some(command) => run_in_dir(Command::new("sh").arg("-c").arg(command), path),
+        None => run_shell(&get_shell(), path),
+    };
+    if let Err(e) = result {
         match e {
             ShellError::ExitCode(code) => std::process::exit(code),
             ShellError::Terminated => std::process::exit(1),
@@ -172,7 +393,12 @@ fn launch_subshell(path: &str) {
 /// Run a shell command in the specified directory
 /// Returns Ok(()) if shell exits successfully, Err otherwise
 fn run_shell(shell: &str, path: &str) -> std::result::Result<(), ShellError> {
-    match Command::new(shell).current_dir(path).status() {
+    run_in_dir(&mut Command::new(shell), path)
+}
+
+/// Run a command in the specified directory, mapping its exit status
+fn run_in_dir(command: &mut Command, path: &str) -> std::result::Result<(), ShellError> {
+    match command.current_dir(path).status() {
         Ok(status) => {
             if status.success() {
                 Ok(())
@@ -200,6 +426,28 @@ mod tests {
         let cli = Cli::parse_from(["gwm"]);
         assert!(cli.config.is_none());
         assert!(!cli.print_path);
+        assert!(!cli.list);
+        assert_eq!(cli.format, list::ListFormat::Text);
+        assert!(!cli.dry_run);
+    }
+
+    #[test]
+    fn test_cli_parse_dry_run() {
+        let cli = Cli::parse_from(["gwm", "--dry-run", "-p"]);
+        assert!(cli.dry_run);
+        assert!(cli.print_path);
+    }
+
+    #[test]
+    fn test_cli_parse_list_json() {
+        let cli = Cli::parse_from(["gwm", "--list", "--format=json"]);
+        assert!(cli.list);
+        assert_eq!(cli.format, list::ListFormat::Json);
+    }
+
+    #[test]
+    fn test_cli_parse_format_requires_list() {
+        assert!(Cli::try_parse_from(["gwm", "--format", "json"]).is_err());
     }
 
     #[test]
@@ -233,6 +481,17 @@ mod tests {
         assert_eq!(cli.config, Some(PathBuf::from("/path/to/config.toml")));
     }
 
+    #[test]
+    fn test_terminal_guard_restore_is_idempotent() {
+        // Not in raw mode here, so restoring only flips the guard's state
+        let mut guard = TerminalGuard { active: true };
+
+        assert!(guard.restore().is_ok());
+        assert!(!guard.active);
+        assert!(guard.restore().is_ok());
+        assert!(!guard.active);
+    }
+
     #[test]
     fn test_get_shell_returns_shell_env_or_fallback() {
         // Test that get_shell returns either $SHELL or /bin/sh
@@ -273,6 +532,16 @@ mod tests {
         assert_eq!(status.code(), Some(42));
     }
 
+    #[test]
+    fn test_run_in_dir_shell_command() {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg("test \"$(pwd)\" = /tmp && exit 3");
+
+        let result = run_in_dir(&mut command, "/tmp");
+
+        assert!(matches!(result, Err(ShellError::ExitCode(3))));
+    }
+
     #[test]
     fn test_shell_error_debug() {
         // Test that ShellError implements Debug
@@ -0,0 +1,164 @@
+//! Open selected worktrees in a terminal multiplexer session instead of a nested shell
+
+use crate::shell::shell_quote;
+use std::path::Path;
+
+/// Supported terminal multiplexers (`integration.multiplexer`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Multiplexer {
+    Tmux,
+    Zellij,
+}
+
+impl Multiplexer {
+    /// Parse a configured name; "none" and unknown names disable the integration
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "tmux" => Some(Self::Tmux),
+            "zellij" => Some(Self::Zellij),
+            _ => None,
+        }
+    }
+
+    fn binary(self) -> &'static str {
+        match self {
+            Self::Tmux => "tmux",
+            Self::Zellij => "zellij",
+        }
+    }
+
+    /// Whether gwm itself runs inside a session of this multiplexer
+    pub fn is_inside(self) -> bool {
+        let var = match self {
+            Self::Tmux => "TMUX",
+            Self::Zellij => "ZELLIJ",
+        };
+        std::env::var_os(var).is_some_and(|v| !v.is_empty())
+    }
+
+    /// Command that opens `path` in the session `session`: switch to it (creating it
+    /// first if needed) from inside the multiplexer, or attach to it from outside
+    pub fn command(self, session: &str, path: &str, inside: bool) -> String {
+        let name = shell_quote(session);
+        let path = shell_quote(path);
+        match (self, inside) {
+            (Self::Tmux, true) => {
+                // "=" makes tmux match the session name exactly
+                let target = shell_quote(&format!("={}", session));
+                format!(
+                    "tmux has-session -t {target} 2>/dev/null || tmux new-session -d -s {name} -c {path}; tmux switch-client -t {target}"
+                )
+            }
+            (Self::Tmux, false) => format!("tmux new-session -A -s {name} -c {path}"),
+            // Zellij cannot switch sessions from the command line, so open a tab instead
+            (Self::Zellij, true) => format!("zellij action new-tab --name {name} --cwd {path}"),
+            // New sessions start in the working directory, which is the worktree
+            (Self::Zellij, false) => format!("zellij attach --create {name}"),
+        }
+    }
+}
+
+/// Session name for a worktree of the repository `repo`: "repo-dir", so
+/// worktrees sharing a directory name in different repositories don't share
+/// a session. Characters tmux does not allow in session names are replaced
+pub fn session_name(repo: &str, path: &Path) -> String {
+    let dir = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name = match (repo.is_empty(), dir.is_empty()) {
+        (true, true) => "gwm".to_string(),
+        (true, false) => dir,
+        (false, true) => repo.to_string(),
+        // The main worktree's directory usually is the repository name
+        (false, false) if dir == repo => dir,
+        (false, false) => format!("{}-{}", repo, dir),
+    };
+    name.replace(['.', ':'], "-")
+}
+
+/// Command opening the worktree at `path` of the repository `repo` in
+/// `multiplexer`, or None when the multiplexer is not installed
+pub fn open_command(multiplexer: Multiplexer, repo: &str, path: &str) -> Option<String> {
+    if !is_installed(multiplexer.binary()) {
+        return None;
+    }
+    Some(multiplexer.command(
+        &session_name(repo, Path::new(path)),
+        path,
+        multiplexer.is_inside(),
+    ))
+}
+
+/// Check whether `binary` is an executable file on $PATH
+fn is_installed(binary: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|paths| std::env::split_paths(&paths).any(|dir| dir.join(binary).is_file()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Multiplexer::parse("tmux"), Some(Multiplexer::Tmux));
+        assert_eq!(Multiplexer::parse("Zellij"), Some(Multiplexer::Zellij));
+        assert_eq!(Multiplexer::parse("none"), None);
+        assert_eq!(Multiplexer::parse("screen"), None);
+    }
+
+    #[test]
+    fn test_session_name_replaces_invalid_characters() {
+        assert_eq!(session_name("", Path::new("/wt/feature-x")), "feature-x");
+        assert_eq!(
+            session_name("my.app", Path::new("/wt/v1.2:fix")),
+            "my-app-v1-2-fix"
+        );
+        assert_eq!(session_name("", Path::new("/")), "gwm");
+    }
+
+    #[test]
+    fn test_session_name_separates_repositories() {
+        let api = session_name("api", Path::new("/worktrees/api/feature-x"));
+        let web = session_name("web", Path::new("/worktrees/web/feature-x"));
+
+        assert_eq!(api, "api-feature-x");
+        assert_eq!(web, "web-feature-x");
+        assert_eq!(session_name("api", Path::new("/src/api")), "api");
+    }
+
+    #[test]
+    fn test_tmux_command_outside_session_attaches() {
+        assert_eq!(
+            Multiplexer::Tmux.command("wt", "/repo/wt", false),
+            "tmux new-session -A -s 'wt' -c '/repo/wt'"
+        );
+    }
+
+    #[test]
+    fn test_tmux_command_inside_session_switches() {
+        assert_eq!(
+            Multiplexer::Tmux.command("wt", "/repo/wt", true),
+            "tmux has-session -t '=wt' 2>/dev/null || tmux new-session -d -s 'wt' -c '/repo/wt'; tmux switch-client -t '=wt'"
+        );
+    }
+
+    #[test]
+    fn test_zellij_commands() {
+        assert_eq!(
+            Multiplexer::Zellij.command("wt", "/repo/wt", false),
+            "zellij attach --create 'wt'"
+        );
+        assert_eq!(
+            Multiplexer::Zellij.command("wt", "/repo/wt", true),
+            "zellij action new-tab --name 'wt' --cwd '/repo/wt'"
+        );
+    }
+
+    #[test]
+    fn test_is_installed() {
+        assert!(is_installed("sh"));
+        assert!(!is_installed("gwm-nonexistent-binary"));
+    }
+}
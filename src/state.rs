@@ -0,0 +1,82 @@
+//! Per-repository UI state persisted between launches
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// State file name, stored inside the repository's git directory
+const STATE_FILE_NAME: &str = "gwm-state.toml";
+
+#[derive(Error, Debug)]
+pub enum StateError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to serialize state: {0}")]
+    Serialize(#[from] toml::ser::Error),
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct State {
+    /// Path of the worktree that was selected when gwm last exited
+    pub last_selected: Option<PathBuf>,
+}
+
+impl State {
+    /// Location of the state file for a repository
+    pub fn file_path(git_dir: &Path) -> PathBuf {
+        git_dir.join(STATE_FILE_NAME)
+    }
+
+    /// Load state, falling back to defaults when missing or unreadable
+    pub fn load(git_dir: &Path) -> Self {
+        std::fs::read_to_string(Self::file_path(git_dir))
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, git_dir: &Path) -> Result<(), StateError> {
+        let content = toml::to_string(self)?;
+        std::fs::write(Self::file_path(git_dir), content)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_missing_file_returns_default() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let state = State::load(temp_dir.path());
+
+        assert_eq!(state, State::default());
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let state = State {
+            last_selected: Some(PathBuf::from("/repo/feature-a")),
+        };
+
+        state.save(temp_dir.path()).unwrap();
+        let loaded = State::load(temp_dir.path());
+
+        assert_eq!(loaded, state);
+        assert!(temp_dir.path().join(STATE_FILE_NAME).exists());
+    }
+
+    #[test]
+    fn test_load_invalid_file_returns_default() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join(STATE_FILE_NAME), "not = [valid").unwrap();
+
+        let state = State::load(temp_dir.path());
+
+        assert_eq!(state, State::default());
+    }
+}
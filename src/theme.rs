@@ -108,6 +108,28 @@ pub struct ThemeColorsConfig {
 }
 
 impl ThemeColorsConfig {
+    /// Color overrides that are set, as (field name, value) pairs
+    pub fn entries(&self) -> Vec<(&'static str, &str)> {
+        [
+            ("header", &self.header),
+            ("selected", &self.selected),
+            ("branch", &self.branch),
+            ("remote", &self.remote),
+            ("main_worktree", &self.main_worktree),
+            ("key", &self.key),
+            ("description", &self.description),
+            ("text", &self.text),
+            ("text_muted", &self.text_muted),
+            ("separator", &self.separator),
+            ("success", &self.success),
+            ("error", &self.error),
+            ("warning", &self.warning),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.as_deref().map(|v| (name, v)))
+        .collect()
+    }
+
     /// Apply color overrides to a ThemeColors instance
     pub fn apply_to(&self, base: &mut ThemeColors) {
         if let Some(ref c) = self.header {
@@ -1,15 +1,19 @@
-use crate::app::{App, AppMode, ConfirmAction};
+use crate::app::{App, AppMode, ConfirmAction, CreateField, Focus, MessageLevel, SearchField};
+use crate::git::Worktree;
+use crate::input::KEY_BINDINGS;
 use crate::theme::ThemeColors;
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Margin, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, Padding, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Padding, Paragraph},
     Frame,
 };
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 /// Branch icon (NerdFont)
 const BRANCH_ICON: &str = "\u{e725}";
+const LOCK_ICON: &str = "\u{f023}";
 
 /// Spinner animation frames (braille pattern)
 const SPINNER_FRAMES: &[char] = &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
@@ -23,6 +27,227 @@ fn format_branch_with_icon(branch: &str, icons_enabled: bool) -> String {
     }
 }
 
+/// Variables of a `ui.list_format` template
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ListToken {
+    Name,
+    Branch,
+    Path,
+    Dirty,
+    Locked,
+    Main,
+    Head,
+}
+
+impl ListToken {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "name" => Some(Self::Name),
+            "branch" => Some(Self::Branch),
+            "path" => Some(Self::Path),
+            "dirty" => Some(Self::Dirty),
+            "locked" => Some(Self::Locked),
+            "main" => Some(Self::Main),
+            "head" => Some(Self::Head),
+            _ => None,
+        }
+    }
+}
+
+/// Expand a `ui.list_format` template into lines of `(token, text)` parts,
+/// where literal text has no token. `value` supplies each variable's text.
+/// Literal text right before a variable without a value is dropped, so
+/// separators like " | " disappear along with an empty `{branch}`.
+/// Unknown `{...}` is kept as written.
+fn expand_list_format(
+    template: &str,
+    value: impl Fn(ListToken) -> String,
+) -> Vec<Vec<(Option<ListToken>, String)>> {
+    template
+        .split('\n')
+        .map(|line| {
+            let mut parts = Vec::new();
+            let mut literal = String::new();
+            let mut rest = line;
+            while let Some(start) = rest.find('{') {
+                let token = rest[start..].find('}').and_then(|len| {
+                    ListToken::parse(&rest[start + 1..start + len]).map(|t| (t, len))
+                });
+                let Some((token, len)) = token else {
+                    literal.push_str(&rest[..=start]);
+                    rest = &rest[start + 1..];
+                    continue;
+                };
+                literal.push_str(&rest[..start]);
+                rest = &rest[start + len + 1..];
+                let text = value(token);
+                if text.is_empty() {
+                    literal.clear();
+                    continue;
+                }
+                if !literal.is_empty() {
+                    parts.push((None, std::mem::take(&mut literal)));
+                }
+                parts.push((Some(token), text));
+            }
+            literal.push_str(rest);
+            if !literal.is_empty() {
+                parts.push((None, literal));
+            }
+            parts
+        })
+        .collect()
+}
+
+/// Join the lines of a row into one for `ui.compact_list`, two spaces apart
+fn join_list_lines(
+    lines: Vec<Vec<(Option<ListToken>, String)>>,
+) -> Vec<(Option<ListToken>, String)> {
+    let mut joined = Vec::new();
+    for parts in lines.into_iter().filter(|parts| !parts.is_empty()) {
+        if !joined.is_empty() {
+            joined.push((None, "  ".to_string()));
+        }
+        joined.extend(parts);
+    }
+    joined
+}
+
+/// Shorten `{path}` so a row of `used` columns fits in `width`, keeping the
+/// end of the path, which tells worktrees apart
+fn fit_row_path(parts: &mut [(Option<ListToken>, String)], used: usize, width: usize) {
+    if used <= width {
+        return;
+    }
+    let Some((_, path)) = parts
+        .iter_mut()
+        .find(|(token, _)| *token == Some(ListToken::Path))
+    else {
+        return;
+    };
+    let rest = used - path.width();
+    *path = truncate_start(path, width.saturating_sub(rest));
+}
+
+/// Keep the last `width` columns of `text`, marking the cut with "…"
+fn truncate_start(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let mut kept = Vec::new();
+    let mut used = 1; // "…"
+    for c in text.chars().rev() {
+        let w = c.width().unwrap_or(0);
+        if used + w > width {
+            break;
+        }
+        used += w;
+        kept.push(c);
+    }
+    std::iter::once('…').chain(kept.into_iter().rev()).collect()
+}
+
+/// Style a status message with the theme color of its level
+fn level_style(level: MessageLevel, colors: &ThemeColors) -> Style {
+    match level {
+        MessageLevel::Success => Style::default().fg(colors.success),
+        MessageLevel::Warning => Style::default().fg(colors.warning),
+        MessageLevel::Error => Style::default().fg(colors.error),
+    }
+}
+
+/// Format how long ago a message was shown, e.g. "42s ago" or "3m ago"
+fn format_elapsed(elapsed: std::time::Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        format!("{}s ago", secs)
+    } else if secs < 3600 {
+        format!("{}m ago", secs / 60)
+    } else if secs < 86_400 {
+        format!("{}h ago", secs / 3600)
+    } else {
+        format!("{}d ago", secs / 86_400)
+    }
+}
+
+/// Format a byte count with binary units, e.g. "512 B" or "1.5 GiB"
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Disk space the confirm dialog's delete frees (`confirm.show_size`)
+fn size_summary(app: &App) -> Option<String> {
+    if app.measuring_size() {
+        return Some("Measuring disk usage…".to_string());
+    }
+    app.confirm_size.map(|size| {
+        if size.partial {
+            format!("Frees at least {}", format_bytes(size.bytes))
+        } else {
+            format!("Frees {}", format_bytes(size.bytes))
+        }
+    })
+}
+
+/// Describe where "(Create new branch)" branches off, so it reads differently
+/// from checking out one of the listed branches
+fn create_new_base_label(app: &App) -> String {
+    let base_ref = app.create_base_ref();
+    if !base_ref.is_empty() {
+        return format!(" from {}", base_ref);
+    }
+    match app.branches.iter().find(|b| b.is_head) {
+        Some(head) => format!(" from HEAD ({})", head.name),
+        None => " from HEAD".to_string(),
+    }
+}
+
+/// Repository context shown under the header: name, worktree count and main branch
+fn status_text(app: &App) -> String {
+    let mut text = format!("{} · {} worktree(s)", app.repo_name(), app.worktrees.len());
+    if let Some(branch) = app.main_branch() {
+        text.push_str(&format!(" · main: {}", branch));
+    }
+    if app.dry_run {
+        text.push_str(" · dry run");
+    }
+    text
+}
+
+/// Cut `text` to fit `width` columns, ending with an ellipsis when shortened
+fn truncate_to_width(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let mut result = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w + 1 > width {
+            break;
+        }
+        result.push(c);
+        used += w;
+    }
+    if width > 0 {
+        result.push('…');
+    }
+    result
+}
+
 /// Get the inner area with 1 character margin on all sides
 fn inner_area(frame: &Frame) -> Rect {
     frame.area().inner(Margin {
@@ -41,30 +266,61 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
             draw_normal_mode(frame, app, area, &colors);
             draw_confirm_dialog(frame, app, &colors);
         }
-        AppMode::Deleting => {
+        AppMode::Creating => {
+            draw_create_mode(frame, app, area, &colors);
+            draw_progress_dialog(frame, app, &colors);
+        }
+        AppMode::Deleting | AppMode::Fetching | AppMode::SettingUp => {
+            draw_normal_mode(frame, app, area, &colors);
+            draw_progress_dialog(frame, app, &colors);
+        }
+        AppMode::Move => {
+            draw_normal_mode(frame, app, area, &colors);
+            draw_move_dialog(frame, app, &colors);
+        }
+        AppMode::LockReason => {
             draw_normal_mode(frame, app, area, &colors);
-            draw_deleting_dialog(frame, app, &colors);
+            draw_lock_dialog(frame, app, &colors);
         }
         AppMode::Config => {
             draw_normal_mode(frame, app, area, &colors);
             draw_config_dialog(frame, app, &colors);
         }
+        AppMode::Help => {
+            draw_normal_mode(frame, app, area, &colors);
+            draw_help_dialog(frame, app, &colors);
+        }
+        AppMode::MessageLog => {
+            draw_normal_mode(frame, app, area, &colors);
+            draw_message_log_dialog(frame, app, &colors);
+        }
     }
 }
 
-fn draw_normal_mode(frame: &mut Frame, app: &App, area: Rect, colors: &ThemeColors) {
+fn draw_normal_mode(frame: &mut Frame, app: &mut App, area: Rect, colors: &ThemeColors) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(1), // Header with search
-            Constraint::Length(1), // Spacer
+            Constraint::Length(1), // Repository status
             Constraint::Min(3),    // Main content (list + detail)
             Constraint::Length(1), // Footer/Status
         ])
         .split(area);
 
-    // Header with search
-    let header = if app.input.is_empty() {
+    let status = truncate_to_width(&status_text(app), chunks[1].width as usize);
+    frame.render_widget(
+        Paragraph::new(status).style(Style::default().fg(colors.text_muted)),
+        chunks[1],
+    );
+
+    // Header with search (the input holds the prompt text in Move and LockReason mode)
+    let query = if matches!(app.mode, AppMode::Move | AppMode::LockReason) {
+        ""
+    } else {
+        app.input.as_str()
+    };
+    let header = if query.is_empty() {
         Paragraph::new(Line::from(vec![
             Span::styled(
                 "gwm",
@@ -84,13 +340,20 @@ fn draw_normal_mode(frame: &mut Frame, app: &App, area: Rect, colors: &ThemeColo
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(" │ ", Style::default().fg(colors.separator)),
-            Span::styled(&app.input, Style::default().fg(colors.text)),
+            Span::styled(query, Style::default().fg(colors.text)),
         ]))
     };
     frame.render_widget(header, chunks[0]);
 
+    let sort_indicator = Paragraph::new(Line::from(vec![
+        Span::styled("sort: ", Style::default().fg(colors.text_muted)),
+        Span::styled(app.sort_mode.label(), Style::default().fg(colors.text)),
+    ]))
+    .alignment(Alignment::Right);
+    frame.render_widget(sort_indicator, chunks[0]);
+
     // Show cursor at search position
-    let cursor_x = chunks[0].x + 6 + app.input.len() as u16; // "gwm │ " = 6 chars
+    let cursor_x = chunks[0].x + 6 + app.input_before_cursor().width() as u16; // "gwm │ " = 6 chars
     frame.set_cursor_position((cursor_x, chunks[0].y));
 
     // Split main content into left (list) and right (detail)
@@ -102,8 +365,15 @@ fn draw_normal_mode(frame: &mut Frame, app: &App, area: Rect, colors: &ThemeColo
         ])
         .split(chunks[2]);
 
+    // Remember how many rows fit for page navigation (minus borders)
+    app.list_height = (main_chunks[0].height.saturating_sub(2) / app.list_item_height()).max(1);
+
     // Worktree list (use filtered_worktrees)
     let icons_enabled = app.icons_enabled();
+    let list_format = app.list_format();
+    // Inside the borders and horizontal padding
+    let row_width = main_chunks[0].width.saturating_sub(4) as usize;
+    let spinner = SPINNER_FRAMES[(app.tick as usize) % SPINNER_FRAMES.len()];
     let items: Vec<ListItem> = app
         .filtered_worktrees
         .iter()
@@ -118,13 +388,12 @@ fn draw_normal_mode(frame: &mut Frame, app: &App, area: Rect, colors: &ThemeColo
                     .add_modifier(Modifier::BOLD)
             } else if wt.is_main {
                 Style::default().fg(colors.main_worktree)
+            } else if app.is_stale(wt) {
+                Style::default().fg(colors.text_muted)
             } else {
                 Style::default()
             };
 
-            // Hide branch name if it matches worktree name
-            let branch_display = wt.branch.as_ref().filter(|b| *b != &wt.name);
-
             let mut spans = vec![
                 Span::styled(
                     prefix,
@@ -134,36 +403,81 @@ fn draw_normal_mode(frame: &mut Frame, app: &App, area: Rect, colors: &ThemeColo
                         Style::default()
                     },
                 ),
-                Span::styled(&wt.name, name_style),
+                if app.marked_worktrees.contains(&wt.path) {
+                    Span::styled("[x] ", Style::default().fg(colors.warning))
+                } else if !app.marked_worktrees.is_empty() {
+                    Span::styled("[ ] ", Style::default().fg(colors.text_muted))
+                } else {
+                    Span::raw("")
+                },
             ];
 
-            // Add separator and branch only if branch is different from worktree name
-            if let Some(branch) = branch_display {
-                spans.push(Span::styled(" | ", Style::default().fg(colors.separator)));
-                spans.push(Span::styled(
-                    format_branch_with_icon(branch, icons_enabled),
-                    Style::default()
-                        .fg(colors.branch)
-                        .add_modifier(Modifier::DIM),
-                ));
-            }
+            let pending = app.pending_status.contains(&wt.path);
+            let value = |token: ListToken| match token {
+                ListToken::Name => wt.name.clone(),
+                // Hide branch name if it matches worktree name
+                ListToken::Branch => match (&wt.branch, &wt.head_id) {
+                    (Some(branch), _) if *branch == wt.name => String::new(),
+                    (Some(branch), _) => format_branch_with_icon(branch, icons_enabled),
+                    (None, Some(id)) => format!("(detached {})", id),
+                    (None, None) => String::new(),
+                },
+                ListToken::Path => app.format_path(&wt.path.to_string_lossy()),
+                // Status still loading in the background
+                ListToken::Dirty if pending => spinner.to_string(),
+                ListToken::Dirty if wt.is_dirty => "●".to_string(),
+                ListToken::Locked if wt.is_locked && icons_enabled => LOCK_ICON.to_string(),
+                ListToken::Locked if wt.is_locked => "[locked]".to_string(),
+                ListToken::Main if wt.is_main => "[main]".to_string(),
+                ListToken::Head => wt.head_id.clone().unwrap_or_default(),
+                ListToken::Dirty | ListToken::Locked | ListToken::Main => String::new(),
+            };
+            let style = |token: Option<ListToken>| match token {
+                None => Style::default().fg(colors.separator),
+                Some(ListToken::Name) => name_style,
+                Some(ListToken::Dirty) if pending => Style::default().fg(colors.text_muted),
+                Some(ListToken::Dirty | ListToken::Locked) => Style::default().fg(colors.warning),
+                Some(ListToken::Branch) if wt.branch.is_none() => {
+                    Style::default().fg(colors.text_muted)
+                }
+                Some(ListToken::Branch) => Style::default()
+                    .fg(colors.branch)
+                    .add_modifier(Modifier::DIM),
+                Some(ListToken::Main) => Style::default()
+                    .fg(colors.main_worktree)
+                    .add_modifier(Modifier::DIM),
+                Some(ListToken::Path | ListToken::Head) => Style::default().fg(colors.text_muted),
+            };
 
-            if wt.is_main {
-                spans.push(Span::styled(
-                    " [main]",
-                    Style::default()
-                        .fg(colors.main_worktree)
-                        .add_modifier(Modifier::DIM),
-                ));
+            let mut row = expand_list_format(list_format, value);
+            if app.compact_list {
+                let mut parts = join_list_lines(row);
+                let used = spans.iter().map(|s| s.width()).sum::<usize>()
+                    + parts.iter().map(|(_, text)| text.width()).sum::<usize>();
+                fit_row_path(&mut parts, used, row_width);
+                row = vec![parts];
             }
 
-            let content = Line::from(spans);
+            // Continuation lines line up with the first line's text
+            let indent = " ".repeat(spans.iter().map(|s| s.width()).sum());
+            let mut lines = Vec::new();
+            for (i, parts) in row.into_iter().enumerate() {
+                if i > 0 {
+                    spans = vec![Span::raw(indent.clone())];
+                }
+                spans.extend(
+                    parts
+                        .into_iter()
+                        .map(|(token, text)| Span::styled(text, style(token))),
+                );
+                lines.push(Line::from(std::mem::take(&mut spans)));
+            }
 
-            ListItem::new(content)
+            ListItem::new(lines)
         })
         .collect();
 
-    let title = if app.input.is_empty() {
+    let mut title = if app.input.is_empty() {
         "Worktrees".to_string()
     } else {
         format!(
@@ -172,20 +486,27 @@ fn draw_normal_mode(frame: &mut Frame, app: &App, area: Rect, colors: &ThemeColo
             app.worktrees.len()
         )
     };
+    if !app.marked_worktrees.is_empty() {
+        title.push_str(&format!(" [{} marked]", app.marked_worktrees.len()));
+    }
     let list = List::new(items).block(
         Block::default()
             .borders(Borders::ALL)
+            .border_style(pane_border_style(app.focus == Focus::List, colors))
             .title(title)
             .padding(Padding::horizontal(1)),
     );
-    frame.render_widget(list, main_chunks[0]);
+    let mut list_state = ListState::default().with_selected(Some(app.selected_worktree));
+    frame.render_stateful_widget(list, main_chunks[0], &mut list_state);
+    app.list_area = main_chunks[0];
+    app.list_offset = list_state.offset();
 
     // Detail pane
     draw_detail_pane(frame, app, main_chunks[1], colors);
 
     // Footer
     if let Some(msg) = &app.message {
-        let footer = Paragraph::new(msg.as_str()).style(Style::default().fg(colors.success));
+        let footer = Paragraph::new(msg.as_str()).style(level_style(app.message_level, colors));
         frame.render_widget(footer, chunks[3]);
     } else {
         let footer = render_normal_footer(colors);
@@ -193,7 +514,32 @@ fn draw_normal_mode(frame: &mut Frame, app: &App, area: Rect, colors: &ThemeColo
     }
 }
 
-fn draw_detail_pane(frame: &mut Frame, app: &App, area: Rect, colors: &ThemeColors) {
+fn draw_detail_pane(frame: &mut Frame, app: &mut App, area: Rect, colors: &ThemeColors) {
+    // Field matched by the search query, with its value when not otherwise shown
+    let search_match = app.selected_search_match().map(|field| {
+        let worktree = &app.filtered_worktrees[app.selected_worktree];
+        let value = match field {
+            SearchField::CommitMessage => worktree.commit_message.clone(),
+            SearchField::CommitAuthor => worktree.commit_author.clone(),
+            _ => None,
+        };
+        (field, value)
+    });
+    let query = app.input.clone();
+    let created_at = app
+        .filtered_worktrees
+        .get(app.selected_worktree)
+        .and_then(|w| w.created_at);
+    let head_id = app
+        .filtered_worktrees
+        .get(app.selected_worktree)
+        .and_then(|w| w.head_id.clone());
+    let lock = app
+        .filtered_worktrees
+        .get(app.selected_worktree)
+        .filter(|w| w.is_locked)
+        .map(|w| w.lock_reason.clone());
+    app.sync_detail_scroll();
     let detail = app.get_selected_worktree_detail();
     let icons_enabled = app.icons_enabled();
 
@@ -201,7 +547,11 @@ fn draw_detail_pane(frame: &mut Frame, app: &App, area: Rect, colors: &ThemeColo
 
     if let Some(detail) = detail {
         // Branch
-        let branch_name = detail.branch.as_deref().unwrap_or("(detached)").to_string();
+        let branch_name = match (&detail.branch, &head_id) {
+            (Some(branch), _) => branch.clone(),
+            (None, Some(id)) => format!("(detached {})", id),
+            (None, None) => "(detached)".to_string(),
+        };
         let icon_span = if icons_enabled {
             Span::styled(
                 format!("{} ", BRANCH_ICON),
@@ -210,23 +560,101 @@ fn draw_detail_pane(frame: &mut Frame, app: &App, area: Rect, colors: &ThemeColo
         } else {
             Span::raw("")
         };
-        lines.push(Line::from(vec![
+        let branch_style = Style::default()
+            .fg(colors.branch)
+            .add_modifier(Modifier::BOLD);
+        let mut branch_line = vec![
             Span::styled("Branch: ", Style::default().fg(colors.text_muted)),
             icon_span,
-            Span::styled(
-                branch_name,
-                Style::default()
-                    .fg(colors.branch)
-                    .add_modifier(Modifier::BOLD),
+        ];
+        match search_match {
+            Some((SearchField::Branch | SearchField::CommitId, _)) if detail.branch.is_none() => {
+                branch_line.extend(highlight_match(branch_name, &query, branch_style, colors))
+            }
+            Some((SearchField::Branch, _)) => {
+                branch_line.extend(highlight_match(branch_name, &query, branch_style, colors))
+            }
+            _ => branch_line.push(Span::styled(branch_name, branch_style)),
+        }
+        lines.push(Line::from(branch_line));
+
+        // Ahead/behind upstream
+        let sync_span = match detail.ahead_behind {
+            Some((ahead, behind)) => Span::styled(
+                format!("↑{} ↓{}", ahead, behind),
+                Style::default().fg(colors.text),
             ),
+            None => Span::styled("no upstream", Style::default().fg(colors.text_muted)),
+        };
+        lines.push(Line::from(vec![
+            Span::styled("Sync:   ", Style::default().fg(colors.text_muted)),
+            sync_span,
         ]));
 
         // Path
         let display_path = app.format_path(&detail.path);
-        lines.push(Line::from(vec![
-            Span::styled("Path:   ", Style::default().fg(colors.text_muted)),
-            Span::styled(display_path, Style::default().fg(colors.text)),
-        ]));
+        let path_style = Style::default().fg(colors.text);
+        let mut path_line = vec![Span::styled(
+            "Path:   ",
+            Style::default().fg(colors.text_muted),
+        )];
+        match search_match {
+            Some((SearchField::Path, _)) => {
+                path_line.extend(highlight_match(display_path, &query, path_style, colors))
+            }
+            _ => path_line.push(Span::styled(display_path, path_style)),
+        }
+        lines.push(Line::from(path_line));
+
+        // When the linked worktree was added
+        if let Some(created_at) = created_at {
+            let age = (crate::app::unix_now() - created_at).max(0) as u64;
+            lines.push(Line::from(vec![
+                Span::styled("Added:  ", Style::default().fg(colors.text_muted)),
+                Span::styled(
+                    format_elapsed(std::time::Duration::from_secs(age)),
+                    Style::default().fg(colors.text),
+                ),
+            ]));
+        }
+
+        // Lock state, with the reason given when locking
+        if let Some(reason) = lock {
+            let icon = if icons_enabled {
+                format!("{} ", LOCK_ICON)
+            } else {
+                String::new()
+            };
+            let reason_span = match reason {
+                Some(reason) => Span::styled(reason, Style::default().fg(colors.text)),
+                None => Span::styled("no reason given", Style::default().fg(colors.text_muted)),
+            };
+            lines.push(Line::from(vec![
+                Span::styled("Locked: ", Style::default().fg(colors.text_muted)),
+                Span::styled(icon, Style::default().fg(colors.warning)),
+                reason_span,
+            ]));
+        }
+
+        // HEAD commit message or author, when that is what the search matched
+        let matched_commit = match search_match {
+            Some((SearchField::CommitId, _)) if detail.branch.is_some() => {
+                head_id.clone().map(|id| ("HEAD:   ", id))
+            }
+            Some((SearchField::CommitMessage, Some(value))) => Some(("Commit: ", value)),
+            Some((SearchField::CommitAuthor, Some(value))) => Some(("Author: ", value)),
+            _ => None,
+        };
+        if let Some((label, value)) = matched_commit {
+            let mut match_line = vec![Span::styled(label, Style::default().fg(colors.text_muted))];
+            match_line.extend(highlight_match(
+                value,
+                &query,
+                Style::default().fg(colors.text),
+                colors,
+            ));
+            lines.push(Line::from(match_line));
+        }
 
         lines.push(Line::from(""));
 
@@ -264,22 +692,22 @@ fn draw_detail_pane(frame: &mut Frame, app: &App, area: Rect, colors: &ThemeColo
             ]));
         }
 
-        lines.push(Line::from(""));
-
-        // Recent commits
-        lines.push(Line::from(vec![Span::styled(
-            "Recent Commits",
-            Style::default()
-                .fg(colors.text_muted)
-                .add_modifier(Modifier::UNDERLINED),
-        )]));
-
-        if detail.recent_commits.is_empty() {
+        // Recent commits (hidden with ui.recent_commits = 0)
+        if app.show_recent_commits() {
+            lines.push(Line::from(""));
             lines.push(Line::from(vec![Span::styled(
-                "  (no commits)",
-                Style::default().fg(colors.text_muted),
+                "Recent Commits",
+                Style::default()
+                    .fg(colors.text_muted)
+                    .add_modifier(Modifier::UNDERLINED),
             )]));
-        } else {
+
+            if detail.recent_commits.is_empty() {
+                lines.push(Line::from(vec![Span::styled(
+                    "  (no commits)",
+                    Style::default().fg(colors.text_muted),
+                )]));
+            }
             for commit in detail.recent_commits {
                 let graph_char = if commit.is_merge { "●" } else { "○" };
                 lines.push(Line::from(vec![
@@ -300,16 +728,62 @@ fn draw_detail_pane(frame: &mut Frame, app: &App, area: Rect, colors: &ThemeColo
         )]));
     }
 
-    let detail_widget = Paragraph::new(lines).block(
+    // Long commit lists can overflow; keep the offset within the content
+    let visible_height = area.height.saturating_sub(2);
+    app.detail_scroll_max = (lines.len() as u16).saturating_sub(visible_height);
+    if app.detail_scroll > app.detail_scroll_max {
+        app.detail_scroll = app.detail_scroll_max;
+    }
+
+    let detail_widget = Paragraph::new(lines).scroll((app.detail_scroll, 0)).block(
         Block::default()
             .borders(Borders::ALL)
+            .border_style(pane_border_style(app.focus == Focus::Detail, colors))
             .title("Details")
             .padding(Padding::horizontal(1)),
     );
     frame.render_widget(detail_widget, area);
 }
 
-fn draw_create_mode(frame: &mut Frame, app: &App, area: Rect, colors: &ThemeColors) {
+/// Border of a Normal mode pane; the focused one takes the header color
+fn pane_border_style(focused: bool, colors: &ThemeColors) -> Style {
+    if focused {
+        Style::default().fg(colors.header)
+    } else {
+        Style::default()
+    }
+}
+
+/// Split `text` into spans, highlighting the first case-insensitive occurrence of `query`
+fn highlight_match(
+    text: String,
+    query: &str,
+    style: Style,
+    colors: &ThemeColors,
+) -> Vec<Span<'static>> {
+    let lower = text.to_lowercase();
+    let query = query.to_lowercase();
+    // Lowercasing can shift byte offsets for some characters; skip highlighting then
+    let range = lower
+        .find(&query)
+        .filter(|_| !query.is_empty() && lower.len() == text.len())
+        .map(|start| (start, start + query.len()))
+        .filter(|&(start, end)| text.is_char_boundary(start) && text.is_char_boundary(end));
+    let Some((start, end)) = range else {
+        return vec![Span::styled(text, style)];
+    };
+
+    let highlight = style
+        .fg(colors.selected)
+        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+    vec![
+        Span::styled(text[..start].to_string(), style),
+        Span::styled(text[start..end].to_string(), highlight),
+        Span::styled(text[end..].to_string(), style),
+    ]
+}
+
+fn draw_create_mode(frame: &mut Frame, app: &mut App, area: Rect, colors: &ThemeColors) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -329,18 +803,45 @@ fn draw_create_mode(frame: &mut Frame, app: &App, area: Rect, colors: &ThemeColo
                 .fg(colors.header)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::styled(" - Create Worktree", Style::default().fg(colors.text_muted)),
+        Span::styled(
+            if app.open_after_create {
+                " - Checkout Branch"
+            } else if app.quick_create {
+                " - New Branch"
+            } else {
+                " - Create Worktree"
+            },
+            Style::default().fg(colors.text_muted),
+        ),
     ]));
     frame.render_widget(header, chunks[0]);
 
     // Input field - title changes based on selection
     let input_title = if app.selected_branch == 0 {
         // "Create new branch" is selected
-        "New branch name"
+        match app.create_field {
+            CreateField::BranchName if app.quick_create => format!(
+                "New branch name{} (Tab: worktree name)",
+                create_new_base_label(app)
+            ),
+            CreateField::BranchName => "New branch name (Tab: worktree name)".to_string(),
+            CreateField::WorktreeName => format!(
+                "Worktree name for '{}' (Tab: start point)",
+                app.branch_filter
+            ),
+            CreateField::BaseRef if app.branch_filter.is_empty() => {
+                "Start point: commit, tag or ref; detached without a branch name (Tab: branch name)"
+                    .to_string()
+            }
+            CreateField::BaseRef => format!(
+                "Start point for '{}': commit, tag or ref (Tab: branch name)",
+                app.branch_filter
+            ),
+        }
     } else if app.input.is_empty() {
-        "Worktree name (empty = branch name)"
+        "Worktree name (empty = branch name)".to_string()
     } else {
-        "Worktree name"
+        "Worktree name".to_string()
     };
     let input = Paragraph::new(app.input.as_str()).block(
         Block::default()
@@ -351,7 +852,27 @@ fn draw_create_mode(frame: &mut Frame, app: &App, area: Rect, colors: &ThemeColo
     frame.render_widget(input, chunks[2]);
 
     // Show cursor in input field (border + padding = 2)
-    frame.set_cursor_position((chunks[2].x + app.input.len() as u16 + 2, chunks[2].y + 1));
+    frame.set_cursor_position((
+        chunks[2].x + app.input_before_cursor().width() as u16 + 2,
+        chunks[2].y + 1,
+    ));
+
+    // Footer
+    if let Some(msg) = &app.message {
+        let footer = Paragraph::new(msg.as_str()).style(level_style(app.message_level, colors));
+        frame.render_widget(footer, chunks[4]);
+    } else {
+        let footer = render_create_footer(colors);
+        frame.render_widget(footer, chunks[4]);
+    }
+
+    // Quick create is only the prompt
+    if app.quick_create {
+        return;
+    }
+
+    // Remember the visible list height for page navigation (minus borders)
+    app.list_height = chunks[3].height.saturating_sub(2);
 
     // Branch list - start with "Create new branch" option
     let icons_enabled = app.icons_enabled();
@@ -377,6 +898,10 @@ fn draw_create_mode(frame: &mut Frame, app: &App, area: Rect, colors: &ThemeColo
             },
         ),
         Span::styled("(Create new branch)", create_new_style),
+        Span::styled(
+            create_new_base_label(app),
+            Style::default().fg(colors.text_muted),
+        ),
     ])));
 
     // Add existing branches (index 1+)
@@ -431,22 +956,23 @@ fn draw_create_mode(frame: &mut Frame, app: &App, area: Rect, colors: &ThemeColo
         items.push(ListItem::new(content));
     }
 
+    let branches_title = if app.branch_filter.is_empty() {
+        "Branches".to_string()
+    } else {
+        format!(
+            "Branches ({}/{})",
+            app.filtered_branches.len(),
+            app.branches.len()
+        )
+    };
     let list = List::new(items).block(
         Block::default()
             .borders(Borders::ALL)
-            .title("Branches")
+            .title(branches_title)
             .padding(Padding::horizontal(1)),
     );
-    frame.render_widget(list, chunks[3]);
-
-    // Footer
-    if let Some(msg) = &app.message {
-        let footer = Paragraph::new(msg.as_str()).style(Style::default().fg(colors.success));
-        frame.render_widget(footer, chunks[4]);
-    } else {
-        let footer = render_create_footer(colors);
-        frame.render_widget(footer, chunks[4]);
-    }
+    let mut list_state = ListState::default().with_selected(Some(app.selected_branch));
+    frame.render_stateful_widget(list, chunks[3], &mut list_state);
 }
 
 fn render_normal_footer(colors: &ThemeColors) -> Paragraph<'static> {
@@ -461,8 +987,10 @@ fn render_normal_footer(colors: &ThemeColors) -> Paragraph<'static> {
         Span::styled(": delete  ", Style::default().fg(colors.description)),
         Span::styled("D", Style::default().fg(colors.key)),
         Span::styled(": prune  ", Style::default().fg(colors.description)),
+        Span::styled("C-s", Style::default().fg(colors.key)),
+        Span::styled(": sort  ", Style::default().fg(colors.description)),
         Span::styled("?", Style::default().fg(colors.key)),
-        Span::styled(": config  ", Style::default().fg(colors.description)),
+        Span::styled(": help  ", Style::default().fg(colors.description)),
         Span::styled("C-q", Style::default().fg(colors.key)),
         Span::styled(": quit", Style::default().fg(colors.description)),
     ]))
@@ -482,30 +1010,111 @@ fn render_create_footer(colors: &ThemeColors) -> Paragraph<'static> {
 }
 
 fn draw_confirm_dialog(frame: &mut Frame, app: &App, colors: &ThemeColors) {
-    let area = centered_rect(60, 30, frame.area());
+    // Batch deletes list every target, so give them more room
+    let percent_y = match app.confirm_action {
+        Some(ConfirmAction::DeleteSingle) | None => 30,
+        Some(ConfirmAction::DeleteMarked | ConfirmAction::Prune) => 60,
+    };
+    let area = centered_rect(60, percent_y, frame.area());
     let clear_area = expand_area(area, frame.area());
+    // Borders plus the question and blank line above the list
+    let max_items = area.height.saturating_sub(4) as usize;
 
+    let mut highlight_row = None;
     let message = match app.confirm_action {
         Some(ConfirmAction::DeleteSingle) => {
             let wt = &app.filtered_worktrees[app.selected_worktree];
-            format!("Delete worktree '{}'?", wt.name)
+            if wt.is_locked {
+                format!(
+                    "Worktree '{}' is locked.\n\nPress f to force delete it.",
+                    wt.name
+                )
+            } else if wt.is_dirty {
+                format!(
+                    "Worktree '{}' has uncommitted changes.\n\nPress s to stash them and delete it.",
+                    wt.name
+                )
+            } else {
+                format!("Delete worktree '{}'?", wt.name)
+            }
         }
-        Some(ConfirmAction::Prune) => {
-            let names: Vec<_> = app
-                .merged_worktrees
+        Some(ConfirmAction::DeleteMarked) => {
+            let (marked, locked, dirty) = app.marked_targets();
+            let mut message = format!(
+                "Delete {} marked worktree(s)?\n\n{}",
+                marked.len(),
+                worktree_summary_lines(&marked, max_items).join("\n")
+            );
+            let checking = marked
                 .iter()
-                .map(|w| w.name.as_str())
-                .collect();
-            format!(
-                "Prune {} merged worktree(s)?\n\n{}",
-                names.len(),
-                names.join(", ")
-            )
+                .filter(|w| app.pending_status.contains(&w.path))
+                .count();
+            if checking > 0 {
+                message.push_str(&format!(
+                    "\n\nChecking {} for uncommitted changes...",
+                    checking
+                ));
+            }
+            for (heading, kept) in [
+                ("Kept because locked", &locked),
+                ("Kept because of uncommitted changes", &dirty),
+            ] {
+                if !kept.is_empty() {
+                    message.push_str(&format!(
+                        "\n\n{}:\n{}",
+                        heading,
+                        worktree_summary_lines(kept, max_items)
+                            .join("\n")
+                            .replace('•', "◦")
+                    ));
+                }
+            }
+            if !locked.is_empty() || !dirty.is_empty() {
+                message.push_str("\n\nDelete those one at a time to force or stash.");
+            }
+            message
+        }
+        Some(ConfirmAction::Prune) => {
+            let (review, cursor_row) = prune_review_lines(app, max_items);
+            // The question and a blank line come first
+            highlight_row = cursor_row.map(|row| row + 2);
+            let mut message = format!(
+                "Prune {} of {} worktree(s) merged into '{}'?\n\n{}",
+                app.prune_selection().len(),
+                app.merged_worktrees.len(),
+                app.prune_base,
+                review.join("\n")
+            );
+            if !app.prune_excluded.is_empty() {
+                message.push_str(&format!(
+                    "\n\nKept by worktree.prune_exclude:\n{}",
+                    worktree_summary_lines(&app.prune_excluded, max_items)
+                        .join("\n")
+                        .replace('•', "◦")
+                ));
+            }
+            if !app.prune_locked.is_empty() {
+                message.push_str(&format!(
+                    "\n\nKept because locked:\n{}",
+                    worktree_summary_lines(&app.prune_locked, max_items)
+                        .join("\n")
+                        .replace('•', "◦")
+                ));
+            }
+            message
         }
         None => String::new(),
     };
+    let message = match &app.confirm_reason {
+        Some(reason) => format!("{}\n\n{}", message, reason),
+        None => message,
+    };
+    let message = match size_summary(app) {
+        Some(size) => format!("{}\n\n{}", message, size),
+        None => message,
+    };
 
-    let shortcut_line = Line::from(vec![
+    let mut shortcuts = vec![
         Span::styled(" y", Style::default().fg(colors.key)),
         Span::styled(": worktree ", Style::default().fg(colors.description)),
         Span::styled("Y", Style::default().fg(colors.key)),
@@ -513,19 +1122,49 @@ fn draw_confirm_dialog(frame: &mut Frame, app: &App, colors: &ThemeColors) {
             ": worktree & branch ",
             Style::default().fg(colors.description),
         ),
+    ];
+    if app.confirm_action == Some(ConfirmAction::Prune) {
+        shortcuts.push(Span::styled("Space", Style::default().fg(colors.key)));
+        shortcuts.push(Span::styled(
+            ": check/uncheck ",
+            Style::default().fg(colors.description),
+        ));
+    }
+    // Force delete and stashing only apply to a single worktree
+    if app.confirm_action == Some(ConfirmAction::DeleteSingle) {
+        shortcuts.push(Span::styled("f", Style::default().fg(colors.key)));
+        shortcuts.push(Span::styled(
+            ": force ",
+            Style::default().fg(colors.description),
+        ));
+        if app.filtered_worktrees[app.selected_worktree].is_dirty {
+            shortcuts.push(Span::styled("s", Style::default().fg(colors.key)));
+            shortcuts.push(Span::styled(
+                ": stash & delete ",
+                Style::default().fg(colors.description),
+            ));
+        }
+    }
+    shortcuts.extend([
         Span::styled("n", Style::default().fg(colors.key)),
         Span::styled("/", Style::default().fg(colors.description)),
         Span::styled("Esc", Style::default().fg(colors.key)),
         Span::styled(": cancel ", Style::default().fg(colors.description)),
     ]);
+    let shortcut_line = Line::from(shortcuts);
 
     let lines: Vec<Line> = message
         .lines()
-        .map(|l| {
-            Line::from(Span::styled(
-                l.to_string(),
-                Style::default().fg(colors.text),
-            ))
+        .enumerate()
+        .map(|(i, l)| {
+            let style = if highlight_row == Some(i) {
+                Style::default()
+                    .fg(colors.selected)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(colors.text)
+            };
+            Line::from(Span::styled(l.to_string(), style))
         })
         .collect();
 
@@ -542,7 +1181,58 @@ fn draw_confirm_dialog(frame: &mut Frame, app: &App, colors: &ThemeColors) {
     frame.render_widget(dialog, area);
 }
 
-fn draw_deleting_dialog(frame: &mut Frame, app: &App, colors: &ThemeColors) {
+/// Checkbox rows of the prune dialog, scrolled to keep the highlighted one
+/// within `max` lines. Also returns the highlighted row's index
+fn prune_review_lines(app: &App, max: usize) -> (Vec<String>, Option<usize>) {
+    let total = app.merged_worktrees.len();
+    let max = max.max(1);
+    // Scroll just enough to keep the cursor visible
+    let start = app
+        .prune_cursor
+        .saturating_sub(max - 1)
+        .min(total.saturating_sub(max));
+    let lines = app
+        .merged_worktrees
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(max)
+        .map(|(i, w)| {
+            let pointer = if i == app.prune_cursor { "▶" } else { " " };
+            let mark = if app.prune_unchecked.contains(&w.path) {
+                "[ ]"
+            } else {
+                "[x]"
+            };
+            match &w.branch {
+                Some(branch) if *branch != w.name => {
+                    format!("{} {} {} ({})", pointer, mark, w.name, branch)
+                }
+                _ => format!("{} {} {}", pointer, mark, w.name),
+            }
+        })
+        .collect();
+    let cursor = app.prune_cursor.checked_sub(start).filter(|&row| row < max);
+    (lines, cursor)
+}
+
+/// One line per worktree with its branch, capped at `max` lines
+fn worktree_summary_lines(worktrees: &[Worktree], max: usize) -> Vec<String> {
+    let describe = |w: &Worktree| match &w.branch {
+        Some(branch) if *branch != w.name => format!("• {} ({})", w.name, branch),
+        _ => format!("• {}", w.name),
+    };
+    if worktrees.len() <= max {
+        return worktrees.iter().map(describe).collect();
+    }
+    // Keep the last line for the overflow count
+    let shown = max.saturating_sub(1);
+    let mut lines: Vec<String> = worktrees.iter().take(shown).map(describe).collect();
+    lines.push(format!("… and {} more", worktrees.len() - shown));
+    lines
+}
+
+fn draw_progress_dialog(frame: &mut Frame, app: &App, colors: &ThemeColors) {
     let area = centered_rect(50, 20, frame.area());
     let clear_area = expand_area(area, frame.area());
 
@@ -550,13 +1240,30 @@ fn draw_deleting_dialog(frame: &mut Frame, app: &App, colors: &ThemeColors) {
     let message = format!(
         "{} {}",
         spinner,
-        app.deleting_message.as_deref().unwrap_or("Deleting...")
+        app.progress_message.as_deref().unwrap_or("Deleting...")
     );
 
-    let wait_hint = Line::from(vec![Span::styled(
-        " Please wait... ",
-        Style::default().fg(colors.text_muted),
-    )]);
+    let wait_hint = if app.quit_pending {
+        Line::from(vec![
+            Span::styled(
+                " Operation in progress, quit anyway? ",
+                Style::default().fg(colors.error),
+            ),
+            Span::styled("y", Style::default().fg(colors.key)),
+            Span::styled("/", Style::default().fg(colors.description)),
+            Span::styled("n ", Style::default().fg(colors.key)),
+        ])
+    } else if app.can_cancel_setup() {
+        Line::from(vec![
+            Span::styled(" Esc", Style::default().fg(colors.key)),
+            Span::styled(": cancel setup ", Style::default().fg(colors.description)),
+        ])
+    } else {
+        Line::from(vec![Span::styled(
+            " Please wait... ",
+            Style::default().fg(colors.text_muted),
+        )])
+    };
 
     let dialog = Paragraph::new(Line::from(vec![Span::styled(
         message,
@@ -575,8 +1282,74 @@ fn draw_deleting_dialog(frame: &mut Frame, app: &App, colors: &ThemeColors) {
     frame.render_widget(dialog, area);
 }
 
-fn draw_config_dialog(frame: &mut Frame, app: &mut App, colors: &ThemeColors) {
-    let area = centered_rect(70, 80, frame.area());
+fn draw_move_dialog(frame: &mut Frame, app: &App, colors: &ThemeColors) {
+    let title = match &app.move_source {
+        Some(wt) => format!("Move worktree '{}' to", wt.name),
+        None => "Move worktree to".to_string(),
+    };
+    draw_prompt_dialog(frame, app, &title, "move", colors);
+}
+
+fn draw_lock_dialog(frame: &mut Frame, app: &App, colors: &ThemeColors) {
+    let title = match app.filtered_worktrees.get(app.selected_worktree) {
+        Some(wt) => format!("Lock worktree '{}' (reason, optional)", wt.name),
+        None => "Lock worktree (reason, optional)".to_string(),
+    };
+    draw_prompt_dialog(frame, app, &title, "lock", colors);
+}
+
+/// Single-line input dialog over the list, editing `app.input`
+fn draw_prompt_dialog(
+    frame: &mut Frame,
+    app: &App,
+    title: &str,
+    action: &str,
+    colors: &ThemeColors,
+) {
+    let popup = centered_rect(70, 20, frame.area());
+    // Border plus a single input line
+    let area = Rect {
+        y: popup.y + popup.height.saturating_sub(3) / 2,
+        height: 3.min(frame.area().height),
+        ..popup
+    };
+    let clear_area = expand_area(area, frame.area());
+
+    let hint = Line::from(vec![
+        Span::styled(" Enter", Style::default().fg(colors.key)),
+        Span::styled(
+            format!(": {}  ", action),
+            Style::default().fg(colors.description),
+        ),
+        Span::styled("Esc", Style::default().fg(colors.key)),
+        Span::styled(": cancel ", Style::default().fg(colors.description)),
+    ]);
+
+    let dialog = Paragraph::new(Span::styled(
+        app.input.as_str(),
+        Style::default().fg(colors.text),
+    ))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .title_bottom(hint)
+            .style(Style::default().fg(colors.header))
+            .padding(Padding::horizontal(1)),
+    );
+
+    frame.render_widget(Clear, clear_area);
+    frame.render_widget(dialog, area);
+
+    // Show cursor in input field (border + padding = 2)
+    frame.set_cursor_position((
+        area.x + app.input_before_cursor().width() as u16 + 2,
+        area.y + 1,
+    ));
+}
+
+fn draw_config_dialog(frame: &mut Frame, app: &mut App, colors: &ThemeColors) {
+    let area = centered_rect(70, 80, frame.area());
     let clear_area = expand_area(area, frame.area());
 
     let sources = &app.config_sources;
@@ -719,6 +1492,109 @@ fn draw_config_dialog(frame: &mut Frame, app: &mut App, colors: &ThemeColors) {
     frame.render_widget(dialog, area);
 }
 
+fn draw_help_dialog(frame: &mut Frame, app: &mut App, colors: &ThemeColors) {
+    let area = centered_rect(70, 80, frame.area());
+    let clear_area = expand_area(area, frame.area());
+
+    let mut lines: Vec<Line> = Vec::new();
+    for (i, (mode, bindings)) in KEY_BINDINGS.iter().enumerate() {
+        if i > 0 {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(vec![Span::styled(
+            *mode,
+            Style::default()
+                .fg(colors.header)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        for (key, description) in bindings.iter() {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:<14}", key), Style::default().fg(colors.key)),
+                Span::styled(*description, Style::default().fg(colors.description)),
+            ]));
+        }
+    }
+
+    let visible_height = area.height.saturating_sub(2);
+    app.config_scroll_max = (lines.len() as u16).saturating_sub(visible_height);
+    if app.config_scroll > app.config_scroll_max {
+        app.config_scroll = app.config_scroll_max;
+    }
+
+    let close_hint = Line::from(vec![
+        Span::styled(" ↑↓", Style::default().fg(colors.key)),
+        Span::styled(": scroll  ", Style::default().fg(colors.description)),
+        Span::styled("c", Style::default().fg(colors.key)),
+        Span::styled(": config  ", Style::default().fg(colors.description)),
+        Span::styled("Esc", Style::default().fg(colors.key)),
+        Span::styled("/", Style::default().fg(colors.description)),
+        Span::styled("q", Style::default().fg(colors.key)),
+        Span::styled(": close ", Style::default().fg(colors.description)),
+    ]);
+
+    let dialog = Paragraph::new(lines).scroll((app.config_scroll, 0)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Help")
+            .title_bottom(close_hint)
+            .style(Style::default().fg(colors.header))
+            .padding(Padding::horizontal(1)),
+    );
+
+    frame.render_widget(Clear, clear_area);
+    frame.render_widget(dialog, area);
+}
+
+fn draw_message_log_dialog(frame: &mut Frame, app: &mut App, colors: &ThemeColors) {
+    let area = centered_rect(70, 80, frame.area());
+    let clear_area = expand_area(area, frame.area());
+
+    let mut lines: Vec<Line> = Vec::new();
+    if app.message_log.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            "(no messages yet)",
+            Style::default().fg(colors.text_muted),
+        )]));
+    }
+    // Newest first
+    for entry in app.message_log.iter().rev() {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:>8}  ", format_elapsed(entry.at.elapsed())),
+                Style::default().fg(colors.text_muted),
+            ),
+            Span::styled(entry.text.clone(), level_style(entry.level, colors)),
+        ]));
+    }
+
+    let visible_height = area.height.saturating_sub(2);
+    app.config_scroll_max = (lines.len() as u16).saturating_sub(visible_height);
+    if app.config_scroll > app.config_scroll_max {
+        app.config_scroll = app.config_scroll_max;
+    }
+
+    let close_hint = Line::from(vec![
+        Span::styled(" ↑↓", Style::default().fg(colors.key)),
+        Span::styled(": scroll  ", Style::default().fg(colors.description)),
+        Span::styled("Esc", Style::default().fg(colors.key)),
+        Span::styled("/", Style::default().fg(colors.description)),
+        Span::styled("q", Style::default().fg(colors.key)),
+        Span::styled(": close ", Style::default().fg(colors.description)),
+    ]);
+
+    let dialog = Paragraph::new(lines).scroll((app.config_scroll, 0)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Messages")
+            .title_bottom(close_hint)
+            .style(Style::default().fg(colors.header))
+            .padding(Padding::horizontal(1)),
+    );
+
+    frame.render_widget(Clear, clear_area);
+    frame.render_widget(dialog, area);
+}
+
 /// Extract explicitly set config entries as (key, value) pairs
 fn config_entries(config: &crate::config::Config) -> Vec<(&'static str, String)> {
     let mut entries = Vec::new();
@@ -728,9 +1604,54 @@ fn config_entries(config: &crate::config::Config) -> Vec<(&'static str, String)>
     if let Some(v) = config.worktree.auto_mkdir {
         entries.push(("worktree.auto_mkdir", v.to_string()));
     }
+    if let Some(v) = config.worktree.show_status {
+        entries.push(("worktree.show_status", v.to_string()));
+    }
+    if let Some(ref v) = config.worktree.editor {
+        entries.push(("worktree.editor", format!("\"{}\"", v)));
+    }
+    if let Some(ref v) = config.worktree.shell {
+        entries.push(("worktree.shell", format!("\"{}\"", v)));
+    }
+    if let Some(ref v) = config.worktree.diff_command {
+        entries.push(("worktree.diff_command", format!("\"{}\"", v)));
+    }
+    if let Some(ref v) = config.worktree.on_open {
+        entries.push(("worktree.on_open", format!("\"{}\"", v)));
+    }
+    if let Some(ref v) = config.worktree.default_remote {
+        entries.push(("worktree.default_remote", format!("\"{}\"", v)));
+    }
+    if let Some(ref v) = config.worktree.default_base {
+        entries.push(("worktree.default_base", format!("\"{}\"", v)));
+    }
+    if let Some(v) = config.worktree.strict_copy_files {
+        entries.push(("worktree.strict_copy_files", v.to_string()));
+    }
+    if let Some(v) = config.worktree.fetch_on_open {
+        entries.push(("worktree.fetch_on_open", v.to_string()));
+    }
+    if let Some(v) = config.worktree.open_after_create {
+        entries.push(("worktree.open_after_create", v.to_string()));
+    }
+    if let Some(v) = config.worktree.namespace_by_repo {
+        entries.push(("worktree.namespace_by_repo", v.to_string()));
+    }
+    if let Some(ref v) = config.worktree.prune_exclude {
+        entries.push(("worktree.prune_exclude", format!("{:?}", v)));
+    }
+    if let Some(v) = config.worktree.warn_stale_base {
+        entries.push(("worktree.warn_stale_base", v.to_string()));
+    }
+    if let Some(ref v) = config.worktree.create_base {
+        entries.push(("worktree.create_base", format!("\"{}\"", v)));
+    }
     if let Some(ref v) = config.naming.template {
         entries.push(("naming.template", format!("\"{}\"", v)));
     }
+    if let Some(ref v) = config.naming.branch_template {
+        entries.push(("naming.branch_template", format!("\"{}\"", v)));
+    }
     if config.naming.sanitize_chars.is_some() {
         entries.push(("naming.sanitize_chars", "(custom)".to_string()));
     }
@@ -746,11 +1667,67 @@ fn config_entries(config: &crate::config::Config) -> Vec<(&'static str, String)>
     if config.ui.colors.is_some() {
         entries.push(("ui.colors", "(custom)".to_string()));
     }
+    if let Some(v) = config.ui.tick_rate_ms {
+        entries.push(("ui.tick_rate_ms", v.to_string()));
+    }
+    if let Some(v) = config.ui.animations {
+        entries.push(("ui.animations", v.to_string()));
+    }
+    if let Some(v) = config.ui.notification_secs {
+        entries.push(("ui.notification_secs", v.to_string()));
+    }
+    if let Some(v) = config.ui.recent_commits {
+        entries.push(("ui.recent_commits", v.to_string()));
+    }
+    if let Some(v) = config.ui.stale_days {
+        entries.push(("ui.stale_days", v.to_string()));
+    }
+    if let Some(v) = config.ui.set_title {
+        entries.push(("ui.set_title", v.to_string()));
+    }
+    if let Some(v) = config.ui.mouse {
+        entries.push(("ui.mouse", v.to_string()));
+    }
+    if let Some(ref v) = config.ui.list_format {
+        entries.push(("ui.list_format", format!("{:?}", v)));
+    }
+    if let Some(v) = config.ui.compact_list {
+        entries.push(("ui.compact_list", v.to_string()));
+    }
+    if let Some(ref v) = config.integration.multiplexer {
+        entries.push(("integration.multiplexer", format!("\"{}\"", v)));
+    }
+    if let Some(v) = config.confirm.delete {
+        entries.push(("confirm.delete", v.to_string()));
+    }
+    if let Some(v) = config.confirm.prune {
+        entries.push(("confirm.prune", v.to_string()));
+    }
+    if let Some(v) = config.confirm.show_size {
+        entries.push(("confirm.show_size", v.to_string()));
+    }
     if let Some(ref v) = config.copy_files {
         entries.push(("copy_files", format!("{:?}", v)));
     }
+    if let Some(ref v) = config.symlink_files {
+        entries.push(("symlink_files", format!("{:?}", v)));
+    }
     if let Some(ref v) = config.setup_commands {
-        entries.push(("setup_commands", format!("{:?}", v)));
+        entries.push(("setup_commands", format_setup_commands(v)));
+    }
+    if let Some(ref v) = config.setup_shell {
+        entries.push(("setup_shell", format!("\"{}\"", v)));
+    }
+    if let Some(v) = config.setup_timeout_secs {
+        entries.push(("setup_timeout_secs", v.to_string()));
+    }
+    if let Some(ref hooks) = config.hooks {
+        for hook in hooks {
+            entries.push((
+                "hooks",
+                format!("{}: {}", hook.event.as_str(), hook.command),
+            ));
+        }
     }
     entries
 }
@@ -783,6 +1760,119 @@ fn effective_config_entries(
     );
     entries.push(("worktree.auto_mkdir", val, src));
 
+    // worktree.show_status
+    let (val, src) = resolve_source_bool(
+        &env.worktree.show_status,
+        &local.worktree.show_status,
+        &global.worktree.show_status,
+        true,
+    );
+    entries.push(("worktree.show_status", val, src));
+
+    // worktree.editor
+    let (val, src) = resolve_source_opt_str(
+        &env.worktree.editor,
+        &local.worktree.editor,
+        &global.worktree.editor,
+    );
+    entries.push(("worktree.editor", val, src));
+
+    // worktree.shell
+    let (val, src) = resolve_source_opt_str(
+        &env.worktree.shell,
+        &local.worktree.shell,
+        &global.worktree.shell,
+    );
+    entries.push(("worktree.shell", val, src));
+
+    // worktree.diff_command
+    let (val, src) = resolve_source_str(
+        &env.worktree.diff_command,
+        &local.worktree.diff_command,
+        &global.worktree.diff_command,
+        "git diff HEAD",
+    );
+    entries.push(("worktree.diff_command", val, src));
+
+    // worktree.on_open
+    let (val, src) = resolve_source_opt_str(
+        &env.worktree.on_open,
+        &local.worktree.on_open,
+        &global.worktree.on_open,
+    );
+    entries.push(("worktree.on_open", val, src));
+
+    // worktree.default_remote
+    let (val, src) = resolve_source_str(
+        &env.worktree.default_remote,
+        &local.worktree.default_remote,
+        &global.worktree.default_remote,
+        "origin",
+    );
+    entries.push(("worktree.default_remote", val, src));
+
+    // worktree.default_base
+    let (val, src) = resolve_source_opt_str(
+        &env.worktree.default_base,
+        &local.worktree.default_base,
+        &global.worktree.default_base,
+    );
+    entries.push(("worktree.default_base", val, src));
+
+    // worktree.strict_copy_files
+    let (val, src) = resolve_source_bool(
+        &env.worktree.strict_copy_files,
+        &local.worktree.strict_copy_files,
+        &global.worktree.strict_copy_files,
+        false,
+    );
+    entries.push(("worktree.strict_copy_files", val, src));
+
+    // worktree.fetch_on_open
+    let (val, src) = resolve_source_bool(
+        &env.worktree.fetch_on_open,
+        &local.worktree.fetch_on_open,
+        &global.worktree.fetch_on_open,
+        false,
+    );
+    entries.push(("worktree.fetch_on_open", val, src));
+
+    // worktree.open_after_create
+    let (val, src) = resolve_source_bool(
+        &env.worktree.open_after_create,
+        &local.worktree.open_after_create,
+        &global.worktree.open_after_create,
+        false,
+    );
+    entries.push(("worktree.open_after_create", val, src));
+
+    // worktree.namespace_by_repo
+    let (val, src) = resolve_source_bool(
+        &env.worktree.namespace_by_repo,
+        &local.worktree.namespace_by_repo,
+        &global.worktree.namespace_by_repo,
+        false,
+    );
+    entries.push(("worktree.namespace_by_repo", val, src));
+
+    // worktree.warn_stale_base
+    let (val, src) = resolve_source_bool(
+        &env.worktree.warn_stale_base,
+        &local.worktree.warn_stale_base,
+        &global.worktree.warn_stale_base,
+        false,
+    );
+    entries.push(("worktree.warn_stale_base", val, src));
+
+    // worktree.create_base
+    let (val, src) = resolve_source_str(
+        &env.worktree.create_base,
+        &local.worktree.create_base,
+        &global.worktree.create_base,
+        "first",
+    );
+    entries.push(("worktree.create_base", val, src));
+
     // naming.template
     let (val, src) = resolve_source_opt_str(
         &env.naming.template,
@@ -791,6 +1881,14 @@ fn effective_config_entries(
     );
     entries.push(("naming.template", val, src));
 
+    // naming.branch_template
+    let (val, src) = resolve_source_opt_str(
+        &env.naming.branch_template,
+        &local.naming.branch_template,
+        &global.naming.branch_template,
+    );
+    entries.push(("naming.branch_template", val, src));
+
     // ui.icons
     let (val, src) = resolve_source_bool(&env.ui.icons, &local.ui.icons, &global.ui.icons, true);
     entries.push(("ui.icons", val, src));
@@ -809,9 +1907,178 @@ fn effective_config_entries(
         resolve_source_str(&env.ui.theme, &local.ui.theme, &global.ui.theme, "default");
     entries.push(("ui.theme", val, src));
 
+    // ui.tick_rate_ms
+    let (val, src) = resolve_source_u64(
+        &env.ui.tick_rate_ms,
+        &local.ui.tick_rate_ms,
+        &global.ui.tick_rate_ms,
+        250,
+    );
+    entries.push(("ui.tick_rate_ms", val, src));
+
+    // ui.animations
+    let (val, src) = resolve_source_bool(
+        &env.ui.animations,
+        &local.ui.animations,
+        &global.ui.animations,
+        true,
+    );
+    entries.push(("ui.animations", val, src));
+
+    // ui.notification_secs
+    let (val, src) = resolve_source_opt_str(
+        &env.ui.notification_secs.map(|v| v.to_string()),
+        &local.ui.notification_secs.map(|v| v.to_string()),
+        &global.ui.notification_secs.map(|v| v.to_string()),
+    );
+    entries.push(("ui.notification_secs", val, src));
+
+    // ui.recent_commits
+    let (val, src) = resolve_source_u64(
+        &env.ui.recent_commits.map(|v| v as u64),
+        &local.ui.recent_commits.map(|v| v as u64),
+        &global.ui.recent_commits.map(|v| v as u64),
+        5,
+    );
+    entries.push(("ui.recent_commits", val, src));
+
+    // ui.stale_days
+    let (val, src) = resolve_source_opt_str(
+        &env.ui.stale_days.map(|v| v.to_string()),
+        &local.ui.stale_days.map(|v| v.to_string()),
+        &global.ui.stale_days.map(|v| v.to_string()),
+    );
+    entries.push(("ui.stale_days", val, src));
+
+    // ui.set_title
+    let (val, src) = resolve_source_bool(
+        &env.ui.set_title,
+        &local.ui.set_title,
+        &global.ui.set_title,
+        true,
+    );
+    entries.push(("ui.set_title", val, src));
+
+    // ui.mouse
+    let (val, src) = resolve_source_bool(&env.ui.mouse, &local.ui.mouse, &global.ui.mouse, false);
+    entries.push(("ui.mouse", val, src));
+
+    // ui.list_format
+    let (val, src) = resolve_source_str(
+        &env.ui.list_format,
+        &local.ui.list_format,
+        &global.ui.list_format,
+        crate::config::DEFAULT_LIST_FORMAT,
+    );
+    entries.push(("ui.list_format", val.replace('\n', "\\n"), src));
+
+    // ui.compact_list
+    let (val, src) = resolve_source_bool(
+        &env.ui.compact_list,
+        &local.ui.compact_list,
+        &global.ui.compact_list,
+        false,
+    );
+    entries.push(("ui.compact_list", val, src));
+
+    // integration.multiplexer
+    let (val, src) = resolve_source_str(
+        &env.integration.multiplexer,
+        &local.integration.multiplexer,
+        &global.integration.multiplexer,
+        "none",
+    );
+    entries.push(("integration.multiplexer", val, src));
+
+    // confirm.delete
+    let (val, src) = resolve_source_bool(
+        &env.confirm.delete,
+        &local.confirm.delete,
+        &global.confirm.delete,
+        true,
+    );
+    entries.push(("confirm.delete", val, src));
+
+    // confirm.prune
+    let (val, src) = resolve_source_bool(
+        &env.confirm.prune,
+        &local.confirm.prune,
+        &global.confirm.prune,
+        true,
+    );
+    entries.push(("confirm.prune", val, src));
+
+    // confirm.show_size
+    let (val, src) = resolve_source_bool(
+        &env.confirm.show_size,
+        &local.confirm.show_size,
+        &global.confirm.show_size,
+        false,
+    );
+    entries.push(("confirm.show_size", val, src));
+
+    // Lists are replaced as a whole and can only be set in config files
+    let (val, src) = resolve_source_list(&local.copy_files, &global.copy_files, |v| {
+        format!("{:?}", v)
+    });
+    entries.push(("copy_files", val, src));
+
+    let (val, src) = resolve_source_list(
+        &local.worktree.prune_exclude,
+        &global.worktree.prune_exclude,
+        |v| format!("{:?}", v),
+    );
+    entries.push(("worktree.prune_exclude", val, src));
+
+    let (val, src) = resolve_source_list(&local.symlink_files, &global.symlink_files, |v| {
+        format!("{:?}", v)
+    });
+    entries.push(("symlink_files", val, src));
+
+    let (val, src) = resolve_source_list(
+        &local.setup_commands,
+        &global.setup_commands,
+        format_setup_commands,
+    );
+    entries.push(("setup_commands", val, src));
+
+    let (val, src) = resolve_source_str(
+        &env.setup_shell,
+        &local.setup_shell,
+        &global.setup_shell,
+        "sh",
+    );
+    entries.push(("setup_shell", val, src));
+
+    let (val, src) = resolve_source_opt_str(
+        &env.setup_timeout_secs.map(|v| v.to_string()),
+        &local.setup_timeout_secs.map(|v| v.to_string()),
+        &global.setup_timeout_secs.map(|v| v.to_string()),
+    );
+    entries.push(("setup_timeout_secs", val, src));
+
+    let (val, src) = resolve_source_list(&local.hooks, &global.hooks, |v| {
+        format!("{} hook(s)", v.len())
+    });
+    entries.push(("hooks", val, src));
+
     entries
 }
 
+fn resolve_source_list<T>(
+    local: &Option<Vec<T>>,
+    global: &Option<Vec<T>>,
+    format: impl Fn(&[T]) -> String,
+) -> (String, String) {
+    if let Some(v) = local {
+        (format(v), "local".to_string())
+    } else if let Some(v) = global {
+        (format(v), "global".to_string())
+    } else {
+        ("(none)".to_string(), "default".to_string())
+    }
+}
+
 fn resolve_source_str(
     env: &Option<String>,
     local: &Option<String>,
@@ -845,6 +2112,23 @@ fn resolve_source_opt_str(
     }
 }
 
+fn resolve_source_u64(
+    env: &Option<u64>,
+    local: &Option<u64>,
+    global: &Option<u64>,
+    default: u64,
+) -> (String, String) {
+    if let Some(v) = env {
+        (v.to_string(), "env".to_string())
+    } else if let Some(v) = local {
+        (v.to_string(), "local".to_string())
+    } else if let Some(v) = global {
+        (v.to_string(), "global".to_string())
+    } else {
+        (default.to_string(), "default".to_string())
+    }
+}
+
 fn resolve_source_bool(
     env: &Option<bool>,
     local: &Option<bool>,
@@ -862,6 +2146,12 @@ fn resolve_source_bool(
     }
 }
 
+/// Show setup commands like a TOML list, with per-command shells in parentheses
+fn format_setup_commands(commands: &[crate::config::SetupCommand]) -> String {
+    let items: Vec<String> = commands.iter().map(ToString::to_string).collect();
+    format!("[{}]", items.join(", "))
+}
+
 /// Expand a Rect by 1 cell on each side, clamped to the given bounds
 fn expand_area(area: Rect, bounds: Rect) -> Rect {
     let x = area.x.saturating_sub(1).max(bounds.x);
@@ -900,6 +2190,7 @@ fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app::DirSize;
 
     #[test]
     fn test_format_branch_with_icon_enabled() {
@@ -949,6 +2240,25 @@ mod tests {
         assert_eq!(entries[2], ("ui.theme", "\"classic\"".to_string()));
     }
 
+    #[test]
+    fn test_config_entries_lists_hooks() {
+        let config: crate::config::Config = toml::from_str(
+            r#"
+            [[hooks]]
+            event = "post_create"
+            command = "npm install"
+            "#,
+        )
+        .unwrap();
+
+        let entries = config_entries(&config);
+
+        assert_eq!(
+            entries,
+            vec![("hooks", "post_create: npm install".to_string())]
+        );
+    }
+
     // ========== resolve_source tests ==========
 
     #[test]
@@ -990,6 +2300,17 @@ mod tests {
         assert_eq!(src, "default");
     }
 
+    #[test]
+    fn test_resolve_source_u64_priority() {
+        let (val, src) = resolve_source_u64(&None, &Some(100), &Some(500), 250);
+        assert_eq!(val, "100");
+        assert_eq!(src, "local");
+
+        let (val, src) = resolve_source_u64(&None, &None, &None, 250);
+        assert_eq!(val, "250");
+        assert_eq!(src, "default");
+    }
+
     #[test]
     fn test_resolve_source_bool_priority() {
         let (val, src) = resolve_source_bool(&Some(true), &Some(false), &None, false);
@@ -1030,13 +2351,43 @@ mod tests {
         let sources = crate::config::ConfigSources::default();
         let entries = effective_config_entries(&sources);
 
-        assert_eq!(entries.len(), 6);
+        assert_eq!(entries.len(), 40);
         // All should be "default" source
         for (_key, _val, src) in &entries {
             assert_eq!(src, "default");
         }
     }
 
+    #[test]
+    fn test_effective_config_entries_lists_from_files() {
+        let mut sources = crate::config::ConfigSources::default();
+        sources.global.config.copy_files = Some(vec![".env".to_string()]);
+        sources.local.config.copy_files = Some(vec![".envrc".to_string()]);
+        sources.global.config.hooks = Some(vec![crate::config::Hook {
+            event: crate::config::HookEvent::PostCreate,
+            command: "make".to_string(),
+            cwd: None,
+            timeout_secs: None,
+        }]);
+
+        let entries = effective_config_entries(&sources);
+
+        let copy_files = entries.iter().find(|(k, _, _)| *k == "copy_files").unwrap();
+        assert_eq!(copy_files.1, "[\".envrc\"]");
+        assert_eq!(copy_files.2, "local");
+
+        let hooks = entries.iter().find(|(k, _, _)| *k == "hooks").unwrap();
+        assert_eq!(hooks.1, "1 hook(s)");
+        assert_eq!(hooks.2, "global");
+
+        let setup = entries
+            .iter()
+            .find(|(k, _, _)| *k == "setup_commands")
+            .unwrap();
+        assert_eq!(setup.1, "(none)");
+        assert_eq!(setup.2, "default");
+    }
+
     #[test]
     fn test_effective_config_entries_mixed_sources() {
         let mut sources = crate::config::ConfigSources::default();
@@ -1063,4 +2414,582 @@ mod tests {
         let theme = entries.iter().find(|(k, _, _)| *k == "ui.theme").unwrap();
         assert_eq!(theme.2, "default");
     }
+
+    // ========== level_style tests ==========
+
+    #[test]
+    fn test_level_style_uses_theme_colors() {
+        let colors = crate::theme::Theme::default().colors;
+
+        assert_eq!(
+            level_style(MessageLevel::Error, &colors).fg,
+            Some(colors.error)
+        );
+        assert_eq!(
+            level_style(MessageLevel::Warning, &colors).fg,
+            Some(colors.warning)
+        );
+        assert_eq!(
+            level_style(MessageLevel::Success, &colors).fg,
+            Some(colors.success)
+        );
+    }
+
+    #[test]
+    fn test_format_elapsed() {
+        use std::time::Duration;
+
+        assert_eq!(format_elapsed(Duration::from_secs(5)), "5s ago");
+        assert_eq!(format_elapsed(Duration::from_secs(125)), "2m ago");
+        assert_eq!(format_elapsed(Duration::from_secs(7300)), "2h ago");
+        assert_eq!(format_elapsed(Duration::from_secs(3 * 86_400)), "3d ago");
+    }
+
+    // ========== List Format Tests ==========
+
+    fn list_value(token: ListToken) -> String {
+        match token {
+            ListToken::Name => "feature-a".to_string(),
+            ListToken::Branch => "feature/a".to_string(),
+            ListToken::Path => "~/wt/feature-a".to_string(),
+            ListToken::Dirty => "●".to_string(),
+            ListToken::Head => "abc1234".to_string(),
+            ListToken::Locked | ListToken::Main => String::new(),
+        }
+    }
+
+    fn line_text(parts: &[(Option<ListToken>, String)]) -> String {
+        parts.iter().map(|(_, text)| text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_expand_list_format_default_matches_classic_row() {
+        let lines = expand_list_format(crate::config::DEFAULT_LIST_FORMAT, list_value);
+
+        assert_eq!(lines.len(), 1);
+        assert_eq!(line_text(&lines[0]), "feature-a ● | feature/a");
+        assert_eq!(
+            lines[0],
+            vec![
+                (Some(ListToken::Name), "feature-a".to_string()),
+                (None, " ".to_string()),
+                (Some(ListToken::Dirty), "●".to_string()),
+                (None, " | ".to_string()),
+                (Some(ListToken::Branch), "feature/a".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_list_format_drops_separator_of_empty_value() {
+        let lines = expand_list_format(crate::config::DEFAULT_LIST_FORMAT, |token| match token {
+            ListToken::Name => "main".to_string(),
+            ListToken::Main => "[main]".to_string(),
+            _ => String::new(),
+        });
+
+        assert_eq!(line_text(&lines[0]), "main [main]");
+    }
+
+    #[test]
+    fn test_expand_list_format_two_lines() {
+        let lines = expand_list_format("{branch} ({head})\n{path}", list_value);
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(line_text(&lines[0]), "feature/a (abc1234)");
+        assert_eq!(line_text(&lines[1]), "~/wt/feature-a");
+    }
+
+    #[test]
+    fn test_expand_list_format_keeps_unknown_braces() {
+        let lines = expand_list_format("{name} {size} {", list_value);
+
+        assert_eq!(line_text(&lines[0]), "feature-a {size} {");
+    }
+
+    #[test]
+    fn test_join_list_lines_for_compact_rows() {
+        let lines = expand_list_format("{branch}\n{path}  {main}", |token| match token {
+            ListToken::Branch => "feature/a".to_string(),
+            ListToken::Path => "~/wt/feature-a".to_string(),
+            _ => String::new(),
+        });
+
+        assert_eq!(
+            line_text(&join_list_lines(lines)),
+            "feature/a  ~/wt/feature-a"
+        );
+    }
+
+    #[test]
+    fn test_fit_row_path_truncates_from_start() {
+        let mut parts = vec![
+            (Some(ListToken::Name), "wt".to_string()),
+            (None, "  ".to_string()),
+            (Some(ListToken::Path), "~/work/repo/wt".to_string()),
+        ];
+
+        // 18 columns used, 12 available: the path gets 8 columns, "…" included
+        fit_row_path(&mut parts, 18, 12);
+        assert_eq!(parts[2].1, "…repo/wt");
+        assert_eq!(line_text(&parts).width(), 12);
+
+        // No room left for the path at all
+        fit_row_path(&mut parts, 12, 3);
+        assert_eq!(parts[2].1, "");
+    }
+
+    #[test]
+    fn test_fit_row_path_leaves_fitting_rows() {
+        let mut parts = vec![(Some(ListToken::Path), "~/wt".to_string())];
+
+        fit_row_path(&mut parts, 4, 10);
+        assert_eq!(parts[0].1, "~/wt");
+    }
+
+    #[test]
+    fn test_truncate_start_wide_chars() {
+        assert_eq!(truncate_start("日本語", 5), "…本語");
+        assert_eq!(truncate_start("日本語", 4), "…語");
+        assert_eq!(truncate_start("abc", 3), "abc");
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024 / 2), "1.5 GiB");
+    }
+
+    #[test]
+    fn test_size_summary() {
+        let mut app = App::new_for_test(crate::config::Config::default(), Vec::new(), Vec::new());
+        assert_eq!(size_summary(&app), None);
+
+        app.confirm_size = Some(DirSize {
+            bytes: 2048,
+            partial: false,
+        });
+        assert_eq!(size_summary(&app).as_deref(), Some("Frees 2.0 KiB"));
+
+        app.confirm_size = Some(DirSize {
+            bytes: 2048,
+            partial: true,
+        });
+        assert_eq!(
+            size_summary(&app).as_deref(),
+            Some("Frees at least 2.0 KiB")
+        );
+    }
+
+    // ========== Prune Review Tests ==========
+
+    #[test]
+    fn test_prune_review_lines_scroll_to_cursor() {
+        use crate::git::Worktree;
+        use std::path::PathBuf;
+
+        let worktrees: Vec<Worktree> = (0..5)
+            .map(|i| Worktree {
+                name: format!("wt-{}", i),
+                path: PathBuf::from(format!("/repo/wt-{}", i)),
+                branch: Some(format!("feature-{}", i)),
+                is_main: false,
+                ..Default::default()
+            })
+            .collect();
+        let mut app = App::new_for_test(crate::config::Config::default(), vec![], vec![]);
+        app.merged_worktrees = worktrees;
+        app.prune_unchecked.insert(PathBuf::from("/repo/wt-3"));
+        app.prune_cursor = 4;
+
+        let (lines, cursor) = prune_review_lines(&app, 3);
+
+        assert_eq!(
+            lines,
+            vec![
+                "  [x] wt-2 (feature-2)",
+                "  [ ] wt-3 (feature-3)",
+                "▶ [x] wt-4 (feature-4)",
+            ]
+        );
+        assert_eq!(cursor, Some(2));
+    }
+
+    // ========== List Height Tests ==========
+
+    #[test]
+    fn test_draw_records_list_height_for_paging() {
+        use crate::git::Worktree;
+        use ratatui::{backend::TestBackend, Terminal};
+        use std::path::PathBuf;
+
+        let worktrees = (0..40)
+            .map(|i| Worktree {
+                name: format!("wt-{:02}", i),
+                path: PathBuf::from(format!("/repo/wt-{:02}", i)),
+                branch: None,
+                is_main: i == 0,
+                ..Default::default()
+            })
+            .collect();
+        let mut app = App::new_for_test(crate::config::Config::default(), worktrees, Vec::new());
+
+        // 20 rows - 2 margin - header/spacer/footer (3) - list borders (2) = 13
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+        assert_eq!(app.list_height, 13);
+
+        app.page_down();
+        assert_eq!(app.selected_worktree, 12);
+    }
+
+    // ========== Confirm Dialog Tests ==========
+
+    #[test]
+    fn test_draw_delete_marked_lists_kept_worktrees() {
+        use crate::app::{AppMode, ConfirmAction};
+        use crate::git::Worktree;
+        use ratatui::{backend::TestBackend, Terminal};
+        use std::path::PathBuf;
+
+        let worktree = |name: &str, is_locked: bool, is_dirty: bool| Worktree {
+            name: name.to_string(),
+            path: PathBuf::from(format!("/repo/{}", name)),
+            is_locked,
+            is_dirty,
+            ..Default::default()
+        };
+        let worktrees = vec![
+            worktree("clean", false, false),
+            worktree("held", true, false),
+            worktree("wip", false, true),
+        ];
+        let mut app = App::new_for_test(crate::config::Config::default(), worktrees, Vec::new());
+        app.marked_worktrees = app.worktrees.iter().map(|w| w.path.clone()).collect();
+        app.mode = AppMode::Confirm;
+        app.confirm_action = Some(ConfirmAction::DeleteMarked);
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 50)).unwrap();
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let rows: Vec<String> = (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect();
+        let row_of = |text: &str| rows.iter().position(|row| row.contains(text)).unwrap();
+
+        assert!(row_of("Delete 1 marked worktree(s)?") < row_of("• clean"));
+        assert!(row_of("Kept because locked:") < row_of("◦ held"));
+        assert!(row_of("Kept because of uncommitted changes:") < row_of("◦ wip"));
+        assert!(!rows.iter().any(|row| row.contains("Asking although")));
+
+        app.confirm_reason =
+            Some("Asking although confirm.delete is off:\n! wip has uncommitted changes".into());
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+        let rendered: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(rendered.contains("Asking although confirm.delete is off:"));
+        assert!(rendered.contains("! wip has uncommitted changes"));
+    }
+
+    // ========== Focus Tests ==========
+
+    #[test]
+    fn test_focused_pane_border_uses_header_color() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut app = App::new_for_test(crate::config::Config::default(), Vec::new(), Vec::new());
+        let colors = app.theme.colors.clone();
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        // The list's top-left corner is the first one on screen
+        let list_corner_fg = |terminal: &Terminal<TestBackend>| {
+            let buffer = terminal.backend().buffer();
+            buffer
+                .content()
+                .iter()
+                .find(|cell| cell.symbol() == "┌")
+                .unwrap()
+                .fg
+        };
+
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+        assert_eq!(list_corner_fg(&terminal), colors.header);
+
+        app.toggle_focus();
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+        assert_ne!(list_corner_fg(&terminal), colors.header);
+    }
+
+    // ========== Search Highlight Tests ==========
+
+    #[test]
+    fn test_highlight_match_splits_case_insensitively() {
+        let colors = ThemeColors::default();
+        let style = Style::default().fg(colors.text);
+
+        let spans = highlight_match("Fix Auth bug".to_string(), "auth", style, &colors);
+
+        let texts: Vec<&str> = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(texts, vec!["Fix ", "Auth", " bug"]);
+        assert_eq!(spans[1].style.fg, Some(colors.selected));
+        assert_eq!(spans[0].style, style);
+    }
+
+    #[test]
+    fn test_highlight_match_without_match_is_plain() {
+        let colors = ThemeColors::default();
+
+        let spans = highlight_match("main".to_string(), "auth", Style::default(), &colors);
+
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].content, "main");
+    }
+
+    #[test]
+    fn test_draw_detail_shows_matched_commit_message() {
+        use crate::git::Worktree;
+        use ratatui::{backend::TestBackend, Terminal};
+        use std::path::PathBuf;
+
+        let worktrees = vec![Worktree {
+            name: "wt".to_string(),
+            path: PathBuf::from("/repo/wt"),
+            branch: Some("feature/x".to_string()),
+            is_main: false,
+            commit_message: Some("Fix auth bug".to_string()),
+            commit_author: Some("Alice".to_string()),
+            ..Default::default()
+        }];
+        let mut app = App::new_for_test(crate::config::Config::default(), worktrees, Vec::new());
+        app.input = "auth".to_string();
+        app.filter_worktrees();
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+        let rendered: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+
+        assert!(rendered.contains("Commit: Fix auth bug"));
+        assert!(!rendered.contains("Alice"));
+    }
+
+    // ========== Icon Rendering Tests ==========
+
+    fn render_with_icons(icons: Option<bool>) -> String {
+        use crate::git::Worktree;
+        use ratatui::{backend::TestBackend, Terminal};
+        use std::path::PathBuf;
+
+        let worktrees = vec![Worktree {
+            name: "wt".to_string(),
+            path: PathBuf::from("/repo/wt"),
+            branch: Some("feature/x".to_string()),
+            is_main: false,
+            ..Default::default()
+        }];
+        let mut config = crate::config::Config::default();
+        config.ui.icons = icons;
+        let mut app = App::new_for_test(config, worktrees, Vec::new());
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn test_draw_shows_branch_icon_when_enabled() {
+        let rendered = render_with_icons(None);
+        assert!(rendered.contains(BRANCH_ICON));
+        assert!(rendered.contains("feature/x"));
+    }
+
+    #[test]
+    fn test_draw_hides_branch_icon_when_disabled() {
+        let rendered = render_with_icons(Some(false));
+        assert!(!rendered.contains(BRANCH_ICON));
+        assert!(rendered.contains("| feature/x"));
+    }
+
+    // ========== Create New Branch Label Tests ==========
+
+    #[test]
+    fn test_create_new_base_label_names_head_branch() {
+        use crate::git::Branch;
+
+        let branches = vec![
+            Branch {
+                name: "develop".to_string(),
+                is_head: false,
+                is_remote: false,
+            },
+            Branch {
+                name: "main".to_string(),
+                is_head: true,
+                is_remote: false,
+            },
+        ];
+        let app = App::new_for_test(crate::config::Config::default(), Vec::new(), branches);
+
+        assert_eq!(create_new_base_label(&app), " from HEAD (main)");
+    }
+
+    #[test]
+    fn test_create_new_base_label_detached_head() {
+        let app = App::new_for_test(crate::config::Config::default(), Vec::new(), Vec::new());
+
+        assert_eq!(create_new_base_label(&app), " from HEAD");
+    }
+
+    #[test]
+    fn test_create_new_base_label_uses_start_point() {
+        let mut app = App::new_for_test(crate::config::Config::default(), Vec::new(), Vec::new());
+        app.base_ref_input = "v1.0".to_string();
+
+        assert_eq!(create_new_base_label(&app), " from v1.0");
+    }
+
+    // ========== Status Line Tests ==========
+
+    #[test]
+    fn test_truncate_to_width_fits() {
+        assert_eq!(truncate_to_width("gwm · 3", 20), "gwm · 3");
+        assert_eq!(truncate_to_width("abc", 3), "abc");
+    }
+
+    #[test]
+    fn test_truncate_to_width_adds_ellipsis() {
+        assert_eq!(truncate_to_width("abcdef", 4), "abc…");
+        assert_eq!(truncate_to_width("日本語", 4), "日…");
+        assert_eq!(truncate_to_width("abc", 0), "");
+    }
+
+    #[test]
+    fn test_status_text_shows_repo_count_and_main_branch() {
+        use crate::git::Worktree;
+        use std::path::PathBuf;
+
+        let worktrees = vec![
+            Worktree {
+                name: "gwm".to_string(),
+                path: PathBuf::from("/src/gwm"),
+                branch: Some("main".to_string()),
+                is_main: true,
+                ..Default::default()
+            },
+            Worktree {
+                name: "feature".to_string(),
+                path: PathBuf::from("/src/worktrees/feature"),
+                branch: Some("feature".to_string()),
+                is_main: false,
+                ..Default::default()
+            },
+        ];
+        let app = App::new_for_test(crate::config::Config::default(), worktrees, Vec::new());
+
+        assert_eq!(status_text(&app), "gwm · 2 worktree(s) · main: main");
+    }
+
+    #[test]
+    fn test_draw_without_main_worktree() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        // Bare repositories list no main worktree and may have no linked ones yet
+        let mut app = App::new_for_test(crate::config::Config::default(), Vec::new(), Vec::new());
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+
+        assert_eq!(app.main_branch(), None);
+        assert!(status_text(&app).contains("0 worktree(s)"));
+    }
+
+    // ========== Move Dialog Tests ==========
+
+    #[test]
+    fn test_draw_move_dialog_shows_destination() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let worktrees = vec![
+            summary_worktree("main", Some("main")),
+            summary_worktree("wt-a", Some("feature/a")),
+        ];
+        let mut app = App::new_for_test(crate::config::Config::default(), worktrees, Vec::new());
+        app.selected_worktree = 1;
+        app.enter_move_mode();
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+        let rendered: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+
+        assert!(rendered.contains("Move worktree 'wt-a' to"));
+        assert!(rendered.contains("/repo/wt-a"));
+        // The destination is not echoed as a search query
+        assert!(rendered.contains("Search"));
+    }
+
+    // ========== worktree_summary_lines Tests ==========
+
+    fn summary_worktree(name: &str, branch: Option<&str>) -> Worktree {
+        Worktree {
+            name: name.to_string(),
+            path: std::path::PathBuf::from(format!("/repo/{}", name)),
+            branch: branch.map(String::from),
+            is_main: false,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_worktree_summary_lines_include_branches() {
+        let worktrees = vec![
+            summary_worktree("wt-a", Some("feature/a")),
+            summary_worktree("feature-b", Some("feature-b")),
+            summary_worktree("detached", None),
+        ];
+
+        let lines = worktree_summary_lines(&worktrees, 10);
+
+        assert_eq!(
+            lines,
+            vec!["• wt-a (feature/a)", "• feature-b", "• detached"]
+        );
+    }
+
+    #[test]
+    fn test_worktree_summary_lines_truncates_with_count() {
+        let worktrees: Vec<Worktree> = (0..5)
+            .map(|i| summary_worktree(&format!("wt-{}", i), None))
+            .collect();
+
+        let lines = worktree_summary_lines(&worktrees, 3);
+
+        assert_eq!(lines, vec!["• wt-0", "• wt-1", "… and 3 more"]);
+    }
 }
@@ -0,0 +1,52 @@
+//! Integration tests for running the gwm binary
+
+mod common;
+
+use common::GitTestRepo;
+use std::process::Command;
+use tempfile::TempDir;
+
+/// Running outside a repository exits with a short message instead of a report
+#[test]
+fn test_outside_repository_prints_friendly_error() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_gwm"))
+        .current_dir(temp_dir.path())
+        // Keep discovery from walking up into an enclosing repository
+        .env("GIT_CEILING_DIRECTORIES", temp_dir.path())
+        // Isolate from the user's configuration
+        .env("HOME", temp_dir.path())
+        .env("XDG_CONFIG_HOME", temp_dir.path())
+        .output()
+        .expect("Failed to run gwm");
+
+    assert!(!output.status.success());
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(stderr.trim(), "gwm must be run inside a git repository");
+}
+
+/// `--list --format=json` prints worktrees without needing a terminal
+#[test]
+fn test_list_json_outputs_worktrees() {
+    let repo = GitTestRepo::new();
+    repo.create_branch("feature-json");
+    repo.create_worktree("wt-json", "feature-json");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_gwm"))
+        .args(["--list", "--format=json"])
+        .current_dir(&repo.path)
+        .env("HOME", &repo.path)
+        .env("XDG_CONFIG_HOME", &repo.path)
+        .output()
+        .expect("Failed to run gwm");
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entries = json.as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["is_main"], true);
+    assert_eq!(entries[1]["name"], "wt-json");
+    assert_eq!(entries[1]["branch"], "feature-json");
+}
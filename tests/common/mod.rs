@@ -1,4 +1,10 @@
 //! Common test utilities for integration tests
+//!
+//! Tests in a file run in parallel in one process, so they must never call
+//! `std::env::set_current_dir`. Run git with `current_dir` and open managers
+//! with [`GitTestRepo::manager`] instead.
+
+#![allow(dead_code)]
 
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -31,6 +37,11 @@ impl GitTestRepo {
         Self { temp_dir, path }
     }
 
+    /// Open a GitManager for this repository without touching the process cwd
+    pub fn manager(&self) -> gwm::git::GitManager {
+        gwm::git::GitManager::from_path(&self.path).expect("Failed to open test repository")
+    }
+
     /// Create a new branch at the current HEAD
     pub fn create_branch(&self, name: &str) {
         run_git(&self.path, &["branch", name]);
@@ -0,0 +1,67 @@
+//! Integration tests for using gwm's worktree logic as a library
+
+mod common;
+
+use common::GitTestRepo;
+use gwm::git::{GitError, GitManager};
+
+/// Test listing, creating and removing worktrees without changing directory
+#[test]
+fn test_manage_worktrees_from_path() {
+    let repo = GitTestRepo::new();
+    let git = repo.manager();
+
+    let created = git
+        .create_worktree_with_new_branch("wt-lib", "feature-lib", ".worktrees")
+        .unwrap();
+    assert!(created.path.ends_with(".worktrees/wt-lib"));
+
+    let worktrees = git.list_worktrees().unwrap();
+    assert!(worktrees
+        .iter()
+        .any(|w| w.name == "wt-lib" && w.branch.as_deref() == Some("feature-lib")));
+
+    git.delete_worktree("wt-lib").unwrap();
+
+    let worktrees = git.list_worktrees().unwrap();
+    assert!(!worktrees.iter().any(|w| w.name == "wt-lib"));
+    assert!(!created.path.exists());
+}
+
+/// Test finding worktrees whose branches are merged into main
+#[test]
+fn test_find_merged_worktrees_from_path() {
+    let repo = GitTestRepo::new();
+    repo.create_merged_branch("feature-done");
+    repo.create_worktree("wt-done", "feature-done");
+    let git = repo.manager();
+
+    let merged = git.find_merged_worktrees("main").unwrap();
+
+    assert_eq!(merged.len(), 1);
+    assert_eq!(merged[0].branch.as_deref(), Some("feature-done"));
+}
+
+/// Test that worktrees serialize for tools that emit JSON
+#[test]
+fn test_worktree_serializes_to_json() {
+    let repo = GitTestRepo::new();
+    let git = repo.manager();
+
+    let main = git.list_worktrees().unwrap().remove(0);
+    let json = serde_json::to_value(&main).unwrap();
+
+    assert_eq!(json["is_main"], true);
+    assert_eq!(json["branch"], "main");
+    assert_eq!(json["commit_message"], "Initial commit");
+}
+
+/// Test that a directory outside any repository is reported as such
+#[test]
+fn test_from_path_outside_repository() {
+    let dir = tempfile::TempDir::new().unwrap();
+
+    let result = GitManager::from_path(dir.path());
+
+    assert!(matches!(result, Err(GitError::NotARepository)));
+}